@@ -50,6 +50,16 @@ where
     net_state.set_commit(!no_commit);
     net_state.set_timeout(timeout);
     net_state.set_memory_only(matches.is_present("MEMORY_ONLY"));
+    net_state.set_allow_mgmt_disruption(matches.is_present("FORCE"));
+    net_state.set_record_history(matches.is_present("HISTORY"));
+
+    if matches.is_present("DRY_RUN") {
+        let mut plan = net_state.plan()?;
+        if !matches.is_present("SHOW_SECRETS") {
+            plan.hide_secrets();
+        }
+        return Ok(serde_yaml::to_string(&plan)?);
+    }
 
     net_state.apply()?;
     if !matches.is_present("SHOW_SECRETS") {
@@ -110,6 +120,7 @@ pub(crate) fn state_edit(
     desire_state.set_verify_change(!matches.is_present("NO_VERIFY"));
     desire_state.set_commit(!matches.is_present("NO_COMMIT"));
     desire_state.set_memory_only(matches.is_present("MEMORY_ONLY"));
+    desire_state.set_allow_mgmt_disruption(matches.is_present("FORCE"));
     desire_state.apply()?;
     Ok(serde_yaml::to_string(&desire_state)?)
 }
@@ -0,0 +1,30 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use nmstate::NetworkState;
+
+use crate::error::CliError;
+
+pub(crate) fn checkpoint_list() -> Result<String, CliError> {
+    Ok(serde_yaml::to_string(&NetworkState::checkpoint_list()?)?)
+}
+
+// Show the specified checkpoint or the last one if not specified.
+pub(crate) fn checkpoint_show(
+    matches: &clap::ArgMatches,
+) -> Result<String, CliError> {
+    let mut checkpoints = NetworkState::checkpoint_list()?;
+    let checkpoint = match matches.value_of("CHECKPOINT") {
+        Some(path) => checkpoints.into_iter().find(|c| c.path == path),
+        None => checkpoints.pop(),
+    };
+    match checkpoint {
+        Some(c) => Ok(serde_yaml::to_string(&c)?),
+        None => Err(CliError {
+            code: crate::error::EX_DATAERR,
+            error_msg: match matches.value_of("CHECKPOINT") {
+                Some(path) => format!("Checkpoint {} not found", path),
+                None => "No checkpoint found".to_string(),
+            },
+        }),
+    }
+}
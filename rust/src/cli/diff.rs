@@ -0,0 +1,31 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use nmstate::NetworkState;
+
+use crate::error::CliError;
+
+// Compare the first state file with the second one, or the current network
+// state with the first state file as desired state when only one file is
+// given.
+pub(crate) fn diff(matches: &clap::ArgMatches) -> Result<String, CliError> {
+    let file_path = match matches.value_of("STATE_FILE") {
+        Some(f) => f,
+        None => return Err("No state file defined".into()),
+    };
+    let diff = match matches.value_of("STATE_FILE2") {
+        Some(file_path2) => {
+            read_state(file_path)?.diff(&read_state(file_path2)?)?
+        }
+        None => {
+            let mut cur_state = NetworkState::new();
+            cur_state.retrieve()?;
+            cur_state.diff_desired(&read_state(file_path)?)?
+        }
+    };
+    Ok(serde_yaml::to_string(&diff)?)
+}
+
+fn read_state(file_path: &str) -> Result<NetworkState, CliError> {
+    let fd = std::fs::File::open(file_path)?;
+    Ok(serde_yaml::from_reader(fd)?)
+}
@@ -47,9 +47,15 @@ impl From<std::io::Error> for CliError {
 
 impl From<NmstateError> for CliError {
     fn from(e: NmstateError) -> Self {
+        let mut error_msg = format!("NmstateError: {}", e);
+        if !e.differences().is_empty() {
+            if let Ok(report) = serde_yaml::to_string(e.differences()) {
+                error_msg += &format!("\nVerification report:\n{}", report);
+            }
+        }
         Self {
             code: DEFAULT_ERROR_CODE,
-            error_msg: format!("NmstateError: {}", e),
+            error_msg,
         }
     }
 }
@@ -11,3 +11,18 @@ pub(crate) fn gen_conf(file_path: &str) -> Result<String, CliError> {
     let escaped_string = serde_yaml::to_string(&confs)?;
     Ok(escaped_string.replace("\\n", "\n\n"))
 }
+
+pub(crate) fn import(matches: &clap::ArgMatches) -> Result<String, CliError> {
+    let paths: Vec<&str> = match matches.values_of("PATH") {
+        Some(p) => p.collect(),
+        None => return Err("No configuration file defined".into()),
+    };
+    let net_state = match matches.value_of("FROM") {
+        Some("nm") | None => NetworkState::from_nm_keyfiles(&paths)?,
+        Some("netplan") => NetworkState::from_netplan(&paths)?,
+        Some("ifcfg") => NetworkState::from_ifcfg(&paths)?,
+        Some("networkd") => NetworkState::from_networkd(&paths)?,
+        Some(f) => return Err(format!("Unsupported format {}", f).into()),
+    };
+    Ok(serde_yaml::to_string(&net_state)?)
+}
@@ -0,0 +1,74 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use nmstate::{NetworkState, NetworkStateRevision};
+use serde::Serialize;
+
+use crate::error::CliError;
+
+#[derive(Serialize)]
+#[serde(rename_all = "kebab-case")]
+struct RevisionSummary {
+    id: u64,
+    user: String,
+    started: u64,
+    finished: u64,
+    checkpoint: String,
+    no_commit: bool,
+}
+
+impl From<&NetworkStateRevision> for RevisionSummary {
+    fn from(rev: &NetworkStateRevision) -> Self {
+        Self {
+            id: rev.id,
+            user: rev.user.clone(),
+            started: rev.started,
+            finished: rev.finished,
+            checkpoint: rev.checkpoint.clone(),
+            no_commit: rev.no_commit,
+        }
+    }
+}
+
+pub(crate) fn history_list() -> Result<String, CliError> {
+    let summaries: Vec<RevisionSummary> = NetworkState::history_list()?
+        .iter()
+        .map(RevisionSummary::from)
+        .collect();
+    Ok(serde_yaml::to_string(&summaries)?)
+}
+
+pub(crate) fn history_show(
+    matches: &clap::ArgMatches,
+) -> Result<String, CliError> {
+    let mut rev = NetworkState::history_get(get_revision_id(matches)?)?;
+    if !matches.is_present("SHOW_SECRETS") {
+        rev.desired.hide_secrets();
+        rev.pre_apply.hide_secrets();
+        rev.result.hide_secrets();
+    }
+    Ok(serde_yaml::to_string(&rev)?)
+}
+
+// Bring the network back to the resulting state of specified revision.
+// The revert itself is recorded as a new revision.
+pub(crate) fn history_revert(
+    matches: &clap::ArgMatches,
+) -> Result<String, CliError> {
+    let mut net_state = NetworkState::new();
+    net_state.set_kernel_only(matches.is_present("KERNEL"));
+    net_state.set_allow_mgmt_disruption(matches.is_present("FORCE"));
+    net_state.set_record_history(true);
+    let mut revert_state =
+        net_state.history_revert(get_revision_id(matches)?)?;
+    if !matches.is_present("SHOW_SECRETS") {
+        revert_state.hide_secrets();
+    }
+    Ok(serde_yaml::to_string(&revert_state)?)
+}
+
+fn get_revision_id(matches: &clap::ArgMatches) -> Result<u64, CliError> {
+    matches.value_of_t("REVISION").map_err(|e| CliError {
+        code: crate::error::EX_DATAERR,
+        error_msg: e.to_string(),
+    })
+}
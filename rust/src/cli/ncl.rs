@@ -4,14 +4,23 @@
 mod apply;
 #[cfg(feature = "query_apply")]
 mod autoconf;
+#[cfg(feature = "query_apply")]
+mod checkpoint;
+mod diff;
 mod error;
 #[cfg(feature = "gen_conf")]
 mod gen_conf;
+mod history;
+#[cfg(feature = "query_apply")]
+mod persist_nic_names;
+mod policy;
 #[cfg(feature = "query_apply")]
 mod query;
 mod result;
 #[cfg(feature = "query_apply")]
 mod service;
+mod unit_tests;
+mod validate;
 
 use env_logger::Builder;
 use log::LevelFilter;
@@ -22,13 +31,21 @@ use crate::apply::{
 };
 #[cfg(feature = "query_apply")]
 use crate::autoconf::autoconf;
+#[cfg(feature = "query_apply")]
+use crate::checkpoint::{checkpoint_list, checkpoint_show};
+use crate::diff::diff;
 #[cfg(feature = "gen_conf")]
-use crate::gen_conf::gen_conf;
+use crate::gen_conf::{gen_conf, import};
+use crate::history::{history_list, history_revert, history_show};
+#[cfg(feature = "query_apply")]
+use crate::persist_nic_names::persist_nic_names;
+use crate::policy::policy;
 #[cfg(feature = "query_apply")]
 use crate::query::show;
 use crate::result::print_result_and_exit;
 #[cfg(feature = "query_apply")]
 use crate::service::ncl_service;
+use crate::validate::validate;
 
 pub(crate) const DEFAULT_SERVICE_FOLDER: &str = "/etc/nmstate";
 pub(crate) const CONFIG_FOLDER_KEY: &str = "CONFIG_FOLDER";
@@ -36,11 +53,23 @@ pub(crate) const CONFIG_FOLDER_KEY: &str = "CONFIG_FOLDER";
 const APP_NAME: &str = "nmstatectl";
 
 const SUB_CMD_GEN_CONF: &str = "gc";
+const SUB_CMD_IMPORT: &str = "import";
 const SUB_CMD_SHOW: &str = "show";
 const SUB_CMD_APPLY: &str = "apply";
 const SUB_CMD_COMMIT: &str = "commit";
 const SUB_CMD_ROLLBACK: &str = "rollback";
+const SUB_CMD_CHECKPOINT: &str = "checkpoint";
+const SUB_CMD_CHECKPOINT_LIST: &str = "list";
+const SUB_CMD_CHECKPOINT_SHOW: &str = "show";
+const SUB_CMD_HISTORY: &str = "history";
+const SUB_CMD_HISTORY_LIST: &str = "list";
+const SUB_CMD_HISTORY_SHOW: &str = "show";
+const SUB_CMD_HISTORY_REVERT: &str = "revert";
+const SUB_CMD_DIFF: &str = "diff";
 const SUB_CMD_EDIT: &str = "edit";
+const SUB_CMD_POLICY: &str = "policy";
+const SUB_CMD_VALIDATE: &str = "validate";
+const SUB_CMD_PERSIST_NIC_NAMES: &str = "persist-nic-names";
 const SUB_CMD_VERSION: &str = "version";
 const SUB_CMD_AUTOCONF: &str = "autoconf";
 const SUB_CMD_SERVICE: &str = "service";
@@ -113,6 +142,16 @@ fn main() {
                         .takes_value(false)
                         .help("Show secrets(hide by default)"),
                 )
+                .arg(
+                    clap::Arg::new("WATCH")
+                        .short('w')
+                        .long("watch")
+                        .takes_value(false)
+                        .help(
+                            "Keep running and show the changes whenever \
+                            network state changed",
+                        ),
+                )
         )
         .subcommand(
             clap::Command::new(SUB_CMD_APPLY)
@@ -171,6 +210,35 @@ fn main() {
                         .takes_value(false)
                         .help("Do not make the state persistent"),
                 )
+                .arg(
+                    clap::Arg::new("FORCE")
+                        .long("force")
+                        .takes_value(false)
+                        .help(
+                            "Allow taking down, removing or readdressing the \
+                            interface holding the default route or current \
+                            SSH session",
+                        ),
+                )
+                .arg(
+                    clap::Arg::new("DRY_RUN")
+                        .long("dry-run")
+                        .takes_value(false)
+                        .help(
+                            "Print the changes to apply without touching the \
+                            system",
+                        ),
+                )
+                .arg(
+                    clap::Arg::new("HISTORY")
+                        .long("history")
+                        .takes_value(false)
+                        .conflicts_with("DRY_RUN")
+                        .help(
+                            "Record desired, pre-apply and resulting state as \
+                            a new revision in history",
+                        ),
+                )
         )
         .subcommand(
             clap::Command::new(SUB_CMD_GEN_CONF)
@@ -182,6 +250,31 @@ fn main() {
                         .help("Network state file"),
                 ),
         )
+        .subcommand(
+            clap::Command::new(SUB_CMD_IMPORT)
+                .about(
+                    "Convert existing network configuration files to \
+                    network state without touching the system",
+                )
+                .arg(
+                    clap::Arg::new("FROM")
+                        .long("from")
+                        .takes_value(true)
+                        .possible_values(["nm", "netplan", "ifcfg", "networkd"])
+                        .default_value("nm")
+                        .help("Format of the configuration files"),
+                )
+                .arg(
+                    clap::Arg::new("PATH")
+                        .required(true)
+                        .multiple_occurrences(true)
+                        .index(1)
+                        .help(
+                            "Configuration files or folders holding \
+                            them",
+                        ),
+                ),
+        )
         .subcommand(
             clap::Command::new(SUB_CMD_COMMIT)
                 .about("Commit a change")
@@ -202,6 +295,165 @@ fn main() {
                         .help("checkpoint to rollback"),
                 ),
         )
+        .subcommand(
+            clap::Command::new(SUB_CMD_CHECKPOINT)
+                .about("Inspect checkpoints")
+                .subcommand_required(true)
+                .subcommand(
+                    clap::Command::new(SUB_CMD_CHECKPOINT_LIST)
+                        .about("List checkpoints"),
+                )
+                .subcommand(
+                    clap::Command::new(SUB_CMD_CHECKPOINT_SHOW)
+                        .about("Show checkpoint, the last one by default")
+                        .arg(
+                            clap::Arg::new("CHECKPOINT")
+                                .required(false)
+                                .index(1)
+                                .help("checkpoint to show"),
+                        ),
+                ),
+        )
+        .subcommand(
+            clap::Command::new(SUB_CMD_HISTORY)
+                .about("Inspect or revert to revisions recorded by apply")
+                .subcommand_required(true)
+                .subcommand(
+                    clap::Command::new(SUB_CMD_HISTORY_LIST)
+                        .about("List revisions"),
+                )
+                .subcommand(
+                    clap::Command::new(SUB_CMD_HISTORY_SHOW)
+                        .about("Show revision")
+                        .arg(
+                            clap::Arg::new("REVISION")
+                                .required(true)
+                                .index(1)
+                                .help("Revision ID to show"),
+                        )
+                        .arg(
+                            clap::Arg::new("SHOW_SECRETS")
+                                .short('s')
+                                .long("show-secrets")
+                                .takes_value(false)
+                                .help("Show secrets(hide by default)"),
+                        ),
+                )
+                .subcommand(
+                    clap::Command::new(SUB_CMD_HISTORY_REVERT)
+                        .about(
+                            "Apply the resulting state of revision, \
+                            removing virtual interfaces and routes created \
+                            after it",
+                        )
+                        .arg(
+                            clap::Arg::new("REVISION")
+                                .required(true)
+                                .index(1)
+                                .help("Revision ID to revert to"),
+                        )
+                        .arg(
+                            clap::Arg::new("KERNEL")
+                                .short('k')
+                                .long("kernel")
+                                .takes_value(false)
+                                .help("Apply network state to kernel only"),
+                        )
+                        .arg(
+                            clap::Arg::new("SHOW_SECRETS")
+                                .short('s')
+                                .long("show-secrets")
+                                .takes_value(false)
+                                .help("Show secrets(hide by default)"),
+                        )
+                        .arg(
+                            clap::Arg::new("FORCE")
+                                .long("force")
+                                .takes_value(false)
+                                .help(
+                                    "Allow taking down, removing or \
+                                    readdressing the interface holding the \
+                                    default route or current SSH session",
+                                ),
+                        ),
+                ),
+        )
+        .subcommand(
+            clap::Command::new(SUB_CMD_DIFF)
+                .about(
+                    "Show difference between current network state and \
+                    state file, or between two state files",
+                )
+                .arg(
+                    clap::Arg::new("STATE_FILE")
+                        .required(true)
+                        .index(1)
+                        .help("Network state file"),
+                )
+                .arg(
+                    clap::Arg::new("STATE_FILE2")
+                        .required(false)
+                        .index(2)
+                        .help(
+                            "Network state file to compare with the first \
+                            one instead of current network state",
+                        ),
+                ),
+        )
+        .subcommand(
+            clap::Command::new(SUB_CMD_POLICY)
+                .about(
+                    "Generate network state from policy file using values \
+                    captured from current network state",
+                )
+                .arg(
+                    clap::Arg::new("POLICY_FILE")
+                        .required(true)
+                        .index(1)
+                        .help("Network policy file"),
+                )
+                .arg(
+                    clap::Arg::new("CURRENT_STATE")
+                        .long("current")
+                        .short('c')
+                        .takes_value(true)
+                        .help(
+                            "Network state file to capture from instead of \
+                            current network state",
+                        ),
+                ),
+        )
+        .subcommand(
+            clap::Command::new(SUB_CMD_VALIDATE)
+                .about(
+                    "Validate state files offline, rejecting unknown \
+                    properties and changes nmstate cannot apply",
+                )
+                .arg(
+                    clap::Arg::new("STATE_FILE")
+                        .required_unless_present("SCHEMA")
+                        .multiple_occurrences(true)
+                        .index(1)
+                        .help("Network state files"),
+                )
+                .arg(
+                    clap::Arg::new("SCHEMA")
+                        .long("schema")
+                        .takes_value(false)
+                        .help("Print JSON Schema of network state"),
+                )
+                .arg(
+                    clap::Arg::new("CURRENT_STATE")
+                        .long("current")
+                        .short('c')
+                        .takes_value(true)
+                        .conflicts_with("SCHEMA")
+                        .help(
+                            "Network state file of the target host used to \
+                            check changes against",
+                        ),
+                ),
+        )
         .subcommand(
             clap::Command::new(SUB_CMD_EDIT)
                 .about("Edit network state in EDITOR")
@@ -241,6 +493,16 @@ fn main() {
                         .takes_value(false)
                         .help("Do not make the state persistent"),
                 )
+                .arg(
+                    clap::Arg::new("FORCE")
+                        .long("force")
+                        .takes_value(false)
+                        .help(
+                            "Allow taking down, removing or readdressing the \
+                            interface holding the default route or current \
+                            SSH session",
+                        ),
+                )
         )
         .subcommand(
             clap::Command::new(SUB_CMD_SERVICE)
@@ -253,6 +515,93 @@ fn main() {
                         .takes_value(true)
                         .default_value(DEFAULT_SERVICE_FOLDER)
                         .help("Folder hold network state files"),
+                )
+                .arg(
+                    clap::Arg::new("MERGE")
+                        .long("merge")
+                        .takes_value(false)
+                        .help(
+                            "Apply all state files as single merged state \
+                            with single checkpoint",
+                        ),
+                )
+                .arg(
+                    clap::Arg::new("ROLLBACK_ON_FAILURE")
+                        .long("rollback-on-failure")
+                        .takes_value(false)
+                        .conflicts_with("MERGE")
+                        .help(
+                            "Apply state files one by one and rollback \
+                            all of them on first failure",
+                        ),
+                )
+                .arg(
+                    clap::Arg::new("RECONCILE")
+                        .long("reconcile")
+                        .takes_value(false)
+                        .help(
+                            "Keep checking applied state files against \
+                            current network state after applying",
+                        ),
+                )
+                .arg(
+                    clap::Arg::new("DRIFT_POLICY")
+                        .long("drift-policy")
+                        .takes_value(true)
+                        .requires("RECONCILE")
+                        .possible_values(["report", "correct"])
+                        .help(
+                            "Only report the drift or re-apply the drifted \
+                            state file, default is report",
+                        ),
+                )
+                .arg(
+                    clap::Arg::new("INTERVAL")
+                        .long("interval")
+                        .takes_value(true)
+                        .requires("RECONCILE")
+                        .help(
+                            "Seconds between periodic drift checks, \
+                            default is 60",
+                        ),
+                )
+                .arg(
+                    clap::Arg::new("STATUS_FILE")
+                        .long("status-file")
+                        .takes_value(true)
+                        .requires("RECONCILE")
+                        .help("Write drift status of each file to this file"),
+                ),
+        )
+        .subcommand(
+            clap::Command::new(SUB_CMD_PERSIST_NIC_NAMES)
+                .about(
+                    "Pin ethernet interface names to permanent MAC address \
+                    using systemd .link files",
+                )
+                .arg(
+                    clap::Arg::new("ROOT")
+                        .long("root")
+                        .takes_value(true)
+                        .help(
+                            "Root folder to store .link files in, \
+                            default is /",
+                        ),
+                )
+                .arg(
+                    clap::Arg::new("DRY_RUN")
+                        .long("dry-run")
+                        .takes_value(false)
+                        .help("Only show what would be changed"),
+                )
+                .arg(
+                    clap::Arg::new("CLEANUP")
+                        .long("cleanup")
+                        .takes_value(false)
+                        .help(
+                            "Remove .link files generated by nmstate for \
+                            interfaces no longer exist",
+                        ),
                 ),
         )
         .subcommand(
@@ -282,6 +631,8 @@ fn main() {
         if let Some(file_path) = matches.value_of("STATE_FILE") {
             print_result_and_exit(gen_conf(file_path));
         }
+    } else if let Some(matches) = matches.subcommand_matches(SUB_CMD_IMPORT) {
+        print_result_and_exit(import(matches));
     } else if let Some(matches) = matches.subcommand_matches(SUB_CMD_SHOW) {
         print_result_and_exit(show(matches));
     } else if let Some(matches) = matches.subcommand_matches(SUB_CMD_APPLY) {
@@ -311,10 +662,42 @@ fn main() {
         } else {
             print_result_and_exit(rollback(""))
         }
+    } else if let Some(matches) =
+        matches.subcommand_matches(SUB_CMD_CHECKPOINT)
+    {
+        if matches.subcommand_matches(SUB_CMD_CHECKPOINT_LIST).is_some() {
+            print_result_and_exit(checkpoint_list());
+        } else if let Some(matches) =
+            matches.subcommand_matches(SUB_CMD_CHECKPOINT_SHOW)
+        {
+            print_result_and_exit(checkpoint_show(matches));
+        }
+    } else if let Some(matches) = matches.subcommand_matches(SUB_CMD_HISTORY) {
+        if matches.subcommand_matches(SUB_CMD_HISTORY_LIST).is_some() {
+            print_result_and_exit(history_list());
+        } else if let Some(matches) =
+            matches.subcommand_matches(SUB_CMD_HISTORY_SHOW)
+        {
+            print_result_and_exit(history_show(matches));
+        } else if let Some(matches) =
+            matches.subcommand_matches(SUB_CMD_HISTORY_REVERT)
+        {
+            print_result_and_exit(history_revert(matches));
+        }
+    } else if let Some(matches) = matches.subcommand_matches(SUB_CMD_DIFF) {
+        print_result_and_exit(diff(matches));
+    } else if let Some(matches) = matches.subcommand_matches(SUB_CMD_POLICY) {
+        print_result_and_exit(policy(matches));
+    } else if let Some(matches) = matches.subcommand_matches(SUB_CMD_VALIDATE) {
+        print_result_and_exit(validate(matches));
     } else if let Some(matches) = matches.subcommand_matches(SUB_CMD_EDIT) {
         print_result_and_exit(state_edit(matches));
     } else if let Some(matches) = matches.subcommand_matches(SUB_CMD_SERVICE) {
         print_result_and_exit(ncl_service(matches));
+    } else if let Some(matches) =
+        matches.subcommand_matches(SUB_CMD_PERSIST_NIC_NAMES)
+    {
+        print_result_and_exit(persist_nic_names(matches));
     } else if matches.subcommand_matches(SUB_CMD_VERSION).is_some() {
         print_result_and_exit(Ok(format!(
             "{} {}",
@@ -331,6 +714,15 @@ fn gen_conf(_file_path: &str) -> Result<String, crate::error::CliError> {
         .into())
 }
 
+#[cfg(not(feature = "gen_conf"))]
+fn import(
+    _matches: &clap::ArgMatches,
+) -> Result<String, crate::error::CliError> {
+    Err("The import sub-command require `gen_conf` feature been \
+        enabled during compiling"
+        .into())
+}
+
 #[cfg(not(feature = "query_apply"))]
 fn show(_matches: &clap::ArgMatches) -> Result<String, crate::error::CliError> {
     Err("The show sub-command require `query_apply` feature been \
@@ -373,6 +765,26 @@ fn rollback(_checkpoint: &str) -> Result<String, crate::error::CliError> {
     )
 }
 
+#[cfg(not(feature = "query_apply"))]
+fn checkpoint_list() -> Result<String, crate::error::CliError> {
+    Err(
+        "The checkpoint sub-command require `query_apply` feature been \
+        enabled during compiling"
+            .into(),
+    )
+}
+
+#[cfg(not(feature = "query_apply"))]
+fn checkpoint_show(
+    _matches: &clap::ArgMatches,
+) -> Result<String, crate::error::CliError> {
+    Err(
+        "The checkpoint sub-command require `query_apply` feature been \
+        enabled during compiling"
+            .into(),
+    )
+}
+
 #[cfg(not(feature = "query_apply"))]
 fn state_edit(
     _matches: &clap::ArgMatches,
@@ -401,3 +813,14 @@ fn ncl_service(
             .into(),
     )
 }
+
+#[cfg(not(feature = "query_apply"))]
+fn persist_nic_names(
+    _matches: &clap::ArgMatches,
+) -> Result<String, crate::error::CliError> {
+    Err(
+        "The persist-nic-names sub-command require `query_apply` feature \
+        been enabled during compiling"
+            .into(),
+    )
+}
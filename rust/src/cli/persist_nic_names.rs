@@ -0,0 +1,73 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use std::ffi::OsStr;
+use std::path::Path;
+
+use nmstate::NetworkState;
+
+use crate::error::CliError;
+
+const SYSTEMD_NETWORK_FOLDER: &str = "etc/systemd/network";
+const LINK_FILE_EXTENTION: &str = "link";
+
+// Pin the name of each ethernet interface to its permanent MAC address by
+// writing systemd .link files. With `--cleanup`, remove .link files
+// generated by nmstate for MAC addresses no longer found.
+pub(crate) fn persist_nic_names(
+    matches: &clap::ArgMatches,
+) -> Result<String, CliError> {
+    let root = matches.value_of("ROOT").unwrap_or("/");
+    let dry_run = matches.is_present("DRY_RUN");
+    let folder = Path::new(root).join(SYSTEMD_NETWORK_FOLDER);
+
+    let mut cur_state = NetworkState::new();
+    cur_state.retrieve()?;
+    let exist_files = read_link_files(&folder)?;
+
+    let mut actions = Vec::new();
+    if matches.is_present("CLEANUP") {
+        for file_name in cur_state.get_stale_nic_name_link_files(&exist_files) {
+            let file_path = folder.join(&file_name);
+            if !dry_run {
+                std::fs::remove_file(&file_path)?;
+            }
+            actions.push(format!("Removed {}", file_path.display()));
+        }
+    } else {
+        for (file_name, content) in cur_state.gen_nic_name_link_files() {
+            if exist_files.contains(&(file_name.clone(), content.clone())) {
+                continue;
+            }
+            let file_path = folder.join(&file_name);
+            if !dry_run {
+                std::fs::create_dir_all(&folder)?;
+                std::fs::write(&file_path, content)?;
+            }
+            actions.push(format!("Wrote {}", file_path.display()));
+        }
+    }
+    if dry_run {
+        for action in actions.iter_mut() {
+            *action = format!("[dry-run] {}", action);
+        }
+    }
+    Ok(actions.join("\n"))
+}
+
+// Return (file_name, content) of all files ending with `.link`.
+fn read_link_files(folder: &Path) -> Result<Vec<(String, String)>, CliError> {
+    let mut ret = Vec::new();
+    if !folder.is_dir() {
+        return Ok(ret);
+    }
+    for entry in folder.read_dir()? {
+        let file = entry?.path();
+        if file.extension() != Some(OsStr::new(LINK_FILE_EXTENTION)) {
+            continue;
+        }
+        if let Some(file_name) = file.file_name().and_then(|n| n.to_str()) {
+            ret.push((file_name.to_string(), std::fs::read_to_string(&file)?));
+        }
+    }
+    Ok(ret)
+}
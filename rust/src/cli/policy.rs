@@ -0,0 +1,27 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use nmstate::{NetworkPolicy, NetworkState};
+
+use crate::error::CliError;
+
+// Render the policy file against the saved current state file or the current
+// network state when not defined.
+pub(crate) fn policy(matches: &clap::ArgMatches) -> Result<String, CliError> {
+    let policy_path = match matches.value_of("POLICY_FILE") {
+        Some(f) => f,
+        None => return Err("No policy file defined".into()),
+    };
+    let policy: NetworkPolicy =
+        serde_yaml::from_reader(std::fs::File::open(policy_path)?)?;
+    let current = match matches.value_of("CURRENT_STATE") {
+        Some(f) => serde_yaml::from_reader(std::fs::File::open(f)?)?,
+        None => {
+            let mut cur_state = NetworkState::new();
+            cur_state.retrieve()?;
+            cur_state
+        }
+    };
+    Ok(serde_yaml::to_string(&NetworkState::new_from_policy(
+        &policy, &current,
+    )?)?)
+}
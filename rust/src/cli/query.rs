@@ -1,8 +1,8 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use nmstate::{
-    DnsState, HostNameState, NetworkState, OvsDbGlobalConfig, RouteRules,
-    Routes,
+    DnsState, HostNameState, NetworkState, NetworkStateDiff, OvsDbGlobalConfig,
+    RouteRules, Routes,
 };
 use serde::Serialize;
 use serde_yaml::Value;
@@ -35,7 +35,17 @@ pub(crate) fn show(matches: &clap::ArgMatches) -> Result<String, CliError> {
         net_state.set_running_config_only(true);
     }
     net_state.set_include_secrets(matches.is_present("SHOW_SECRETS"));
+    if matches.is_present("WATCH") {
+        return watch(&net_state, matches);
+    }
     net_state.retrieve()?;
+    show_state(net_state, matches)
+}
+
+fn show_state(
+    net_state: NetworkState,
+    matches: &clap::ArgMatches,
+) -> Result<String, CliError> {
     Ok(if let Some(ifname) = matches.value_of("IFNAME") {
         let mut new_net_state = filter_net_state_with_iface(&net_state, ifname);
         new_net_state.set_kernel_only(matches.is_present("KERNEL"));
@@ -51,6 +61,74 @@ pub(crate) fn show(matches: &clap::ArgMatches) -> Result<String, CliError> {
     })
 }
 
+// Print current network state, then the changes whenever network state
+// changed. Only return on failure.
+fn watch(
+    net_state: &NetworkState,
+    matches: &clap::ArgMatches,
+) -> Result<String, CliError> {
+    let mut result = Ok(String::new());
+    let mut is_initial = true;
+    net_state.monitor(|new_state, changes| {
+        // The initial state from monitor is the baseline of later changes
+        if is_initial {
+            is_initial = false;
+            return match show_state(new_state.clone(), matches) {
+                Ok(s) => {
+                    println!("{}", s);
+                    true
+                }
+                Err(e) => {
+                    result = Err(e);
+                    false
+                }
+            };
+        }
+        let changes = match matches.value_of("IFNAME") {
+            Some(ifname) => filter_diff_with_iface(changes, ifname),
+            None => changes.clone(),
+        };
+        if changes.is_empty() {
+            return true;
+        }
+        let output = if matches.is_present("JSON") {
+            serde_json::to_string_pretty(&changes).map_err(CliError::from)
+        } else {
+            serde_yaml::to_string(&changes).map_err(CliError::from)
+        };
+        match output {
+            Ok(s) => {
+                println!("{}", s);
+                true
+            }
+            Err(e) => {
+                result = Err(e);
+                false
+            }
+        }
+    })?;
+    result
+}
+
+fn filter_diff_with_iface(
+    changes: &NetworkStateDiff,
+    iface_name: &str,
+) -> NetworkStateDiff {
+    let mut ret = changes.clone();
+    ret.add_interfaces.retain(|i| i.name() == iface_name);
+    ret.remove_interfaces.retain(|i| i.name == iface_name);
+    ret.change_interfaces.retain(|i| i.name == iface_name);
+    ret.add_routes
+        .retain(|r| r.next_hop_iface.as_deref() == Some(iface_name));
+    ret.remove_routes
+        .retain(|r| r.next_hop_iface.as_deref() == Some(iface_name));
+    ret.add_route_rules.clear();
+    ret.remove_route_rules.clear();
+    ret.dns.clear();
+    ret.hostname = None;
+    ret
+}
+
 pub(crate) fn sort_netstate(
     net_state: NetworkState,
 ) -> Result<SortedNetworkState, CliError> {
@@ -1,12 +1,20 @@
+use std::collections::{BTreeMap, HashMap};
 use std::ffi::OsStr;
 use std::path::{Path, PathBuf};
+use std::sync::mpsc::{channel, RecvTimeoutError};
+use std::time::{Duration, Instant, SystemTime};
 
-use nmstate::NetworkState;
+use nmstate::{ErrorKind, NetworkState, VerificationDifference};
+use serde::Serialize;
 
 use crate::error::CliError;
 
 const CONFIG_FILE_EXTENTION: &str = "yml";
 const RELOCATE_FILE_EXTENTION: &str = "applied";
+// No extension, hence not treated as state file
+const APPLIED_HASH_FILE: &str = ".applied_hashes";
+const DEFAULT_RECONCILE_INTERVAL: u64 = 60;
+const MAX_RECONCILE_BACKOFF: u64 = 3600;
 
 pub(crate) fn ncl_service(
     matches: &clap::ArgMatches,
@@ -15,47 +23,68 @@ pub(crate) fn ncl_service(
         .value_of(crate::CONFIG_FOLDER_KEY)
         .unwrap_or(crate::DEFAULT_SERVICE_FOLDER);
 
-    let config_files = get_config_files(folder)?;
+    let mut hashes = read_applied_hashes(folder);
+    let config_files = get_pending_files(folder, &mut hashes)?;
     if config_files.is_empty() {
         log::info!(
-            "No nmstate config(end with .{}) found in config folder {}",
+            "No new or changed nmstate config(end with .{}) found in config \
+            folder {}",
             CONFIG_FILE_EXTENTION,
             folder
         );
     }
 
-    for file_path in config_files {
-        match apply_file(&file_path) {
-            Ok(()) => {
-                log::info!("Applied nmstate config: {}", file_path.display());
-                if let Err(e) = relocate_file(&file_path) {
+    let result = if matches.is_present("MERGE") {
+        apply_merged(&config_files, &mut hashes)
+    } else if matches.is_present("ROLLBACK_ON_FAILURE") {
+        apply_all_or_rollback(&config_files, &mut hashes)
+    } else {
+        for file_path in config_files {
+            match apply_file(&file_path) {
+                Ok(()) => mark_applied(&file_path, &mut hashes),
+                Err(e) => {
                     log::error!(
-                        "Failed to rename applied state file: {} {}",
+                        "Failed to apply state file {}: {}",
                         file_path.display(),
                         e
                     );
                 }
             }
-            Err(e) => {
-                log::error!(
-                    "Failed to apply state file {}: {}",
-                    file_path.display(),
-                    e
-                );
-            }
         }
+        Ok(())
+    };
+    if let Err(e) = write_applied_hashes(folder, &mut hashes) {
+        log::error!("Failed to save hashes of applied state files: {}", e);
+    }
+    result?;
+
+    if matches.is_present("RECONCILE") {
+        let policy = match matches.value_of("DRIFT_POLICY") {
+            Some("correct") => DriftPolicy::Correct,
+            _ => DriftPolicy::Report,
+        };
+        let interval = match matches.value_of("INTERVAL") {
+            Some(i) => i.parse::<u64>().map_err(|e| {
+                CliError::from(format!("Invalid interval {}: {}", i, e))
+            })?,
+            None => DEFAULT_RECONCILE_INTERVAL,
+        };
+        reconcile(folder, policy, interval, matches.value_of("STATUS_FILE"))?;
     }
 
     Ok("".to_string())
 }
 
-// All file ending with `.yml` will be included.
-fn get_config_files(folder: &str) -> Result<Vec<PathBuf>, CliError> {
+// All file ending with specified extension will be included.
+fn get_config_files(
+    folder: &str,
+    extension: &str,
+) -> Result<Vec<PathBuf>, CliError> {
     let folder = Path::new(folder);
     let mut ret = Vec::new();
     for entry in folder.read_dir()? {
         let file = entry?.path();
-        if file.extension() == Some(OsStr::new(CONFIG_FILE_EXTENTION)) {
+        if file.extension() == Some(OsStr::new(extension)) {
             ret.push(folder.join(file));
         }
     }
@@ -63,6 +92,271 @@ fn get_config_files(folder: &str) -> Result<Vec<PathBuf>, CliError> {
     Ok(ret)
 }
 
+// Files ending with `.yml` and the applied files changed since last apply,
+// sorted by file name without extension.
+fn get_pending_files(
+    folder: &str,
+    hashes: &mut HashMap<String, String>,
+) -> Result<Vec<PathBuf>, CliError> {
+    let mut ret = get_config_files(folder, CONFIG_FILE_EXTENTION)?;
+    for file_path in get_config_files(folder, RELOCATE_FILE_EXTENTION)? {
+        let file_name = get_file_name(&file_path);
+        let hash = hash_file(&file_path)?;
+        match hashes.get(&file_name) {
+            Some(applied_hash) if applied_hash == &hash => (),
+            Some(_) => {
+                log::info!(
+                    "Applied state file {} changed since last apply",
+                    file_path.display()
+                );
+                ret.push(file_path);
+            }
+            // Applied by older version not tracking the hash
+            None => {
+                hashes.insert(file_name, hash);
+            }
+        }
+    }
+    ret.sort_unstable_by(|a, b| a.file_stem().cmp(&b.file_stem()));
+    Ok(ret)
+}
+
+// Apply all files as single merged state, hence single checkpoint.
+fn apply_merged(
+    file_paths: &[PathBuf],
+    hashes: &mut HashMap<String, String>,
+) -> Result<(), CliError> {
+    if file_paths.is_empty() {
+        return Ok(());
+    }
+    let mut net_state = merge_state_files(file_paths)?;
+    net_state.set_allow_mgmt_disruption(true);
+    if let Err(e) = net_state.apply() {
+        log::error!("Failed to apply merged state files: {}", e);
+        return Err(e.into());
+    }
+    for file_path in file_paths {
+        mark_applied(file_path, hashes);
+    }
+    Ok(())
+}
+
+// Apply files one by one in order and stop at the first failure. The failed
+// file is rolled back by `apply()` itself, the network is then brought back
+// to the state before the first file, so that no state file is left applied.
+fn apply_all_or_rollback(
+    file_paths: &[PathBuf],
+    hashes: &mut HashMap<String, String>,
+) -> Result<(), CliError> {
+    if file_paths.is_empty() {
+        return Ok(());
+    }
+    let mut pre_apply_state = NetworkState::new();
+    pre_apply_state.set_running_config_only(true);
+    pre_apply_state.set_include_secrets(true);
+    pre_apply_state.retrieve()?;
+
+    for (index, file_path) in file_paths.iter().enumerate() {
+        if let Err(e) = apply_file(file_path) {
+            log::error!(
+                "Failed to apply state file {}: {}",
+                file_path.display(),
+                e
+            );
+            if index > 0 {
+                let mut net_state = NetworkState::new();
+                net_state.set_allow_mgmt_disruption(true);
+                match net_state.revert_to(&pre_apply_state) {
+                    Ok(_) => log::info!(
+                        "Rollbacked {} state files applied before {}",
+                        index,
+                        file_path.display()
+                    ),
+                    Err(e) => log::error!(
+                        "Failed to rollback applied state files: {}",
+                        e
+                    ),
+                }
+            }
+            return Err(e);
+        }
+    }
+    for file_path in file_paths {
+        mark_applied(file_path, hashes);
+    }
+    Ok(())
+}
+
+// Merge state files in order into single state, the same as applying them
+// one by one:
+//  * Interfaces of the same name are merged with later file winning.
+//  * The lists of routes and route rules are concatenated.
+//  * Other sections are overridden by later files.
+pub(crate) fn merge_state_files(
+    file_paths: &[PathBuf],
+) -> Result<NetworkState, CliError> {
+    let mut merged = serde_yaml::Mapping::new();
+    for file_path in file_paths {
+        let fd = std::fs::File::open(file_path)?;
+        let sections = match serde_yaml::from_reader(fd)? {
+            serde_yaml::Value::Mapping(m) => m,
+            serde_yaml::Value::Null => continue,
+            _ => {
+                return Err(CliError::from(format!(
+                    "Invalid network state file {}",
+                    file_path.display()
+                )));
+            }
+        };
+        for (key, value) in sections {
+            let section = key.as_str().unwrap_or_default().to_string();
+            match (section.as_str(), merged.get_mut(&key), value) {
+                (
+                    "interfaces",
+                    Some(serde_yaml::Value::Sequence(ifaces)),
+                    serde_yaml::Value::Sequence(new_ifaces),
+                ) => merge_ifaces(ifaces, new_ifaces),
+                (
+                    "routes" | "route-rules",
+                    Some(serde_yaml::Value::Mapping(cur)),
+                    serde_yaml::Value::Mapping(new),
+                ) => {
+                    for (sub_key, sub_value) in new {
+                        match (cur.get_mut(&sub_key), sub_value) {
+                            (
+                                Some(serde_yaml::Value::Sequence(list)),
+                                serde_yaml::Value::Sequence(new_list),
+                            ) => list.extend(new_list),
+                            (_, sub_value) => {
+                                cur.insert(sub_key, sub_value);
+                            }
+                        }
+                    }
+                }
+                (_, _, value) => {
+                    merged.insert(key, value);
+                }
+            }
+        }
+    }
+    Ok(serde_yaml::from_value(serde_yaml::Value::Mapping(merged))?)
+}
+
+// Interface without type in either file is treated as the same interface
+// when name matches.
+fn merge_ifaces(
+    ifaces: &mut Vec<serde_yaml::Value>,
+    new_ifaces: Vec<serde_yaml::Value>,
+) {
+    let get_str = |iface: &serde_yaml::Value, key: &str| {
+        iface
+            .get(key)
+            .and_then(|v| v.as_str())
+            .map(|v| v.to_string())
+    };
+    for new_iface in new_ifaces {
+        let name = get_str(&new_iface, "name");
+        let iface_type = get_str(&new_iface, "type");
+        let cur_iface = ifaces.iter_mut().find(|iface| {
+            name.is_some()
+                && get_str(iface, "name") == name
+                && match (get_str(iface, "type"), iface_type.as_ref()) {
+                    (Some(t), Some(new_t)) => &t == new_t,
+                    _ => true,
+                }
+        });
+        match cur_iface {
+            Some(cur_iface) => merge_yaml_value(cur_iface, new_iface),
+            None => ifaces.push(new_iface),
+        }
+    }
+}
+
+// Mappings are merged recursively, other values are overridden.
+fn merge_yaml_value(cur: &mut serde_yaml::Value, new: serde_yaml::Value) {
+    match (cur, new) {
+        (serde_yaml::Value::Mapping(cur), serde_yaml::Value::Mapping(new)) => {
+            for (key, value) in new {
+                match cur.get_mut(&key) {
+                    Some(cur_value) => merge_yaml_value(cur_value, value),
+                    None => {
+                        cur.insert(key, value);
+                    }
+                }
+            }
+        }
+        (cur, new) => *cur = new,
+    }
+}
+
+// Rename `.yml` file to `.applied` and record its hash.
+fn mark_applied(file_path: &Path, hashes: &mut HashMap<String, String>) {
+    log::info!("Applied nmstate config: {}", file_path.display());
+    let applied_path = file_path.with_extension(RELOCATE_FILE_EXTENTION);
+    if file_path != applied_path {
+        if let Err(e) = relocate_file(file_path) {
+            log::error!(
+                "Failed to rename applied state file: {} {}",
+                file_path.display(),
+                e
+            );
+            return;
+        }
+    }
+    match hash_file(&applied_path) {
+        Ok(hash) => {
+            hashes.insert(get_file_name(&applied_path), hash);
+        }
+        Err(e) => log::error!(
+            "Failed to hash applied state file {}: {}",
+            applied_path.display(),
+            e
+        ),
+    }
+}
+
+fn get_file_name(file_path: &Path) -> String {
+    file_path
+        .file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_default()
+}
+
+// FNV-1a 64 bits hash, stable across rust releases unlike `DefaultHasher`.
+fn hash_file(file_path: &Path) -> Result<String, CliError> {
+    let mut hash: u64 = 0xcbf29ce484222325;
+    for byte in std::fs::read(file_path)? {
+        hash ^= u64::from(byte);
+        hash = hash.wrapping_mul(0x100000001b3);
+    }
+    Ok(format!("{:016x}", hash))
+}
+
+// Map of applied file name to its hash. Missing or invalid file is treated
+// as empty.
+fn read_applied_hashes(folder: &str) -> HashMap<String, String> {
+    let file_path = Path::new(folder).join(APPLIED_HASH_FILE);
+    std::fs::read_to_string(&file_path)
+        .ok()
+        .and_then(|content| serde_yaml::from_str(&content).ok())
+        .unwrap_or_default()
+}
+
+fn write_applied_hashes(
+    folder: &str,
+    hashes: &mut HashMap<String, String>,
+) -> Result<(), CliError> {
+    let folder = Path::new(folder);
+    hashes.retain(|file_name, _| folder.join(file_name).exists());
+    // Sorted for human reading
+    let sorted_hashes: BTreeMap<&String, &String> = hashes.iter().collect();
+    std::fs::write(
+        folder.join(APPLIED_HASH_FILE),
+        serde_yaml::to_string(&sorted_hashes)?,
+    )?;
+    Ok(())
+}
+
 // rename file by adding a suffix `.applied`.
 fn relocate_file(file_path: &Path) -> Result<(), CliError> {
     let new_path = file_path.with_extension(RELOCATE_FILE_EXTENTION);
@@ -77,7 +371,187 @@ fn relocate_file(file_path: &Path) -> Result<(), CliError> {
 
 fn apply_file(file_path: &Path) -> Result<(), CliError> {
     let fd = std::fs::File::open(file_path)?;
-    let net_state: NetworkState = serde_yaml::from_reader(fd)?;
+    let mut net_state: NetworkState = serde_yaml::from_reader(fd)?;
+    // The service folder holds the state files deployed on purpose with no
+    // interactive session to protect.
+    net_state.set_allow_mgmt_disruption(true);
     net_state.apply()?;
     Ok(())
 }
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum DriftPolicy {
+    Report,
+    Correct,
+}
+
+#[derive(Debug, Default, Serialize)]
+#[serde(rename_all = "kebab-case")]
+struct DriftStatus {
+    // Applied state files merged in order as the expected state
+    files: Vec<String>,
+    // Seconds since UNIX epoch
+    last_check: u64,
+    drifted: bool,
+    corrected: bool,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    differences: Vec<VerificationDifference>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<String>,
+    // Count of continuous failures of re-apply
+    #[serde(skip_serializing_if = "is_zero")]
+    failures: u32,
+}
+
+fn is_zero(v: &u32) -> bool {
+    *v == 0
+}
+
+// Keep checking the merged state of all applied state files against current
+// network state every `interval` seconds or on network state changes.
+// Checking each file alone would report drift on every file overridden by a
+// later one. Never return unless failed to read the service folder.
+fn reconcile(
+    folder: &str,
+    policy: DriftPolicy,
+    interval: u64,
+    status_file: Option<&str>,
+) -> Result<(), CliError> {
+    log::info!(
+        "Checking drift of applied state files in {} with policy {:?}",
+        folder,
+        policy
+    );
+    let (tx, rx) = channel();
+    std::thread::spawn(move || {
+        // Skip the initial state, which has no changes
+        if let Err(e) = NetworkState::new()
+            .monitor(|_, changes| changes.is_empty() || tx.send(()).is_ok())
+        {
+            log::warn!(
+                "Failed to monitor network state, checking drift \
+                periodically only: {}",
+                e
+            );
+        }
+    });
+
+    let interval = Duration::from_secs(interval);
+    // Continuous failure count and time of next re-apply
+    let mut backoff: Option<(u32, Instant)> = None;
+    loop {
+        let mut file_paths = get_config_files(folder, RELOCATE_FILE_EXTENTION)?;
+        file_paths.sort_unstable_by(|a, b| a.file_stem().cmp(&b.file_stem()));
+        let status = check_drift(&file_paths, policy, interval, &mut backoff);
+        if let Some(status_file) = status_file {
+            if let Err(e) = write_status_file(status_file, &status) {
+                log::error!(
+                    "Failed to write status file {}: {}",
+                    status_file,
+                    e
+                );
+            }
+        }
+        if let Err(RecvTimeoutError::Disconnected) = rx.recv_timeout(interval) {
+            std::thread::sleep(interval);
+        }
+        // Events during the check are covered by next check
+        while rx.try_recv().is_ok() {}
+    }
+}
+
+fn check_drift(
+    file_paths: &[PathBuf],
+    policy: DriftPolicy,
+    interval: Duration,
+    backoff: &mut Option<(u32, Instant)>,
+) -> DriftStatus {
+    let mut status = DriftStatus {
+        files: file_paths.iter().map(|f| f.display().to_string()).collect(),
+        last_check: SystemTime::now()
+            .duration_since(SystemTime::UNIX_EPOCH)
+            .map(|d| d.as_secs())
+            .unwrap_or_default(),
+        ..Default::default()
+    };
+    if file_paths.is_empty() {
+        *backoff = None;
+        return status;
+    }
+    let mut net_state = match merge_state_files(file_paths) {
+        Ok(s) => s,
+        Err(e) => {
+            log::error!("Failed to read applied state files: {}", e);
+            status.error = Some(e.to_string());
+            return status;
+        }
+    };
+    match net_state.verify_current() {
+        Ok(()) => {
+            *backoff = None;
+            return status;
+        }
+        Err(e) if e.kind() == ErrorKind::VerificationError => {
+            log::warn!("Network state drifted from applied state files: {}", e);
+            status.drifted = true;
+            status.differences = e.differences().to_vec();
+        }
+        Err(e) => {
+            log::error!("Failed to check drift of applied state files: {}", e);
+            status.error = Some(e.to_string());
+            return status;
+        }
+    }
+
+    if policy == DriftPolicy::Correct {
+        let failures = match backoff {
+            Some((failures, next_try)) if Instant::now() < *next_try => {
+                log::info!(
+                    "Skip re-applying applied state files after {} failures",
+                    failures
+                );
+                status.failures = *failures;
+                return status;
+            }
+            Some((failures, _)) => *failures,
+            None => 0,
+        };
+        // The service folder holds the state files deployed on purpose with
+        // no interactive session to protect.
+        net_state.set_allow_mgmt_disruption(true);
+        match net_state.apply() {
+            Ok(()) => {
+                log::info!("Re-applied applied state files");
+                *backoff = None;
+                status.corrected = true;
+            }
+            Err(e) => {
+                let failures = failures + 1;
+                // Double the wait time on every failure
+                let wait = interval
+                    .saturating_mul(2u32.saturating_pow(failures))
+                    .min(Duration::from_secs(MAX_RECONCILE_BACKOFF));
+                log::error!(
+                    "Failed to re-apply applied state files, retry in {} \
+                    seconds: {}",
+                    wait.as_secs(),
+                    e
+                );
+                *backoff = Some((failures, Instant::now() + wait));
+                status.failures = failures;
+                status.error = Some(e.to_string());
+            }
+        }
+    }
+    status
+}
+
+fn write_status_file(
+    file_path: &str,
+    status: &DriftStatus,
+) -> Result<(), CliError> {
+    let tmp_path = format!("{}.tmp", file_path);
+    std::fs::write(&tmp_path, serde_yaml::to_string(status)?)?;
+    std::fs::rename(&tmp_path, file_path)?;
+    Ok(())
+}
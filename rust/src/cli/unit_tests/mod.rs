@@ -0,0 +1,2 @@
+#[cfg(all(test, feature = "query_apply"))]
+mod service;
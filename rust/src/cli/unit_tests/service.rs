@@ -0,0 +1,67 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use std::path::PathBuf;
+
+use nmstate::{Interface, InterfaceType};
+
+use crate::service::merge_state_files;
+
+fn write_test_files(contents: &[&str]) -> (PathBuf, Vec<PathBuf>) {
+    let dir = std::env::temp_dir()
+        .join(format!("nmstatectl-test-{}", uuid::Uuid::new_v4()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let mut file_paths = Vec::new();
+    for (index, content) in contents.iter().enumerate() {
+        let file_path = dir.join(format!("{:02}.yml", index));
+        std::fs::write(&file_path, content).unwrap();
+        file_paths.push(file_path);
+    }
+    (dir, file_paths)
+}
+
+#[test]
+fn test_merge_state_files_same_iface() {
+    let (dir, file_paths) = write_test_files(&[
+        r#"---
+interfaces:
+- name: eth1
+  type: ethernet
+  state: up
+  mtu: 1500
+  ipv4:
+    enabled: true
+    dhcp: false
+    address:
+    - ip: 192.0.2.1
+      prefix-length: 24
+"#,
+        r#"---
+interfaces:
+- name: eth1
+  state: up
+  mtu: 9000
+  ipv6:
+    enabled: false
+- name: eth2
+  type: ethernet
+  state: up
+"#,
+    ]);
+
+    let merged = merge_state_files(&file_paths);
+    std::fs::remove_dir_all(&dir).unwrap();
+    let merged = merged.unwrap();
+
+    let ifaces = merged.interfaces.to_vec();
+    assert_eq!(ifaces.len(), 2);
+    let eth1 =
+        match merged.interfaces.get_iface("eth1", InterfaceType::Ethernet) {
+            Some(Interface::Ethernet(iface)) => iface,
+            _ => panic!("eth1 not found in merged state"),
+        };
+    assert_eq!(eth1.base.mtu, Some(9000));
+    let ipv4 = eth1.base.ipv4.as_ref().unwrap();
+    assert!(ipv4.enabled);
+    assert_eq!(ipv4.addresses.as_ref().map(Vec::len), Some(1));
+    assert_eq!(eth1.base.ipv6.as_ref().map(|i| i.enabled), Some(false));
+}
@@ -0,0 +1,63 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use nmstate::NetworkState;
+
+use crate::error::CliError;
+
+// Validate each state file without touching current network state, or
+// print the JSON Schema when `--schema` is defined.
+// Besides unknown properties, the semantic checks done before apply are also
+// included when `gen_conf` feature is enabled, using the state file from
+// `--current` as current network state if defined.
+pub(crate) fn validate(matches: &clap::ArgMatches) -> Result<String, CliError> {
+    if matches.is_present("SCHEMA") {
+        return Ok(NetworkState::json_schema()?);
+    }
+    let file_paths: Vec<&str> = match matches.values_of("STATE_FILE") {
+        Some(f) => f.collect(),
+        None => return Err("No state file defined".into()),
+    };
+    let current = match matches.value_of("CURRENT_STATE") {
+        Some(f) => Some(read_state(f)?),
+        None => None,
+    };
+    let mut errors = Vec::new();
+    for file_path in &file_paths {
+        if let Err(e) = validate_file(file_path, current.as_ref()) {
+            errors.push(format!("{}: {}", file_path, e.error_msg));
+        }
+    }
+    if errors.is_empty() {
+        Ok(file_paths
+            .iter()
+            .map(|f| format!("{}: valid", f))
+            .collect::<Vec<String>>()
+            .join("\n"))
+    } else {
+        Err(CliError {
+            code: crate::error::EX_DATAERR,
+            error_msg: errors.join("\n"),
+        })
+    }
+}
+
+fn validate_file(
+    file_path: &str,
+    current: Option<&NetworkState>,
+) -> Result<(), CliError> {
+    let content = std::fs::read_to_string(file_path)?;
+    // Convert YAML to JSON so that property path in error is the same for
+    // both formats
+    let value: serde_json::Value = serde_yaml::from_str(&content)?;
+    let net_state =
+        NetworkState::validate_offline(&serde_json::to_string(&value)?)?;
+    if cfg!(feature = "gen_conf") || current.is_some() {
+        net_state.validate(current)?;
+    }
+    Ok(())
+}
+
+fn read_state(file_path: &str) -> Result<NetworkState, CliError> {
+    let fd = std::fs::File::open(file_path)?;
+    Ok(serde_yaml::from_reader(fd)?)
+}
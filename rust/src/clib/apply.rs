@@ -8,8 +8,9 @@ use libc::{c_char, c_int};
 use crate::{
     init_logger,
     query::{
-        NMSTATE_FLAG_KERNEL_ONLY, NMSTATE_FLAG_MEMORY_ONLY,
-        NMSTATE_FLAG_NO_COMMIT, NMSTATE_FLAG_NO_VERIFY,
+        NMSTATE_FLAG_ALLOW_MGMT_DISRUPTION, NMSTATE_FLAG_KERNEL_ONLY,
+        NMSTATE_FLAG_MEMORY_ONLY, NMSTATE_FLAG_NO_COMMIT,
+        NMSTATE_FLAG_NO_VERIFY,
     },
     NMSTATE_FAIL, NMSTATE_PASS,
 };
@@ -104,6 +105,10 @@ pub extern "C" fn nmstate_net_state_apply(
         net_state.set_memory_only(true);
     }
 
+    if (flags & NMSTATE_FLAG_ALLOW_MGMT_DISRUPTION) > 0 {
+        net_state.set_allow_mgmt_disruption(true);
+    }
+
     net_state.set_timeout(rollback_timeout);
 
     let result = net_state.apply();
@@ -155,3 +155,74 @@ pub extern "C" fn nmstate_checkpoint_rollback(
         NMSTATE_PASS
     }
 }
+
+#[allow(clippy::not_unsafe_ptr_arg_deref)]
+#[no_mangle]
+pub extern "C" fn nmstate_checkpoint_list(
+    checkpoints: *mut *mut c_char,
+    log: *mut *mut c_char,
+    err_kind: *mut *mut c_char,
+    err_msg: *mut *mut c_char,
+) -> c_int {
+    assert!(!checkpoints.is_null());
+    assert!(!log.is_null());
+    assert!(!err_kind.is_null());
+    assert!(!err_msg.is_null());
+
+    unsafe {
+        *checkpoints = std::ptr::null_mut();
+        *log = std::ptr::null_mut();
+        *err_kind = std::ptr::null_mut();
+        *err_msg = std::ptr::null_mut();
+    }
+
+    let logger = match init_logger() {
+        Ok(l) => l,
+        Err(e) => {
+            unsafe {
+                *err_msg =
+                    CString::new(format!("Failed to setup logger: {}", e))
+                        .unwrap()
+                        .into_raw();
+            }
+            return NMSTATE_FAIL;
+        }
+    };
+    let now = SystemTime::now();
+
+    let result = nmstate::NetworkState::checkpoint_list();
+    unsafe {
+        *log = CString::new(logger.drain(now)).unwrap().into_raw();
+    }
+
+    match result {
+        Ok(c) => match serde_json::to_string(&c) {
+            Ok(checkpoints_str) => unsafe {
+                *checkpoints =
+                    CString::new(checkpoints_str).unwrap().into_raw();
+                NMSTATE_PASS
+            },
+            Err(e) => unsafe {
+                *err_msg = CString::new(format!(
+                    "serde_json::to_string failure: {}",
+                    e
+                ))
+                .unwrap()
+                .into_raw();
+                *err_kind =
+                    CString::new(format!("{}", nmstate::ErrorKind::Bug))
+                        .unwrap()
+                        .into_raw();
+                NMSTATE_FAIL
+            },
+        },
+        Err(e) => {
+            unsafe {
+                *err_msg = CString::new(e.msg()).unwrap().into_raw();
+                *err_kind =
+                    CString::new(format!("{}", &e.kind())).unwrap().into_raw();
+            }
+            NMSTATE_FAIL
+        }
+    }
+}
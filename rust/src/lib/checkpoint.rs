@@ -0,0 +1,26 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case", deny_unknown_fields)]
+#[non_exhaustive]
+pub struct CheckpointInfo {
+    // The checkpoint ID used by `NetworkState::checkpoint_commit()` and
+    // `NetworkState::checkpoint_rollback()`.
+    pub path: String,
+    // Seconds since UNIX epoch.
+    pub created: u64,
+    // Seconds, 0 means no automatic rollback.
+    pub rollback_timeout: u32,
+    // Seconds left before automatic rollback.
+    pub remaining_timeout: u32,
+    // Interfaces covered by this checkpoint.
+    pub devices: Vec<String>,
+}
+
+impl CheckpointInfo {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
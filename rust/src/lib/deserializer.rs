@@ -293,3 +293,55 @@ impl std::convert::TryFrom<serde_json::Value> for NumberAsString {
         }
     }
 }
+
+// Return the field names of a struct deriving `Deserialize`, as serde
+// passes them to `Deserializer::deserialize_struct()`.
+#[cfg(feature = "gen_conf")]
+pub(crate) fn get_struct_field_names<'de, T>() -> &'static [&'static str]
+where
+    T: Deserialize<'de>,
+{
+    let mut fields: &'static [&'static str] = &[];
+    T::deserialize(StructFieldNamesDeserializer {
+        fields: &mut fields,
+    })
+    .ok();
+    fields
+}
+
+// Stop deserialization once the struct field names are known.
+#[cfg(feature = "gen_conf")]
+struct StructFieldNamesDeserializer<'a> {
+    fields: &'a mut &'static [&'static str],
+}
+
+#[cfg(feature = "gen_conf")]
+impl<'de, 'a> Deserializer<'de> for StructFieldNamesDeserializer<'a> {
+    type Error = de::value::Error;
+
+    fn deserialize_any<V>(self, _visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        Err(de::Error::custom("Not a struct"))
+    }
+
+    fn deserialize_struct<V>(
+        self,
+        _name: &'static str,
+        fields: &'static [&'static str],
+        _visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        *self.fields = fields;
+        Err(de::Error::custom("Only collecting struct field names"))
+    }
+
+    serde::forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+        bytes byte_buf option unit unit_struct newtype_struct seq tuple
+        tuple_struct map enum identifier ignored_any
+    }
+}
@@ -0,0 +1,304 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::{
+    Interface, InterfaceType, NetworkState, NmstateError, RouteEntry,
+    RouteRuleEntry, RouteRuleState, RouteState,
+};
+
+// Difference between two network states generated by
+// `NetworkState::diff()`.
+#[derive(Debug, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+#[non_exhaustive]
+pub struct NetworkStateDiff {
+    #[serde(skip_serializing_if = "Vec::is_empty", default)]
+    pub add_interfaces: Vec<Interface>,
+    #[serde(skip_serializing_if = "Vec::is_empty", default)]
+    pub remove_interfaces: Vec<InterfaceDiff>,
+    #[serde(skip_serializing_if = "Vec::is_empty", default)]
+    pub change_interfaces: Vec<InterfaceDiff>,
+    #[serde(skip_serializing_if = "Vec::is_empty", default)]
+    pub add_routes: Vec<RouteEntry>,
+    #[serde(skip_serializing_if = "Vec::is_empty", default)]
+    pub remove_routes: Vec<RouteEntry>,
+    #[serde(skip_serializing_if = "Vec::is_empty", default)]
+    pub add_route_rules: Vec<RouteRuleEntry>,
+    #[serde(skip_serializing_if = "Vec::is_empty", default)]
+    pub remove_route_rules: Vec<RouteRuleEntry>,
+    #[serde(skip_serializing_if = "Vec::is_empty", default)]
+    pub dns: Vec<PropertyDiff>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub hostname: Option<PropertyDiff>,
+}
+
+impl NetworkStateDiff {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self == &Self::default()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[non_exhaustive]
+pub struct InterfaceDiff {
+    pub name: String,
+    #[serde(rename = "type")]
+    pub iface_type: InterfaceType,
+    // Changed properties, empty for removed interface.
+    #[serde(skip_serializing_if = "Vec::is_empty", default)]
+    pub changes: Vec<PropertyDiff>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[non_exhaustive]
+pub struct PropertyDiff {
+    // Path to the property, for example `ipv4.address[0].ip` for interface
+    // or `dns-resolver.config.server` for DNS.
+    pub path: String,
+    pub old: Value,
+    pub new: Value,
+}
+
+impl PropertyDiff {
+    pub fn new(path: String, old: Value, new: Value) -> Self {
+        Self { path, old, new }
+    }
+}
+
+impl NetworkState {
+    // Compare `other` against this state, like two state files: only
+    // properties defined in `other` are compared, while interfaces, routes and
+    // route rules marked as absent or missing in `other` are reported as
+    // removed when found in this state.
+    pub fn diff(&self, other: &Self) -> Result<NetworkStateDiff, NmstateError> {
+        self.gen_diff(other, true)
+    }
+
+    // Compare `desired` against this state, like the current state against a
+    // desired state file. Unlike `diff()`, interfaces, routes and route rules
+    // missing in `desired` are untouched by apply, hence not reported.
+    pub fn diff_desired(
+        &self,
+        desired: &Self,
+    ) -> Result<NetworkStateDiff, NmstateError> {
+        self.gen_diff(desired, false)
+    }
+
+    fn gen_diff(
+        &self,
+        other: &Self,
+        report_missing: bool,
+    ) -> Result<NetworkStateDiff, NmstateError> {
+        let mut diff = NetworkStateDiff::new();
+        for new_iface in other.interfaces.to_vec() {
+            let old_iface = self
+                .interfaces
+                .get_iface(new_iface.name(), new_iface.iface_type());
+            match old_iface {
+                Some(old_iface) if new_iface.is_absent() => {
+                    diff.remove_interfaces.push(InterfaceDiff {
+                        name: old_iface.name().to_string(),
+                        iface_type: old_iface.iface_type(),
+                        changes: Vec::new(),
+                    });
+                }
+                Some(old_iface) => {
+                    let changes = get_property_diffs(
+                        "",
+                        &serde_json::to_value(old_iface)?,
+                        &serde_json::to_value(new_iface)?,
+                    );
+                    if !changes.is_empty() {
+                        diff.change_interfaces.push(InterfaceDiff {
+                            name: old_iface.name().to_string(),
+                            iface_type: old_iface.iface_type(),
+                            changes,
+                        });
+                    }
+                }
+                None if new_iface.is_absent() => (),
+                None => diff.add_interfaces.push(new_iface.clone()),
+            }
+        }
+        for old_iface in self.interfaces.to_vec() {
+            if report_missing
+                && other
+                    .interfaces
+                    .get_iface(old_iface.name(), old_iface.iface_type())
+                    .is_none()
+            {
+                diff.remove_interfaces.push(InterfaceDiff {
+                    name: old_iface.name().to_string(),
+                    iface_type: old_iface.iface_type(),
+                    changes: Vec::new(),
+                });
+            }
+        }
+
+        let (add_routes, mut remove_routes) =
+            other.routes.gen_add_and_remove(&self.routes, |_| false);
+        let (add_rules, mut remove_rules) =
+            other.rules.gen_add_and_remove(&self.rules);
+        if report_missing {
+            for mut route in get_removed(
+                self.routes.config.as_deref().unwrap_or_default(),
+                other.routes.config.as_deref().unwrap_or_default(),
+                |old, new| !new.is_absent() && new.is_match(old),
+            ) {
+                route.state = Some(RouteState::Absent);
+                if !remove_routes.contains(&route) {
+                    remove_routes.push(route);
+                }
+            }
+            for mut rule in get_removed(
+                self.rules.config.as_deref().unwrap_or_default(),
+                other.rules.config.as_deref().unwrap_or_default(),
+                |old, new| !new.is_absent() && new.is_match(old),
+            ) {
+                rule.state = Some(RouteRuleState::Absent);
+                if !remove_rules.contains(&rule) {
+                    remove_rules.push(rule);
+                }
+            }
+        }
+        diff.add_routes = add_routes;
+        diff.remove_routes = remove_routes;
+        diff.add_route_rules = add_rules;
+        diff.remove_route_rules = remove_rules;
+
+        if let Some(new_dns) = other.dns.config.as_ref() {
+            diff.dns = get_property_diffs(
+                "dns-resolver.config",
+                &serde_json::to_value(&self.dns.config)?,
+                &serde_json::to_value(new_dns)?,
+            );
+        }
+
+        if let Some(new_hostname) =
+            other.hostname.as_ref().and_then(|h| h.config.as_ref())
+        {
+            let old_hostname =
+                self.hostname.as_ref().and_then(|h| h.config.as_ref());
+            if old_hostname != Some(new_hostname) {
+                diff.hostname = Some(PropertyDiff::new(
+                    "hostname.config".to_string(),
+                    serde_json::to_value(old_hostname)?,
+                    serde_json::to_value(new_hostname)?,
+                ));
+            }
+        }
+        Ok(diff)
+    }
+
+    // Compare two full network states, like two retrieved current states.
+    // Unlike `diff()`, running routes, route rules, DNS and hostname are
+    // compared against the same section of the other state as they are.
+    #[cfg(feature = "query_apply")]
+    pub(crate) fn get_changes(
+        &self,
+        new: &Self,
+    ) -> Result<NetworkStateDiff, NmstateError> {
+        let mut diff = self.diff(new)?;
+        let old_routes = self.routes.running.as_deref().unwrap_or_default();
+        let new_routes = new.routes.running.as_deref().unwrap_or_default();
+        diff.add_routes = get_removed(new_routes, old_routes, |n, o| n == o);
+        diff.remove_routes = get_removed(old_routes, new_routes, |o, n| o == n);
+
+        let old_rules = self.rules.config.as_deref().unwrap_or_default();
+        let new_rules = new.rules.config.as_deref().unwrap_or_default();
+        diff.add_route_rules = get_removed(new_rules, old_rules, |n, o| n == o);
+        diff.remove_route_rules =
+            get_removed(old_rules, new_rules, |o, n| o == n);
+
+        diff.dns = get_property_diffs(
+            "dns-resolver",
+            &serde_json::to_value(&self.dns)?,
+            &serde_json::to_value(&new.dns)?,
+        );
+        diff.hostname = if self.hostname != new.hostname {
+            Some(PropertyDiff::new(
+                "hostname".to_string(),
+                serde_json::to_value(&self.hostname)?,
+                serde_json::to_value(&new.hostname)?,
+            ))
+        } else {
+            None
+        };
+        Ok(diff)
+    }
+}
+
+// Entries of `old` matching none of `new`.
+fn get_removed<T, F>(old: &[T], new: &[T], is_match: F) -> Vec<T>
+where
+    T: Clone,
+    F: Fn(&T, &T) -> bool,
+{
+    old.iter()
+        .filter(|o| !new.iter().any(|n| is_match(*o, n)))
+        .cloned()
+        .collect()
+}
+
+fn get_property_diffs(
+    prefix: &str,
+    old: &Value,
+    new: &Value,
+) -> Vec<PropertyDiff> {
+    let mut diffs = Vec::new();
+    _get_property_diffs(prefix.to_string(), old, new, &mut diffs);
+    diffs
+}
+
+fn _get_property_diffs(
+    path: String,
+    old: &Value,
+    new: &Value,
+    diffs: &mut Vec<PropertyDiff>,
+) {
+    match (old, new) {
+        (_, Value::Null) => (),
+        (Value::Object(old_map), Value::Object(new_map)) => {
+            for (key, new_value) in new_map.iter() {
+                let path = if path.is_empty() {
+                    key.to_string()
+                } else {
+                    format!("{}.{}", path, key)
+                };
+                _get_property_diffs(
+                    path,
+                    old_map.get(key).unwrap_or(&Value::Null),
+                    new_value,
+                    diffs,
+                );
+            }
+        }
+        (Value::Array(old_items), Value::Array(new_items))
+            if old_items.len() == new_items.len() =>
+        {
+            for (index, (old_item, new_item)) in
+                old_items.iter().zip(new_items.iter()).enumerate()
+            {
+                _get_property_diffs(
+                    format!("{}[{}]", path, index),
+                    old_item,
+                    new_item,
+                    diffs,
+                );
+            }
+        }
+        (_, _) => {
+            if old != new
+                && new.as_str() != Some(NetworkState::PASSWORD_HID_BY_NMSTATE)
+            {
+                diffs.push(PropertyDiff::new(path, old.clone(), new.clone()));
+            }
+        }
+    }
+}
@@ -1,5 +1,7 @@
 use std::error::Error;
 
+use serde::{Deserialize, Serialize};
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 #[non_exhaustive]
 #[allow(dead_code)]
@@ -33,11 +35,76 @@ impl Error for NmstateError {}
 pub struct NmstateError {
     kind: ErrorKind,
     msg: String,
+    differences: Vec<VerificationDifference>,
 }
 
 impl NmstateError {
     pub fn new(kind: ErrorKind, msg: String) -> Self {
-        Self { kind, msg }
+        Self {
+            kind,
+            msg,
+            differences: Vec::new(),
+        }
+    }
+
+    pub(crate) fn new_verification_error(
+        msg: String,
+        differences: Vec<VerificationDifference>,
+    ) -> Self {
+        Self {
+            kind: ErrorKind::VerificationError,
+            msg,
+            differences,
+        }
+    }
+
+    #[cfg(feature = "query_apply")]
+    pub(crate) fn new_kernel_integer_rounded_error(
+        msg: String,
+        differences: Vec<VerificationDifference>,
+    ) -> Self {
+        Self {
+            kind: ErrorKind::KernelIntegerRoundedError,
+            msg,
+            differences,
+        }
+    }
+
+    // Combine all verification errors into single error holding all the
+    // differences. The kernel integer rounded error is only kept when no
+    // other difference found. Other kind of error is returned as it is.
+    pub(crate) fn merge_verification_errors(
+        errors: Vec<Self>,
+    ) -> Result<(), Self> {
+        let mut msgs = Vec::new();
+        let mut differences = Vec::new();
+        let mut kind = ErrorKind::KernelIntegerRoundedError;
+        for e in errors {
+            match e.kind {
+                ErrorKind::VerificationError => {
+                    kind = ErrorKind::VerificationError;
+                }
+                ErrorKind::KernelIntegerRoundedError => (),
+                _ => return Err(e),
+            }
+            msgs.push(e.msg);
+            differences.extend(e.differences);
+        }
+        if msgs.is_empty() {
+            Ok(())
+        } else {
+            Err(Self {
+                kind,
+                msg: msgs.join("\n"),
+                differences,
+            })
+        }
+    }
+
+    // All the differences found by verification, empty for other kind of
+    // errors.
+    pub fn differences(&self) -> &[VerificationDifference] {
+        self.differences.as_slice()
     }
 
     pub fn kind(&self) -> ErrorKind {
@@ -75,3 +142,28 @@ impl From<ipnet::AddrParseError> for NmstateError {
         )
     }
 }
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+#[non_exhaustive]
+pub struct VerificationDifference {
+    // Path to the property, for example `eth1.interface.mtu` or
+    // `routes.config[0]`.
+    pub path: String,
+    pub desired: serde_json::Value,
+    pub current: serde_json::Value,
+}
+
+impl VerificationDifference {
+    pub fn new(
+        path: String,
+        desired: serde_json::Value,
+        current: serde_json::Value,
+    ) -> Self {
+        Self {
+            path,
+            desired,
+            current,
+        }
+    }
+}
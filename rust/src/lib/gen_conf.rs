@@ -1,12 +1,27 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use std::collections::HashMap;
+use std::path::Path;
+
+use serde::Deserialize;
 
 use crate::{
-    nm::nm_gen_conf, EthernetInterface, Interface, InterfaceType, Interfaces,
+    ifcfg::{ifcfg_gen_conf, ifcfg_import, is_ifcfg_file_name},
+    ifupdown::ifupdown_gen_conf,
+    iproute::shell_gen_conf,
+    netplan::netplan_import,
+    networkd::{
+        networkd_import, NETWORKD_NETDEV_SUFFIX, NETWORKD_NETWORK_SUFFIX,
+    },
+    nm::{nm_gen_conf, nm_import_keyfiles},
+    ovsdb::ovsdb_gen_conf,
+    ErrorKind, EthernetInterface, Interface, InterfaceType, Interfaces,
     NetworkState, NmstateError,
 };
 
+const NM_KEYFILE_SUFFIX: &str = ".nmconnection";
+const NETPLAN_SUFFIX: &str = ".yaml";
+
 impl NetworkState {
     pub fn gen_conf(
         &self,
@@ -14,23 +29,151 @@ impl NetworkState {
         let mut ret = HashMap::new();
         let mut self_clone = self.clone();
         self_clone.interfaces.set_unknown_iface_to_eth();
-        self_clone.interfaces.set_missing_port_to_eth();
-        let (add_net_state, _, _) =
+        self_clone
+            .interfaces
+            .set_missing_port_to_eth(&Interfaces::new());
+        let (mut add_net_state, _, _) =
             self_clone.gen_state_for_apply(&Self::new())?;
+        add_net_state.ovsdb = self_clone.ovsdb.clone();
         ret.insert("NetworkManager".to_string(), nm_gen_conf(&add_net_state)?);
+        ret.insert("ifcfg-rh".to_string(), ifcfg_gen_conf(&add_net_state)?);
+        ret.insert("ifupdown".to_string(), ifupdown_gen_conf(&add_net_state)?);
+        ret.insert("shell".to_string(), shell_gen_conf(&add_net_state)?);
+        ret.insert("OpenvSwitch".to_string(), ovsdb_gen_conf(&add_net_state)?);
+        ret.insert(
+            "systemd-link".to_string(),
+            add_net_state.gen_nic_name_link_files(),
+        );
         Ok(ret)
     }
+
+    /// Check desired state for the errors [NetworkState::apply()] would
+    /// raise before touching the system, like controller overbooking, bond
+    /// options incompatible with bond mode or invalid routes, without
+    /// connecting to any network backend.
+    /// The `current` state, for example saved from `nmstatectl show` on the
+    /// target host, is used for merging and MTU range checks. When not
+    /// defined, interfaces are treated as new like [NetworkState::gen_conf()]
+    /// does.
+    pub fn validate(&self, current: Option<&Self>) -> Result<(), NmstateError> {
+        let current = current.cloned().unwrap_or_default();
+        let mut self_clone = self.clone();
+        self_clone
+            .interfaces
+            .set_unknown_iface_type(&current.interfaces)?;
+        self_clone.interfaces.set_unknown_iface_to_eth();
+        self_clone
+            .interfaces
+            .set_missing_port_to_eth(&current.interfaces);
+        self_clone.gen_state_for_apply(&current)?;
+        Ok(())
+    }
+
+    /// Convert NetworkManager keyfiles to desired state without connecting
+    /// to NetworkManager daemon. Folder in `paths` will be searched for
+    /// files with `.nmconnection` suffix.
+    pub fn from_nm_keyfiles<P: AsRef<Path>>(
+        paths: &[P],
+    ) -> Result<Self, NmstateError> {
+        nm_import_keyfiles(&read_conf_files(paths, |f| {
+            f.ends_with(NM_KEYFILE_SUFFIX)
+        })?)
+    }
+
+    /// Convert netplan YAML files to desired state. Folder in `paths` will
+    /// be searched for files with `.yaml` suffix. Like netplan, files are
+    /// applied in the order of `paths` and file names, later files amend or
+    /// override the earlier ones.
+    pub fn from_netplan<P: AsRef<Path>>(
+        paths: &[P],
+    ) -> Result<Self, NmstateError> {
+        netplan_import(&read_conf_files(paths, |f| {
+            f.ends_with(NETPLAN_SUFFIX)
+        })?)
+    }
+
+    /// Convert ifcfg-rh files to desired state. Folder in `paths` will be
+    /// searched for `ifcfg-*`, `route-*`, `route6-*`, `rule-*` and
+    /// `rule6-*` files.
+    pub fn from_ifcfg<P: AsRef<Path>>(
+        paths: &[P],
+    ) -> Result<Self, NmstateError> {
+        ifcfg_import(&read_conf_files(paths, is_ifcfg_file_name)?)
+    }
+
+    /// Convert systemd-networkd files to desired state. Folder in `paths`
+    /// will be searched for files with `.netdev` or `.network` suffix.
+    pub fn from_networkd<P: AsRef<Path>>(
+        paths: &[P],
+    ) -> Result<Self, NmstateError> {
+        networkd_import(&read_conf_files(paths, |f| {
+            f.ends_with(NETWORKD_NETDEV_SUFFIX)
+                || f.ends_with(NETWORKD_NETWORK_SUFFIX)
+        })?)
+    }
+}
+
+// Read the files in `paths` in the form of (file_path, content). For folder,
+// the files with name matching `filter` are read in the order of file name.
+fn read_conf_files<P: AsRef<Path>>(
+    paths: &[P],
+    filter: fn(&str) -> bool,
+) -> Result<Vec<(String, String)>, NmstateError> {
+    let mut ret = Vec::new();
+    for path in paths {
+        let path = path.as_ref();
+        if path.is_dir() {
+            let mut file_paths = Vec::new();
+            for entry in std::fs::read_dir(path)
+                .map_err(|e| io_error_to_nmstate(path, e))?
+            {
+                let file_path =
+                    entry.map_err(|e| io_error_to_nmstate(path, e))?.path();
+                if file_path
+                    .file_name()
+                    .and_then(|f| f.to_str())
+                    .map(filter)
+                    .unwrap_or_default()
+                {
+                    file_paths.push(file_path);
+                }
+            }
+            file_paths.sort_unstable();
+            for file_path in file_paths {
+                ret.push(read_conf_file(&file_path)?);
+            }
+        } else {
+            ret.push(read_conf_file(path)?);
+        }
+    }
+    Ok(ret)
+}
+
+fn read_conf_file(path: &Path) -> Result<(String, String), NmstateError> {
+    let content = std::fs::read_to_string(path)
+        .map_err(|e| io_error_to_nmstate(path, e))?;
+    Ok((path.display().to_string(), content))
+}
+
+fn io_error_to_nmstate(path: &Path, e: std::io::Error) -> NmstateError {
+    NmstateError::new(
+        ErrorKind::InvalidArgument,
+        format!("Failed to read {}: {}", path.display(), e),
+    )
 }
 
 impl Interfaces {
-    fn set_missing_port_to_eth(&mut self) {
+    // Ports found in `current` are left untouched.
+    fn set_missing_port_to_eth(&mut self, current: &Self) {
         let mut iface_names_to_add = Vec::new();
         for iface in
             self.kernel_ifaces.values().chain(self.user_ifaces.values())
         {
             if let Some(ports) = iface.ports() {
                 for port in ports {
-                    if !self.kernel_ifaces.contains_key(port) {
+                    if !self.kernel_ifaces.contains_key(port)
+                        && !current.kernel_ifaces.contains_key(port)
+                    {
                         iface_names_to_add.push(port.to_string());
                     }
                 }
@@ -40,8 +183,8 @@ impl Interfaces {
             let mut iface = EthernetInterface::default();
             iface.base.name = iface_name.clone();
             log::warn!("Assuming undefined port {} as ethernet", iface_name);
-            self.kernel_ifaces
-                .insert(iface_name, Interface::Ethernet(iface));
+            // Use push() to include it in the up priority calculation.
+            self.push(Interface::Ethernet(iface));
         }
     }
 
@@ -56,4 +199,109 @@ impl Interfaces {
             }
         }
     }
+
+    // Use the interface type found in `current` for unknown type interfaces
+    // like `NetworkState::apply()` does.
+    fn set_unknown_iface_type(
+        &mut self,
+        current: &Self,
+    ) -> Result<(), NmstateError> {
+        let mut resolved_ifaces = Vec::new();
+        for iface in self
+            .kernel_ifaces
+            .values()
+            .filter(|i| i.iface_type() == InterfaceType::Unknown)
+        {
+            if let Some(cur_iface) = current.kernel_ifaces.get(iface.name()) {
+                let mut iface_value = serde_json::to_value(iface)?;
+                if let Some(obj) = iface_value.as_object_mut() {
+                    obj.insert(
+                        "type".to_string(),
+                        serde_json::Value::String(
+                            cur_iface.iface_type().to_string(),
+                        ),
+                    );
+                }
+                resolved_ifaces.push(Interface::deserialize(&iface_value)?);
+            }
+        }
+        for iface in resolved_ifaces {
+            self.kernel_ifaces.remove(iface.name());
+            self.push(iface);
+        }
+        Ok(())
+    }
+}
+
+// The legacy network scripts(ifcfg-rh and ifupdown) have no equivalent for
+// these interface properties, return their property paths.
+pub(crate) fn get_unsupported_legacy_props(iface: &Interface) -> Vec<String> {
+    let base_iface = iface.base_iface();
+    let mut ret = Vec::new();
+    if base_iface.ethtool.is_some() {
+        ret.push("ethtool");
+    }
+    if base_iface.ieee8021x.is_some() {
+        ret.push("802.1x");
+    }
+    if base_iface.lldp.is_some() {
+        ret.push("lldp");
+    }
+    if base_iface.mptcp.is_some() {
+        ret.push("mptcp");
+    }
+    if base_iface.ovsdb.is_some() {
+        ret.push("ovs-db");
+    }
+    if base_iface.accept_all_mac_addresses.is_some() {
+        ret.push("accept-all-mac-addresses");
+    }
+    if base_iface.wait_ip.is_some() {
+        ret.push("wait-ip");
+    }
+    if base_iface
+        .ipv4
+        .as_ref()
+        .and_then(|i| i.auto_table_id)
+        .is_some()
+    {
+        ret.push("ipv4.auto-route-table-id");
+    }
+    if base_iface
+        .ipv6
+        .as_ref()
+        .and_then(|i| i.auto_table_id)
+        .is_some()
+    {
+        ret.push("ipv6.auto-route-table-id");
+    }
+    if base_iface
+        .ipv4
+        .as_ref()
+        .and_then(|i| i.dhcp_client_id.as_ref())
+        .is_some()
+    {
+        ret.push("ipv4.dhcp-client-id");
+    }
+    if base_iface
+        .ipv6
+        .as_ref()
+        .and_then(|i| i.dhcp_duid.as_ref())
+        .is_some()
+    {
+        ret.push("ipv6.dhcp-duid");
+    }
+    if let Interface::Ethernet(eth_iface) = iface {
+        if eth_iface
+            .ethernet
+            .as_ref()
+            .and_then(|e| e.sr_iov.as_ref())
+            .is_some()
+        {
+            ret.push("ethernet.sr-iov");
+        }
+    }
+    ret.iter()
+        .map(|p| format!("interfaces.{}.{}", iface.name(), p))
+        .collect()
 }
@@ -0,0 +1,322 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+#[cfg(feature = "query_apply")]
+use crate::snapshot::now_secs;
+use crate::{ErrorKind, NetworkState, NmstateError};
+
+// Each successful apply with history enabled is stored as `<id>.json` in this
+// folder. The states stored include secrets, hence files are only readable by
+// owner.
+const HISTORY_DIR: &str = "/var/lib/nmstate/history";
+// Only the newest revisions are kept, older ones are removed on recording.
+#[cfg(feature = "query_apply")]
+const HISTORY_MAX_REVISIONS: usize = 100;
+
+#[derive(Debug, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case", deny_unknown_fields)]
+#[non_exhaustive]
+pub struct NetworkStateRevision {
+    // Incremental revision number starting from 1.
+    pub id: u64,
+    // User invoking the apply.
+    pub user: String,
+    // Seconds since UNIX epoch when apply started.
+    pub started: u64,
+    // Seconds since UNIX epoch when apply finished.
+    pub finished: u64,
+    // The checkpoint ID used by this apply.
+    pub checkpoint: String,
+    // Applied without commit, the checkpoint might be rolled back after
+    // this revision recorded.
+    #[serde(default)]
+    pub no_commit: bool,
+    // Desired state applied.
+    pub desired: NetworkState,
+    // Current state before apply.
+    pub pre_apply: NetworkState,
+    // Running configuration after apply.
+    pub result: NetworkState,
+}
+
+impl NetworkStateRevision {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl NetworkState {
+    // List revisions stored by `NetworkState::apply()` with history enabled,
+    // sorted by revision ID.
+    pub fn history_list() -> Result<Vec<NetworkStateRevision>, NmstateError> {
+        history_list_from_dir(Path::new(HISTORY_DIR))
+    }
+
+    pub fn history_get(id: u64) -> Result<NetworkStateRevision, NmstateError> {
+        history_get_from_dir(Path::new(HISTORY_DIR), id)
+    }
+
+    // Bring the network back to the resulting state of specified revision
+    // using the apply options of this state, virtual interfaces, routes and
+    // route rules created after that revision are removed.
+    // Return the state applied.
+    #[cfg(feature = "query_apply")]
+    pub fn history_revert(&self, id: u64) -> Result<Self, NmstateError> {
+        self.revert_to(&Self::history_get(id)?.result)
+    }
+
+    #[cfg(not(feature = "query_apply"))]
+    pub fn history_revert(&self, _id: u64) -> Result<Self, NmstateError> {
+        Err(NmstateError::new(
+            ErrorKind::DependencyError,
+            "NetworkState::history_revert() need `query_apply` feature enabled"
+                .into(),
+        ))
+    }
+}
+
+pub(crate) fn history_list_from_dir(
+    dir: &Path,
+) -> Result<Vec<NetworkStateRevision>, NmstateError> {
+    let entries = match std::fs::read_dir(dir) {
+        Ok(e) => e,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+            return Ok(Vec::new());
+        }
+        Err(e) => {
+            return Err(history_error(
+                format!("Failed to read folder {}", dir.display()),
+                e,
+            ));
+        }
+    };
+    let mut ret = Vec::new();
+    for id in get_revision_ids(entries) {
+        // Do not let a single broken file hide all other revisions
+        match read_revision(&dir.join(format!("{}.json", id))) {
+            Ok(rev) => ret.push(rev),
+            Err(e) => log::warn!("Ignoring revision file: {}", e),
+        }
+    }
+    ret.sort_unstable_by_key(|r| r.id);
+    Ok(ret)
+}
+
+// Revision IDs from the `<id>.json` file names, without reading the files.
+fn get_revision_ids(entries: std::fs::ReadDir) -> Vec<u64> {
+    let mut ret: Vec<u64> = entries
+        .filter_map(|e| e.ok())
+        .filter_map(|e| {
+            e.file_name()
+                .to_str()?
+                .strip_suffix(".json")?
+                .parse::<u64>()
+                .ok()
+        })
+        .collect();
+    ret.sort_unstable();
+    ret
+}
+
+pub(crate) fn history_get_from_dir(
+    dir: &Path,
+    id: u64,
+) -> Result<NetworkStateRevision, NmstateError> {
+    let path = dir.join(format!("{}.json", id));
+    if !path.exists() {
+        let e = NmstateError::new(
+            ErrorKind::InvalidArgument,
+            format!("Revision {} not found in {}", id, dir.display()),
+        );
+        log::error!("{}", e);
+        return Err(e);
+    }
+    read_revision(&path).map_err(|e| {
+        log::error!("{}", e);
+        e
+    })
+}
+
+fn read_revision(path: &Path) -> Result<NetworkStateRevision, NmstateError> {
+    let content = std::fs::read_to_string(path).map_err(|e| {
+        NmstateError::new(
+            ErrorKind::PluginFailure,
+            format!("Failed to read {}: {}", path.display(), e),
+        )
+    })?;
+    serde_json::from_str(&content).map_err(|e| {
+        NmstateError::new(
+            ErrorKind::InvalidArgument,
+            format!("Invalid revision file {}: {}", path.display(), e),
+        )
+    })
+}
+
+#[cfg(feature = "query_apply")]
+pub(crate) fn history_record(
+    desired: &NetworkState,
+    pre_apply: &NetworkState,
+    checkpoint: &str,
+    started: u64,
+) -> Result<u64, NmstateError> {
+    let mut result = NetworkState::new();
+    result.set_kernel_only(desired.kernel_only);
+    result.set_include_secrets(true);
+    result.set_running_config_only(true);
+    result.retrieve()?;
+
+    let mut rev = NetworkStateRevision::new();
+    rev.user = get_user();
+    rev.started = started;
+    rev.finished = now_secs();
+    rev.checkpoint = checkpoint.to_string();
+    rev.no_commit = desired.no_commit;
+    rev.desired = desired.clone();
+    rev.pre_apply = pre_apply.clone();
+    rev.result = result;
+    let id = history_save_to_dir(Path::new(HISTORY_DIR), rev)?;
+    if let Err(e) =
+        history_prune_dir(Path::new(HISTORY_DIR), HISTORY_MAX_REVISIONS)
+    {
+        log::warn!("Failed to remove old revisions: {}", e);
+    }
+    Ok(id)
+}
+
+// Store the revision with the next free ID and return that ID.
+// The content is written to a temporary file first and then linked to the
+// revision file, so no empty or partial revision file is left on failure.
+// Unlike rename, link never overrides the revision file stored by another
+// nmstate process.
+#[cfg(feature = "query_apply")]
+pub(crate) fn history_save_to_dir(
+    dir: &Path,
+    mut rev: NetworkStateRevision,
+) -> Result<u64, NmstateError> {
+    std::fs::create_dir_all(dir).map_err(|e| {
+        history_error(format!("Failed to create folder {}", dir.display()), e)
+    })?;
+    rev.id = std::fs::read_dir(dir)
+        .map(get_revision_ids)
+        .map_err(|e| {
+            history_error(format!("Failed to read folder {}", dir.display()), e)
+        })?
+        .last()
+        .copied()
+        .unwrap_or_default();
+    let tmp_path = dir.join(format!(".{}.tmp", std::process::id()));
+    let result = loop {
+        rev.id += 1;
+        if let Err(e) = write_revision(&tmp_path, &rev) {
+            break Err(e);
+        }
+        let path = dir.join(format!("{}.json", rev.id));
+        match std::fs::hard_link(&tmp_path, &path) {
+            Ok(()) => {
+                log::info!(
+                    "Recorded revision {} in {}",
+                    rev.id,
+                    path.display()
+                );
+                break Ok(rev.id);
+            }
+            // Another nmstate process took the same ID, try the next one.
+            Err(e) if e.kind() == std::io::ErrorKind::AlreadyExists => (),
+            Err(e) => {
+                break Err(history_error(
+                    format!("Failed to create {}", path.display()),
+                    e,
+                ));
+            }
+        }
+    };
+    if let Err(e) = std::fs::remove_file(&tmp_path) {
+        log::warn!("Failed to remove {}: {}", tmp_path.display(), e);
+    }
+    result
+}
+
+// Remove the oldest revisions, keeping the newest `max_revisions` ones.
+#[cfg(feature = "query_apply")]
+pub(crate) fn history_prune_dir(
+    dir: &Path,
+    max_revisions: usize,
+) -> Result<(), NmstateError> {
+    let ids = std::fs::read_dir(dir).map(get_revision_ids).map_err(|e| {
+        history_error(format!("Failed to read folder {}", dir.display()), e)
+    })?;
+    for id in &ids[..ids.len().saturating_sub(max_revisions)] {
+        let path = dir.join(format!("{}.json", id));
+        // Might be removed by another nmstate process already
+        match std::fs::remove_file(&path) {
+            Ok(()) => log::info!("Removed old revision {}", path.display()),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => (),
+            Err(e) => {
+                return Err(history_error(
+                    format!("Failed to remove {}", path.display()),
+                    e,
+                ));
+            }
+        }
+    }
+    Ok(())
+}
+
+// The states stored include secrets, hence only readable by owner.
+#[cfg(feature = "query_apply")]
+fn write_revision(
+    path: &Path,
+    rev: &NetworkStateRevision,
+) -> Result<(), NmstateError> {
+    use std::io::Write;
+    use std::os::unix::fs::OpenOptionsExt;
+
+    let content = serde_json::to_string_pretty(rev).map_err(|e| {
+        NmstateError::new(
+            ErrorKind::Bug,
+            format!("Failed to serialize revision {}: {}", rev.id, e),
+        )
+    })?;
+    let mut fd = std::fs::OpenOptions::new()
+        .write(true)
+        .create(true)
+        .truncate(true)
+        .mode(0o600)
+        .open(path)
+        .map_err(|e| {
+            history_error(format!("Failed to create {}", path.display()), e)
+        })?;
+    fd.write_all(content.as_bytes()).map_err(|e| {
+        history_error(format!("Failed to write {}", path.display()), e)
+    })
+}
+
+// Prefer the user invoking sudo over root.
+#[cfg(feature = "query_apply")]
+fn get_user() -> String {
+    use std::os::unix::fs::MetadataExt;
+
+    for env_name in ["SUDO_USER", "USER"] {
+        if let Ok(user) = std::env::var(env_name) {
+            if !user.is_empty() {
+                return user;
+            }
+        }
+    }
+    match std::fs::metadata("/proc/self") {
+        Ok(m) => format!("uid {}", m.uid()),
+        Err(_) => "unknown".to_string(),
+    }
+}
+
+fn history_error(action: String, e: std::io::Error) -> NmstateError {
+    let e = NmstateError::new(
+        ErrorKind::PluginFailure,
+        format!("{}: {}", action, e),
+    );
+    log::error!("{}", e);
+    e
+}
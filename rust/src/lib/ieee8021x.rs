@@ -3,7 +3,8 @@ use serde::{Deserialize, Serialize};
 use crate::NetworkState;
 
 #[derive(Debug, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
-#[serde(rename_all = "kebab-case")]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "kebab-case", deny_unknown_fields)]
 #[non_exhaustive]
 pub struct Ieee8021XConfig {
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -11,6 +11,7 @@ use crate::{
 
 // TODO: Use prop_list to Serialize like InterfaceIpv4 did
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 #[serde(rename_all = "kebab-case", deny_unknown_fields)]
 #[non_exhaustive]
 pub struct BaseInterface {
@@ -21,12 +22,23 @@ pub struct BaseInterface {
     pub prop_list: Vec<&'static str>,
     #[serde(rename = "type", default = "default_iface_type")]
     pub iface_type: InterfaceType,
+    // How to find the kernel interface, by `name` if not defined.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub identifier: Option<InterfaceIdentifier>,
+    // The name of interface in desire state when it is identified by
+    // `mac-address` or `pci-address` and resolved to a different kernel
+    // interface name. Used as NetworkManager profile name.
+    #[serde(skip)]
+    pub(crate) profile_name: Option<String>,
     #[serde(default = "default_state")]
     pub state: InterfaceState,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub mac_address: Option<String>,
     #[serde(skip)]
     pub permanent_mac_address: Option<String>,
+    // PCI bus address like `0000:03:00.0`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub pci_address: Option<String>,
     #[serde(
         skip_serializing_if = "Option::is_none",
         default,
@@ -37,6 +49,9 @@ pub struct BaseInterface {
     pub min_mtu: Option<u64>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub max_mtu: Option<u64>,
+    // Kernel driver of the device, query only.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub driver: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub wait_ip: Option<WaitIp>,
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -76,7 +91,9 @@ pub struct BaseInterface {
     pub(crate) routes: Option<Vec<RouteEntry>>,
     #[serde(skip)]
     pub(crate) rules: Option<Vec<RouteRuleEntry>>,
+    // Unknown properties, rejected by `NetworkState::validate_offline()`
     #[serde(flatten)]
+    #[cfg_attr(feature = "schema", schemars(skip))]
     pub _other: serde_json::Map<String, serde_json::Value>,
 }
 
@@ -88,10 +105,16 @@ impl BaseInterface {
         self.validate_mtu(current)?;
         validate_mptcp(self)?;
         validate_wait_ip(self)?;
+        self.validate_identifier()?;
 
-        // Do not allow changing min_mtu and max_mtu
+        // Do not allow changing min_mtu, max_mtu and driver
         self.max_mtu = None;
         self.min_mtu = None;
+        self.driver = None;
+        // PCI address is query only unless used as identifier
+        if self.identifier != Some(InterfaceIdentifier::PciAddress) {
+            self.pci_address = None;
+        }
         if !self.can_have_ip()
             && (self.ipv4.as_ref().map(|ipv4| ipv4.enabled) == Some(true)
                 || self.ipv6.as_ref().map(|ipv6| ipv6.enabled) == Some(true))
@@ -186,10 +209,52 @@ impl BaseInterface {
             name: self.name.clone(),
             iface_type: self.iface_type.clone(),
             state: InterfaceState::Up,
+            identifier: self.identifier,
+            profile_name: self.profile_name.clone(),
+            mac_address: match self.identifier {
+                Some(InterfaceIdentifier::MacAddress) => {
+                    self.mac_address.clone()
+                }
+                _ => None,
+            },
+            pci_address: match self.identifier {
+                Some(InterfaceIdentifier::PciAddress) => {
+                    self.pci_address.clone()
+                }
+                _ => None,
+            },
             ..Default::default()
         }
     }
 
+    fn validate_identifier(&self) -> Result<(), NmstateError> {
+        let (prop_name, prop_value) = match self.identifier {
+            Some(InterfaceIdentifier::MacAddress) => {
+                ("mac-address", self.mac_address.as_ref())
+            }
+            Some(InterfaceIdentifier::PciAddress) => {
+                ("pci-address", self.pci_address.as_ref())
+            }
+            _ => return Ok(()),
+        };
+        if prop_value.is_none() {
+            let e = NmstateError::new(
+                ErrorKind::InvalidArgument,
+                format!(
+                    "Interface {} is identified by {} but no {} defined",
+                    self.name, prop_name, prop_name
+                ),
+            );
+            error!("{}", e);
+            return Err(e);
+        }
+        Ok(())
+    }
+
+    pub(crate) fn is_identified_by_name(&self) -> bool {
+        matches!(self.identifier, None | Some(InterfaceIdentifier::Name))
+    }
+
     pub(crate) fn copy_ip_config_if_none(&mut self, current: &Self) {
         if self.ipv4.is_none() {
             self.ipv4 = current.ipv4.clone();
@@ -206,6 +271,16 @@ impl BaseInterface {
     }
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "kebab-case")]
+#[non_exhaustive]
+pub enum InterfaceIdentifier {
+    Name,
+    MacAddress,
+    PciAddress,
+}
+
 fn default_state() -> InterfaceState {
     InterfaceState::Up
 }
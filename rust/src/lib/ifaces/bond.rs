@@ -2,12 +2,15 @@ use std::convert::TryFrom;
 
 use serde::{Deserialize, Serialize};
 
+#[cfg(feature = "gen_conf")]
+use crate::deserializer::get_struct_field_names;
 use crate::{
     deserializer::NumberAsString, BaseInterface, ErrorKind, Interface,
     InterfaceType, NmstateError,
 };
 
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 #[serde(rename_all = "kebab-case")]
 #[non_exhaustive]
 pub struct BondInterface {
@@ -145,6 +148,7 @@ impl BondInterface {
 }
 
 #[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone, Copy)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 #[non_exhaustive]
 #[serde(try_from = "NumberAsString")]
 pub enum BondMode {
@@ -211,6 +215,7 @@ impl std::fmt::Display for BondMode {
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 #[serde(rename_all = "kebab-case", deny_unknown_fields)]
 #[non_exhaustive]
 pub struct BondConfig {
@@ -236,6 +241,7 @@ impl BondConfig {
 }
 
 #[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone, Copy)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 #[non_exhaustive]
 #[serde(rename_all = "kebab-case")]
 #[serde(try_from = "NumberAsString")]
@@ -289,6 +295,7 @@ impl std::fmt::Display for BondAdSelect {
 }
 
 #[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 #[serde(rename_all = "kebab-case", try_from = "NumberAsString")]
 #[non_exhaustive]
 pub enum BondLacpRate {
@@ -328,6 +335,7 @@ impl std::fmt::Display for BondLacpRate {
 }
 
 #[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone, Copy)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 #[serde(rename_all = "kebab-case", try_from = "NumberAsString")]
 #[non_exhaustive]
 pub enum BondAllPortsActive {
@@ -376,6 +384,7 @@ impl From<BondAllPortsActive> for u8 {
 }
 
 #[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 #[serde(rename_all = "kebab-case", try_from = "NumberAsString")]
 #[non_exhaustive]
 pub enum BondArpAllTargets {
@@ -415,6 +424,7 @@ impl std::fmt::Display for BondArpAllTargets {
 }
 
 #[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 #[serde(rename_all = "kebab-case", try_from = "NumberAsString")]
 #[non_exhaustive]
 pub enum BondArpValidate {
@@ -470,6 +480,7 @@ impl std::fmt::Display for BondArpValidate {
 }
 
 #[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone, Copy)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 #[serde(rename_all = "kebab-case", try_from = "NumberAsString")]
 #[non_exhaustive]
 pub enum BondFailOverMac {
@@ -512,6 +523,7 @@ impl std::fmt::Display for BondFailOverMac {
 }
 
 #[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 #[serde(rename_all = "kebab-case", try_from = "NumberAsString")]
 #[non_exhaustive]
 pub enum BondPrimaryReselect {
@@ -554,6 +566,7 @@ impl std::fmt::Display for BondPrimaryReselect {
 }
 
 #[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 #[serde(try_from = "NumberAsString")]
 #[non_exhaustive]
 pub enum BondXmitHashPolicy {
@@ -625,6 +638,7 @@ impl std::fmt::Display for BondXmitHashPolicy {
 }
 
 #[derive(Debug, Serialize, Deserialize, Default, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 #[non_exhaustive]
 #[serde(deny_unknown_fields)]
 pub struct BondOptions {
@@ -779,4 +793,66 @@ impl BondOptions {
         }
         Ok(())
     }
+
+    // Option name and value pairs in the format of kernel sysfs, sorted by
+    // option name.
+    #[cfg(any(feature = "query_apply", feature = "gen_conf"))]
+    pub(crate) fn to_kernel_options(&self) -> Vec<(String, String)> {
+        let mut ret = Vec::new();
+        if let Ok(serde_json::Value::Object(opts)) = serde_json::to_value(self)
+        {
+            for (name, value) in opts.iter() {
+                let value = match value {
+                    serde_json::Value::Bool(b) => {
+                        if *b { "1" } else { "0" }.to_string()
+                    }
+                    serde_json::Value::String(s) => s.to_string(),
+                    v => v.to_string(),
+                };
+                ret.push((name.to_string(), value));
+            }
+        }
+        if let Some(v) = self.all_slaves_active {
+            for (name, value) in ret.iter_mut() {
+                if name == "all_slaves_active" {
+                    *value = u8::from(v).to_string();
+                }
+            }
+        }
+        ret.sort_unstable();
+        ret
+    }
+
+    // Build from option name and value pairs in the format of kernel sysfs.
+    // The names of options nmstate does not support are returned along.
+    #[cfg(feature = "gen_conf")]
+    pub(crate) fn from_kernel_options(
+        opts: &[(String, String)],
+    ) -> Result<(Self, Vec<String>), NmstateError> {
+        let mut supported = serde_json::Map::new();
+        let mut unsupported = Vec::new();
+        for (name, value) in opts {
+            if !get_struct_field_names::<Self>().contains(&name.as_str()) {
+                unsupported.push(name.to_string());
+                continue;
+            }
+            let mut opt = serde_json::Map::new();
+            opt.insert(name.to_string(), value.as_str().into());
+            if let Err(e) = serde_json::from_value::<Self>(opt.into()) {
+                return Err(NmstateError::new(
+                    ErrorKind::InvalidArgument,
+                    format!("Invalid bond option {}={}: {}", name, value, e),
+                ));
+            }
+            supported.insert(name.to_string(), value.as_str().into());
+        }
+        let bond_opts =
+            serde_json::from_value(supported.into()).map_err(|e| {
+                NmstateError::new(
+                    ErrorKind::InvalidArgument,
+                    format!("Invalid bond options: {}", e),
+                )
+            })?;
+        Ok((bond_opts, unsupported))
+    }
 }
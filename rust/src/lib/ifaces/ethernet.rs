@@ -6,6 +6,7 @@ use crate::{
 };
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 #[non_exhaustive]
 pub struct EthernetInterface {
     #[serde(flatten)]
@@ -49,18 +50,10 @@ impl EthernetInterface {
     pub fn new() -> Self {
         Self::default()
     }
-
-    pub(crate) fn sriov_is_enabled(&self) -> bool {
-        self.ethernet
-            .as_ref()
-            .and_then(|eth_conf| {
-                eth_conf.sr_iov.as_ref().map(SrIovConfig::sriov_is_enabled)
-            })
-            .unwrap_or_default()
-    }
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 #[serde(rename_all = "kebab-case")]
 #[non_exhaustive]
 pub enum EthernetDuplex {
@@ -82,6 +75,7 @@ impl std::fmt::Display for EthernetDuplex {
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 #[serde(rename_all = "kebab-case", deny_unknown_fields)]
 #[non_exhaustive]
 pub struct EthernetConfig {
@@ -111,7 +105,9 @@ impl EthernetConfig {
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 #[non_exhaustive]
+#[serde(deny_unknown_fields)]
 pub struct VethConfig {
     pub peer: String,
 }
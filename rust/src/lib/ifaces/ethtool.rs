@@ -28,6 +28,7 @@ const ETHTOOL_FEATURE_CLI_ALIAS: [(&str, &str); 17] = [
 pub type EthtoolFeatureConfig = HashMap<String, bool>;
 
 #[derive(Serialize, Deserialize, Debug, Eq, PartialEq, Clone, Default)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 #[non_exhaustive]
 #[serde(deny_unknown_fields)]
 pub struct EthtoolConfig {
@@ -69,6 +70,7 @@ impl EthtoolConfig {
 #[derive(
     Serialize, Deserialize, Debug, Eq, PartialEq, Clone, Default, Copy,
 )]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 #[non_exhaustive]
 #[serde(deny_unknown_fields)]
 pub struct EthtoolPauseConfig {
@@ -101,6 +103,7 @@ impl EthtoolPauseConfig {
 #[derive(
     Serialize, Deserialize, Debug, Eq, PartialEq, Clone, Default, Copy,
 )]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 #[serde(rename_all = "kebab-case", deny_unknown_fields)]
 #[non_exhaustive]
 pub struct EthtoolCoalesceConfig {
@@ -245,6 +248,7 @@ impl EthtoolCoalesceConfig {
 }
 
 #[derive(Serialize, Deserialize, Debug, Eq, PartialEq, Clone, Default)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 #[serde(rename_all = "kebab-case", deny_unknown_fields)]
 #[non_exhaustive]
 pub struct EthtoolRingConfig {
@@ -5,6 +5,7 @@ use serde::{Deserialize, Serialize, Serializer};
 use crate::{BaseInterface, InterfaceType};
 
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 #[serde(rename_all = "kebab-case")]
 #[non_exhaustive]
 pub struct InfiniBandInterface {
@@ -29,6 +30,7 @@ impl InfiniBandInterface {
 }
 
 #[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone, Copy)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 #[serde(rename_all = "kebab-case")]
 #[non_exhaustive]
 pub enum InfiniBandMode {
@@ -56,6 +58,7 @@ impl std::fmt::Display for InfiniBandMode {
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 #[serde(rename_all = "kebab-case", deny_unknown_fields)]
 #[non_exhaustive]
 pub struct InfiniBandConfig {
@@ -66,6 +69,10 @@ pub struct InfiniBandConfig {
         serialize_with = "show_as_hex",
         deserialize_with = "crate::deserializer::option_u16_or_string"
     )]
+    #[cfg_attr(
+        feature = "schema",
+        schemars(with = "Option<crate::schema::json_schema::IntegerOrString>")
+    )]
     pub pkey: Option<u16>,
 }
 
@@ -13,7 +13,6 @@ use crate::{
         preserve_ctrl_cfg_if_unchanged, set_ifaces_up_priority,
         validate_new_ovs_iface_has_controller,
     },
-    ifaces::sriov::check_sriov_capability,
     ErrorKind, Interface, InterfaceState, InterfaceType, NmstateError,
 };
 
@@ -134,9 +133,6 @@ impl Interfaces {
         self.set_up_priority()?;
         check_overbook_ports(self, current)?;
         check_infiniband_as_ports(self, current)?;
-        if !current.kernel_ifaces.is_empty() {
-            check_sriov_capability(self)?;
-        }
 
         for iface in self.to_vec() {
             if iface.is_absent() {
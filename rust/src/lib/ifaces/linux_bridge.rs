@@ -13,6 +13,7 @@ use crate::{
 };
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 #[non_exhaustive]
 pub struct LinuxBridgeInterface {
     #[serde(flatten)]
@@ -114,6 +115,7 @@ impl LinuxBridgeInterface {
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 #[serde(rename_all = "kebab-case", deny_unknown_fields)]
 #[non_exhaustive]
 pub struct LinuxBridgeConfig {
@@ -145,6 +147,7 @@ impl LinuxBridgeConfig {
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 #[serde(rename_all = "kebab-case", deny_unknown_fields)]
 #[non_exhaustive]
 pub struct LinuxBridgePortConfig {
@@ -198,9 +201,33 @@ impl LinuxBridgePortConfig {
             .as_ref()
             .map_or(true, |v| *v != BridgePortVlanConfig::default())
     }
+
+    // Set port option from the name and value in the format of kernel sysfs.
+    // Return false if nmstate has no equivalent of this option.
+    #[cfg(feature = "gen_conf")]
+    pub(crate) fn set_kernel_option(
+        &mut self,
+        name: &str,
+        value: &str,
+    ) -> Result<bool, NmstateError> {
+        match name {
+            "priority" => {
+                self.stp_priority = Some(parse_kernel_opt(name, value)?)
+            }
+            "path_cost" => {
+                self.stp_path_cost = Some(parse_kernel_opt(name, value)?)
+            }
+            "hairpin_mode" => {
+                self.stp_hairpin_mode = Some(parse_kernel_bool(name, value)?)
+            }
+            _ => return Ok(false),
+        }
+        Ok(true)
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 #[serde(rename_all = "kebab-case", deny_unknown_fields)]
 #[non_exhaustive]
 pub struct LinuxBridgeOptions {
@@ -327,9 +354,211 @@ impl LinuxBridgeOptions {
             .transpose()?;
         Ok(())
     }
+
+    // Set bridge option from the name and value in the format of kernel
+    // sysfs, the STP timers are in seconds like nmstate does.
+    // Return false if nmstate has no equivalent of this option.
+    #[cfg(feature = "gen_conf")]
+    pub(crate) fn set_kernel_option(
+        &mut self,
+        name: &str,
+        value: &str,
+    ) -> Result<bool, NmstateError> {
+        match name {
+            "stp_state" => {
+                self.stp_mut().enabled = Some(parse_kernel_bool(name, value)?)
+            }
+            "forward_delay" => {
+                self.stp_mut().forward_delay =
+                    Some(parse_kernel_opt(name, value)?)
+            }
+            "hello_time" => {
+                self.stp_mut().hello_time = Some(parse_kernel_opt(name, value)?)
+            }
+            "max_age" => {
+                self.stp_mut().max_age = Some(parse_kernel_opt(name, value)?)
+            }
+            "priority" => {
+                self.stp_mut().priority = Some(parse_kernel_opt(name, value)?)
+            }
+            "ageing_time" => {
+                self.mac_ageing_time = Some(parse_kernel_opt(name, value)?)
+            }
+            "group_addr" => self.group_addr = Some(value.to_string()),
+            "group_fwd_mask" => {
+                self.group_fwd_mask = Some(parse_kernel_opt(name, value)?)
+            }
+            "hash_max" => self.hash_max = Some(parse_kernel_opt(name, value)?),
+            "multicast_last_member_count" => {
+                self.multicast_last_member_count =
+                    Some(parse_kernel_opt(name, value)?)
+            }
+            "multicast_querier" => {
+                self.multicast_querier = Some(parse_kernel_bool(name, value)?)
+            }
+            "multicast_query_use_ifaddr" => {
+                self.multicast_query_use_ifaddr =
+                    Some(parse_kernel_bool(name, value)?)
+            }
+            "multicast_router" => {
+                self.multicast_router = Some(match value.parse::<u64>() {
+                    Ok(v) => LinuxBridgeMulticastRouterType::try_from(v)?,
+                    Err(_) => LinuxBridgeMulticastRouterType::from_str(value)?,
+                })
+            }
+            "multicast_snooping" => {
+                self.multicast_snooping = Some(parse_kernel_bool(name, value)?)
+            }
+            "multicast_startup_query_count" => {
+                self.multicast_startup_query_count =
+                    Some(parse_kernel_opt(name, value)?)
+            }
+            _ => return Ok(false),
+        }
+        Ok(true)
+    }
+
+    #[cfg(feature = "gen_conf")]
+    fn stp_mut(&mut self) -> &mut LinuxBridgeStpOptions {
+        self.stp.get_or_insert_with(LinuxBridgeStpOptions::new)
+    }
+
+    // Option name and value pairs in the format of kernel sysfs, sorted by
+    // option name. The STP timers and MAC ageing time are converted from
+    // seconds to the 1/100 seconds used by kernel.
+    #[cfg(feature = "query_apply")]
+    pub(crate) fn to_kernel_options(&self) -> Vec<(String, String)> {
+        let mut ret = Vec::new();
+        if let Some(stp_opts) = self.stp.as_ref() {
+            if let Some(v) = stp_opts.enabled {
+                ret.push(("stp_state", u8::from(v).to_string()));
+            }
+            for (name, value) in [
+                ("forward_delay", stp_opts.forward_delay),
+                ("hello_time", stp_opts.hello_time),
+                ("max_age", stp_opts.max_age),
+            ] {
+                if let Some(v) = value {
+                    ret.push((name, (u32::from(v) * 100).to_string()));
+                }
+            }
+            if let Some(v) = stp_opts.priority {
+                ret.push(("priority", v.to_string()));
+            }
+        }
+        if let Some(v) = self.mac_ageing_time {
+            ret.push(("ageing_time", (u64::from(v) * 100).to_string()));
+        }
+        if let Some(v) = self.group_addr.as_ref() {
+            ret.push(("group_addr", v.to_string()));
+        }
+        if let Some(v) = self.group_fwd_mask.or(self.group_forward_mask) {
+            ret.push(("group_fwd_mask", v.to_string()));
+        }
+        if let Some(v) = self.hash_max {
+            ret.push(("hash_max", v.to_string()));
+        }
+        for (name, value) in [
+            (
+                "multicast_last_member_count",
+                self.multicast_last_member_count,
+            ),
+            (
+                "multicast_startup_query_count",
+                self.multicast_startup_query_count,
+            ),
+        ] {
+            if let Some(v) = value {
+                ret.push((name, v.to_string()));
+            }
+        }
+        // Multicast intervals of nmstate are already in 1/100 seconds.
+        for (name, value) in [
+            (
+                "multicast_last_member_interval",
+                self.multicast_last_member_interval,
+            ),
+            (
+                "multicast_membership_interval",
+                self.multicast_membership_interval,
+            ),
+            (
+                "multicast_querier_interval",
+                self.multicast_querier_interval,
+            ),
+            ("multicast_query_interval", self.multicast_query_interval),
+            (
+                "multicast_query_response_interval",
+                self.multicast_query_response_interval,
+            ),
+            (
+                "multicast_startup_query_interval",
+                self.multicast_startup_query_interval,
+            ),
+        ] {
+            if let Some(v) = value {
+                ret.push((name, v.to_string()));
+            }
+        }
+        for (name, value) in [
+            ("multicast_querier", self.multicast_querier),
+            (
+                "multicast_query_use_ifaddr",
+                self.multicast_query_use_ifaddr,
+            ),
+            ("multicast_snooping", self.multicast_snooping),
+        ] {
+            if let Some(v) = value {
+                ret.push((name, u8::from(v).to_string()));
+            }
+        }
+        if let Some(v) = self.multicast_router.as_ref() {
+            ret.push(("multicast_router", (v.clone() as u8).to_string()));
+        }
+        if let Some(v) = self.vlan_protocol {
+            ret.push((
+                "vlan_protocol",
+                match v {
+                    VlanProtocol::Ieee8021Q => "0x8100",
+                    VlanProtocol::Ieee8021Ad => "0x88a8",
+                }
+                .to_string(),
+            ));
+        }
+        let mut ret: Vec<(String, String)> =
+            ret.into_iter().map(|(n, v)| (n.to_string(), v)).collect();
+        ret.sort_unstable();
+        ret
+    }
+}
+
+#[cfg(feature = "gen_conf")]
+fn parse_kernel_opt<T: FromStr>(
+    name: &str,
+    value: &str,
+) -> Result<T, NmstateError> {
+    value.parse::<T>().map_err(|_| {
+        NmstateError::new(
+            ErrorKind::InvalidArgument,
+            format!("Invalid value {} for bridge option {}", value, name),
+        )
+    })
+}
+
+#[cfg(feature = "gen_conf")]
+fn parse_kernel_bool(name: &str, value: &str) -> Result<bool, NmstateError> {
+    match value {
+        "1" | "yes" | "on" | "true" => Ok(true),
+        "0" | "no" | "off" | "false" => Ok(false),
+        _ => Err(NmstateError::new(
+            ErrorKind::InvalidArgument,
+            format!("Invalid value {} for bridge option {}", value, name),
+        )),
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 #[serde(rename_all = "kebab-case", deny_unknown_fields)]
 #[non_exhaustive]
 pub struct LinuxBridgeStpOptions {
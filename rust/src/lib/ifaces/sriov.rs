@@ -2,9 +2,8 @@
 
 use serde::{Deserialize, Serialize};
 
-use crate::{ErrorKind, Interface, Interfaces, NmstateError};
-
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 #[serde(rename_all = "kebab-case", deny_unknown_fields)]
 #[non_exhaustive]
 pub struct SrIovConfig {
@@ -22,13 +21,10 @@ impl SrIovConfig {
     pub fn new() -> Self {
         Self::default()
     }
-
-    pub(crate) fn sriov_is_enabled(&self) -> bool {
-        matches!(self.total_vfs, Some(i) if i > 0)
-    }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 #[serde(rename_all = "kebab-case", deny_unknown_fields)]
 #[non_exhaustive]
 pub struct SrIovVfConfig {
@@ -81,32 +77,3 @@ impl SrIovVfConfig {
         Self::default()
     }
 }
-
-pub(crate) fn check_sriov_capability(
-    ifaces: &Interfaces,
-) -> Result<(), NmstateError> {
-    for iface in ifaces.kernel_ifaces.values() {
-        if let Interface::Ethernet(eth_iface) = iface {
-            if eth_iface.sriov_is_enabled() && !is_sriov_supported(iface.name())
-            {
-                let e = NmstateError::new(
-                    ErrorKind::NotSupportedError,
-                    format!(
-                        "SR-IOV is not supported by interface {}",
-                        iface.name()
-                    ),
-                );
-                log::error!("{}", e);
-                return Err(e);
-            }
-        }
-    }
-    Ok(())
-}
-
-// Checking existence of file:
-//      /sys/class/net/<iface_name>/device/sriov_numvfs
-fn is_sriov_supported(iface_name: &str) -> bool {
-    let path = format!("/sys/class/net/{}/device/sriov_numvfs", iface_name);
-    std::path::Path::new(&path).exists()
-}
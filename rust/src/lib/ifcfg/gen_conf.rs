@@ -0,0 +1,435 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use std::fmt::Write;
+
+use crate::{
+    gen_conf::get_unsupported_legacy_props,
+    ip::is_ipv6_addr,
+    iproute::{route_to_iproute_args, rule_to_iproute_args},
+    BaseInterface, BondInterface, Interface, InterfaceIpv4, InterfaceIpv6,
+    InterfaceType, LinuxBridgeInterface, LinuxBridgePortConfig, NetworkState,
+    NmstateError, VlanInterface,
+};
+
+const IFCFG_BACKEND_NAME: &str = "ifcfg-rh";
+
+// Generate the /etc/sysconfig/network-scripts files for each interface:
+//  * ifcfg-<iface>
+//  * route-<iface> and route6-<iface>
+//  * rule-<iface> and rule6-<iface>
+pub(crate) fn ifcfg_gen_conf(
+    net_state: &NetworkState,
+) -> Result<Vec<(String, String)>, NmstateError> {
+    let mut ret = Vec::new();
+    if net_state
+        .hostname
+        .as_ref()
+        .and_then(|c| c.config.as_ref())
+        .is_some()
+    {
+        log::warn!(
+            "Cannot store hostname configuration to ifcfg files, \
+            please edit /etc/hostname manually"
+        );
+    }
+    let mut ifaces = net_state.interfaces.to_vec();
+    ifaces.sort_unstable_by_key(|i| i.name());
+    for iface in ifaces {
+        if !iface.is_up() {
+            log::warn!(
+                "ignoring iface {} because is down or absent",
+                iface.name(),
+            );
+            continue;
+        }
+        let mut ifcfg = IfcfgFile::new();
+        gen_base_ifcfg(iface.base_iface(), &mut ifcfg);
+        match iface {
+            Interface::Ethernet(_) => {
+                ifcfg.push("TYPE", "Ethernet");
+            }
+            Interface::InfiniBand(_) => {
+                ifcfg.push("TYPE", "InfiniBand");
+            }
+            Interface::Bond(bond_iface) => {
+                gen_bond_ifcfg(bond_iface, &mut ifcfg);
+            }
+            Interface::LinuxBridge(br_iface) => {
+                gen_bridge_ifcfg(br_iface, &mut ifcfg);
+            }
+            Interface::Vlan(vlan_iface) => {
+                gen_vlan_ifcfg(vlan_iface, &mut ifcfg);
+            }
+            _ => {
+                log::warn!(
+                    "Interface type {} of interface {} is not supported \
+                    by {}, ignoring",
+                    iface.iface_type(),
+                    iface.name(),
+                    IFCFG_BACKEND_NAME
+                );
+                continue;
+            }
+        }
+        for prop_path in get_unsupported_legacy_props(iface) {
+            log::warn!(
+                "Property {} is not supported by {}, ignoring",
+                prop_path,
+                IFCFG_BACKEND_NAME
+            );
+        }
+        gen_controller_ifcfg(iface.base_iface(), net_state, &mut ifcfg);
+        gen_ipv4_ifcfg(iface.base_iface().ipv4.as_ref(), &mut ifcfg);
+        gen_ipv6_ifcfg(iface.base_iface().ipv6.as_ref(), &mut ifcfg);
+        gen_dns_ifcfg(iface.base_iface(), &mut ifcfg);
+
+        ret.push((format!("ifcfg-{}", iface.name()), ifcfg.to_string()));
+        ret.extend(gen_route_files(iface.base_iface()));
+        ret.extend(gen_rule_files(iface.base_iface()));
+    }
+    Ok(ret)
+}
+
+#[derive(Debug, Default)]
+struct IfcfgFile {
+    lines: Vec<(String, String)>,
+}
+
+impl IfcfgFile {
+    fn new() -> Self {
+        Self::default()
+    }
+
+    fn push(&mut self, key: &str, value: &str) {
+        self.lines.push((key.to_string(), value.to_string()));
+    }
+}
+
+impl std::fmt::Display for IfcfgFile {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (key, value) in self.lines.iter() {
+            if value.contains(char::is_whitespace) {
+                writeln!(f, "{}=\"{}\"", key, value)?;
+            } else {
+                writeln!(f, "{}={}", key, value)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+fn gen_base_ifcfg(base_iface: &BaseInterface, ifcfg: &mut IfcfgFile) {
+    ifcfg.push("DEVICE", &base_iface.name);
+    ifcfg.push("NAME", &base_iface.name);
+    ifcfg.push("ONBOOT", "yes");
+    if let Some(mac) = base_iface.mac_address.as_ref() {
+        ifcfg.push("MACADDR", mac);
+    }
+    if let Some(mtu) = base_iface.mtu {
+        ifcfg.push("MTU", &mtu.to_string());
+    }
+}
+
+fn gen_controller_ifcfg(
+    base_iface: &BaseInterface,
+    net_state: &NetworkState,
+    ifcfg: &mut IfcfgFile,
+) {
+    let ctrl_name = match base_iface.controller.as_deref() {
+        Some(c) if !c.is_empty() => c,
+        _ => return,
+    };
+    match base_iface.controller_type.as_ref() {
+        Some(InterfaceType::Bond) => {
+            ifcfg.push("MASTER", ctrl_name);
+            ifcfg.push("SLAVE", "yes");
+        }
+        Some(InterfaceType::LinuxBridge) => {
+            ifcfg.push("BRIDGE", ctrl_name);
+            if let Some(Interface::LinuxBridge(br_iface)) = net_state
+                .interfaces
+                .get_iface(ctrl_name, InterfaceType::LinuxBridge)
+            {
+                if let Some(port_conf) =
+                    br_iface.get_port_conf(&base_iface.name)
+                {
+                    gen_bridge_port_ifcfg(ctrl_name, port_conf, ifcfg);
+                }
+            }
+        }
+        Some(ctrl_type) => {
+            log::warn!(
+                "Controller type {} of interface {} is not supported by {}, \
+                ignoring property interfaces.{}.controller",
+                ctrl_type,
+                base_iface.name,
+                IFCFG_BACKEND_NAME,
+                base_iface.name,
+            );
+        }
+        None => (),
+    }
+}
+
+fn gen_bond_ifcfg(bond_iface: &BondInterface, ifcfg: &mut IfcfgFile) {
+    ifcfg.push("TYPE", "Bond");
+    ifcfg.push("BONDING_MASTER", "yes");
+    let mut opts = Vec::new();
+    if let Some(bond_conf) = bond_iface.bond.as_ref() {
+        if let Some(mode) = bond_conf.mode {
+            opts.push(format!("mode={}", mode));
+        }
+        if let Some(bond_opts) = bond_conf.options.as_ref() {
+            for (name, value) in bond_opts.to_kernel_options() {
+                opts.push(format!("{}={}", name, value));
+            }
+        }
+    }
+    if !opts.is_empty() {
+        ifcfg.push("BONDING_OPTS", &opts.join(" "));
+    }
+}
+
+fn gen_bridge_ifcfg(br_iface: &LinuxBridgeInterface, ifcfg: &mut IfcfgFile) {
+    ifcfg.push("TYPE", "Bridge");
+    let br_opts =
+        match br_iface.bridge.as_ref().and_then(|b| b.options.as_ref()) {
+            Some(o) => o,
+            None => return,
+        };
+    let mut opts = Vec::new();
+    if let Some(stp_opts) = br_opts.stp.as_ref() {
+        if let Some(enabled) = stp_opts.enabled {
+            ifcfg.push("STP", if enabled { "yes" } else { "no" });
+        }
+        if let Some(v) = stp_opts.forward_delay {
+            ifcfg.push("DELAY", &v.to_string());
+        }
+        if let Some(v) = stp_opts.hello_time {
+            opts.push(format!("hello_time={}", v));
+        }
+        if let Some(v) = stp_opts.max_age {
+            opts.push(format!("max_age={}", v));
+        }
+        if let Some(v) = stp_opts.priority {
+            opts.push(format!("priority={}", v));
+        }
+    }
+    if let Some(v) = br_opts.mac_ageing_time {
+        opts.push(format!("ageing_time={}", v));
+    }
+    if let Some(v) = br_opts.group_fwd_mask {
+        opts.push(format!("group_fwd_mask={}", v));
+    }
+    if let Some(v) = br_opts.hash_max {
+        opts.push(format!("hash_max={}", v));
+    }
+    if let Some(v) = br_opts.multicast_snooping {
+        opts.push(format!("multicast_snooping={}", u8::from(v)));
+    }
+    if let Some(v) = br_opts.multicast_querier {
+        opts.push(format!("multicast_querier={}", u8::from(v)));
+    }
+    if let Some(v) = br_opts.multicast_router.as_ref() {
+        opts.push(format!("multicast_router={}", v.clone() as u8));
+    }
+    if !opts.is_empty() {
+        ifcfg.push("BRIDGING_OPTS", &opts.join(" "));
+    }
+}
+
+fn gen_bridge_port_ifcfg(
+    ctrl_name: &str,
+    port_conf: &LinuxBridgePortConfig,
+    ifcfg: &mut IfcfgFile,
+) {
+    let mut opts = Vec::new();
+    if let Some(v) = port_conf.stp_priority {
+        opts.push(format!("priority={}", v));
+    }
+    if let Some(v) = port_conf.stp_path_cost {
+        opts.push(format!("path_cost={}", v));
+    }
+    if let Some(v) = port_conf.stp_hairpin_mode {
+        opts.push(format!("hairpin_mode={}", u8::from(v)));
+    }
+    if port_conf.vlan.is_some() {
+        log::warn!(
+            "Property interfaces.{}.bridge.port.{}.vlan is not supported \
+            by {}, ignoring",
+            ctrl_name,
+            port_conf.name,
+            IFCFG_BACKEND_NAME
+        );
+    }
+    if !opts.is_empty() {
+        ifcfg.push("BRIDGING_OPTS", &opts.join(" "));
+    }
+}
+
+fn gen_vlan_ifcfg(vlan_iface: &VlanInterface, ifcfg: &mut IfcfgFile) {
+    ifcfg.push("VLAN", "yes");
+    if let Some(vlan_conf) = vlan_iface.vlan.as_ref() {
+        ifcfg.push("PHYSDEV", &vlan_conf.base_iface);
+        ifcfg.push("VLAN_ID", &vlan_conf.id.to_string());
+    }
+}
+
+fn gen_ipv4_ifcfg(ipv4: Option<&InterfaceIpv4>, ifcfg: &mut IfcfgFile) {
+    let ipv4 = match ipv4 {
+        Some(i) if i.enabled => i,
+        _ => {
+            ifcfg.push("BOOTPROTO", "none");
+            return;
+        }
+    };
+    if ipv4.is_auto() {
+        ifcfg.push("BOOTPROTO", "dhcp");
+        if ipv4.auto_dns == Some(false) {
+            ifcfg.push("PEERDNS", "no");
+        }
+        if ipv4.auto_gateway == Some(false) {
+            ifcfg.push("DEFROUTE", "no");
+        }
+        if ipv4.auto_routes == Some(false) {
+            ifcfg.push("PEERROUTES", "no");
+        }
+    } else {
+        ifcfg.push("BOOTPROTO", "none");
+        for (i, addr) in ipv4
+            .addresses
+            .as_deref()
+            .unwrap_or_default()
+            .iter()
+            .enumerate()
+        {
+            ifcfg.push(&format!("IPADDR{}", i), &addr.ip.to_string());
+            ifcfg
+                .push(&format!("PREFIX{}", i), &addr.prefix_length.to_string());
+        }
+    }
+}
+
+fn gen_ipv6_ifcfg(ipv6: Option<&InterfaceIpv6>, ifcfg: &mut IfcfgFile) {
+    let ipv6 = match ipv6 {
+        Some(i) if i.enabled => i,
+        _ => {
+            ifcfg.push("IPV6INIT", "no");
+            return;
+        }
+    };
+    ifcfg.push("IPV6INIT", "yes");
+    ifcfg.push(
+        "IPV6_AUTOCONF",
+        if ipv6.autoconf == Some(true) {
+            "yes"
+        } else {
+            "no"
+        },
+    );
+    if ipv6.dhcp == Some(true) {
+        ifcfg.push("DHCPV6C", "yes");
+    }
+    if ipv6.is_auto() {
+        if ipv6.auto_dns == Some(false) {
+            ifcfg.push("IPV6_PEERDNS", "no");
+        }
+        if ipv6.auto_gateway == Some(false) {
+            ifcfg.push("IPV6_DEFROUTE", "no");
+        }
+        if ipv6.auto_routes == Some(false) {
+            ifcfg.push("IPV6_PEERROUTES", "no");
+        }
+    }
+    if let Some(addr_gen_mode) = ipv6.addr_gen_mode.as_ref() {
+        ifcfg.push("IPV6_ADDR_GEN_MODE", &String::from(addr_gen_mode.clone()));
+    }
+    let addrs: Vec<String> = ipv6
+        .addresses
+        .as_deref()
+        .unwrap_or_default()
+        .iter()
+        .map(String::from)
+        .collect();
+    if let Some((first, others)) = addrs.split_first() {
+        ifcfg.push("IPV6ADDR", first);
+        if !others.is_empty() {
+            ifcfg.push("IPV6ADDR_SECONDARIES", &others.join(" "));
+        }
+    }
+}
+
+fn gen_dns_ifcfg(base_iface: &BaseInterface, ifcfg: &mut IfcfgFile) {
+    let mut servers = Vec::new();
+    let mut searches = Vec::new();
+    for dns in [
+        base_iface.ipv4.as_ref().and_then(|i| i.dns.as_ref()),
+        base_iface.ipv6.as_ref().and_then(|i| i.dns.as_ref()),
+    ]
+    .iter()
+    .flatten()
+    {
+        servers.extend(dns.server.as_deref().unwrap_or_default());
+        searches.extend(dns.search.as_deref().unwrap_or_default());
+    }
+    for (i, server) in servers.iter().enumerate() {
+        ifcfg.push(&format!("DNS{}", i + 1), server);
+    }
+    if !searches.is_empty() {
+        let searches: Vec<&str> = searches.iter().map(|s| s.as_str()).collect();
+        ifcfg.push("DOMAIN", &searches.join(" "));
+    }
+}
+
+fn gen_route_files(base_iface: &BaseInterface) -> Vec<(String, String)> {
+    let mut ret = Vec::new();
+    let mut ipv4_routes = String::new();
+    let mut ipv6_routes = String::new();
+    for route in base_iface.routes.as_deref().unwrap_or_default() {
+        let is_ipv6 = route
+            .destination
+            .as_deref()
+            .map(is_ipv6_addr)
+            .unwrap_or_default();
+        let content = if is_ipv6 {
+            &mut ipv6_routes
+        } else {
+            &mut ipv4_routes
+        };
+        let _ = writeln!(content, "{}", route_to_iproute_args(route));
+    }
+    if !ipv4_routes.is_empty() {
+        ret.push((format!("route-{}", base_iface.name), ipv4_routes));
+    }
+    if !ipv6_routes.is_empty() {
+        ret.push((format!("route6-{}", base_iface.name), ipv6_routes));
+    }
+    ret
+}
+
+fn gen_rule_files(base_iface: &BaseInterface) -> Vec<(String, String)> {
+    let mut ret = Vec::new();
+    let mut ipv4_rules = String::new();
+    let mut ipv6_rules = String::new();
+    for rule in base_iface.rules.as_deref().unwrap_or_default() {
+        let is_ipv6 = rule
+            .ip_from
+            .as_deref()
+            .or(rule.ip_to.as_deref())
+            .map(is_ipv6_addr)
+            .unwrap_or_default();
+        let content = if is_ipv6 {
+            &mut ipv6_rules
+        } else {
+            &mut ipv4_rules
+        };
+        let _ = writeln!(content, "{}", rule_to_iproute_args(rule));
+    }
+    if !ipv4_rules.is_empty() {
+        ret.push((format!("rule-{}", base_iface.name), ipv4_rules));
+    }
+    if !ipv6_rules.is_empty() {
+        ret.push((format!("rule6-{}", base_iface.name), ipv6_rules));
+    }
+    ret
+}
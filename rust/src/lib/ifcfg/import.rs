@@ -0,0 +1,773 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use std::cell::RefCell;
+use std::collections::{HashMap, HashSet};
+use std::convert::TryFrom;
+use std::net::Ipv4Addr;
+
+use crate::import::{
+    bridge_opts_mut, default_route, import_warn, ipv4_mut, ipv6_mut,
+    new_imported_iface, parse_bool, parse_enum, set_bond_kernel_options,
+    to_ip_network, ImportedNetState,
+};
+use crate::{
+    ErrorKind, InfiniBandConfig, InfiniBandMode, Interface, InterfaceIpAddr,
+    InterfaceState, InterfaceType, Ipv6AddrGenMode, LinuxBridgePortConfig,
+    NetworkState, NmstateError, RouteEntry, RouteRuleEntry, VlanConfig,
+};
+
+const IFCFG_PREFIX: &str = "ifcfg-";
+const ROUTE_PREFIX: &str = "route-";
+const ROUTE6_PREFIX: &str = "route6-";
+const RULE_PREFIX: &str = "rule-";
+const RULE6_PREFIX: &str = "rule6-";
+
+// Keys having no effect on the network state.
+const IGNORED_KEYS: [&str; 11] = [
+    "BROWSER_ONLY",
+    "DEVICE",
+    "HWADDR",
+    "IPV4_FAILURE_FATAL",
+    "IPV6_FAILURE_FATAL",
+    "NAME",
+    "NM_CONTROLLED",
+    "PROXY_METHOD",
+    "TYPE",
+    "USERCTL",
+    "UUID",
+];
+
+// File names with these prefixes are handled by this importer.
+pub(crate) fn is_ifcfg_file_name(file_name: &str) -> bool {
+    [
+        IFCFG_PREFIX,
+        ROUTE_PREFIX,
+        ROUTE6_PREFIX,
+        RULE_PREFIX,
+        RULE6_PREFIX,
+    ]
+    .iter()
+    .any(|p| file_name.starts_with(p))
+        && ![".bak", ".orig", ".rpmnew", ".rpmsave", "~"]
+            .iter()
+            .any(|s| file_name.ends_with(s))
+}
+
+// Key value pairs of ifcfg file with the line number of each key. The keys
+// been read are tracked for warning on the untranslatable ones.
+struct IfcfgFile<'a> {
+    file_path: &'a str,
+    values: HashMap<String, (String, usize)>,
+    used_keys: RefCell<HashSet<String>>,
+}
+
+impl<'a> IfcfgFile<'a> {
+    fn parse(file_path: &'a str, content: &str) -> Result<Self, NmstateError> {
+        let mut values = HashMap::new();
+        for (i, line) in content.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let (key, value) = match line.split_once('=') {
+                Some((k, v)) => (k.trim(), v.trim()),
+                None => {
+                    return Err(NmstateError::new(
+                        ErrorKind::InvalidArgument,
+                        format!(
+                            "{}:{}: Expecting KEY=VALUE, got {}",
+                            file_path,
+                            i + 1,
+                            line
+                        ),
+                    ));
+                }
+            };
+            values.insert(key.to_string(), (shell_unquote(value), i + 1));
+        }
+        Ok(Self {
+            file_path,
+            values,
+            used_keys: RefCell::new(HashSet::new()),
+        })
+    }
+
+    fn get(&self, key: &str) -> Option<&str> {
+        self.used_keys.borrow_mut().insert(key.to_string());
+        self.values
+            .get(key)
+            .map(|(v, _)| v.as_str())
+            .filter(|v| !v.is_empty())
+    }
+
+    fn get_bool(&self, key: &str) -> Option<bool> {
+        let value = self.get(key)?;
+        let ret = parse_bool(value);
+        if ret.is_none() {
+            self.warn(key, &format!("Ignoring invalid boolean {}", value));
+        }
+        ret
+    }
+
+    // Keys with index suffix like `IPADDR0`, `IPADDR1` sorted by line number.
+    fn indexed_keys(&self, prefix: &str) -> Vec<&str> {
+        let mut keys: Vec<&str> = self
+            .values
+            .keys()
+            .filter(|k| {
+                k.strip_prefix(prefix)
+                    .map(|i| i.chars().all(|c| c.is_ascii_digit()))
+                    .unwrap_or_default()
+            })
+            .map(String::as_str)
+            .collect();
+        keys.sort_unstable_by_key(|k| self.line(k));
+        keys
+    }
+
+    fn warn_unused_keys(&self) {
+        let used_keys = self.used_keys.borrow();
+        let mut keys: Vec<(&String, usize)> = self
+            .values
+            .iter()
+            .filter(|(k, _)| !used_keys.contains(k.as_str()))
+            .map(|(k, (_, l))| (k, *l))
+            .collect();
+        keys.sort_unstable_by_key(|(_, l)| *l);
+        for (key, line) in keys {
+            import_warn(
+                self.file_path,
+                line,
+                &format!("Ignoring unsupported key {}", key),
+            );
+        }
+    }
+
+    fn line(&self, key: &str) -> usize {
+        self.values.get(key).map(|(_, l)| *l).unwrap_or_default()
+    }
+
+    fn warn(&self, key: &str, msg: &str) {
+        import_warn(self.file_path, self.line(key), msg);
+    }
+
+    fn invalid(&self, key: &str) -> NmstateError {
+        NmstateError::new(
+            ErrorKind::InvalidArgument,
+            format!(
+                "{}:{}: Invalid value {} for {}",
+                self.file_path,
+                self.line(key),
+                self.get(key).unwrap_or_default(),
+                key
+            ),
+        )
+    }
+}
+
+// Convert the content of ifcfg-rh files to desired network state.
+// The `files` is list of (file_path, content), the type of file is decided
+// by the prefix of file name: `ifcfg-`, `route-`, `route6-`, `rule-` and
+// `rule6-`.
+pub(crate) fn ifcfg_import(
+    files: &[(String, String)],
+) -> Result<NetworkState, NmstateError> {
+    let mut ifcfgs = Vec::new();
+    let mut route_files = Vec::new();
+    let mut rule_files = Vec::new();
+    for (file_path, content) in files {
+        let file_name = file_path.rsplit('/').next().unwrap_or_default();
+        if let Some(name) = file_name.strip_prefix(IFCFG_PREFIX) {
+            if name == "lo" {
+                continue;
+            }
+            ifcfgs.push((name, IfcfgFile::parse(file_path, content)?));
+        } else if let Some(name) = file_name.strip_prefix(ROUTE6_PREFIX) {
+            route_files.push((name, file_path.as_str(), content.as_str()));
+        } else if let Some(name) = file_name.strip_prefix(ROUTE_PREFIX) {
+            route_files.push((name, file_path.as_str(), content.as_str()));
+        } else if let Some(name) = file_name.strip_prefix(RULE6_PREFIX) {
+            rule_files.push((name, file_path.as_str(), content.as_str()));
+        } else if let Some(name) = file_name.strip_prefix(RULE_PREFIX) {
+            rule_files.push((name, file_path.as_str(), content.as_str()));
+        } else {
+            log::warn!("Ignoring {} as it is not ifcfg-rh file", file_path);
+        }
+    }
+
+    // The MASTER and BRIDGE could refer to UUID of NetworkManager profile.
+    let mut uuid_to_iface: HashMap<String, String> = HashMap::new();
+    for (name, ifcfg) in ifcfgs.as_slice() {
+        if let Some(uuid) = ifcfg.get("UUID") {
+            uuid_to_iface.insert(
+                uuid.to_string(),
+                ifcfg.get("DEVICE").unwrap_or(name).to_string(),
+            );
+        }
+    }
+    let resolve = |name: &str| -> String {
+        uuid_to_iface
+            .get(name)
+            .cloned()
+            .unwrap_or_else(|| name.to_string())
+    };
+
+    let mut imported = ImportedNetState::new();
+    let mut file_name_to_iface: HashMap<&str, String> = HashMap::new();
+    for (name, ifcfg) in ifcfgs.as_slice() {
+        if let Some(iface_name) =
+            ifcfg_to_iface(name, ifcfg, &resolve, &mut imported)?
+        {
+            file_name_to_iface.insert(name, iface_name);
+        }
+    }
+
+    for (name, file_path, content) in route_files {
+        let iface_name = file_name_to_iface
+            .get(name)
+            .map(String::as_str)
+            .unwrap_or(name);
+        for route in parse_route_file(iface_name, file_path, content)? {
+            imported.add_route(route);
+        }
+    }
+    for (_, file_path, content) in rule_files {
+        for rule in parse_rule_file(file_path, content)? {
+            imported.add_rule(rule);
+        }
+    }
+    imported.build()
+}
+
+// Return the interface name if imported.
+fn ifcfg_to_iface(
+    file_name: &str,
+    ifcfg: &IfcfgFile,
+    resolve: &dyn Fn(&str) -> String,
+    imported: &mut ImportedNetState,
+) -> Result<Option<String>, NmstateError> {
+    for key in IGNORED_KEYS {
+        ifcfg.get(key);
+    }
+    let iface_name = ifcfg.get("DEVICE").unwrap_or(file_name).to_string();
+    let iface_type = if ifcfg.get_bool("BONDING_MASTER") == Some(true) {
+        InterfaceType::Bond
+    } else if ifcfg.get_bool("VLAN") == Some(true) {
+        InterfaceType::Vlan
+    } else {
+        match ifcfg.get("TYPE").map(|t| t.to_lowercase()).as_deref() {
+            None | Some("ethernet") => InterfaceType::Ethernet,
+            Some("bond") => InterfaceType::Bond,
+            Some("bridge") => InterfaceType::LinuxBridge,
+            Some("vlan") => InterfaceType::Vlan,
+            Some("infiniband") => InterfaceType::InfiniBand,
+            Some(t) => {
+                ifcfg.warn(
+                    "TYPE",
+                    &format!(
+                        "Ignoring interface {} with unsupported type {}",
+                        iface_name, t
+                    ),
+                );
+                return Ok(None);
+            }
+        }
+    };
+    let mut iface = match new_imported_iface(&iface_name, iface_type) {
+        Some(i) => i,
+        None => return Ok(None),
+    };
+
+    if ifcfg.get_bool("ONBOOT") == Some(false) {
+        iface.base_iface_mut().state = InterfaceState::Down;
+    }
+    if let Some(mtu) = ifcfg.get("MTU") {
+        iface.base_iface_mut().mtu =
+            Some(mtu.parse().map_err(|_| ifcfg.invalid("MTU"))?);
+    }
+    if let Some(mac) = ifcfg.get("MACADDR") {
+        iface.base_iface_mut().mac_address = Some(mac.to_string());
+    }
+
+    match &mut iface {
+        Interface::Bond(_) => {
+            if let Some(opts) = ifcfg.get("BONDING_OPTS") {
+                set_bond_kernel_options(
+                    &mut iface,
+                    &parse_kernel_opts(opts),
+                    ifcfg.file_path,
+                    ifcfg.line("BONDING_OPTS"),
+                )?;
+            }
+        }
+        Interface::LinuxBridge(_) => {
+            if let Some(br_opts) = bridge_opts_mut(&mut iface) {
+                if let Some(stp) = ifcfg.get_bool("STP") {
+                    br_opts.set_kernel_option(
+                        "stp_state",
+                        if stp { "1" } else { "0" },
+                    )?;
+                }
+                if let Some(delay) = ifcfg.get("DELAY") {
+                    br_opts
+                        .set_kernel_option("forward_delay", delay)
+                        .map_err(|_| ifcfg.invalid("DELAY"))?;
+                }
+                for (name, value) in
+                    parse_kernel_opts(ifcfg.get("BRIDGING_OPTS").unwrap_or(""))
+                {
+                    if !br_opts
+                        .set_kernel_option(&name, &value)
+                        .map_err(|_| ifcfg.invalid("BRIDGING_OPTS"))?
+                    {
+                        ifcfg.warn(
+                            "BRIDGING_OPTS",
+                            &format!(
+                                "Ignoring unsupported bridge option {}",
+                                name
+                            ),
+                        );
+                    }
+                }
+            }
+        }
+        Interface::Vlan(vlan_iface) => {
+            // The VLAN ID and parent could be omitted when interface name
+            // is in the format of `<parent>.<id>`.
+            let (name_parent, name_id) = match iface_name.rsplit_once('.') {
+                Some((p, i)) => (Some(p), i.parse::<u16>().ok()),
+                None => (None, None),
+            };
+            let id = match ifcfg.get("VLAN_ID") {
+                Some(i) => {
+                    Some(i.parse().map_err(|_| ifcfg.invalid("VLAN_ID"))?)
+                }
+                None => name_id,
+            };
+            match (ifcfg.get("PHYSDEV").or(name_parent), id) {
+                (Some(parent), Some(id)) => {
+                    vlan_iface.vlan = Some(VlanConfig {
+                        base_iface: resolve(parent),
+                        id,
+                    });
+                }
+                _ => {
+                    ifcfg.warn(
+                        "VLAN",
+                        &format!(
+                            "Ignoring VLAN {} without parent or VLAN ID",
+                            iface_name
+                        ),
+                    );
+                    return Ok(None);
+                }
+            }
+        }
+        Interface::InfiniBand(ib_iface) => {
+            let mut ib_conf = InfiniBandConfig::new();
+            ib_conf.mode = if ifcfg.get_bool("CONNECTED_MODE") == Some(true) {
+                InfiniBandMode::Connected
+            } else {
+                InfiniBandMode::Datagram
+            };
+            if ifcfg.get_bool("PKEY") == Some(true) {
+                ib_conf.base_iface = ifcfg.get("PHYSDEV").map(resolve);
+                ib_conf.pkey = ifcfg
+                    .get("PKEY_ID")
+                    .map(parse_pkey)
+                    .transpose()
+                    .map_err(|_| ifcfg.invalid("PKEY_ID"))?;
+            }
+            ib_iface.ib = Some(ib_conf);
+        }
+        _ => (),
+    }
+
+    if let Some(ctrl) = ifcfg.get("MASTER") {
+        ifcfg.get("SLAVE");
+        imported.add_port(&resolve(ctrl), new_port_conf(&iface_name));
+    } else if let Some(ctrl) = ifcfg.get("BRIDGE") {
+        let mut port_conf = new_port_conf(&iface_name);
+        for (name, value) in
+            parse_kernel_opts(ifcfg.get("BRIDGING_OPTS").unwrap_or(""))
+        {
+            if !port_conf
+                .set_kernel_option(&name, &value)
+                .map_err(|_| ifcfg.invalid("BRIDGING_OPTS"))?
+            {
+                ifcfg.warn(
+                    "BRIDGING_OPTS",
+                    &format!(
+                        "Ignoring unsupported bridge port option {}",
+                        name
+                    ),
+                );
+            }
+        }
+        imported.add_port(&resolve(ctrl), port_conf);
+    } else {
+        ifcfg_to_ip(&mut iface, ifcfg, imported)?;
+    }
+
+    ifcfg.warn_unused_keys();
+    imported.add_iface(iface);
+    Ok(Some(iface_name))
+}
+
+fn new_port_conf(iface_name: &str) -> LinuxBridgePortConfig {
+    let mut port_conf = LinuxBridgePortConfig::new();
+    port_conf.name = iface_name.to_string();
+    port_conf
+}
+
+fn ifcfg_to_ip(
+    iface: &mut Interface,
+    ifcfg: &IfcfgFile,
+    imported: &mut ImportedNetState,
+) -> Result<(), NmstateError> {
+    let iface_name = iface.name().to_string();
+    let defroute = ifcfg.get_bool("DEFROUTE").unwrap_or(true);
+    let dhcp = match ifcfg.get("BOOTPROTO").map(|b| b.to_lowercase()).as_deref()
+    {
+        Some("dhcp") | Some("bootp") => true,
+        None | Some("none") | Some("static") => false,
+        Some(b) => {
+            ifcfg.warn(
+                "BOOTPROTO",
+                &format!("Unsupported BOOTPROTO {}, treating as static", b),
+            );
+            false
+        }
+    };
+    // The IPADDR could be suffixed by index number, for example IPADDR0,
+    // IPADDR1, with PREFIX or NETMASK using the same suffix.
+    let mut addrs = Vec::new();
+    for addr_key in ifcfg.indexed_keys("IPADDR") {
+        let addr = match ifcfg.get(addr_key) {
+            Some(a) => a,
+            None => continue,
+        };
+        let index = &addr_key["IPADDR".len()..];
+        let prefix_key = format!("PREFIX{}", index);
+        let netmask_key = format!("NETMASK{}", index);
+        let prefix = if let Some(p) = ifcfg.get(&prefix_key) {
+            p.parse().map_err(|_| ifcfg.invalid(&prefix_key))?
+        } else if let Some(m) = ifcfg.get(&netmask_key) {
+            m.parse::<Ipv4Addr>()
+                .map(|m| u32::from(m).count_ones() as u8)
+                .map_err(|_| ifcfg.invalid(&netmask_key))?
+        } else {
+            classful_prefix(addr)
+        };
+        addrs.push(parse_ip_addr(
+            ifcfg,
+            addr_key,
+            &format!("{}/{}", addr, prefix),
+        )?);
+    }
+    if dhcp || !addrs.is_empty() {
+        let ipv4 = ipv4_mut(iface);
+        ipv4.dhcp = Some(dhcp);
+        if dhcp {
+            ipv4.auto_gateway = Some(defroute);
+            ipv4.auto_dns = Some(ifcfg.get_bool("PEERDNS").unwrap_or(true));
+            ipv4.auto_routes =
+                Some(ifcfg.get_bool("PEERROUTES").unwrap_or(true));
+        }
+        if !addrs.is_empty() {
+            ipv4.addresses = Some(addrs);
+        }
+    }
+    for key in ifcfg.indexed_keys("GATEWAY") {
+        if let Some(gw) = ifcfg.get(key) {
+            if defroute {
+                imported.add_route(default_route(&iface_name, gw, None));
+            }
+        }
+    }
+    for key in ifcfg.indexed_keys("DNS") {
+        if let Some(srv) = ifcfg.get(key) {
+            imported.add_dns_server(srv);
+        }
+    }
+    for sch in ifcfg.get("DOMAIN").unwrap_or_default().split_whitespace() {
+        imported.add_dns_search(sch);
+    }
+
+    if ifcfg.get_bool("IPV6INIT") != Some(true) {
+        return Ok(());
+    }
+    let ipv6 = ipv6_mut(iface);
+    let autoconf = ifcfg.get_bool("IPV6_AUTOCONF").unwrap_or(true);
+    let dhcp = ifcfg.get_bool("DHCPV6C").unwrap_or_default();
+    ipv6.autoconf = Some(autoconf);
+    ipv6.dhcp = Some(dhcp);
+    let defroute = ifcfg.get_bool("IPV6_DEFROUTE").unwrap_or(true);
+    if autoconf || dhcp {
+        ipv6.auto_gateway = Some(defroute);
+        ipv6.auto_dns = Some(ifcfg.get_bool("IPV6_PEERDNS").unwrap_or(true));
+        ipv6.auto_routes =
+            Some(ifcfg.get_bool("IPV6_PEERROUTES").unwrap_or(true));
+    }
+    if let Some(mode) = ifcfg.get("IPV6_ADDR_GEN_MODE") {
+        ipv6.addr_gen_mode = Some(
+            parse_enum::<Ipv6AddrGenMode>(mode, "IPV6_ADDR_GEN_MODE")
+                .map_err(|_| ifcfg.invalid("IPV6_ADDR_GEN_MODE"))?,
+        );
+    }
+    for key in ["IPV6ADDR", "IPV6ADDR_SECONDARIES"] {
+        for addr in ifcfg.get(key).unwrap_or_default().split_whitespace() {
+            // IPv6 address without prefix length is using /64.
+            let addr = if addr.contains('/') {
+                addr.to_string()
+            } else {
+                format!("{}/64", addr)
+            };
+            ipv6.addresses
+                .get_or_insert_with(Vec::new)
+                .push(parse_ip_addr(ifcfg, key, &addr)?);
+        }
+    }
+    if let Some(gw) = ifcfg.get("IPV6_DEFAULTGW") {
+        // The gateway could be in the format of `<address>%<interface>`.
+        let gw = gw.split('%').next().unwrap_or_default();
+        if defroute {
+            imported.add_route(default_route(&iface_name, gw, None));
+        }
+    }
+    Ok(())
+}
+
+fn parse_ip_addr(
+    ifcfg: &IfcfgFile,
+    key: &str,
+    addr: &str,
+) -> Result<InterfaceIpAddr, NmstateError> {
+    InterfaceIpAddr::try_from(addr).map_err(|_| ifcfg.invalid(key))
+}
+
+fn parse_pkey(value: &str) -> Result<u16, std::num::ParseIntError> {
+    match value.strip_prefix("0x") {
+        Some(v) => u16::from_str_radix(v, 16),
+        None => value.parse(),
+    }
+}
+
+// The prefix length used by initscripts when PREFIX and NETMASK is not
+// defined.
+fn classful_prefix(addr: &str) -> u8 {
+    match addr.parse::<Ipv4Addr>().map(|a| a.octets()[0]) {
+        Ok(o) if o < 128 => 8,
+        Ok(o) if o < 192 => 16,
+        _ => 24,
+    }
+}
+
+// Parse space separated `name=value` pairs.
+fn parse_kernel_opts(opts: &str) -> Vec<(String, String)> {
+    opts.split_whitespace()
+        .filter_map(|opt| opt.split_once('='))
+        .map(|(k, v)| (k.to_string(), v.to_string()))
+        .collect()
+}
+
+// Remove the shell quoting of ifcfg value.
+fn shell_unquote(value: &str) -> String {
+    if let Some(v) = value.strip_prefix('\'').and_then(|v| v.strip_suffix('\''))
+    {
+        return v.to_string();
+    }
+    let value = value
+        .strip_prefix('"')
+        .and_then(|v| v.strip_suffix('"'))
+        .or_else(|| value.strip_prefix("$'").and_then(|v| v.strip_suffix('\'')))
+        .unwrap_or(value);
+    let mut ret = String::new();
+    let mut chars = value.chars();
+    while let Some(c) = chars.next() {
+        if c == '\\' {
+            if let Some(n) = chars.next() {
+                ret.push(n);
+            }
+        } else {
+            ret.push(c);
+        }
+    }
+    ret
+}
+
+// The route file is either in the format of `ip route` arguments or using
+// `ADDRESSn`, `NETMASKn`, `GATEWAYn` and `METRICn` keys.
+fn parse_route_file(
+    iface_name: &str,
+    file_path: &str,
+    content: &str,
+) -> Result<Vec<RouteEntry>, NmstateError> {
+    if content
+        .lines()
+        .any(|l| l.trim_start().starts_with("ADDRESS"))
+    {
+        return parse_route_file_keys(iface_name, file_path, content);
+    }
+    let mut ret = Vec::new();
+    for (i, line) in content.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let mut args = line.split_whitespace();
+        let mut rt = RouteEntry::new();
+        rt.next_hop_iface = Some(iface_name.to_string());
+        let mut dest = args.next().map(|d| d.to_string());
+        while let Some(arg) = args.next() {
+            let value = args.next();
+            match (arg, value) {
+                ("via", Some(v)) => rt.next_hop_addr = Some(v.to_string()),
+                ("dev", Some(v)) => rt.next_hop_iface = Some(v.to_string()),
+                ("metric", Some(v)) => {
+                    rt.metric = Some(
+                        v.parse()
+                            .map_err(|_| invalid_line(file_path, i, line))?,
+                    )
+                }
+                ("table", Some(v)) => {
+                    rt.table_id = Some(
+                        v.parse()
+                            .map_err(|_| invalid_line(file_path, i, line))?,
+                    )
+                }
+                (arg, _) => {
+                    import_warn(
+                        file_path,
+                        i + 1,
+                        &format!("Ignoring unsupported route option {}", arg),
+                    );
+                    break;
+                }
+            }
+        }
+        if dest.as_deref() == Some("default") {
+            dest = Some(
+                if file_path.contains(ROUTE6_PREFIX) {
+                    "::/0"
+                } else {
+                    "0.0.0.0/0"
+                }
+                .to_string(),
+            );
+        }
+        rt.destination = dest.map(|d| to_ip_network(&d));
+        ret.push(rt);
+    }
+    Ok(ret)
+}
+
+fn parse_route_file_keys(
+    iface_name: &str,
+    file_path: &str,
+    content: &str,
+) -> Result<Vec<RouteEntry>, NmstateError> {
+    let ifcfg = IfcfgFile::parse(file_path, content)?;
+    let mut addr_keys: Vec<&String> = ifcfg
+        .values
+        .keys()
+        .filter(|k| k.starts_with("ADDRESS"))
+        .collect();
+    addr_keys.sort_unstable_by_key(|k| ifcfg.line(k));
+    let mut ret = Vec::new();
+    for addr_key in addr_keys {
+        let index = &addr_key["ADDRESS".len()..];
+        let addr = match ifcfg.get(addr_key) {
+            Some(a) => a,
+            None => continue,
+        };
+        let netmask_key = format!("NETMASK{}", index);
+        let prefix = match ifcfg.get(&netmask_key) {
+            Some(m) => m
+                .parse::<Ipv4Addr>()
+                .map(|m| u32::from(m).count_ones())
+                .map_err(|_| ifcfg.invalid(&netmask_key))?,
+            None => 32,
+        };
+        let mut rt = RouteEntry::new();
+        rt.destination = Some(format!("{}/{}", addr, prefix));
+        rt.next_hop_iface = Some(iface_name.to_string());
+        rt.next_hop_addr =
+            ifcfg.get(&format!("GATEWAY{}", index)).map(String::from);
+        let metric_key = format!("METRIC{}", index);
+        rt.metric = ifcfg
+            .get(&metric_key)
+            .map(|m| m.parse().map_err(|_| ifcfg.invalid(&metric_key)))
+            .transpose()?;
+        if ifcfg.get(&format!("OPTIONS{}", index)).is_some() {
+            ifcfg.warn(
+                &format!("OPTIONS{}", index),
+                "Ignoring unsupported route options",
+            );
+        }
+        ret.push(rt);
+    }
+    Ok(ret)
+}
+
+// The rule file is in the format of `ip rule` arguments.
+fn parse_rule_file(
+    file_path: &str,
+    content: &str,
+) -> Result<Vec<RouteRuleEntry>, NmstateError> {
+    let mut ret = Vec::new();
+    for (i, line) in content.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let mut rule = RouteRuleEntry::new();
+        let mut args = line.split_whitespace();
+        while let Some(arg) = args.next() {
+            let value = args.next();
+            match (arg, value) {
+                ("from", Some(v)) => {
+                    if v != "all" {
+                        rule.ip_from = Some(to_ip_network(v));
+                    }
+                }
+                ("to", Some(v)) => {
+                    if v != "all" {
+                        rule.ip_to = Some(to_ip_network(v));
+                    }
+                }
+                ("priority", Some(v))
+                | ("pref", Some(v))
+                | ("preference", Some(v)) => {
+                    rule.priority = Some(
+                        v.parse()
+                            .map_err(|_| invalid_line(file_path, i, line))?,
+                    )
+                }
+                ("table", Some(v)) | ("lookup", Some(v)) => {
+                    rule.table_id = Some(
+                        v.parse()
+                            .map_err(|_| invalid_line(file_path, i, line))?,
+                    )
+                }
+                (arg, _) => {
+                    import_warn(
+                        file_path,
+                        i + 1,
+                        &format!("Ignoring unsupported rule option {}", arg),
+                    );
+                    break;
+                }
+            }
+        }
+        ret.push(rule);
+    }
+    Ok(ret)
+}
+
+fn invalid_line(file_path: &str, index: usize, line: &str) -> NmstateError {
+    NmstateError::new(
+        ErrorKind::InvalidArgument,
+        format!("{}:{}: Invalid line {}", file_path, index + 1, line),
+    )
+}
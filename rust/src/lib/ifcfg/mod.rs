@@ -0,0 +1,11 @@
+// SPDX-License-Identifier: Apache-2.0
+
+#[cfg(feature = "gen_conf")]
+mod gen_conf;
+#[cfg(feature = "gen_conf")]
+mod import;
+
+#[cfg(feature = "gen_conf")]
+pub(crate) use gen_conf::ifcfg_gen_conf;
+#[cfg(feature = "gen_conf")]
+pub(crate) use import::{ifcfg_import, is_ifcfg_file_name};
@@ -0,0 +1,377 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use std::fmt::Write;
+
+use crate::{
+    gen_conf::get_unsupported_legacy_props,
+    iproute::{route_to_iproute_args, rule_to_iproute_args},
+    BaseInterface, BondInterface, Interface, InterfaceIpv4, InterfaceIpv6,
+    InterfaceType, LinuxBridgeInterface, NetworkState, NmstateError,
+    VlanInterface,
+};
+
+const IFUPDOWN_BACKEND_NAME: &str = "ifupdown";
+
+// Generate one file per interface holding the stanzas of
+// /etc/network/interfaces, they are expected to be placed in
+// /etc/network/interfaces.d/ folder.
+pub(crate) fn ifupdown_gen_conf(
+    net_state: &NetworkState,
+) -> Result<Vec<(String, String)>, NmstateError> {
+    let mut ret = Vec::new();
+    if net_state
+        .hostname
+        .as_ref()
+        .and_then(|c| c.config.as_ref())
+        .is_some()
+    {
+        log::warn!(
+            "Cannot store hostname configuration to ifupdown files, \
+            please edit /etc/hostname manually"
+        );
+    }
+    let mut ifaces = net_state.interfaces.to_vec();
+    ifaces.sort_unstable_by_key(|i| i.name());
+    for iface in ifaces {
+        if !iface.is_up() {
+            log::warn!(
+                "ignoring iface {} because is down or absent",
+                iface.name(),
+            );
+            continue;
+        }
+        let mut opts = Vec::new();
+        match iface {
+            Interface::Ethernet(_) => (),
+            Interface::Bond(bond_iface) => {
+                gen_bond_opts(bond_iface, &mut opts);
+            }
+            Interface::LinuxBridge(br_iface) => {
+                gen_bridge_opts(br_iface, &mut opts);
+            }
+            Interface::Vlan(vlan_iface) => {
+                gen_vlan_opts(vlan_iface, &mut opts);
+            }
+            _ => {
+                log::warn!(
+                    "Interface type {} of interface {} is not supported \
+                    by {}, ignoring",
+                    iface.iface_type(),
+                    iface.name(),
+                    IFUPDOWN_BACKEND_NAME
+                );
+                continue;
+            }
+        }
+        for prop_path in get_unsupported_legacy_props(iface) {
+            log::warn!(
+                "Property {} is not supported by {}, ignoring",
+                prop_path,
+                IFUPDOWN_BACKEND_NAME
+            );
+        }
+        let base_iface = iface.base_iface();
+        gen_base_opts(base_iface, &mut opts);
+        gen_controller_opts(base_iface, &mut opts);
+        gen_dns_opts(base_iface, &mut opts);
+        gen_route_opts(base_iface, &mut opts);
+
+        let mut content = String::new();
+        let _ = writeln!(content, "auto {}", base_iface.name);
+        gen_ipv4_stanza(base_iface, opts, &mut content);
+        gen_ipv6_stanza(base_iface, &mut content);
+        ret.push((base_iface.name.to_string(), content));
+    }
+    Ok(ret)
+}
+
+fn gen_base_opts(base_iface: &BaseInterface, opts: &mut Vec<String>) {
+    if let Some(mac) = base_iface.mac_address.as_ref() {
+        opts.push(format!("hwaddress ether {}", mac));
+    }
+    if let Some(mtu) = base_iface.mtu {
+        opts.push(format!("mtu {}", mtu));
+    }
+}
+
+fn gen_controller_opts(base_iface: &BaseInterface, opts: &mut Vec<String>) {
+    let ctrl_name = match base_iface.controller.as_deref() {
+        Some(c) if !c.is_empty() => c,
+        _ => return,
+    };
+    match base_iface.controller_type.as_ref() {
+        Some(InterfaceType::Bond) => {
+            opts.push(format!("bond-master {}", ctrl_name));
+        }
+        // Linux bridge ports are defined by `bridge_ports` of the bridge.
+        Some(InterfaceType::LinuxBridge) => (),
+        Some(ctrl_type) => {
+            log::warn!(
+                "Controller type {} of interface {} is not supported by {}, \
+                ignoring property interfaces.{}.controller",
+                ctrl_type,
+                base_iface.name,
+                IFUPDOWN_BACKEND_NAME,
+                base_iface.name,
+            );
+        }
+        None => (),
+    }
+}
+
+fn gen_bond_opts(bond_iface: &BondInterface, opts: &mut Vec<String>) {
+    let bond_conf = match bond_iface.bond.as_ref() {
+        Some(b) => b,
+        None => return,
+    };
+    let ports = bond_conf.port.as_deref().unwrap_or_default();
+    opts.push(format!(
+        "bond-slaves {}",
+        if ports.is_empty() {
+            "none".to_string()
+        } else {
+            ports.join(" ")
+        }
+    ));
+    if let Some(mode) = bond_conf.mode {
+        opts.push(format!("bond-mode {}", mode));
+    }
+    if let Some(bond_opts) = bond_conf.options.as_ref() {
+        for (name, value) in bond_opts.to_kernel_options() {
+            opts.push(format!("bond-{} {}", name.replace('_', "-"), value));
+        }
+    }
+}
+
+fn gen_bridge_opts(br_iface: &LinuxBridgeInterface, opts: &mut Vec<String>) {
+    let ports = br_iface.ports().unwrap_or_default();
+    opts.push(format!(
+        "bridge_ports {}",
+        if ports.is_empty() {
+            "none".to_string()
+        } else {
+            ports.join(" ")
+        }
+    ));
+    if let Some(br_opts) =
+        br_iface.bridge.as_ref().and_then(|b| b.options.as_ref())
+    {
+        if let Some(stp_opts) = br_opts.stp.as_ref() {
+            if let Some(enabled) = stp_opts.enabled {
+                opts.push(format!(
+                    "bridge_stp {}",
+                    if enabled { "on" } else { "off" }
+                ));
+            }
+            if let Some(v) = stp_opts.forward_delay {
+                opts.push(format!("bridge_fd {}", v));
+            }
+            if let Some(v) = stp_opts.hello_time {
+                opts.push(format!("bridge_hello {}", v));
+            }
+            if let Some(v) = stp_opts.max_age {
+                opts.push(format!("bridge_maxage {}", v));
+            }
+            if let Some(v) = stp_opts.priority {
+                opts.push(format!("bridge_bridgeprio {}", v));
+            }
+        }
+        if let Some(v) = br_opts.mac_ageing_time {
+            opts.push(format!("bridge_ageing {}", v));
+        }
+        if let Some(v) = br_opts.hash_max {
+            opts.push(format!("bridge_hashmax {}", v));
+        }
+        if let Some(v) = br_opts.multicast_snooping {
+            opts.push(format!("bridge_mcsnoop {}", u8::from(v)));
+        }
+        if let Some(v) = br_opts.multicast_querier {
+            opts.push(format!("bridge_mcquerier {}", u8::from(v)));
+        }
+    }
+    for port_conf in br_iface
+        .bridge
+        .as_ref()
+        .and_then(|b| b.port.as_deref())
+        .unwrap_or_default()
+    {
+        if let Some(v) = port_conf.stp_path_cost {
+            opts.push(format!("bridge_pathcost {} {}", port_conf.name, v));
+        }
+        if let Some(v) = port_conf.stp_priority {
+            opts.push(format!("bridge_portprio {} {}", port_conf.name, v));
+        }
+        if let Some(v) = port_conf.stp_hairpin_mode {
+            opts.push(format!(
+                "bridge_hairpin {} {}",
+                port_conf.name,
+                if v { "on" } else { "off" }
+            ));
+        }
+        if port_conf.vlan.is_some() {
+            log::warn!(
+                "Property interfaces.{}.bridge.port.{}.vlan is not supported \
+                by {}, ignoring",
+                br_iface.base.name,
+                port_conf.name,
+                IFUPDOWN_BACKEND_NAME
+            );
+        }
+    }
+}
+
+// The vlan package of Debian can only deduce the VLAN ID from interface names
+// like `eth0.10` or `vlan10`, for other names we create the VLAN interface by
+// ip command.
+fn gen_vlan_opts(vlan_iface: &VlanInterface, opts: &mut Vec<String>) {
+    let vlan_conf = match vlan_iface.vlan.as_ref() {
+        Some(v) => v,
+        None => return,
+    };
+    let name = vlan_iface.base.name.as_str();
+    if name == format!("{}.{}", vlan_conf.base_iface, vlan_conf.id)
+        || name == format!("vlan{}", vlan_conf.id)
+    {
+        opts.push(format!("vlan-raw-device {}", vlan_conf.base_iface));
+    } else {
+        opts.push(format!(
+            "pre-up ip link add link {} name {} type vlan id {}",
+            vlan_conf.base_iface, name, vlan_conf.id
+        ));
+        opts.push(format!("post-down ip link del {}", name));
+    }
+}
+
+fn gen_dns_opts(base_iface: &BaseInterface, opts: &mut Vec<String>) {
+    let mut servers = Vec::new();
+    let mut searches = Vec::new();
+    for dns in [
+        base_iface.ipv4.as_ref().and_then(|i| i.dns.as_ref()),
+        base_iface.ipv6.as_ref().and_then(|i| i.dns.as_ref()),
+    ]
+    .iter()
+    .flatten()
+    {
+        servers.extend(dns.server.as_deref().unwrap_or_default());
+        searches.extend(dns.search.as_deref().unwrap_or_default());
+    }
+    if !servers.is_empty() {
+        let servers: Vec<&str> = servers.iter().map(|s| s.as_str()).collect();
+        opts.push(format!("dns-nameservers {}", servers.join(" ")));
+    }
+    if !searches.is_empty() {
+        let searches: Vec<&str> = searches.iter().map(|s| s.as_str()).collect();
+        opts.push(format!("dns-search {}", searches.join(" ")));
+    }
+}
+
+fn gen_route_opts(base_iface: &BaseInterface, opts: &mut Vec<String>) {
+    for route in base_iface.routes.as_deref().unwrap_or_default() {
+        let args = route_to_iproute_args(route);
+        opts.push(format!("up ip route add {}", args));
+        opts.push(format!("pre-down ip route del {}", args));
+    }
+    for rule in base_iface.rules.as_deref().unwrap_or_default() {
+        let args = rule_to_iproute_args(rule);
+        opts.push(format!("up ip rule add {}", args));
+        opts.push(format!("pre-down ip rule del {}", args));
+    }
+}
+
+fn gen_ipv4_stanza(
+    base_iface: &BaseInterface,
+    mut opts: Vec<String>,
+    content: &mut String,
+) {
+    let method = match base_iface.ipv4.as_ref() {
+        Some(ipv4) if ipv4.is_auto() => {
+            warn_dhcp_opts_ipv4(&base_iface.name, ipv4);
+            "dhcp"
+        }
+        Some(ipv4) if ipv4.is_static() => {
+            let mut addr_opts: Vec<String> = ipv4
+                .addresses
+                .as_deref()
+                .unwrap_or_default()
+                .iter()
+                .map(|a| format!("address {}", String::from(a)))
+                .collect();
+            addr_opts.append(&mut opts);
+            opts = addr_opts;
+            "static"
+        }
+        _ => "manual",
+    };
+    let _ = writeln!(content, "iface {} inet {}", base_iface.name, method);
+    for opt in opts {
+        let _ = writeln!(content, "    {}", opt);
+    }
+}
+
+fn gen_ipv6_stanza(base_iface: &BaseInterface, content: &mut String) {
+    let ipv6 = match base_iface.ipv6.as_ref() {
+        Some(i) if i.enabled => i,
+        _ => return,
+    };
+    let mut opts = Vec::new();
+    let method = if ipv6.is_static() {
+        for addr in ipv6.addresses.as_deref().unwrap_or_default() {
+            opts.push(format!("address {}", String::from(addr)));
+        }
+        if ipv6.autoconf == Some(true) {
+            opts.push("autoconf 1".to_string());
+        }
+        "static"
+    } else if ipv6.dhcp == Some(true) {
+        warn_dhcp_opts_ipv6(&base_iface.name, ipv6);
+        if ipv6.autoconf == Some(true) {
+            opts.push("accept_ra 2".to_string());
+        }
+        "dhcp"
+    } else if ipv6.autoconf == Some(true) {
+        warn_dhcp_opts_ipv6(&base_iface.name, ipv6);
+        "auto"
+    } else {
+        "manual"
+    };
+    let _ = writeln!(content, "iface {} inet6 {}", base_iface.name, method);
+    for opt in opts {
+        let _ = writeln!(content, "    {}", opt);
+    }
+}
+
+fn warn_dhcp_opts_ipv4(iface_name: &str, ipv4: &InterfaceIpv4) {
+    for (prop, value) in [
+        ("auto-dns", ipv4.auto_dns),
+        ("auto-gateway", ipv4.auto_gateway),
+        ("auto-routes", ipv4.auto_routes),
+    ] {
+        if value == Some(false) {
+            log::warn!(
+                "Property interfaces.{}.ipv4.{} is not supported by {}, \
+                ignoring",
+                iface_name,
+                prop,
+                IFUPDOWN_BACKEND_NAME
+            );
+        }
+    }
+}
+
+fn warn_dhcp_opts_ipv6(iface_name: &str, ipv6: &InterfaceIpv6) {
+    for (prop, value) in [
+        ("auto-dns", ipv6.auto_dns),
+        ("auto-gateway", ipv6.auto_gateway),
+        ("auto-routes", ipv6.auto_routes),
+    ] {
+        if value == Some(false) {
+            log::warn!(
+                "Property interfaces.{}.ipv6.{} is not supported by {}, \
+                ignoring",
+                iface_name,
+                prop,
+                IFUPDOWN_BACKEND_NAME
+            );
+        }
+    }
+}
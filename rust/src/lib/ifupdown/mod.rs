@@ -0,0 +1,7 @@
+// SPDX-License-Identifier: Apache-2.0
+
+#[cfg(feature = "gen_conf")]
+mod gen_conf;
+
+#[cfg(feature = "gen_conf")]
+pub(crate) use gen_conf::ifupdown_gen_conf;
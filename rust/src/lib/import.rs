@@ -0,0 +1,498 @@
+// SPDX-License-Identifier: Apache-2.0
+
+// Shared code of importers converting the network configuration files of
+// other tools(netplan, ifcfg-rh and systemd-networkd) to NetworkState.
+
+use std::collections::HashSet;
+
+use crate::{
+    BaseInterface, BondConfig, BondInterface, BondMode, BondOptions,
+    BridgePortTunkTag, BridgePortVlanConfig, BridgePortVlanMode,
+    BridgePortVlanRange, DnsClientState, DummyInterface, ErrorKind,
+    EthernetInterface, InfiniBandInterface, Interface, InterfaceIpv4,
+    InterfaceIpv6, InterfaceState, InterfaceType, LinuxBridgeConfig,
+    LinuxBridgeInterface, LinuxBridgeOptions, LinuxBridgePortConfig,
+    MacVlanInterface, MacVtapInterface, NetworkState, NmstateError, RouteEntry,
+    RouteRuleEntry, VlanInterface, VrfConfig, VrfInterface, VxlanInterface,
+};
+
+// Report configuration which cannot be translated to nmstate along with the
+// file and line it is found.
+pub(crate) fn import_warn(file_path: &str, line: usize, msg: &str) {
+    log::warn!("{}:{}: {}", file_path, line, msg);
+}
+
+// Create interface in up state. Controllers are created with empty port
+// list, type specific configuration of other interface types is left for
+// caller to fill.
+pub(crate) fn new_imported_iface(
+    name: &str,
+    iface_type: InterfaceType,
+) -> Option<Interface> {
+    let mut base = BaseInterface::new();
+    base.name = name.to_string();
+    base.iface_type = iface_type.clone();
+    base.state = InterfaceState::Up;
+    let iface = match iface_type {
+        InterfaceType::Ethernet => {
+            let mut iface = EthernetInterface::new();
+            iface.base = base;
+            Interface::Ethernet(iface)
+        }
+        InterfaceType::Bond => {
+            let mut iface = BondInterface::new();
+            iface.base = base;
+            let mut bond_conf = BondConfig::new();
+            bond_conf.port = Some(Vec::new());
+            iface.bond = Some(bond_conf);
+            Interface::Bond(iface)
+        }
+        InterfaceType::LinuxBridge => {
+            let mut iface = LinuxBridgeInterface::new();
+            iface.base = base;
+            let mut br_conf = LinuxBridgeConfig::new();
+            br_conf.port = Some(Vec::new());
+            iface.bridge = Some(br_conf);
+            Interface::LinuxBridge(iface)
+        }
+        InterfaceType::Vrf => {
+            let mut iface = VrfInterface::new();
+            iface.base = base;
+            iface.vrf = Some(VrfConfig {
+                port: Some(Vec::new()),
+                table_id: 0,
+            });
+            Interface::Vrf(iface)
+        }
+        InterfaceType::Vlan => {
+            let mut iface = VlanInterface::new();
+            iface.base = base;
+            Interface::Vlan(iface)
+        }
+        InterfaceType::Vxlan => {
+            let mut iface = VxlanInterface::new();
+            iface.base = base;
+            Interface::Vxlan(iface)
+        }
+        InterfaceType::MacVlan => {
+            let mut iface = MacVlanInterface::new();
+            iface.base = base;
+            Interface::MacVlan(iface)
+        }
+        InterfaceType::MacVtap => {
+            let mut iface = MacVtapInterface::new();
+            iface.base = base;
+            Interface::MacVtap(iface)
+        }
+        InterfaceType::Dummy => {
+            let mut iface = DummyInterface::new();
+            iface.base = base;
+            Interface::Dummy(iface)
+        }
+        InterfaceType::InfiniBand => {
+            Interface::InfiniBand(InfiniBandInterface {
+                base,
+                ..Default::default()
+            })
+        }
+        _ => return None,
+    };
+    Some(iface)
+}
+
+// Get IPv4 configure of interface, create enabled one if not exist.
+pub(crate) fn ipv4_mut(iface: &mut Interface) -> &mut InterfaceIpv4 {
+    iface.base_iface_mut().ipv4.get_or_insert_with(|| {
+        let mut ip = InterfaceIpv4::new();
+        ip.enabled = true;
+        ip.prop_list.push("enabled");
+        ip
+    })
+}
+
+// Get IPv6 configure of interface, create enabled one if not exist.
+pub(crate) fn ipv6_mut(iface: &mut Interface) -> &mut InterfaceIpv6 {
+    iface.base_iface_mut().ipv6.get_or_insert_with(|| {
+        let mut ip = InterfaceIpv6::new();
+        ip.enabled = true;
+        ip.prop_list.push("enabled");
+        ip
+    })
+}
+
+pub(crate) fn bond_mut(iface: &mut Interface) -> Option<&mut BondConfig> {
+    if let Interface::Bond(bond_iface) = iface {
+        bond_iface.bond.as_mut()
+    } else {
+        None
+    }
+}
+
+pub(crate) fn bridge_opts_mut(
+    iface: &mut Interface,
+) -> Option<&mut LinuxBridgeOptions> {
+    if let Interface::LinuxBridge(br_iface) = iface {
+        br_iface
+            .bridge
+            .as_mut()
+            .map(|b| b.options.get_or_insert_with(LinuxBridgeOptions::new))
+    } else {
+        None
+    }
+}
+
+// Apply bond options in the format of kernel sysfs to the bond interface.
+// The `file_path` and `line` are used for warning on unsupported options.
+pub(crate) fn set_bond_kernel_options(
+    iface: &mut Interface,
+    opts: &[(String, String)],
+    file_path: &str,
+    line: usize,
+) -> Result<(), NmstateError> {
+    let iface_name = iface.name().to_string();
+    let bond_conf = match bond_mut(iface) {
+        Some(b) => b,
+        None => return Ok(()),
+    };
+    let mut other_opts = Vec::new();
+    for (name, value) in opts {
+        if name == "mode" {
+            bond_conf.mode = Some(parse_enum::<BondMode>(value, "bond mode")?);
+        } else {
+            other_opts.push((name.to_string(), value.to_string()));
+        }
+    }
+    if other_opts.is_empty() {
+        return Ok(());
+    }
+    let (bond_opts, unsupported) =
+        BondOptions::from_kernel_options(&other_opts).map_err(|e| {
+            NmstateError::new(
+                e.kind(),
+                format!(
+                    "{}:{}: {} of {}",
+                    file_path,
+                    line,
+                    e.msg(),
+                    iface_name
+                ),
+            )
+        })?;
+    for name in unsupported {
+        import_warn(
+            file_path,
+            line,
+            &format!("Ignoring unsupported bond option {}", name),
+        );
+    }
+    bond_conf.options = Some(bond_opts);
+    Ok(())
+}
+
+// Build the VLAN filtering configure of bridge port from the list of VLAN
+// ranges, the PVID and the untagged VLAN ranges.
+pub(crate) fn bridge_port_vlan_conf(
+    vlans: &[(u16, u16)],
+    pvid: Option<u16>,
+    untagged: &[(u16, u16)],
+) -> Option<BridgePortVlanConfig> {
+    let mut conf = BridgePortVlanConfig::new();
+    let mut trunk_tags = Vec::new();
+    for (min, max) in vlans.iter().chain(untagged.iter()) {
+        if Some(*min) == pvid && min == max {
+            continue;
+        }
+        if min == max {
+            trunk_tags.push(BridgePortTunkTag::Id(*min));
+        } else {
+            trunk_tags.push(BridgePortTunkTag::IdRange(BridgePortVlanRange {
+                min: *min,
+                max: *max,
+            }));
+        }
+    }
+    if trunk_tags.is_empty() && pvid.is_none() {
+        return None;
+    }
+    conf.tag = pvid;
+    if trunk_tags.is_empty() {
+        conf.mode = Some(BridgePortVlanMode::Access);
+    } else {
+        conf.mode = Some(BridgePortVlanMode::Trunk);
+        conf.enable_native = Some(pvid.is_some());
+        conf.trunk_tags = Some(trunk_tags);
+    }
+    Some(conf)
+}
+
+// Parse VLAN ID or range like `100-200`.
+pub(crate) fn parse_vlan_range(value: &str) -> Option<(u16, u16)> {
+    match value.split_once('-') {
+        Some((min, max)) => {
+            Some((min.trim().parse().ok()?, max.trim().parse().ok()?))
+        }
+        None => {
+            let id = value.trim().parse().ok()?;
+            Some((id, id))
+        }
+    }
+}
+
+pub(crate) fn parse_bool(value: &str) -> Option<bool> {
+    match value.to_lowercase().as_str() {
+        "1" | "yes" | "y" | "true" | "on" => Some(true),
+        "0" | "no" | "n" | "false" | "off" => Some(false),
+        _ => None,
+    }
+}
+
+// Parse time span like `100ms`, `1.5s` or `1min 30s` to milliseconds.
+// The `default_unit_ms` is used for number without unit.
+pub(crate) fn parse_time_span_ms(
+    value: &str,
+    default_unit_ms: u64,
+) -> Option<u64> {
+    let mut total = 0f64;
+    let mut rest = value.trim();
+    if rest.is_empty() {
+        return None;
+    }
+    while !rest.is_empty() {
+        let num_end = rest
+            .find(|c: char| !(c.is_ascii_digit() || c == '.'))
+            .unwrap_or(rest.len());
+        let num: f64 = rest[..num_end].parse().ok()?;
+        rest = rest[num_end..].trim_start();
+        let unit_end = rest
+            .find(|c: char| !c.is_ascii_alphabetic())
+            .unwrap_or(rest.len());
+        let unit_ms = match &rest[..unit_end] {
+            "" => default_unit_ms as f64,
+            "us" | "usec" => 0.001,
+            "ms" | "msec" => 1.0,
+            "s" | "sec" | "second" | "seconds" => 1000.0,
+            "m" | "min" | "minute" | "minutes" => 60_000.0,
+            "h" | "hr" | "hour" | "hours" => 3_600_000.0,
+            _ => return None,
+        };
+        total += num * unit_ms;
+        rest = rest[unit_end..].trim_start();
+    }
+    Some(total.round() as u64)
+}
+
+pub(crate) fn parse_enum<T: serde::de::DeserializeOwned>(
+    value: &str,
+    prop: &str,
+) -> Result<T, NmstateError> {
+    serde_json::from_value(serde_json::Value::String(value.to_string()))
+        .map_err(|e| {
+            NmstateError::new(
+                ErrorKind::InvalidArgument,
+                format!("Invalid value {} for {}: {}", value, prop, e),
+            )
+        })
+}
+
+// Append prefix length to IP address without it.
+pub(crate) fn to_ip_network(addr: &str) -> String {
+    if addr.contains('/') {
+        addr.to_string()
+    } else if addr.contains(':') {
+        format!("{}/128", addr)
+    } else {
+        format!("{}/32", addr)
+    }
+}
+
+pub(crate) fn default_route(
+    iface_name: &str,
+    gateway: &str,
+    table_id: Option<u32>,
+) -> RouteEntry {
+    let mut rt = RouteEntry::new();
+    rt.destination = Some(
+        if gateway.contains(':') {
+            "::/0"
+        } else {
+            "0.0.0.0/0"
+        }
+        .to_string(),
+    );
+    rt.next_hop_iface = Some(iface_name.to_string());
+    rt.next_hop_addr = Some(gateway.to_string());
+    rt.table_id = table_id;
+    rt
+}
+
+// Interfaces and global configurations collected by importer.
+#[derive(Debug, Default)]
+pub(crate) struct ImportedNetState {
+    ifaces: Vec<Interface>,
+    // Tuple of (controller, port config), the port config other than name
+    // is only used by linux bridge.
+    ports: Vec<(String, LinuxBridgePortConfig)>,
+    dns_srvs: Vec<String>,
+    dns_schs: Vec<String>,
+    routes: Vec<RouteEntry>,
+    rules: Vec<RouteRuleEntry>,
+}
+
+impl ImportedNetState {
+    pub(crate) fn new() -> Self {
+        Self::default()
+    }
+
+    // Add interface, the existing one with the same name will be replaced.
+    pub(crate) fn add_iface(&mut self, iface: Interface) {
+        self.ifaces.retain(|i| i.name() != iface.name());
+        self.ifaces.push(iface);
+    }
+
+    pub(crate) fn iface_mut(&mut self, name: &str) -> Option<&mut Interface> {
+        self.ifaces.iter_mut().find(|i| i.name() == name)
+    }
+
+    pub(crate) fn add_port(&mut self, ctrl: &str, port: LinuxBridgePortConfig) {
+        self.ports.push((ctrl.to_string(), port));
+    }
+
+    pub(crate) fn add_dns_server(&mut self, srv: &str) {
+        if !self.dns_srvs.iter().any(|s| s == srv) {
+            self.dns_srvs.push(srv.to_string());
+        }
+    }
+
+    pub(crate) fn add_dns_search(&mut self, sch: &str) {
+        if !self.dns_schs.iter().any(|s| s == sch) {
+            self.dns_schs.push(sch.to_string());
+        }
+    }
+
+    pub(crate) fn add_route(&mut self, route: RouteEntry) {
+        self.routes.push(route);
+    }
+
+    pub(crate) fn add_rule(&mut self, rule: RouteRuleEntry) {
+        self.rules.push(rule);
+    }
+
+    // Attach ports to their controllers and fill the IP configure not
+    // mentioned by imported files: IP is disabled on non-port interfaces
+    // without IP configure, static IP is used when DHCP is not enabled.
+    pub(crate) fn build(mut self) -> Result<NetworkState, NmstateError> {
+        let mut port_names = HashSet::new();
+        for (ctrl, port_conf) in std::mem::take(&mut self.ports) {
+            let port_name = port_conf.name.clone();
+            match self.iface_mut(&ctrl) {
+                Some(Interface::Bond(iface)) => {
+                    if let Some(ports) =
+                        iface.bond.as_mut().and_then(|b| b.port.as_mut())
+                    {
+                        ports.push(port_name.clone());
+                    }
+                }
+                Some(Interface::LinuxBridge(iface)) => {
+                    if let Some(ports) =
+                        iface.bridge.as_mut().and_then(|b| b.port.as_mut())
+                    {
+                        ports.push(port_conf);
+                    }
+                }
+                Some(Interface::Vrf(iface)) => {
+                    if let Some(ports) =
+                        iface.vrf.as_mut().and_then(|v| v.port.as_mut())
+                    {
+                        ports.push(port_name.clone());
+                    }
+                }
+                Some(_) => {
+                    log::warn!(
+                        "Ignoring port {} of unsupported controller {}",
+                        port_name,
+                        ctrl
+                    );
+                    continue;
+                }
+                None => {
+                    log::warn!(
+                        "Controller {} of port {} is not defined",
+                        ctrl,
+                        port_name
+                    );
+                    continue;
+                }
+            }
+            port_names.insert(port_name);
+        }
+
+        let mut net_state = NetworkState::new();
+        for mut iface in self.ifaces {
+            let is_port = port_names.contains(iface.name());
+            let base_iface = iface.base_iface_mut();
+            if is_port {
+                base_iface.ipv4 = None;
+                base_iface.ipv6 = None;
+            } else {
+                fill_ipv4(base_iface.ipv4.get_or_insert_with(|| {
+                    let mut ip = InterfaceIpv4::new();
+                    ip.prop_list.push("enabled");
+                    ip
+                }));
+                fill_ipv6(base_iface.ipv6.get_or_insert_with(|| {
+                    let mut ip = InterfaceIpv6::new();
+                    ip.prop_list.push("enabled");
+                    ip
+                }));
+            }
+            net_state.append_interface_data(iface);
+        }
+
+        if !self.dns_srvs.is_empty() || !self.dns_schs.is_empty() {
+            let mut dns_conf = DnsClientState::new();
+            dns_conf.server = Some(self.dns_srvs);
+            dns_conf.search = Some(self.dns_schs);
+            net_state.dns.config = Some(dns_conf);
+        }
+        if !self.routes.is_empty() {
+            net_state.routes.config = Some(self.routes);
+        }
+        if !self.rules.is_empty() {
+            net_state.rules.config = Some(self.rules);
+        }
+
+        // Round trip through serde to get the same `prop_list` as desired
+        // state loaded from user input.
+        serde_json::to_value(&net_state)
+            .and_then(serde_json::from_value)
+            .map_err(|e| {
+                NmstateError::new(
+                    ErrorKind::Bug,
+                    format!("Failed to convert imported state: {}", e),
+                )
+            })
+    }
+}
+
+fn fill_ipv4(ip: &mut InterfaceIpv4) {
+    if ip.enabled {
+        if ip.dhcp != Some(true) {
+            ip.dhcp = Some(false);
+            ip.addresses.get_or_insert_with(Vec::new);
+        }
+    } else {
+        ip.addresses = None;
+    }
+}
+
+fn fill_ipv6(ip: &mut InterfaceIpv6) {
+    if ip.enabled {
+        ip.dhcp.get_or_insert(false);
+        ip.autoconf.get_or_insert(false);
+        if ip.dhcp != Some(true) && ip.autoconf != Some(true) {
+            ip.addresses.get_or_insert_with(Vec::new);
+        }
+    } else {
+        ip.addresses = None;
+    }
+}
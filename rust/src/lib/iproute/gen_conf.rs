@@ -0,0 +1,719 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::HashSet;
+use std::fmt::Write;
+
+use crate::{
+    gen_conf::get_unsupported_legacy_props,
+    ip::is_ipv6_addr,
+    iproute::{route_to_iproute_args, rule_to_iproute_args},
+    BaseInterface, BondInterface, BridgePortVlanMode, EthernetInterface,
+    EthtoolConfig, InfiniBandInterface, Interface, InterfaceType,
+    LinuxBridgeInterface, LinuxBridgeOptions, LinuxBridgePortConfig,
+    MacVlanMode, MacVtapMode, NetworkState, NmstateError,
+};
+
+const SHELL_BACKEND_NAME: &str = "shell";
+const SHELL_SCRIPT_FILE_NAME: &str = "nmstate.sh";
+
+// The bridge time options of `ip link` are in the unit of 1/100 seconds.
+const BRIDGE_TIME_UNIT_PER_SEC: u32 = 100;
+
+// Properties of get_unsupported_legacy_props() this backend can handle.
+const SHELL_SUPPORTED_LEGACY_PROPS: [&str; 2] =
+    ["ethtool", "accept-all-mac-addresses"];
+
+// Generate a POSIX shell script of ip, ethtool and bridge commands which
+// recreates the network state on a system without any of the interfaces.
+// Interfaces are created in the order of their up priority, so controllers
+// and parents always exist before their ports and children.
+pub(crate) fn shell_gen_conf(
+    net_state: &NetworkState,
+) -> Result<Vec<(String, String)>, NmstateError> {
+    let mut content = String::new();
+    let _ = writeln!(content, "#!/bin/sh");
+    let _ = writeln!(content, "# Generated by nmstate");
+    let _ = writeln!(content, "set -e");
+
+    if let Some(hostname) = net_state
+        .hostname
+        .as_ref()
+        .and_then(|c| c.config.as_deref())
+    {
+        let _ = writeln!(content);
+        let _ = writeln!(content, "hostname {}", shell_quote(hostname));
+    }
+
+    let mut routes = Vec::new();
+    let mut rules = Vec::new();
+    let mut has_dns = false;
+    // The veth peers created along with their veth interfaces
+    let mut veth_peers: HashSet<String> = HashSet::new();
+    for iface in net_state.interfaces.to_vec() {
+        if iface.is_absent() {
+            continue;
+        }
+        let mut cmds = Vec::new();
+        match iface {
+            Interface::Ethernet(eth_iface) => {
+                gen_ethernet_cmds(eth_iface, &mut veth_peers, &mut cmds);
+            }
+            Interface::Dummy(_) => {
+                cmds.push(format!("ip link add {} type dummy", quoted(iface)));
+            }
+            Interface::Bond(bond_iface) => {
+                gen_bond_cmds(bond_iface, &mut cmds);
+            }
+            Interface::LinuxBridge(br_iface) => {
+                gen_bridge_cmds(br_iface, &mut cmds);
+            }
+            Interface::Vlan(vlan_iface) => {
+                if let Some(vlan_conf) = vlan_iface.vlan.as_ref() {
+                    cmds.push(format!(
+                        "ip link add link {} name {} type vlan id {}",
+                        shell_quote(&vlan_conf.base_iface),
+                        quoted(iface),
+                        vlan_conf.id
+                    ));
+                }
+            }
+            Interface::Vxlan(vxlan_iface) => {
+                if let Some(vxlan_conf) = vxlan_iface.vxlan.as_ref() {
+                    let mut cmd = format!(
+                        "ip link add {} type vxlan id {}",
+                        quoted(iface),
+                        vxlan_conf.id
+                    );
+                    if !vxlan_conf.base_iface.is_empty() {
+                        let _ = write!(
+                            cmd,
+                            " dev {}",
+                            shell_quote(&vxlan_conf.base_iface)
+                        );
+                    }
+                    if let Some(remote) = vxlan_conf.remote.as_ref() {
+                        let _ = write!(cmd, " remote {}", remote);
+                    }
+                    if let Some(dst_port) = vxlan_conf.dst_port {
+                        let _ = write!(cmd, " dstport {}", dst_port);
+                    }
+                    cmds.push(cmd);
+                }
+            }
+            Interface::MacVlan(mac_vlan_iface) => {
+                if let Some(conf) = mac_vlan_iface.mac_vlan.as_ref() {
+                    cmds.push(format!(
+                        "ip link add link {} name {} type macvlan mode {}",
+                        shell_quote(&conf.base_iface),
+                        quoted(iface),
+                        mac_vlan_mode_str(conf.mode),
+                    ));
+                }
+            }
+            Interface::MacVtap(mac_vtap_iface) => {
+                if let Some(conf) = mac_vtap_iface.mac_vtap.as_ref() {
+                    cmds.push(format!(
+                        "ip link add link {} name {} type macvtap mode {}",
+                        shell_quote(&conf.base_iface),
+                        quoted(iface),
+                        mac_vtap_mode_str(conf.mode),
+                    ));
+                }
+            }
+            Interface::Vrf(vrf_iface) => {
+                if let Some(vrf_conf) = vrf_iface.vrf.as_ref() {
+                    cmds.push(format!(
+                        "ip link add {} type vrf table {}",
+                        quoted(iface),
+                        vrf_conf.table_id
+                    ));
+                }
+            }
+            Interface::InfiniBand(ib_iface) => {
+                gen_ib_cmds(ib_iface, &mut cmds);
+            }
+            _ => {
+                log::warn!(
+                    "Interface type {} of interface {} is not supported \
+                    by {}, ignoring",
+                    iface.iface_type(),
+                    iface.name(),
+                    SHELL_BACKEND_NAME
+                );
+                continue;
+            }
+        }
+        for prop_path in get_unsupported_legacy_props(iface) {
+            if SHELL_SUPPORTED_LEGACY_PROPS.iter().any(|p| {
+                prop_path == format!("interfaces.{}.{}", iface.name(), p)
+            }) {
+                continue;
+            }
+            log::warn!(
+                "Property {} is not supported by {}, ignoring",
+                prop_path,
+                SHELL_BACKEND_NAME
+            );
+        }
+        let base_iface = iface.base_iface();
+        gen_link_cmds(base_iface, &mut cmds);
+        gen_controller_cmds(base_iface, &mut cmds);
+        if let Some(ethtool_conf) = base_iface.ethtool.as_ref() {
+            gen_ethtool_cmds(&base_iface.name, ethtool_conf, &mut cmds);
+        }
+        if let Some(port_conf) = get_bridge_port_conf(net_state, base_iface) {
+            gen_bridge_port_conf_cmds(port_conf, &mut cmds);
+        }
+        if iface.is_up() {
+            cmds.push(format!("ip link set dev {} up", quoted(iface)));
+            gen_ip_cmds(base_iface, &mut cmds);
+        }
+        has_dns |= [
+            base_iface.ipv4.as_ref().and_then(|i| i.dns.as_ref()),
+            base_iface.ipv6.as_ref().and_then(|i| i.dns.as_ref()),
+        ]
+        .iter()
+        .flatten()
+        .any(|dns| dns.server.is_some() || dns.search.is_some());
+        routes.extend(base_iface.routes.as_deref().unwrap_or_default());
+        rules.extend(base_iface.rules.as_deref().unwrap_or_default());
+
+        let _ = writeln!(content);
+        let _ = writeln!(content, "# {}", base_iface.name);
+        for cmd in cmds {
+            let _ = writeln!(content, "{}", cmd);
+        }
+    }
+    if has_dns {
+        log::warn!(
+            "Cannot store DNS configuration by {}, \
+            please edit /etc/resolv.conf manually",
+            SHELL_BACKEND_NAME
+        );
+    }
+
+    if !routes.is_empty() {
+        let _ = writeln!(content);
+        let _ = writeln!(content, "# Routes");
+        for route in routes {
+            let is_ipv6 = route
+                .destination
+                .as_deref()
+                .map(is_ipv6_addr)
+                .unwrap_or_default();
+            let _ = writeln!(
+                content,
+                "ip {}route add {}",
+                if is_ipv6 { "-6 " } else { "" },
+                route_to_iproute_args(route)
+            );
+        }
+    }
+    if !rules.is_empty() {
+        let _ = writeln!(content);
+        let _ = writeln!(content, "# Route rules");
+        for rule in rules {
+            let is_ipv6 = rule
+                .ip_from
+                .as_deref()
+                .or(rule.ip_to.as_deref())
+                .map(is_ipv6_addr)
+                .unwrap_or_default();
+            let _ = writeln!(
+                content,
+                "ip {}rule add {}",
+                if is_ipv6 { "-6 " } else { "" },
+                rule_to_iproute_args(rule)
+            );
+        }
+    }
+    Ok(vec![(SHELL_SCRIPT_FILE_NAME.to_string(), content)])
+}
+
+fn quoted(iface: &Interface) -> String {
+    shell_quote(iface.name())
+}
+
+// Quote the value for POSIX shell only when required, so the common
+// interface names and addresses stay readable.
+fn shell_quote(value: &str) -> String {
+    if !value.is_empty()
+        && value.chars().all(|c| {
+            c.is_ascii_alphanumeric()
+                || ['.', '_', '-', ':', '/', ',', '+', '@', '='].contains(&c)
+        })
+    {
+        value.to_string()
+    } else {
+        format!("'{}'", value.replace('\'', r#"'\''"#))
+    }
+}
+
+fn on_off(value: bool) -> &'static str {
+    if value {
+        "on"
+    } else {
+        "off"
+    }
+}
+
+fn gen_ethernet_cmds(
+    eth_iface: &EthernetInterface,
+    veth_peers: &mut HashSet<String>,
+    cmds: &mut Vec<String>,
+) {
+    let name = shell_quote(&eth_iface.base.name);
+    if let Some(veth_conf) = eth_iface
+        .veth
+        .as_ref()
+        .filter(|_| !veth_peers.contains(&eth_iface.base.name))
+    {
+        veth_peers.insert(veth_conf.peer.to_string());
+        cmds.push(format!(
+            "ip link add {} type veth peer name {}",
+            name,
+            shell_quote(&veth_conf.peer)
+        ));
+    }
+    if let Some(eth_conf) = eth_iface.ethernet.as_ref() {
+        let mut args = Vec::new();
+        if let Some(auto_neg) = eth_conf.auto_neg {
+            args.push(format!("autoneg {}", on_off(auto_neg)));
+        }
+        if let Some(speed) = eth_conf.speed {
+            args.push(format!("speed {}", speed));
+        }
+        if let Some(duplex) = eth_conf.duplex {
+            args.push(format!("duplex {}", duplex));
+        }
+        if !args.is_empty() {
+            cmds.push(format!("ethtool -s {} {}", name, args.join(" ")));
+        }
+    }
+}
+
+fn gen_bond_cmds(bond_iface: &BondInterface, cmds: &mut Vec<String>) {
+    let mut cmd = format!(
+        "ip link add {} type bond",
+        shell_quote(&bond_iface.base.name)
+    );
+    if let Some(bond_conf) = bond_iface.bond.as_ref() {
+        if let Some(mode) = bond_conf.mode {
+            let _ = write!(cmd, " mode {}", mode);
+        }
+        // The bond options of `ip link` share the names of kernel sysfs.
+        for (name, value) in bond_conf
+            .options
+            .as_ref()
+            .map(|o| o.to_kernel_options())
+            .unwrap_or_default()
+        {
+            let _ = write!(cmd, " {} {}", name, shell_quote(&value));
+        }
+    }
+    cmds.push(cmd);
+}
+
+fn gen_bridge_cmds(br_iface: &LinuxBridgeInterface, cmds: &mut Vec<String>) {
+    let mut cmd = format!(
+        "ip link add {} type bridge",
+        shell_quote(&br_iface.base.name)
+    );
+    if let Some(br_opts) =
+        br_iface.bridge.as_ref().and_then(|b| b.options.as_ref())
+    {
+        for arg in bridge_opts_to_args(br_opts) {
+            let _ = write!(cmd, " {}", arg);
+        }
+    }
+    let has_port_vlan = br_iface
+        .bridge
+        .as_ref()
+        .and_then(|b| b.port.as_deref())
+        .unwrap_or_default()
+        .iter()
+        .any(|p| p.vlan.is_some());
+    if has_port_vlan {
+        cmd.push_str(" vlan_filtering 1");
+    }
+    cmds.push(cmd);
+}
+
+fn bridge_opts_to_args(br_opts: &LinuxBridgeOptions) -> Vec<String> {
+    let mut ret = Vec::new();
+    if let Some(stp_opts) = br_opts.stp.as_ref() {
+        if let Some(enabled) = stp_opts.enabled {
+            ret.push(format!("stp_state {}", u8::from(enabled)));
+        }
+        for (name, value) in [
+            ("forward_delay", stp_opts.forward_delay),
+            ("hello_time", stp_opts.hello_time),
+            ("max_age", stp_opts.max_age),
+        ] {
+            if let Some(v) = value {
+                ret.push(format!(
+                    "{} {}",
+                    name,
+                    u32::from(v) * BRIDGE_TIME_UNIT_PER_SEC
+                ));
+            }
+        }
+        if let Some(v) = stp_opts.priority {
+            ret.push(format!("priority {}", v));
+        }
+    }
+    if let Some(v) = br_opts.mac_ageing_time {
+        ret.push(format!("ageing_time {}", v * BRIDGE_TIME_UNIT_PER_SEC));
+    }
+    if let Some(v) = br_opts.group_addr.as_ref() {
+        ret.push(format!("group_address {}", shell_quote(v)));
+    }
+    if let Some(v) = br_opts.group_fwd_mask {
+        ret.push(format!("group_fwd_mask {}", v));
+    }
+    if let Some(v) = br_opts.hash_max {
+        ret.push(format!("mcast_hash_max {}", v));
+    }
+    if let Some(v) = br_opts.multicast_last_member_count {
+        ret.push(format!("mcast_last_member_count {}", v));
+    }
+    if let Some(v) = br_opts.multicast_startup_query_count {
+        ret.push(format!("mcast_startup_query_count {}", v));
+    }
+    // Multicast intervals of nmstate are already in 1/100 seconds.
+    for (name, value) in [
+        (
+            "mcast_last_member_interval",
+            br_opts.multicast_last_member_interval,
+        ),
+        (
+            "mcast_membership_interval",
+            br_opts.multicast_membership_interval,
+        ),
+        ("mcast_querier_interval", br_opts.multicast_querier_interval),
+        ("mcast_query_interval", br_opts.multicast_query_interval),
+        (
+            "mcast_query_response_interval",
+            br_opts.multicast_query_response_interval,
+        ),
+        (
+            "mcast_startup_query_interval",
+            br_opts.multicast_startup_query_interval,
+        ),
+    ] {
+        if let Some(v) = value {
+            ret.push(format!("{} {}", name, v));
+        }
+    }
+    for (name, value) in [
+        ("mcast_querier", br_opts.multicast_querier),
+        ("mcast_query_use_ifaddr", br_opts.multicast_query_use_ifaddr),
+        ("mcast_snooping", br_opts.multicast_snooping),
+    ] {
+        if let Some(v) = value {
+            ret.push(format!("{} {}", name, u8::from(v)));
+        }
+    }
+    if let Some(v) = br_opts.multicast_router.as_ref() {
+        ret.push(format!("mcast_router {}", v.clone() as u8));
+    }
+    if let Some(v) = br_opts.vlan_protocol {
+        ret.push(format!("vlan_protocol {}", v));
+    }
+    ret
+}
+
+// The bridge port settings are stored in the bridge, but can only be applied
+// after the port attached to it.
+fn get_bridge_port_conf<'a>(
+    net_state: &'a NetworkState,
+    base_iface: &BaseInterface,
+) -> Option<&'a LinuxBridgePortConfig> {
+    if base_iface.controller_type != Some(InterfaceType::LinuxBridge) {
+        return None;
+    }
+    let ctrl_name = base_iface.controller.as_deref()?;
+    if let Some(Interface::LinuxBridge(br_iface)) = net_state
+        .interfaces
+        .get_iface(ctrl_name, InterfaceType::LinuxBridge)
+    {
+        br_iface
+            .bridge
+            .as_ref()
+            .and_then(|b| b.port.as_ref())
+            .and_then(|ports| ports.iter().find(|p| p.name == base_iface.name))
+    } else {
+        None
+    }
+}
+
+fn gen_bridge_port_conf_cmds(
+    port_conf: &LinuxBridgePortConfig,
+    cmds: &mut Vec<String>,
+) {
+    let name = shell_quote(&port_conf.name);
+    let mut args = Vec::new();
+    if let Some(v) = port_conf.stp_path_cost {
+        args.push(format!("cost {}", v));
+    }
+    if let Some(v) = port_conf.stp_priority {
+        args.push(format!("priority {}", v));
+    }
+    if let Some(v) = port_conf.stp_hairpin_mode {
+        args.push(format!("hairpin {}", on_off(v)));
+    }
+    if !args.is_empty() {
+        cmds.push(format!(
+            "ip link set dev {} type bridge_slave {}",
+            name,
+            args.join(" ")
+        ));
+    }
+    let vlan_conf = match port_conf.vlan.as_ref() {
+        Some(v) => v,
+        None => return,
+    };
+    // Remove the default PVID 1 added by kernel when attaching port.
+    cmds.push(format!("bridge vlan del dev {} vid 1", name));
+    if let Some(tag) = vlan_conf.tag {
+        if vlan_conf.mode == Some(BridgePortVlanMode::Trunk) {
+            if vlan_conf.enable_native == Some(true) {
+                cmds.push(format!(
+                    "bridge vlan add dev {} vid {} pvid untagged",
+                    name, tag
+                ));
+            }
+        } else {
+            cmds.push(format!(
+                "bridge vlan add dev {} vid {} pvid untagged",
+                name, tag
+            ));
+        }
+    }
+    for trunk_tag in vlan_conf.trunk_tags.as_deref().unwrap_or_default() {
+        let (min, max) = trunk_tag.get_vlan_tag_range();
+        if min == max {
+            cmds.push(format!("bridge vlan add dev {} vid {}", name, min));
+        } else {
+            cmds.push(format!(
+                "bridge vlan add dev {} vid {}-{}",
+                name, min, max
+            ));
+        }
+    }
+}
+
+fn gen_ib_cmds(ib_iface: &InfiniBandInterface, cmds: &mut Vec<String>) {
+    let ib_conf = match ib_iface.ib.as_ref() {
+        Some(i) => i,
+        None => return,
+    };
+    let name = shell_quote(&ib_iface.base.name);
+    if let (Some(base_iface), Some(pkey)) =
+        (ib_conf.base_iface.as_deref(), ib_conf.pkey)
+    {
+        cmds.push(format!(
+            "ip link add link {} name {} type ipoib pkey {:#06x} mode {}",
+            shell_quote(base_iface),
+            name,
+            pkey,
+            ib_conf.mode
+        ));
+    } else {
+        cmds.push(format!(
+            "ip link set dev {} type ipoib mode {}",
+            name, ib_conf.mode
+        ));
+    }
+}
+
+fn mac_vlan_mode_str(mode: MacVlanMode) -> &'static str {
+    match mode {
+        MacVlanMode::Vepa => "vepa",
+        MacVlanMode::Private => "private",
+        MacVlanMode::Passthru => "passthru",
+        MacVlanMode::Source => "source",
+        _ => "bridge",
+    }
+}
+
+fn mac_vtap_mode_str(mode: MacVtapMode) -> &'static str {
+    match mode {
+        MacVtapMode::Vepa => "vepa",
+        MacVtapMode::Private => "private",
+        MacVtapMode::Passthru => "passthru",
+        MacVtapMode::Source => "source",
+        _ => "bridge",
+    }
+}
+
+fn gen_link_cmds(base_iface: &BaseInterface, cmds: &mut Vec<String>) {
+    let mut args = Vec::new();
+    if let Some(mac) = base_iface.mac_address.as_ref() {
+        args.push(format!("address {}", shell_quote(mac)));
+    }
+    if let Some(mtu) = base_iface.mtu {
+        args.push(format!("mtu {}", mtu));
+    }
+    if let Some(v) = base_iface.accept_all_mac_addresses {
+        args.push(format!("promisc {}", on_off(v)));
+    }
+    if !args.is_empty() {
+        cmds.push(format!(
+            "ip link set dev {} {}",
+            shell_quote(&base_iface.name),
+            args.join(" ")
+        ));
+    }
+}
+
+fn gen_controller_cmds(base_iface: &BaseInterface, cmds: &mut Vec<String>) {
+    let ctrl_name = match base_iface.controller.as_deref() {
+        Some(c) if !c.is_empty() => c,
+        _ => return,
+    };
+    let name = shell_quote(&base_iface.name);
+    match base_iface.controller_type.as_ref() {
+        Some(
+            InterfaceType::Bond
+            | InterfaceType::LinuxBridge
+            | InterfaceType::Vrf,
+        ) => {
+            // Kernel bond refuses to attach port in up state.
+            cmds.push(format!("ip link set dev {} down", name));
+            cmds.push(format!(
+                "ip link set dev {} master {}",
+                name,
+                shell_quote(ctrl_name)
+            ));
+        }
+        Some(ctrl_type) => {
+            log::warn!(
+                "Controller type {} of interface {} is not supported by {}, \
+                ignoring property interfaces.{}.controller",
+                ctrl_type,
+                base_iface.name,
+                SHELL_BACKEND_NAME,
+                base_iface.name,
+            );
+        }
+        None => (),
+    }
+}
+
+fn gen_ethtool_cmds(
+    iface_name: &str,
+    ethtool_conf: &EthtoolConfig,
+    cmds: &mut Vec<String>,
+) {
+    let name = shell_quote(iface_name);
+    if let Some(pause) = ethtool_conf.pause.as_ref() {
+        let mut args = Vec::new();
+        for (opt, value) in [
+            ("autoneg", pause.autoneg),
+            ("rx", pause.rx),
+            ("tx", pause.tx),
+        ] {
+            if let Some(v) = value {
+                args.push(format!("{} {}", opt, on_off(v)));
+            }
+        }
+        if !args.is_empty() {
+            cmds.push(format!("ethtool -A {} {}", name, args.join(" ")));
+        }
+    }
+    if let Some(features) = ethtool_conf.feature.as_ref() {
+        let mut features: Vec<(&String, &bool)> = features.iter().collect();
+        features.sort_unstable();
+        let args: Vec<String> = features
+            .iter()
+            .map(|(k, v)| format!("{} {}", shell_quote(k), on_off(**v)))
+            .collect();
+        if !args.is_empty() {
+            cmds.push(format!("ethtool -K {} {}", name, args.join(" ")));
+        }
+    }
+    if let Some(coalesce) = ethtool_conf.coalesce.as_ref() {
+        let args = ethtool_value_to_args(serde_json::to_value(coalesce));
+        if !args.is_empty() {
+            cmds.push(format!("ethtool -C {} {}", name, args.join(" ")));
+        }
+    }
+    if let Some(ring) = ethtool_conf.ring.as_ref() {
+        let args = ethtool_value_to_args(serde_json::to_value(ring));
+        if !args.is_empty() {
+            cmds.push(format!("ethtool -G {} {}", name, args.join(" ")));
+        }
+    }
+}
+
+// The kebab-case property names of ethtool coalesce and ring are identical
+// to the ethtool command line options. The `*-max` ones are read only.
+fn ethtool_value_to_args(
+    value: Result<serde_json::Value, serde_json::Error>,
+) -> Vec<String> {
+    let mut ret = Vec::new();
+    if let Ok(serde_json::Value::Object(opts)) = value {
+        for (name, value) in opts.iter() {
+            if name.ends_with("-max") {
+                continue;
+            }
+            match value {
+                serde_json::Value::Bool(b) => {
+                    ret.push(format!("{} {}", name, on_off(*b)))
+                }
+                serde_json::Value::Number(n) => {
+                    ret.push(format!("{} {}", name, n))
+                }
+                _ => (),
+            }
+        }
+    }
+    ret.sort_unstable();
+    ret
+}
+
+fn gen_ip_cmds(base_iface: &BaseInterface, cmds: &mut Vec<String>) {
+    let name = shell_quote(&base_iface.name);
+    if let Some(ipv4) = base_iface.ipv4.as_ref().filter(|i| i.enabled) {
+        if ipv4.is_auto() {
+            log::warn!(
+                "Property interfaces.{}.ipv4.dhcp is not supported by {}, \
+                ignoring",
+                base_iface.name,
+                SHELL_BACKEND_NAME
+            );
+        }
+        for addr in ipv4.addresses.as_deref().unwrap_or_default() {
+            cmds.push(format!(
+                "ip addr add {} dev {}",
+                String::from(addr),
+                name
+            ));
+        }
+    }
+    if let Some(ipv6) = base_iface.ipv6.as_ref() {
+        if !ipv6.enabled {
+            return;
+        }
+        for (prop, value) in [("dhcp", ipv6.dhcp), ("autoconf", ipv6.autoconf)]
+        {
+            if value == Some(true) {
+                log::warn!(
+                    "Property interfaces.{}.ipv6.{} is not supported by {}, \
+                    ignoring",
+                    base_iface.name,
+                    prop,
+                    SHELL_BACKEND_NAME
+                );
+            }
+        }
+        for addr in ipv6.addresses.as_deref().unwrap_or_default() {
+            cmds.push(format!(
+                "ip -6 addr add {} dev {}",
+                String::from(addr),
+                name
+            ));
+        }
+    }
+}
@@ -0,0 +1,9 @@
+// SPDX-License-Identifier: Apache-2.0
+
+#[cfg(feature = "gen_conf")]
+mod gen_conf;
+mod route;
+
+#[cfg(feature = "gen_conf")]
+pub(crate) use self::gen_conf::shell_gen_conf;
+pub(crate) use self::route::{route_to_iproute_args, rule_to_iproute_args};
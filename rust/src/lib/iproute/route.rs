@@ -0,0 +1,57 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{RouteEntry, RouteRuleEntry};
+
+// Arguments for `ip route add`, also the line format of ifcfg `route-<iface>`
+// files.
+pub(crate) fn route_to_iproute_args(route: &RouteEntry) -> String {
+    let mut ret = vec![route
+        .destination
+        .as_deref()
+        .unwrap_or("default")
+        .to_string()];
+    if let Some(via) = route.next_hop_addr.as_ref() {
+        ret.push(format!("via {}", via));
+    }
+    if let Some(iface) = route.next_hop_iface.as_ref() {
+        ret.push(format!("dev {}", iface));
+    }
+    if let Some(metric) = route
+        .metric
+        .filter(|m| *m != RouteEntry::USE_DEFAULT_METRIC)
+    {
+        ret.push(format!("metric {}", metric));
+    }
+    if let Some(table_id) = route
+        .table_id
+        .filter(|t| *t != RouteEntry::USE_DEFAULT_ROUTE_TABLE)
+    {
+        ret.push(format!("table {}", table_id));
+    }
+    ret.join(" ")
+}
+
+// Arguments for `ip rule add`, also the line format of ifcfg `rule-<iface>`
+// files.
+pub(crate) fn rule_to_iproute_args(rule: &RouteRuleEntry) -> String {
+    let mut ret = Vec::new();
+    if let Some(from) = rule.ip_from.as_ref() {
+        ret.push(format!("from {}", from));
+    }
+    if let Some(to) = rule.ip_to.as_ref() {
+        ret.push(format!("to {}", to));
+    }
+    if let Some(priority) = rule
+        .priority
+        .filter(|p| *p != RouteRuleEntry::USE_DEFAULT_PRIORITY)
+    {
+        ret.push(format!("priority {}", priority));
+    }
+    ret.push(format!(
+        "table {}",
+        rule.table_id
+            .filter(|t| *t != RouteRuleEntry::USE_DEFAULT_ROUTE_TABLE)
+            .unwrap_or(RouteRuleEntry::DEFAULR_ROUTE_TABLE_ID)
+    ));
+    ret.join(" ")
+}
@@ -1,35 +1,60 @@
 // SPDX-License-Identifier: Apache-2.0
 
+mod checkpoint;
 mod deserializer;
+mod diff;
 mod dns;
 mod error;
 #[cfg(feature = "gen_conf")]
 mod gen_conf;
+mod history;
 mod hostname;
 mod ieee8021x;
 mod iface;
 mod ifaces;
+#[cfg(feature = "gen_conf")]
+mod ifcfg;
+#[cfg(feature = "gen_conf")]
+mod ifupdown;
+#[cfg(feature = "gen_conf")]
+mod import;
 mod ip;
+#[cfg(feature = "gen_conf")]
+mod iproute;
 mod lldp;
 mod mptcp;
 mod net_state;
+#[cfg(feature = "gen_conf")]
+mod netplan;
+#[cfg(feature = "gen_conf")]
+mod networkd;
+mod nic_names;
 #[cfg(feature = "query_apply")]
 mod nispor;
 mod nm;
 mod ovs;
-#[cfg(feature = "query_apply")]
+#[cfg(any(feature = "query_apply", feature = "gen_conf"))]
 mod ovsdb;
+mod plan;
+mod policy;
+mod probe;
 #[cfg(feature = "query_apply")]
 mod query_apply;
 mod route;
 mod route_rule;
+mod schema;
 mod serializer;
 #[cfg(feature = "query_apply")]
+mod snapshot;
+#[cfg(feature = "query_apply")]
 mod state;
 mod unit_tests;
 
+pub use crate::checkpoint::CheckpointInfo;
+pub use crate::diff::{InterfaceDiff, NetworkStateDiff, PropertyDiff};
 pub use crate::dns::{DnsClientState, DnsState};
-pub use crate::error::{ErrorKind, NmstateError};
+pub use crate::error::{ErrorKind, NmstateError, VerificationDifference};
+pub use crate::history::NetworkStateRevision;
 pub use crate::hostname::HostNameState;
 pub use crate::ieee8021x::Ieee8021XConfig;
 pub use crate::iface::{
@@ -43,16 +68,16 @@ pub use crate::ifaces::{
     BridgePortVlanRange, DummyInterface, EthernetConfig, EthernetDuplex,
     EthernetInterface, EthtoolCoalesceConfig, EthtoolConfig,
     EthtoolFeatureConfig, EthtoolPauseConfig, EthtoolRingConfig,
-    InfiniBandConfig, InfiniBandInterface, InfiniBandMode, Interfaces,
-    LinuxBridgeConfig, LinuxBridgeInterface, LinuxBridgeMulticastRouterType,
-    LinuxBridgeOptions, LinuxBridgePortConfig, LinuxBridgeStpOptions,
-    MacVlanConfig, MacVlanInterface, MacVlanMode, MacVtapConfig,
-    MacVtapInterface, MacVtapMode, OvsBridgeBondConfig, OvsBridgeBondMode,
-    OvsBridgeBondPortConfig, OvsBridgeConfig, OvsBridgeInterface,
-    OvsBridgeOptions, OvsBridgePortConfig, OvsDpdkConfig, OvsInterface,
-    OvsPatchConfig, SrIovConfig, SrIovVfConfig, VethConfig, VlanConfig,
-    VlanInterface, VlanProtocol, VrfConfig, VrfInterface, VxlanConfig,
-    VxlanInterface,
+    InfiniBandConfig, InfiniBandInterface, InfiniBandMode, InterfaceIdentifier,
+    Interfaces, LinuxBridgeConfig, LinuxBridgeInterface,
+    LinuxBridgeMulticastRouterType, LinuxBridgeOptions, LinuxBridgePortConfig,
+    LinuxBridgeStpOptions, MacVlanConfig, MacVlanInterface, MacVlanMode,
+    MacVtapConfig, MacVtapInterface, MacVtapMode, OvsBridgeBondConfig,
+    OvsBridgeBondMode, OvsBridgeBondPortConfig, OvsBridgeConfig,
+    OvsBridgeInterface, OvsBridgeOptions, OvsBridgePortConfig, OvsDpdkConfig,
+    OvsInterface, OvsPatchConfig, SrIovConfig, SrIovVfConfig, VethConfig,
+    VlanConfig, VlanInterface, VlanProtocol, VrfConfig, VrfInterface,
+    VxlanConfig, VxlanInterface,
 };
 pub use crate::ip::{
     Dhcpv4ClientId, Dhcpv6Duid, InterfaceIpAddr, InterfaceIpv4, InterfaceIpv6,
@@ -68,5 +93,8 @@ pub use crate::lldp::{
 pub use crate::mptcp::{MptcpAddressFlag, MptcpConfig};
 pub use crate::net_state::NetworkState;
 pub use crate::ovs::{OvsDbGlobalConfig, OvsDbIfaceConfig};
+pub use crate::plan::{NetworkStatePlan, PlanProfile};
+pub use crate::policy::NetworkPolicy;
+pub use crate::probe::{DnsProbe, NetworkProbe, PingProbe, TcpProbe};
 pub use crate::route::{RouteEntry, RouteState, Routes};
 pub use crate::route_rule::{RouteRuleEntry, RouteRuleState, RouteRules};
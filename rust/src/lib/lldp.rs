@@ -51,12 +51,14 @@ const LLDP_SYS_CAP_SVLAN: u16 = 10;
 const LLDP_SYS_CAP_TWO_PORT_MAC_RELAY: u16 = 11;
 
 #[derive(Debug, Clone, PartialEq, Eq, Default, Deserialize, Serialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 #[non_exhaustive]
 #[serde(deny_unknown_fields)]
 pub struct LldpConfig {
     #[serde(deserialize_with = "crate::deserializer::bool_or_string")]
     pub enabled: bool,
     #[serde(skip_deserializing, skip_serializing_if = "Vec::is_empty")]
+    #[cfg_attr(feature = "schema", schemars(skip))]
     pub neighbors: Vec<Vec<LldpNeighborTlv>>,
 }
 
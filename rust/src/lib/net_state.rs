@@ -11,10 +11,13 @@ use crate::{
         reselect_dns_ifaces,
     },
     DnsState, ErrorKind, HostNameState, Interface, InterfaceType, Interfaces,
-    NmstateError, OvsDbGlobalConfig, RouteRules, Routes,
+    NetworkProbe, NmstateError, OvsDbGlobalConfig, RouteRules, Routes,
 };
+#[cfg(not(feature = "query_apply"))]
+use crate::NetworkStatePlan;
 
 #[derive(Clone, Debug, Serialize, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 #[serde(deny_unknown_fields)]
 #[non_exhaustive]
 pub struct NetworkState {
@@ -34,6 +37,8 @@ pub struct NetworkState {
         skip_serializing_if = "OvsDbGlobalConfig::is_none"
     )]
     pub ovsdb: OvsDbGlobalConfig,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub probes: Vec<NetworkProbe>,
     #[serde(skip)]
     // Contain a list of struct member name which is defined explicitly in
     // desire state instead of generated.
@@ -55,6 +60,10 @@ pub struct NetworkState {
     pub(crate) running_config_only: bool,
     #[serde(skip)]
     pub(crate) memory_only: bool,
+    #[serde(skip)]
+    pub(crate) allow_mgmt_disruption: bool,
+    #[serde(skip)]
+    pub(crate) record_history: bool,
 }
 
 impl<'de> Deserialize<'de> for NetworkState {
@@ -89,6 +98,11 @@ impl<'de> Deserialize<'de> for NetworkState {
             net_state.ovsdb = OvsDbGlobalConfig::deserialize(ovsdb_value)
                 .map_err(serde::de::Error::custom)?;
         }
+        if let Some(probes_value) = v.get("probes") {
+            net_state.prop_list.push("probes");
+            net_state.probes = Vec::<NetworkProbe>::deserialize(probes_value)
+                .map_err(serde::de::Error::custom)?;
+        }
         if let Some(hostname_value) = v.get("hostname") {
             net_state.prop_list.push("hostname");
             net_state.hostname = Some(
@@ -149,6 +163,20 @@ impl NetworkState {
         self
     }
 
+    // Allow changes taking down, removing or readdressing the interface
+    // holding the default route or current SSH session.
+    pub fn set_allow_mgmt_disruption(&mut self, value: bool) -> &mut Self {
+        self.allow_mgmt_disruption = value;
+        self
+    }
+
+    // Store desired, pre-apply and resulting state of each successful apply
+    // as a revision, see `NetworkState::history_list()`.
+    pub fn set_record_history(&mut self, value: bool) -> &mut Self {
+        self.record_history = value;
+        self
+    }
+
     pub fn new() -> Self {
         Default::default()
     }
@@ -190,6 +218,35 @@ impl NetworkState {
         ))
     }
 
+    #[cfg(not(feature = "query_apply"))]
+    pub fn verify_current(&self) -> Result<(), NmstateError> {
+        Err(NmstateError::new(
+            ErrorKind::DependencyError,
+            "NetworkState::verify_current() need `query_apply` feature enabled"
+                .into(),
+        ))
+    }
+
+    #[cfg(not(feature = "query_apply"))]
+    pub fn plan(&self) -> Result<NetworkStatePlan, NmstateError> {
+        Err(NmstateError::new(
+            ErrorKind::DependencyError,
+            "NetworkState::plan() need `query_apply` feature enabled".into(),
+        ))
+    }
+
+    #[cfg(not(feature = "query_apply"))]
+    pub fn monitor<F>(&self, _callback: F) -> Result<(), NmstateError>
+    where
+        F: FnMut(&NetworkState, &crate::NetworkStateDiff) -> bool,
+    {
+        Err(NmstateError::new(
+            ErrorKind::DependencyError,
+            "NetworkState::monitor() need `query_apply` feature enabled"
+                .into(),
+        ))
+    }
+
     #[cfg(not(feature = "gen_conf"))]
     pub fn gen_conf(
         &self,
@@ -200,6 +257,63 @@ impl NetworkState {
         ))
     }
 
+    #[cfg(not(feature = "gen_conf"))]
+    pub fn from_nm_keyfiles<P: AsRef<std::path::Path>>(
+        _paths: &[P],
+    ) -> Result<Self, NmstateError> {
+        Err(NmstateError::new(
+            ErrorKind::DependencyError,
+            "NetworkState::from_nm_keyfiles() need `genconf` feature enabled"
+                .into(),
+        ))
+    }
+
+    #[cfg(not(feature = "gen_conf"))]
+    pub fn from_netplan<P: AsRef<std::path::Path>>(
+        _paths: &[P],
+    ) -> Result<Self, NmstateError> {
+        Err(NmstateError::new(
+            ErrorKind::DependencyError,
+            "NetworkState::from_netplan() need `genconf` feature enabled".into(),
+        ))
+    }
+
+    #[cfg(not(feature = "gen_conf"))]
+    pub fn from_ifcfg<P: AsRef<std::path::Path>>(
+        _paths: &[P],
+    ) -> Result<Self, NmstateError> {
+        Err(NmstateError::new(
+            ErrorKind::DependencyError,
+            "NetworkState::from_ifcfg() need `genconf` feature enabled".into(),
+        ))
+    }
+
+    #[cfg(not(feature = "gen_conf"))]
+    pub fn from_networkd<P: AsRef<std::path::Path>>(
+        _paths: &[P],
+    ) -> Result<Self, NmstateError> {
+        Err(NmstateError::new(
+            ErrorKind::DependencyError,
+            "NetworkState::from_networkd() need `genconf` feature enabled".into(),
+        ))
+    }
+
+    #[cfg(not(feature = "gen_conf"))]
+    pub fn validate(&self, _current: Option<&Self>) -> Result<(), NmstateError> {
+        Err(NmstateError::new(
+            ErrorKind::DependencyError,
+            "NetworkState::validate() need `genconf` feature enabled".into(),
+        ))
+    }
+
+    #[cfg(not(feature = "schema"))]
+    pub fn json_schema() -> Result<String, NmstateError> {
+        Err(NmstateError::new(
+            ErrorKind::DependencyError,
+            "NetworkState::json_schema() need `schema` feature enabled".into(),
+        ))
+    }
+
     // Return three NetworkState:
     //  * State for addition.
     //  * State for change.
@@ -546,6 +660,26 @@ impl NetworkState {
         ))
     }
 
+    #[cfg(not(feature = "query_apply"))]
+    pub fn checkpoint_list(
+    ) -> Result<Vec<crate::CheckpointInfo>, NmstateError> {
+        Err(NmstateError::new(
+            ErrorKind::DependencyError,
+            "NetworkState::checkpoint_list() need `query_apply` \
+            feature enabled"
+                .into(),
+        ))
+    }
+
+    #[cfg(not(feature = "query_apply"))]
+    pub fn revert_to(&self, _target: &Self) -> Result<Self, NmstateError> {
+        Err(NmstateError::new(
+            ErrorKind::DependencyError,
+            "NetworkState::revert_to() need `query_apply` feature enabled"
+                .into(),
+        ))
+    }
+
     pub(crate) fn get_kernel_iface_with_route(
         &self,
         iface_name: &str,
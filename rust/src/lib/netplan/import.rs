@@ -0,0 +1,791 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::HashMap;
+use std::convert::TryFrom;
+
+use serde_yaml::{Mapping, Value};
+
+use crate::import::{
+    bridge_opts_mut, default_route, import_warn, ipv4_mut, ipv6_mut,
+    new_imported_iface, parse_bool, parse_enum, set_bond_kernel_options,
+    ImportedNetState,
+};
+use crate::{
+    ErrorKind, Interface, InterfaceIpAddr, InterfaceType, Ipv6AddrGenMode,
+    LinuxBridgePortConfig, NetworkState, NmstateError, RouteEntry,
+    RouteRuleEntry, VlanConfig, VxlanConfig,
+};
+
+const SUPPORTED_SECTIONS: [&str; 7] = [
+    "ethernets",
+    "bonds",
+    "bridges",
+    "vlans",
+    "vrfs",
+    "tunnels",
+    "dummy-devices",
+];
+
+const IP_KEYS: [&str; 14] = [
+    "renderer",
+    "dhcp4",
+    "dhcp6",
+    "dhcp4-overrides",
+    "dhcp6-overrides",
+    "accept-ra",
+    "addresses",
+    "gateway4",
+    "gateway6",
+    "nameservers",
+    "routes",
+    "routing-policy",
+    "ipv6-address-generation",
+    "mtu",
+];
+
+// Netplan bond parameter names and their kernel sysfs names.
+const BOND_PARAMS: [(&str, &str); 21] = [
+    ("mode", "mode"),
+    ("lacp-rate", "lacp_rate"),
+    ("mii-monitor-interval", "miimon"),
+    ("min-links", "min_links"),
+    ("transmit-hash-policy", "xmit_hash_policy"),
+    ("ad-select", "ad_select"),
+    ("all-slaves-active", "all_slaves_active"),
+    ("all-members-active", "all_slaves_active"),
+    ("arp-interval", "arp_interval"),
+    ("arp-ip-targets", "arp_ip_target"),
+    ("arp-validate", "arp_validate"),
+    ("arp-all-targets", "arp_all_targets"),
+    ("up-delay", "updelay"),
+    ("down-delay", "downdelay"),
+    ("fail-over-mac-policy", "fail_over_mac"),
+    ("gratuitous-arp", "num_grat_arp"),
+    ("packets-per-slave", "packets_per_slave"),
+    ("primary-reselect-policy", "primary_reselect"),
+    ("resend-igmp", "resend_igmp"),
+    ("learn-packet-interval", "lp_interval"),
+    ("primary", "primary"),
+];
+
+// Netplan bridge parameter names and their kernel sysfs names.
+const BRIDGE_PARAMS: [(&str, &str); 6] = [
+    ("stp", "stp_state"),
+    ("forward-delay", "forward_delay"),
+    ("hello-time", "hello_time"),
+    ("max-age", "max_age"),
+    ("priority", "priority"),
+    ("ageing-time", "ageing_time"),
+];
+
+// Device definition merged from all netplan files.
+struct NetplanDevice {
+    section: String,
+    id: String,
+    conf: Mapping,
+    // Index of files defining this device
+    file_indexes: Vec<usize>,
+}
+
+struct NetplanImporter<'a> {
+    files: &'a [(String, String)],
+    devices: Vec<NetplanDevice>,
+    id_to_iface: HashMap<String, String>,
+    imported: ImportedNetState,
+}
+
+// Convert the content of netplan YAML files to desired network state.
+// The `files` is list of (file_path, content) sorted in the order of netplan
+// applying them: later files amend or override the earlier ones.
+pub(crate) fn netplan_import(
+    files: &[(String, String)],
+) -> Result<NetworkState, NmstateError> {
+    let mut importer = NetplanImporter {
+        files,
+        devices: Vec::new(),
+        id_to_iface: HashMap::new(),
+        imported: ImportedNetState::new(),
+    };
+    for (i, (file_path, content)) in files.iter().enumerate() {
+        importer.load_file(i, file_path, content)?;
+    }
+    for dev in importer.devices.as_slice() {
+        let iface_name = importer.iface_name_of(dev);
+        importer.id_to_iface.insert(dev.id.clone(), iface_name);
+    }
+    let devices = std::mem::take(&mut importer.devices);
+    for dev in devices.as_slice() {
+        importer.import_device(dev)?;
+    }
+    importer.imported.build()
+}
+
+impl<'a> NetplanImporter<'a> {
+    fn load_file(
+        &mut self,
+        file_index: usize,
+        file_path: &str,
+        content: &str,
+    ) -> Result<(), NmstateError> {
+        let root: Value = serde_yaml::from_str(content).map_err(|e| {
+            NmstateError::new(
+                ErrorKind::InvalidArgument,
+                format!("Failed to parse netplan file {}: {}", file_path, e),
+            )
+        })?;
+        let network = match root.get("network").and_then(Value::as_mapping) {
+            Some(n) => n,
+            None => {
+                if !root.is_null() {
+                    log::warn!(
+                        "Ignoring {} as it has no network section",
+                        file_path
+                    );
+                }
+                return Ok(());
+            }
+        };
+        for (key, value) in network.iter() {
+            let key = key.as_str().unwrap_or_default();
+            if key == "renderer" {
+                continue;
+            }
+            if key == "version" {
+                if value.as_u64() != Some(2) {
+                    import_warn(
+                        file_path,
+                        find_yaml_line(content, &["network", key]),
+                        "Only netplan version 2 is supported",
+                    );
+                }
+                continue;
+            }
+            if !SUPPORTED_SECTIONS.contains(&key) {
+                import_warn(
+                    file_path,
+                    find_yaml_line(content, &["network", key]),
+                    &format!("Ignoring unsupported section {}", key),
+                );
+                continue;
+            }
+            for (id, conf) in value.as_mapping().into_iter().flatten() {
+                let id = id.as_str().unwrap_or_default();
+                let conf = conf.as_mapping().cloned().unwrap_or_default();
+                match self
+                    .devices
+                    .iter_mut()
+                    .find(|d| d.id == id && d.section == key)
+                {
+                    Some(dev) => {
+                        merge_mapping(&mut dev.conf, conf);
+                        dev.file_indexes.push(file_index);
+                    }
+                    None => self.devices.push(NetplanDevice {
+                        section: key.to_string(),
+                        id: id.to_string(),
+                        conf,
+                        file_indexes: vec![file_index],
+                    }),
+                }
+            }
+        }
+        Ok(())
+    }
+
+    // Return the file path and line number of the property of device, the
+    // latest file defining it is preferred.
+    fn locate(&self, dev: &NetplanDevice, path: &[&str]) -> (&str, usize) {
+        let mut full_path = vec!["network", dev.section.as_str(), &dev.id];
+        full_path.extend_from_slice(path);
+        for i in dev.file_indexes.iter().rev() {
+            let (file_path, content) = &self.files[*i];
+            let line = find_yaml_line(content, &full_path);
+            if line > 0 {
+                return (file_path, line);
+            }
+        }
+        let (file_path, content) = &self.files[dev.file_indexes[0]];
+        (file_path, find_yaml_line(content, &full_path[..3]))
+    }
+
+    fn warn(&self, dev: &NetplanDevice, path: &[&str], msg: &str) {
+        let (file_path, line) = self.locate(dev, path);
+        import_warn(file_path, line, msg);
+    }
+
+    fn invalid(&self, dev: &NetplanDevice, path: &[&str]) -> NmstateError {
+        let (file_path, line) = self.locate(dev, path);
+        NmstateError::new(
+            ErrorKind::InvalidArgument,
+            format!(
+                "{}:{}: Invalid value for {} of {}",
+                file_path,
+                line,
+                path.join("."),
+                dev.id
+            ),
+        )
+    }
+
+    // Netplan device ID could be different from interface name when
+    // `match` is used.
+    fn iface_name_of(&self, dev: &NetplanDevice) -> String {
+        if let Some(name) = get_str(&dev.conf, "set-name") {
+            return name;
+        }
+        if let Some(match_conf) = get_mapping(&dev.conf, "match") {
+            match get_str(match_conf, "name") {
+                Some(name) if !name.contains(['*', '?', '[']) => {
+                    return name;
+                }
+                _ => self.warn(
+                    dev,
+                    &["match"],
+                    &format!(
+                        "Matching interface by properties other than name \
+                        is not supported, using {} as interface name",
+                        dev.id
+                    ),
+                ),
+            }
+        }
+        dev.id.clone()
+    }
+
+    fn resolve(&self, id: &str) -> String {
+        self.id_to_iface
+            .get(id)
+            .cloned()
+            .unwrap_or_else(|| id.to_string())
+    }
+
+    fn import_device(
+        &mut self,
+        dev: &NetplanDevice,
+    ) -> Result<(), NmstateError> {
+        let iface_name = self.resolve(&dev.id);
+        let (iface_type, type_keys): (InterfaceType, &[&str]) =
+            match dev.section.as_str() {
+                "ethernets" => (
+                    InterfaceType::Ethernet,
+                    &["match", "set-name", "macaddress"],
+                ),
+                "bonds" => (
+                    InterfaceType::Bond,
+                    &["interfaces", "parameters", "macaddress"],
+                ),
+                "bridges" => (
+                    InterfaceType::LinuxBridge,
+                    &["interfaces", "parameters", "macaddress"],
+                ),
+                "vlans" => (InterfaceType::Vlan, &["id", "link", "macaddress"]),
+                "vrfs" => (InterfaceType::Vrf, &["table", "interfaces"]),
+                "dummy-devices" => (InterfaceType::Dummy, &["macaddress"]),
+                _ => {
+                    let mode = get_str(&dev.conf, "mode").unwrap_or_default();
+                    if mode != "vxlan" {
+                        self.warn(
+                            dev,
+                            &["mode"],
+                            &format!(
+                                "Ignoring tunnel {} of unsupported mode {}",
+                                dev.id, mode
+                            ),
+                        );
+                        return Ok(());
+                    }
+                    (
+                        InterfaceType::Vxlan,
+                        &["mode", "id", "remote", "port", "link"],
+                    )
+                }
+            };
+        for key in dev.conf.iter().filter_map(|(k, _)| k.as_str()) {
+            if !IP_KEYS.contains(&key) && !type_keys.contains(&key) {
+                self.warn(
+                    dev,
+                    &[key],
+                    &format!("Ignoring unsupported property {}", key),
+                );
+            }
+        }
+
+        let mut iface = match new_imported_iface(&iface_name, iface_type) {
+            Some(i) => i,
+            None => return Ok(()),
+        };
+        if let Some(mtu) = get_str(&dev.conf, "mtu") {
+            iface.base_iface_mut().mtu =
+                Some(mtu.parse().map_err(|_| self.invalid(dev, &["mtu"]))?);
+        }
+        if let Some(mac) = get_str(&dev.conf, "macaddress") {
+            iface.base_iface_mut().mac_address = Some(mac);
+        }
+
+        match &mut iface {
+            Interface::Bond(_) => {
+                self.import_bond_params(dev, &mut iface)?;
+                self.import_ports(dev, &iface_name, &HashMap::new());
+            }
+            Interface::LinuxBridge(_) => {
+                let port_confs = self.import_bridge_params(dev, &mut iface)?;
+                self.import_ports(dev, &iface_name, &port_confs);
+            }
+            Interface::Vrf(vrf_iface) => {
+                if let Some(vrf_conf) = vrf_iface.vrf.as_mut() {
+                    vrf_conf.table_id = get_str(&dev.conf, "table")
+                        .and_then(|t| t.parse().ok())
+                        .ok_or_else(|| self.invalid(dev, &["table"]))?;
+                }
+                self.import_ports(dev, &iface_name, &HashMap::new());
+            }
+            Interface::Vlan(vlan_iface) => {
+                vlan_iface.vlan = Some(VlanConfig {
+                    base_iface: get_str(&dev.conf, "link")
+                        .map(|l| self.resolve(&l))
+                        .ok_or_else(|| self.invalid(dev, &["link"]))?,
+                    id: get_str(&dev.conf, "id")
+                        .and_then(|i| i.parse().ok())
+                        .ok_or_else(|| self.invalid(dev, &["id"]))?,
+                });
+            }
+            Interface::Vxlan(vxlan_iface) => {
+                vxlan_iface.vxlan = Some(VxlanConfig {
+                    base_iface: get_str(&dev.conf, "link")
+                        .map(|l| self.resolve(&l))
+                        .unwrap_or_default(),
+                    id: get_str(&dev.conf, "id")
+                        .and_then(|i| i.parse().ok())
+                        .ok_or_else(|| self.invalid(dev, &["id"]))?,
+                    remote: get_str(&dev.conf, "remote")
+                        .map(|r| r.parse())
+                        .transpose()
+                        .map_err(|_| self.invalid(dev, &["remote"]))?,
+                    dst_port: get_str(&dev.conf, "port")
+                        .map(|p| p.parse())
+                        .transpose()
+                        .map_err(|_| self.invalid(dev, &["port"]))?,
+                });
+            }
+            _ => (),
+        }
+
+        self.import_ip(dev, &mut iface)?;
+        self.imported.add_iface(iface);
+        Ok(())
+    }
+
+    fn import_bond_params(
+        &self,
+        dev: &NetplanDevice,
+        iface: &mut Interface,
+    ) -> Result<(), NmstateError> {
+        let params = match get_mapping(&dev.conf, "parameters") {
+            Some(p) => p,
+            None => return Ok(()),
+        };
+        let mut opts = Vec::new();
+        for (key, value) in params.iter() {
+            let key = key.as_str().unwrap_or_default();
+            let kernel_name = match BOND_PARAMS.iter().find(|(k, _)| *k == key)
+            {
+                Some((_, n)) => n,
+                None => {
+                    self.warn(
+                        dev,
+                        &["parameters", key],
+                        &format!("Ignoring unsupported bond parameter {}", key),
+                    );
+                    continue;
+                }
+            };
+            let value = match value {
+                Value::Sequence(items) => items
+                    .iter()
+                    .filter_map(value_to_string)
+                    .collect::<Vec<String>>()
+                    .join(","),
+                Value::Bool(b) => u8::from(*b).to_string(),
+                v => value_to_string(v).unwrap_or_default(),
+            };
+            // The intervals could be suffixed by `ms`.
+            let value = match value.strip_suffix("ms") {
+                Some(v)
+                    if key.ends_with("interval") || key.ends_with("delay") =>
+                {
+                    v.to_string()
+                }
+                _ => value,
+            };
+            opts.push((kernel_name.to_string(), value));
+        }
+        let (file_path, line) = self.locate(dev, &["parameters"]);
+        set_bond_kernel_options(iface, &opts, file_path, line)
+    }
+
+    // Return the bridge port configs defined by `path-cost` and
+    // `port-priority` parameters.
+    fn import_bridge_params(
+        &self,
+        dev: &NetplanDevice,
+        iface: &mut Interface,
+    ) -> Result<HashMap<String, LinuxBridgePortConfig>, NmstateError> {
+        let mut port_confs: HashMap<String, LinuxBridgePortConfig> =
+            HashMap::new();
+        let params = match get_mapping(&dev.conf, "parameters") {
+            Some(p) => p,
+            None => return Ok(port_confs),
+        };
+        let br_opts = match bridge_opts_mut(iface) {
+            Some(o) => o,
+            None => return Ok(port_confs),
+        };
+        for (key, value) in params.iter() {
+            let key = key.as_str().unwrap_or_default();
+            if key == "path-cost" || key == "port-priority" {
+                for (port, v) in value.as_mapping().into_iter().flatten() {
+                    let port = self.resolve(port.as_str().unwrap_or_default());
+                    let v = value_to_string(v).unwrap_or_default();
+                    let port_conf = port_confs.entry(port).or_default();
+                    port_conf
+                        .set_kernel_option(
+                            if key == "path-cost" {
+                                "path_cost"
+                            } else {
+                                "priority"
+                            },
+                            &v,
+                        )
+                        .map_err(|_| self.invalid(dev, &["parameters", key]))?;
+                }
+                continue;
+            }
+            let kernel_name =
+                match BRIDGE_PARAMS.iter().find(|(k, _)| *k == key) {
+                    Some((_, n)) => n,
+                    None => {
+                        self.warn(
+                            dev,
+                            &["parameters", key],
+                            &format!(
+                                "Ignoring unsupported bridge parameter {}",
+                                key
+                            ),
+                        );
+                        continue;
+                    }
+                };
+            let value = match value {
+                Value::Bool(b) => u8::from(*b).to_string(),
+                v => value_to_string(v).unwrap_or_default(),
+            };
+            // The timers could be suffixed by `s`.
+            let value = match value.strip_suffix('s') {
+                Some(v) if key.ends_with("time") || key.ends_with("age") => v,
+                _ => &value,
+            };
+            br_opts
+                .set_kernel_option(kernel_name, value)
+                .map_err(|_| self.invalid(dev, &["parameters", key]))?;
+        }
+        Ok(port_confs)
+    }
+
+    fn import_ports(
+        &mut self,
+        dev: &NetplanDevice,
+        ctrl: &str,
+        port_confs: &HashMap<String, LinuxBridgePortConfig>,
+    ) {
+        for port in get_str_list(&dev.conf, "interfaces") {
+            let port = self.resolve(&port);
+            let mut port_conf =
+                port_confs.get(&port).cloned().unwrap_or_default();
+            port_conf.name = port;
+            self.imported.add_port(ctrl, port_conf);
+        }
+    }
+
+    fn import_ip(
+        &mut self,
+        dev: &NetplanDevice,
+        iface: &mut Interface,
+    ) -> Result<(), NmstateError> {
+        let iface_name = iface.name().to_string();
+        let conf = &dev.conf;
+
+        if get_bool(conf, "dhcp4") == Some(true) {
+            let ipv4 = ipv4_mut(iface);
+            ipv4.dhcp = Some(true);
+            if let Some(overrides) = get_mapping(conf, "dhcp4-overrides") {
+                ipv4.auto_dns = get_bool(overrides, "use-dns");
+                ipv4.auto_routes = get_bool(overrides, "use-routes");
+                ipv4.auto_gateway = get_bool(overrides, "use-routes");
+                ipv4.auto_table_id = get_str(overrides, "route-table")
+                    .and_then(|t| t.parse().ok());
+                self.warn_dhcp_overrides(dev, overrides, "dhcp4-overrides");
+            }
+        }
+        let dhcp6 = get_bool(conf, "dhcp6").unwrap_or_default();
+        let autoconf = get_bool(conf, "accept-ra").unwrap_or(dhcp6);
+        if dhcp6 || autoconf {
+            let ipv6 = ipv6_mut(iface);
+            ipv6.dhcp = Some(dhcp6);
+            ipv6.autoconf = Some(autoconf);
+            if let Some(overrides) = get_mapping(conf, "dhcp6-overrides") {
+                ipv6.auto_dns = get_bool(overrides, "use-dns");
+                ipv6.auto_routes = get_bool(overrides, "use-routes");
+                ipv6.auto_gateway = get_bool(overrides, "use-routes");
+                ipv6.auto_table_id = get_str(overrides, "route-table")
+                    .and_then(|t| t.parse().ok());
+                self.warn_dhcp_overrides(dev, overrides, "dhcp6-overrides");
+            }
+        }
+        if let Some(mode) = get_str(conf, "ipv6-address-generation") {
+            ipv6_mut(iface).addr_gen_mode = Some(
+                parse_enum::<Ipv6AddrGenMode>(&mode, "ipv6-address-generation")
+                    .map_err(|_| {
+                        self.invalid(dev, &["ipv6-address-generation"])
+                    })?,
+            );
+        }
+
+        for addr in conf
+            .get(&Value::from("addresses"))
+            .and_then(Value::as_sequence)
+            .into_iter()
+            .flatten()
+        {
+            // The address could be a mapping holding address options
+            let addr = match addr {
+                Value::Mapping(m) => {
+                    self.warn(
+                        dev,
+                        &["addresses"],
+                        "Ignoring unsupported address options",
+                    );
+                    m.iter().next().and_then(|(k, _)| value_to_string(k))
+                }
+                v => value_to_string(v),
+            }
+            .unwrap_or_default();
+            let ip_addr = InterfaceIpAddr::try_from(addr.as_str())
+                .map_err(|_| self.invalid(dev, &["addresses"]))?;
+            if addr.contains(':') {
+                ipv6_mut(iface)
+                    .addresses
+                    .get_or_insert_with(Vec::new)
+                    .push(ip_addr);
+            } else {
+                ipv4_mut(iface)
+                    .addresses
+                    .get_or_insert_with(Vec::new)
+                    .push(ip_addr);
+            }
+        }
+
+        for key in ["gateway4", "gateway6"] {
+            if let Some(gw) = get_str(conf, key) {
+                self.imported
+                    .add_route(default_route(&iface_name, &gw, None));
+            }
+        }
+        if let Some(ns) = get_mapping(conf, "nameservers") {
+            for srv in get_str_list(ns, "addresses") {
+                self.imported.add_dns_server(&srv);
+            }
+            for sch in get_str_list(ns, "search") {
+                self.imported.add_dns_search(&sch);
+            }
+        }
+        for route in conf
+            .get(&Value::from("routes"))
+            .and_then(Value::as_sequence)
+            .into_iter()
+            .flatten()
+            .filter_map(Value::as_mapping)
+        {
+            self.import_route(dev, &iface_name, route)?;
+        }
+        for rule in conf
+            .get(&Value::from("routing-policy"))
+            .and_then(Value::as_sequence)
+            .into_iter()
+            .flatten()
+            .filter_map(Value::as_mapping)
+        {
+            self.import_rule(dev, rule)?;
+        }
+        Ok(())
+    }
+
+    fn warn_dhcp_overrides(
+        &self,
+        dev: &NetplanDevice,
+        overrides: &Mapping,
+        key: &str,
+    ) {
+        for name in overrides.iter().filter_map(|(k, _)| k.as_str()) {
+            if !["use-dns", "use-routes", "route-table"].contains(&name) {
+                self.warn(
+                    dev,
+                    &[key, name],
+                    &format!("Ignoring unsupported DHCP override {}", name),
+                );
+            }
+        }
+    }
+
+    fn import_route(
+        &mut self,
+        dev: &NetplanDevice,
+        iface_name: &str,
+        route: &Mapping,
+    ) -> Result<(), NmstateError> {
+        let mut rt = RouteEntry::new();
+        rt.next_hop_iface = Some(iface_name.to_string());
+        rt.next_hop_addr = get_str(route, "via");
+        let is_ipv6 = get_str(route, "to")
+            .or_else(|| rt.next_hop_addr.clone())
+            .map(|a| a.contains(':'))
+            .unwrap_or_default();
+        rt.destination = match get_str(route, "to").as_deref() {
+            Some("default") | None => {
+                Some(if is_ipv6 { "::/0" } else { "0.0.0.0/0" }.to_string())
+            }
+            Some(d) => Some(d.to_string()),
+        };
+        rt.metric = get_str(route, "metric")
+            .map(|m| m.parse())
+            .transpose()
+            .map_err(|_| self.invalid(dev, &["routes"]))?;
+        rt.table_id = get_str(route, "table")
+            .map(|t| t.parse())
+            .transpose()
+            .map_err(|_| self.invalid(dev, &["routes"]))?;
+        for key in route.iter().filter_map(|(k, _)| k.as_str()) {
+            if !["to", "via", "metric", "table"].contains(&key) {
+                self.warn(
+                    dev,
+                    &["routes"],
+                    &format!("Ignoring unsupported route property {}", key),
+                );
+            }
+        }
+        self.imported.add_route(rt);
+        Ok(())
+    }
+
+    fn import_rule(
+        &mut self,
+        dev: &NetplanDevice,
+        rule: &Mapping,
+    ) -> Result<(), NmstateError> {
+        let mut rt_rule = RouteRuleEntry::new();
+        rt_rule.ip_from = get_str(rule, "from");
+        rt_rule.ip_to = get_str(rule, "to");
+        rt_rule.priority = get_str(rule, "priority")
+            .map(|p| p.parse())
+            .transpose()
+            .map_err(|_| self.invalid(dev, &["routing-policy"]))?;
+        rt_rule.table_id = get_str(rule, "table")
+            .map(|t| t.parse())
+            .transpose()
+            .map_err(|_| self.invalid(dev, &["routing-policy"]))?;
+        for key in rule.iter().filter_map(|(k, _)| k.as_str()) {
+            if !["from", "to", "priority", "table"].contains(&key) {
+                self.warn(
+                    dev,
+                    &["routing-policy"],
+                    &format!("Ignoring unsupported rule property {}", key),
+                );
+            }
+        }
+        self.imported.add_rule(rt_rule);
+        Ok(())
+    }
+}
+
+// Mappings are merged recursively, other values are overridden.
+fn merge_mapping(old: &mut Mapping, new: Mapping) {
+    for (key, value) in new {
+        match (old.get_mut(&key), value) {
+            (Some(Value::Mapping(old_value)), Value::Mapping(new_value)) => {
+                merge_mapping(old_value, new_value)
+            }
+            (_, value) => {
+                old.insert(key, value);
+            }
+        }
+    }
+}
+
+fn value_to_string(value: &Value) -> Option<String> {
+    match value {
+        Value::String(s) => Some(s.to_string()),
+        Value::Number(n) => Some(n.to_string()),
+        Value::Bool(b) => Some(b.to_string()),
+        _ => None,
+    }
+}
+
+fn get_str(conf: &Mapping, key: &str) -> Option<String> {
+    conf.get(&Value::from(key)).and_then(value_to_string)
+}
+
+fn get_bool(conf: &Mapping, key: &str) -> Option<bool> {
+    match conf.get(&Value::from(key))? {
+        Value::Bool(b) => Some(*b),
+        v => value_to_string(v).and_then(|v| parse_bool(&v)),
+    }
+}
+
+fn get_mapping<'a>(conf: &'a Mapping, key: &str) -> Option<&'a Mapping> {
+    conf.get(&Value::from(key)).and_then(Value::as_mapping)
+}
+
+fn get_str_list(conf: &Mapping, key: &str) -> Vec<String> {
+    conf.get(&Value::from(key))
+        .and_then(Value::as_sequence)
+        .map(|items| items.iter().filter_map(value_to_string).collect())
+        .unwrap_or_default()
+}
+
+// Find the line number of YAML property by its path of keys, 0 for not
+// found. The serde_yaml does not provide location of parsed value.
+fn find_yaml_line(content: &str, path: &[&str]) -> usize {
+    let mut depth = 0;
+    let mut parent_indent: Option<usize> = None;
+    let mut key_indent: Option<usize> = None;
+    for (i, line) in content.lines().enumerate() {
+        let trimmed = line.trim_start();
+        if trimmed.is_empty() || trimmed.starts_with('#') {
+            continue;
+        }
+        let indent = line.len() - trimmed.len();
+        if let Some(parent_indent) = parent_indent {
+            if indent <= parent_indent {
+                return 0;
+            }
+        }
+        // Only the keys of direct child are compared
+        if *key_indent.get_or_insert(indent) != indent {
+            continue;
+        }
+        let key = trimmed
+            .split(':')
+            .next()
+            .unwrap_or_default()
+            .trim()
+            .trim_matches(|c| c == '"' || c == '\'');
+        if key == path[depth] {
+            depth += 1;
+            if depth == path.len() {
+                return i + 1;
+            }
+            parent_indent = Some(indent);
+            key_indent = None;
+        }
+    }
+    0
+}
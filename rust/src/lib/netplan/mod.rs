@@ -0,0 +1,7 @@
+// SPDX-License-Identifier: Apache-2.0
+
+#[cfg(feature = "gen_conf")]
+mod import;
+
+#[cfg(feature = "gen_conf")]
+pub(crate) use import::netplan_import;
@@ -0,0 +1,881 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::HashSet;
+use std::convert::TryFrom;
+
+use crate::import::{
+    bridge_opts_mut, bridge_port_vlan_conf, default_route, import_warn,
+    ipv4_mut, ipv6_mut, new_imported_iface, parse_bool, parse_enum,
+    parse_time_span_ms, parse_vlan_range, set_bond_kernel_options,
+    ImportedNetState,
+};
+use crate::{
+    ErrorKind, Interface, InterfaceIpAddr, InterfaceType, Ipv6AddrGenMode,
+    LinuxBridgePortConfig, MacVlanConfig, MacVlanMode, MacVtapConfig,
+    MacVtapMode, NetworkState, NmstateError, RouteEntry, RouteRuleEntry,
+    VlanConfig, VxlanConfig,
+};
+
+pub(crate) const NETWORKD_NETDEV_SUFFIX: &str = ".netdev";
+pub(crate) const NETWORKD_NETWORK_SUFFIX: &str = ".network";
+
+#[derive(Debug, Clone, Copy)]
+enum OptValue {
+    Plain,
+    // Time span converted to milliseconds
+    Msec,
+    // Time span converted to seconds
+    Sec,
+    // Boolean converted to 1 or 0
+    Bool,
+    // Space separated list converted to comma separated
+    List,
+}
+
+// Option names of networkd bond and their kernel sysfs names.
+const BOND_OPTS: [(&str, &str, OptValue); 22] = [
+    ("Mode", "mode", OptValue::Plain),
+    ("TransmitHashPolicy", "xmit_hash_policy", OptValue::Plain),
+    ("LACPTransmitRate", "lacp_rate", OptValue::Plain),
+    ("MIIMonitorSec", "miimon", OptValue::Msec),
+    ("UpDelaySec", "updelay", OptValue::Msec),
+    ("DownDelaySec", "downdelay", OptValue::Msec),
+    ("LearnPacketIntervalSec", "lp_interval", OptValue::Sec),
+    ("AdSelect", "ad_select", OptValue::Plain),
+    (
+        "AdActorSystemPriority",
+        "ad_actor_sys_prio",
+        OptValue::Plain,
+    ),
+    ("AdUserPortKey", "ad_user_port_key", OptValue::Plain),
+    ("AdActorSystem", "ad_actor_system", OptValue::Plain),
+    ("FailOverMACPolicy", "fail_over_mac", OptValue::Plain),
+    ("ARPValidate", "arp_validate", OptValue::Plain),
+    ("ARPIntervalSec", "arp_interval", OptValue::Msec),
+    ("ARPIPTargets", "arp_ip_target", OptValue::List),
+    ("ARPAllTargets", "arp_all_targets", OptValue::Plain),
+    ("PrimaryReselectPolicy", "primary_reselect", OptValue::Plain),
+    ("ResendIGMP", "resend_igmp", OptValue::Plain),
+    ("PacketsPerSlave", "packets_per_slave", OptValue::Plain),
+    ("GratuitousARP", "num_grat_arp", OptValue::Plain),
+    ("AllSlavesActive", "all_slaves_active", OptValue::Bool),
+    ("MinLinks", "min_links", OptValue::Plain),
+];
+
+// Option names of networkd bridge and their kernel sysfs names.
+const BRIDGE_OPTS: [(&str, &str, OptValue); 9] = [
+    ("STP", "stp_state", OptValue::Bool),
+    ("ForwardDelaySec", "forward_delay", OptValue::Sec),
+    ("HelloTimeSec", "hello_time", OptValue::Sec),
+    ("MaxAgeSec", "max_age", OptValue::Sec),
+    ("AgeingTimeSec", "ageing_time", OptValue::Sec),
+    ("Priority", "priority", OptValue::Plain),
+    ("GroupForwardMask", "group_fwd_mask", OptValue::Plain),
+    ("MulticastSnooping", "multicast_snooping", OptValue::Bool),
+    ("MulticastQuerier", "multicast_querier", OptValue::Bool),
+];
+
+// Option names of `[Bridge]` section in `.network` file and their kernel
+// sysfs names.
+const BRIDGE_PORT_OPTS: [(&str, &str, OptValue); 3] = [
+    ("Cost", "path_cost", OptValue::Plain),
+    ("Priority", "priority", OptValue::Plain),
+    ("HairPin", "hairpin_mode", OptValue::Bool),
+];
+
+const NETWORK_SECTIONS: [&str; 11] = [
+    "Match",
+    "Link",
+    "Network",
+    "Address",
+    "Route",
+    "RoutingPolicyRule",
+    "DHCPv4",
+    "DHCPv6",
+    "IPv6AcceptRA",
+    "Bridge",
+    "BridgeVLAN",
+];
+
+#[derive(Debug)]
+struct NetworkdEntry {
+    key: String,
+    value: String,
+    line: usize,
+}
+
+#[derive(Debug)]
+struct NetworkdSection {
+    name: String,
+    line: usize,
+    entries: Vec<NetworkdEntry>,
+}
+
+impl NetworkdSection {
+    fn get(&self, key: &str) -> Option<&NetworkdEntry> {
+        self.entries
+            .iter()
+            .rev()
+            .find(|e| e.key == key)
+            .filter(|e| !e.value.is_empty())
+    }
+
+    // Keys like `Address=` could be defined multiple times, the empty value
+    // resets the list.
+    fn get_all(&self, key: &str) -> Vec<&NetworkdEntry> {
+        let mut ret = Vec::new();
+        for entry in self.entries.iter().filter(|e| e.key == key) {
+            if entry.value.is_empty() {
+                ret.clear();
+            } else {
+                ret.push(entry);
+            }
+        }
+        ret
+    }
+}
+
+// The systemd unit file in the format of INI with repeatable sections and
+// keys.
+#[derive(Debug)]
+struct NetworkdFile<'a> {
+    file_path: &'a str,
+    sections: Vec<NetworkdSection>,
+}
+
+impl<'a> NetworkdFile<'a> {
+    fn parse(file_path: &'a str, content: &str) -> Result<Self, NmstateError> {
+        let mut sections: Vec<NetworkdSection> = Vec::new();
+        for (i, line) in content.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') || line.starts_with(';')
+            {
+                continue;
+            }
+            if let Some(name) =
+                line.strip_prefix('[').and_then(|l| l.strip_suffix(']'))
+            {
+                sections.push(NetworkdSection {
+                    name: name.to_string(),
+                    line: i + 1,
+                    entries: Vec::new(),
+                });
+                continue;
+            }
+            match (line.split_once('='), sections.last_mut()) {
+                (Some((key, value)), Some(section)) => {
+                    section.entries.push(NetworkdEntry {
+                        key: key.trim().to_string(),
+                        value: value.trim().to_string(),
+                        line: i + 1,
+                    });
+                }
+                _ => {
+                    return Err(NmstateError::new(
+                        ErrorKind::InvalidArgument,
+                        format!(
+                            "{}:{}: Expecting section or Key=Value, got {}",
+                            file_path,
+                            i + 1,
+                            line
+                        ),
+                    ));
+                }
+            }
+        }
+        Ok(Self {
+            file_path,
+            sections,
+        })
+    }
+
+    fn sections(
+        &self,
+        name: &'static str,
+    ) -> impl Iterator<Item = &NetworkdSection> {
+        self.sections.iter().filter(move |s| s.name == name)
+    }
+
+    fn get(&self, section: &'static str, key: &str) -> Option<&NetworkdEntry> {
+        self.sections(section).filter_map(|s| s.get(key)).last()
+    }
+
+    fn get_all(&self, section: &'static str, key: &str) -> Vec<&NetworkdEntry> {
+        self.sections(section)
+            .flat_map(|s| s.get_all(key))
+            .collect()
+    }
+
+    fn get_bool(
+        &self,
+        section: &'static str,
+        key: &str,
+    ) -> Result<Option<bool>, NmstateError> {
+        self.get(section, key)
+            .map(|e| parse_bool(&e.value).ok_or_else(|| self.invalid(e)))
+            .transpose()
+    }
+
+    fn warn(&self, line: usize, msg: &str) {
+        import_warn(self.file_path, line, msg);
+    }
+
+    fn invalid(&self, entry: &NetworkdEntry) -> NmstateError {
+        NmstateError::new(
+            ErrorKind::InvalidArgument,
+            format!(
+                "{}:{}: Invalid value {} for {}",
+                self.file_path, entry.line, entry.value, entry.key
+            ),
+        )
+    }
+
+    fn warn_unsupported_sections(&self, supported: &[&str]) {
+        for section in self.sections.iter() {
+            if !supported.contains(&section.name.as_str()) {
+                self.warn(
+                    section.line,
+                    &format!("Ignoring unsupported section {}", section.name),
+                );
+            }
+        }
+    }
+
+    fn warn_unsupported_keys(&self, section: &'static str, supported: &[&str]) {
+        for entry in self.sections(section).flat_map(|s| s.entries.iter()) {
+            if !supported.contains(&entry.key.as_str()) {
+                self.warn(
+                    entry.line,
+                    &format!(
+                        "Ignoring unsupported option {} of section {}",
+                        entry.key, section
+                    ),
+                );
+            }
+        }
+    }
+}
+
+// Convert the content of systemd-networkd `.netdev` and `.network` files to
+// desired network state. The `files` is list of (file_path, content) sorted
+// by file name.
+pub(crate) fn networkd_import(
+    files: &[(String, String)],
+) -> Result<NetworkState, NmstateError> {
+    let mut netdevs = Vec::new();
+    let mut networks = Vec::new();
+    for (file_path, content) in files {
+        if file_path.ends_with(NETWORKD_NETDEV_SUFFIX) {
+            netdevs.push(NetworkdFile::parse(file_path, content)?);
+        } else if file_path.ends_with(NETWORKD_NETWORK_SUFFIX) {
+            networks.push(NetworkdFile::parse(file_path, content)?);
+        } else {
+            log::warn!(
+                "Ignoring {} as it is not systemd-networkd .netdev or \
+                .network file",
+                file_path
+            );
+        }
+    }
+
+    let mut imported = ImportedNetState::new();
+    for netdev in netdevs.as_slice() {
+        if let Some(iface) = netdev_to_iface(netdev)? {
+            imported.add_iface(iface);
+        }
+    }
+
+    // Tuple of (child, parent, file, line)
+    let mut parents: Vec<(String, String, &str, usize)> = Vec::new();
+    let mut configured: HashSet<String> = HashSet::new();
+    for network in networks.as_slice() {
+        let iface_name = match network_iface_name(network) {
+            Some(n) => n,
+            None => continue,
+        };
+        // Like systemd-networkd, only the first matching `.network` file
+        // is applied.
+        if configured.contains(&iface_name) {
+            network.warn(
+                1,
+                &format!(
+                    "Ignoring as interface {} is already configured by \
+                    other .network file",
+                    iface_name
+                ),
+            );
+            continue;
+        }
+        configured.insert(iface_name.clone());
+        let mut iface = match imported.iface_mut(&iface_name) {
+            Some(i) => i.clone(),
+            None => {
+                match new_imported_iface(&iface_name, InterfaceType::Ethernet) {
+                    Some(i) => i,
+                    None => continue,
+                }
+            }
+        };
+        network_to_iface(network, &mut iface, &mut imported)?;
+        for key in ["VLAN", "MACVLAN", "MACVTAP", "VXLAN"] {
+            for entry in network.get_all("Network", key) {
+                for child in entry.value.split_whitespace() {
+                    parents.push((
+                        child.to_string(),
+                        iface_name.clone(),
+                        network.file_path,
+                        entry.line,
+                    ));
+                }
+            }
+        }
+        imported.add_iface(iface);
+    }
+
+    for (child, parent, file_path, line) in parents {
+        match imported.iface_mut(&child) {
+            Some(Interface::Vlan(iface)) => {
+                if let Some(vlan_conf) = iface.vlan.as_mut() {
+                    vlan_conf.base_iface = parent;
+                }
+            }
+            Some(Interface::MacVlan(iface)) => {
+                if let Some(conf) = iface.mac_vlan.as_mut() {
+                    conf.base_iface = parent;
+                }
+            }
+            Some(Interface::MacVtap(iface)) => {
+                if let Some(conf) = iface.mac_vtap.as_mut() {
+                    conf.base_iface = parent;
+                }
+            }
+            Some(Interface::Vxlan(iface)) => {
+                if let Some(conf) = iface.vxlan.as_mut() {
+                    conf.base_iface = parent;
+                }
+            }
+            _ => import_warn(
+                file_path,
+                line,
+                &format!("Interface {} is not defined by .netdev file", child),
+            ),
+        }
+    }
+    imported.build()
+}
+
+fn netdev_to_iface(
+    netdev: &NetworkdFile,
+) -> Result<Option<Interface>, NmstateError> {
+    let iface_name = match netdev.get("NetDev", "Name") {
+        Some(e) => e.value.as_str(),
+        None => {
+            netdev.warn(1, "Ignoring .netdev file without Name defined");
+            return Ok(None);
+        }
+    };
+    let kind = netdev
+        .get("NetDev", "Kind")
+        .map(|e| e.value.as_str())
+        .unwrap_or_default();
+    let kind_line = netdev.get("NetDev", "Kind").map(|e| e.line).unwrap_or(1);
+    let (iface_type, kind_section): (InterfaceType, &'static str) = match kind {
+        "bond" => (InterfaceType::Bond, "Bond"),
+        "bridge" => (InterfaceType::LinuxBridge, "Bridge"),
+        "vlan" => (InterfaceType::Vlan, "VLAN"),
+        "vxlan" => (InterfaceType::Vxlan, "VXLAN"),
+        "vrf" => (InterfaceType::Vrf, "VRF"),
+        "macvlan" => (InterfaceType::MacVlan, "MACVLAN"),
+        "macvtap" => (InterfaceType::MacVtap, "MACVTAP"),
+        "dummy" => (InterfaceType::Dummy, ""),
+        _ => {
+            netdev.warn(
+                kind_line,
+                &format!(
+                    "Ignoring interface {} of unsupported kind {}",
+                    iface_name, kind
+                ),
+            );
+            return Ok(None);
+        }
+    };
+    netdev.warn_unsupported_sections(&["NetDev", kind_section]);
+    netdev.warn_unsupported_keys(
+        "NetDev",
+        &["Name", "Kind", "MTUBytes", "MACAddress", "Description"],
+    );
+    let mut iface = match new_imported_iface(iface_name, iface_type) {
+        Some(i) => i,
+        None => return Ok(None),
+    };
+    if let Some(entry) = netdev.get("NetDev", "MTUBytes") {
+        iface.base_iface_mut().mtu = Some(
+            parse_bytes(&entry.value).ok_or_else(|| netdev.invalid(entry))?,
+        );
+    }
+    if let Some(entry) = netdev.get("NetDev", "MACAddress") {
+        iface.base_iface_mut().mac_address = Some(entry.value.to_string());
+    }
+
+    match &mut iface {
+        Interface::Bond(_) => {
+            let opts = kernel_opts(netdev, "Bond", &BOND_OPTS)?;
+            let line = netdev.sections("Bond").next().map(|s| s.line);
+            set_bond_kernel_options(
+                &mut iface,
+                &opts,
+                netdev.file_path,
+                line.unwrap_or_default(),
+            )?;
+        }
+        Interface::LinuxBridge(_) => {
+            let opts = kernel_opts(netdev, "Bridge", &BRIDGE_OPTS)?;
+            if let Some(br_opts) = bridge_opts_mut(&mut iface) {
+                for (name, value) in opts {
+                    br_opts.set_kernel_option(&name, &value)?;
+                }
+            }
+        }
+        Interface::Vlan(vlan_iface) => {
+            netdev.warn_unsupported_keys("VLAN", &["Id"]);
+            let entry = netdev.get("VLAN", "Id").ok_or_else(|| {
+                NmstateError::new(
+                    ErrorKind::InvalidArgument,
+                    format!(
+                        "{}: VLAN ID is not defined for {}",
+                        netdev.file_path, iface_name
+                    ),
+                )
+            })?;
+            vlan_iface.vlan = Some(VlanConfig {
+                base_iface: String::new(),
+                id: entry.value.parse().map_err(|_| netdev.invalid(entry))?,
+            });
+        }
+        Interface::Vxlan(vxlan_iface) => {
+            netdev.warn_unsupported_keys(
+                "VXLAN",
+                &["VNI", "Id", "Remote", "DestinationPort"],
+            );
+            let entry = netdev
+                .get("VXLAN", "VNI")
+                .or_else(|| netdev.get("VXLAN", "Id"))
+                .ok_or_else(|| {
+                    NmstateError::new(
+                        ErrorKind::InvalidArgument,
+                        format!(
+                            "{}: VXLAN VNI is not defined for {}",
+                            netdev.file_path, iface_name
+                        ),
+                    )
+                })?;
+            vxlan_iface.vxlan = Some(VxlanConfig {
+                base_iface: String::new(),
+                id: entry.value.parse().map_err(|_| netdev.invalid(entry))?,
+                remote: netdev
+                    .get("VXLAN", "Remote")
+                    .map(|e| e.value.parse().map_err(|_| netdev.invalid(e)))
+                    .transpose()?,
+                dst_port: netdev
+                    .get("VXLAN", "DestinationPort")
+                    .map(|e| e.value.parse().map_err(|_| netdev.invalid(e)))
+                    .transpose()?,
+            });
+        }
+        Interface::Vrf(vrf_iface) => {
+            netdev.warn_unsupported_keys("VRF", &["Table", "TableId"]);
+            if let Some(vrf_conf) = vrf_iface.vrf.as_mut() {
+                if let Some(entry) = netdev
+                    .get("VRF", "Table")
+                    .or_else(|| netdev.get("VRF", "TableId"))
+                {
+                    vrf_conf.table_id = entry
+                        .value
+                        .parse()
+                        .map_err(|_| netdev.invalid(entry))?;
+                }
+            }
+        }
+        Interface::MacVlan(mac_vlan_iface) => {
+            netdev.warn_unsupported_keys("MACVLAN", &["Mode"]);
+            mac_vlan_iface.mac_vlan = Some(MacVlanConfig {
+                base_iface: String::new(),
+                mode: netdev
+                    .get("MACVLAN", "Mode")
+                    .map(|e| {
+                        parse_enum::<MacVlanMode>(&e.value, "MACVLAN Mode")
+                            .map_err(|_| netdev.invalid(e))
+                    })
+                    .transpose()?
+                    .unwrap_or(MacVlanMode::Vepa),
+                accept_all_mac: None,
+            });
+        }
+        Interface::MacVtap(mac_vtap_iface) => {
+            netdev.warn_unsupported_keys("MACVTAP", &["Mode"]);
+            mac_vtap_iface.mac_vtap = Some(MacVtapConfig {
+                base_iface: String::new(),
+                mode: netdev
+                    .get("MACVTAP", "Mode")
+                    .map(|e| {
+                        parse_enum::<MacVtapMode>(&e.value, "MACVTAP Mode")
+                            .map_err(|_| netdev.invalid(e))
+                    })
+                    .transpose()?
+                    .unwrap_or(MacVtapMode::Vepa),
+                accept_all_mac: None,
+            });
+        }
+        _ => (),
+    }
+    Ok(Some(iface))
+}
+
+// Convert options of section to kernel sysfs names and values, warn on the
+// unsupported ones.
+fn kernel_opts(
+    file: &NetworkdFile,
+    section: &'static str,
+    opt_names: &[(&str, &str, OptValue)],
+) -> Result<Vec<(String, String)>, NmstateError> {
+    let mut ret = Vec::new();
+    for entry in file.sections(section).flat_map(|s| s.entries.iter()) {
+        let (kernel_name, opt_value) =
+            match opt_names.iter().find(|(n, _, _)| *n == entry.key) {
+                Some((_, k, v)) => (k, v),
+                None => {
+                    file.warn(
+                        entry.line,
+                        &format!(
+                            "Ignoring unsupported option {} of section {}",
+                            entry.key, section
+                        ),
+                    );
+                    continue;
+                }
+            };
+        let value = match opt_value {
+            OptValue::Plain => Some(entry.value.to_string()),
+            OptValue::Msec => {
+                parse_time_span_ms(&entry.value, 1000).map(|v| v.to_string())
+            }
+            OptValue::Sec => parse_time_span_ms(&entry.value, 1000)
+                .map(|v| (v / 1000).to_string()),
+            OptValue::Bool => {
+                parse_bool(&entry.value).map(|b| u8::from(b).to_string())
+            }
+            OptValue::List => Some(
+                entry.value.split_whitespace().collect::<Vec<_>>().join(","),
+            ),
+        }
+        .ok_or_else(|| file.invalid(entry))?;
+        ret.push((kernel_name.to_string(), value));
+    }
+    Ok(ret)
+}
+
+// Only matching by single interface name is supported.
+fn network_iface_name(network: &NetworkdFile) -> Option<String> {
+    network.warn_unsupported_sections(&NETWORK_SECTIONS);
+    network.warn_unsupported_keys("Match", &["Name"]);
+    let entry = match network.get("Match", "Name") {
+        Some(e) => e,
+        None => {
+            network.warn(
+                1,
+                "Ignoring .network file not matching interface by Name",
+            );
+            return None;
+        }
+    };
+    let names: Vec<&str> = entry.value.split_whitespace().collect();
+    if names.len() != 1 || names[0].contains(['*', '?', '[', '!']) {
+        network.warn(
+            entry.line,
+            &format!(
+                "Ignoring .network file matching multiple interfaces {}",
+                entry.value
+            ),
+        );
+        return None;
+    }
+    Some(names[0].to_string())
+}
+
+fn network_to_iface(
+    network: &NetworkdFile,
+    iface: &mut Interface,
+    imported: &mut ImportedNetState,
+) -> Result<(), NmstateError> {
+    let iface_name = iface.name().to_string();
+    network.warn_unsupported_keys("Link", &["MTUBytes", "MACAddress"]);
+    if let Some(entry) = network.get("Link", "MTUBytes") {
+        iface.base_iface_mut().mtu = Some(
+            parse_bytes(&entry.value).ok_or_else(|| network.invalid(entry))?,
+        );
+    }
+    if let Some(entry) = network.get("Link", "MACAddress") {
+        iface.base_iface_mut().mac_address = Some(entry.value.to_string());
+    }
+
+    network.warn_unsupported_keys(
+        "Network",
+        &[
+            "Description",
+            "DHCP",
+            "Address",
+            "Gateway",
+            "DNS",
+            "Domains",
+            "Bond",
+            "Bridge",
+            "VRF",
+            "VLAN",
+            "MACVLAN",
+            "MACVTAP",
+            "VXLAN",
+            "IPv6AcceptRA",
+            "IPv6LinkLocalAddressGenerationMode",
+        ],
+    );
+    for key in ["Bond", "Bridge", "VRF"] {
+        if let Some(entry) = network.get("Network", key) {
+            let mut port_conf = LinuxBridgePortConfig::new();
+            port_conf.name = iface_name.clone();
+            if key == "Bridge" {
+                bridge_port_conf(network, &mut port_conf)?;
+            }
+            imported.add_port(&entry.value, port_conf);
+            return Ok(());
+        }
+    }
+
+    let (dhcp4, dhcp6) = match network.get("Network", "DHCP") {
+        Some(entry) => match entry.value.as_str() {
+            "ipv4" => (true, false),
+            "ipv6" => (false, true),
+            v => match parse_bool(v) {
+                Some(b) => (b, b),
+                None => return Err(network.invalid(entry)),
+            },
+        },
+        None => (false, false),
+    };
+    if dhcp4 {
+        network.warn_unsupported_keys(
+            "DHCPv4",
+            &["UseDNS", "UseRoutes", "UseGateway", "RouteTable"],
+        );
+        let auto_dns = network.get_bool("DHCPv4", "UseDNS")?;
+        let auto_routes = network.get_bool("DHCPv4", "UseRoutes")?;
+        let auto_gateway = network.get_bool("DHCPv4", "UseGateway")?;
+        let auto_table_id = network
+            .get("DHCPv4", "RouteTable")
+            .map(|e| e.value.parse().map_err(|_| network.invalid(e)))
+            .transpose()?;
+        let ipv4 = ipv4_mut(iface);
+        ipv4.dhcp = Some(true);
+        ipv4.auto_dns = auto_dns;
+        ipv4.auto_routes = auto_routes;
+        ipv4.auto_gateway = auto_gateway.or(auto_routes);
+        ipv4.auto_table_id = auto_table_id;
+    }
+    let autoconf = network
+        .get_bool("Network", "IPv6AcceptRA")?
+        .unwrap_or(dhcp6);
+    if dhcp6 || autoconf {
+        network.warn_unsupported_keys("DHCPv6", &["UseDNS"]);
+        network.warn_unsupported_keys("IPv6AcceptRA", &["UseDNS"]);
+        let auto_dns = network
+            .get_bool("DHCPv6", "UseDNS")?
+            .or(network.get_bool("IPv6AcceptRA", "UseDNS")?);
+        let ipv6 = ipv6_mut(iface);
+        ipv6.dhcp = Some(dhcp6);
+        ipv6.autoconf = Some(autoconf);
+        ipv6.auto_dns = auto_dns;
+    }
+    if let Some(entry) =
+        network.get("Network", "IPv6LinkLocalAddressGenerationMode")
+    {
+        ipv6_mut(iface).addr_gen_mode = Some(
+            parse_enum::<Ipv6AddrGenMode>(&entry.value, &entry.key)
+                .map_err(|_| network.invalid(entry))?,
+        );
+    }
+
+    network.warn_unsupported_keys("Address", &["Address"]);
+    for entry in network
+        .get_all("Network", "Address")
+        .into_iter()
+        .chain(network.get_all("Address", "Address"))
+    {
+        let ip_addr = InterfaceIpAddr::try_from(entry.value.as_str())
+            .map_err(|_| network.invalid(entry))?;
+        if entry.value.contains(':') {
+            ipv6_mut(iface)
+                .addresses
+                .get_or_insert_with(Vec::new)
+                .push(ip_addr);
+        } else {
+            ipv4_mut(iface)
+                .addresses
+                .get_or_insert_with(Vec::new)
+                .push(ip_addr);
+        }
+    }
+    for entry in network.get_all("Network", "Gateway") {
+        imported.add_route(default_route(&iface_name, &entry.value, None));
+    }
+    for entry in network.get_all("Network", "DNS") {
+        for srv in entry.value.split_whitespace() {
+            imported.add_dns_server(srv);
+        }
+    }
+    for entry in network.get_all("Network", "Domains") {
+        // The `~` prefix indicates routing only domain.
+        for sch in entry.value.split_whitespace() {
+            let sch = sch.trim_start_matches('~');
+            if !sch.is_empty() && sch != "." {
+                imported.add_dns_search(sch);
+            }
+        }
+    }
+
+    network.warn_unsupported_keys(
+        "Route",
+        &["Destination", "Gateway", "Metric", "Table"],
+    );
+    for section in network.sections("Route") {
+        imported.add_route(section_to_route(network, section, &iface_name)?);
+    }
+    network.warn_unsupported_keys(
+        "RoutingPolicyRule",
+        &["From", "To", "Priority", "Table"],
+    );
+    for section in network.sections("RoutingPolicyRule") {
+        imported.add_rule(section_to_rule(network, section)?);
+    }
+    Ok(())
+}
+
+fn bridge_port_conf(
+    network: &NetworkdFile,
+    port_conf: &mut LinuxBridgePortConfig,
+) -> Result<(), NmstateError> {
+    for (name, value) in kernel_opts(network, "Bridge", &BRIDGE_PORT_OPTS)? {
+        port_conf.set_kernel_option(&name, &value)?;
+    }
+    network.warn_unsupported_keys(
+        "BridgeVLAN",
+        &["VLAN", "PVID", "EgressUntagged"],
+    );
+    let mut vlans = Vec::new();
+    let mut untagged = Vec::new();
+    let mut pvid = None;
+    for section in network.sections("BridgeVLAN") {
+        for entry in section.get_all("VLAN") {
+            vlans.push(
+                parse_vlan_range(&entry.value)
+                    .ok_or_else(|| network.invalid(entry))?,
+            );
+        }
+        for entry in section.get_all("EgressUntagged") {
+            untagged.push(
+                parse_vlan_range(&entry.value)
+                    .ok_or_else(|| network.invalid(entry))?,
+            );
+        }
+        if let Some(entry) = section.get("PVID") {
+            pvid =
+                Some(entry.value.parse().map_err(|_| network.invalid(entry))?);
+        }
+    }
+    // Untagged VLANs other than PVID cannot be expressed by nmstate.
+    for (min, max) in untagged.iter() {
+        if Some(*min) != pvid || min != max {
+            if let Some(entry) = network.get("BridgeVLAN", "EgressUntagged") {
+                network.warn(
+                    entry.line,
+                    "Only the PVID could be egress untagged, treating \
+                    others as tagged",
+                );
+            }
+            break;
+        }
+    }
+    port_conf.vlan = bridge_port_vlan_conf(&vlans, pvid, &untagged);
+    Ok(())
+}
+
+fn section_to_route(
+    network: &NetworkdFile,
+    section: &NetworkdSection,
+    iface_name: &str,
+) -> Result<RouteEntry, NmstateError> {
+    let mut rt = RouteEntry::new();
+    rt.next_hop_iface = Some(iface_name.to_string());
+    rt.next_hop_addr = section.get("Gateway").map(|e| e.value.to_string());
+    rt.destination = match section.get("Destination") {
+        Some(e) => Some(e.value.to_string()),
+        None => Some(
+            if rt
+                .next_hop_addr
+                .as_deref()
+                .unwrap_or_default()
+                .contains(':')
+            {
+                "::/0"
+            } else {
+                "0.0.0.0/0"
+            }
+            .to_string(),
+        ),
+    };
+    rt.metric = section
+        .get("Metric")
+        .map(|e| e.value.parse().map_err(|_| network.invalid(e)))
+        .transpose()?;
+    rt.table_id = section
+        .get("Table")
+        .map(|e| parse_route_table(&e.value).ok_or_else(|| network.invalid(e)))
+        .transpose()?;
+    Ok(rt)
+}
+
+fn section_to_rule(
+    network: &NetworkdFile,
+    section: &NetworkdSection,
+) -> Result<RouteRuleEntry, NmstateError> {
+    let mut rule = RouteRuleEntry::new();
+    rule.ip_from = section.get("From").map(|e| e.value.to_string());
+    rule.ip_to = section.get("To").map(|e| e.value.to_string());
+    rule.priority = section
+        .get("Priority")
+        .map(|e| e.value.parse().map_err(|_| network.invalid(e)))
+        .transpose()?;
+    rule.table_id = section
+        .get("Table")
+        .map(|e| parse_route_table(&e.value).ok_or_else(|| network.invalid(e)))
+        .transpose()?;
+    Ok(rule)
+}
+
+fn parse_route_table(value: &str) -> Option<u32> {
+    match value {
+        "default" => Some(253),
+        "main" => Some(254),
+        "local" => Some(255),
+        v => v.parse().ok(),
+    }
+}
+
+// Parse size with optional K, M, G suffix in the base of 1024.
+fn parse_bytes(value: &str) -> Option<u64> {
+    let (num, multiplier) = match value.chars().last()? {
+        'K' => (&value[..value.len() - 1], 1u64 << 10),
+        'M' => (&value[..value.len() - 1], 1u64 << 20),
+        'G' => (&value[..value.len() - 1], 1u64 << 30),
+        _ => (value, 1),
+    };
+    num.trim().parse::<u64>().ok().map(|n| n * multiplier)
+}
@@ -0,0 +1,9 @@
+// SPDX-License-Identifier: Apache-2.0
+
+#[cfg(feature = "gen_conf")]
+mod import;
+
+#[cfg(feature = "gen_conf")]
+pub(crate) use import::{
+    networkd_import, NETWORKD_NETDEV_SUFFIX, NETWORKD_NETWORK_SUFFIX,
+};
@@ -0,0 +1,111 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::HashMap;
+use std::fmt::Write;
+
+use crate::{Interface, InterfaceIdentifier, NetworkState};
+
+const LINK_FILE_PREFIX: &str = "98-nmstate-";
+const LINK_FILE_SUFFIX: &str = ".link";
+const LINK_FILE_HEADER: &str = "# Generated by nmstate";
+const LINK_FILE_MAC_KEY: &str = "PermanentMACAddress=";
+const LINK_FILE_NAME_KEY: &str = "Name=";
+
+impl NetworkState {
+    // Render systemd .link files pinning the name of each ethernet
+    // interface to its permanent MAC address. For desired state, only
+    // interfaces identified by `mac-address` are included.
+    // Return a list of (file_name, content).
+    pub fn gen_nic_name_link_files(&self) -> Vec<(String, String)> {
+        let mut ret: Vec<(String, String)> = self
+            .interfaces
+            .kernel_ifaces
+            .values()
+            .filter_map(|iface| {
+                get_pin_mac(iface).map(|mac| gen_link_file(iface.name(), mac))
+            })
+            .collect();
+        ret.sort_unstable();
+        ret
+    }
+
+    // Take a list of (file_name, content) of existing .link files and
+    // return the names of files generated by nmstate which differ from the
+    // file generated for their MAC address now: the MAC address does not
+    // belong to any interface any more, or the file name or `Name=` is not
+    // the current interface name, like the file of old name left beside the
+    // file of new name.
+    pub fn get_stale_nic_name_link_files(
+        &self,
+        exist_files: &[(String, String)],
+    ) -> Vec<String> {
+        let mac_to_name: HashMap<String, &str> = self
+            .interfaces
+            .kernel_ifaces
+            .values()
+            .filter_map(|iface| {
+                get_pin_mac(iface).map(|mac| (mac.to_lowercase(), iface.name()))
+            })
+            .collect();
+        let mut ret = Vec::new();
+        for (file_name, content) in exist_files {
+            if !file_name.starts_with(LINK_FILE_PREFIX)
+                || !file_name.ends_with(LINK_FILE_SUFFIX)
+                || !content.starts_with(LINK_FILE_HEADER)
+            {
+                continue;
+            }
+            let get_value = |key: &str| {
+                content.lines().find_map(|l| l.trim().strip_prefix(key))
+            };
+            let iface_name = get_value(LINK_FILE_MAC_KEY)
+                .and_then(|mac| mac_to_name.get(&mac.to_lowercase()));
+            let is_stale = match iface_name {
+                Some(iface_name) => {
+                    file_name != &gen_link_file_name(iface_name)
+                        || get_value(LINK_FILE_NAME_KEY) != Some(iface_name)
+                }
+                None => true,
+            };
+            if is_stale {
+                ret.push(file_name.to_string());
+            }
+        }
+        ret.sort_unstable();
+        ret
+    }
+}
+
+// Virtual ethernet like veth has no permanent MAC address, hence excluded.
+fn get_pin_mac(iface: &Interface) -> Option<&str> {
+    if let Interface::Ethernet(eth_iface) = iface {
+        if iface.is_absent() || eth_iface.veth.is_some() {
+            return None;
+        }
+        let base_iface = iface.base_iface();
+        let mac =
+            if base_iface.identifier == Some(InterfaceIdentifier::MacAddress) {
+                base_iface.mac_address.as_deref()
+            } else {
+                base_iface.permanent_mac_address.as_deref()
+            };
+        mac.filter(|mac| !mac.is_empty())
+    } else {
+        None
+    }
+}
+
+fn gen_link_file(iface_name: &str, mac: &str) -> (String, String) {
+    let mut content = String::new();
+    let _ = writeln!(content, "{}", LINK_FILE_HEADER);
+    let _ = writeln!(content, "[Match]");
+    let _ = writeln!(content, "{}{}", LINK_FILE_MAC_KEY, mac);
+    let _ = writeln!(content);
+    let _ = writeln!(content, "[Link]");
+    let _ = writeln!(content, "{}{}", LINK_FILE_NAME_KEY, iface_name);
+    (gen_link_file_name(iface_name), content)
+}
+
+fn gen_link_file_name(iface_name: &str) -> String {
+    format!("{}{}{}", LINK_FILE_PREFIX, iface_name, LINK_FILE_SUFFIX)
+}
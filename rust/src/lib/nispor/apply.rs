@@ -1,10 +1,17 @@
 use std::collections::HashMap;
+use std::convert::TryFrom;
 
 use log::warn;
 
 use crate::{
     nispor::{
+        bond::{apply_bond_options, nms_bond_conf_to_np, pre_apply_bond_mode},
         ip::{nmstate_ipv4_to_np, nmstate_ipv6_to_np},
+        link::{create_iface, np_cannot_create, set_promiscuous},
+        linux_bridge::{apply_bridge_options, apply_bridge_port_conf},
+        route::apply_routes,
+        route_rule::apply_route_rules,
+        sysfs::iface_exists,
         veth::nms_veth_conf_to_np,
         vlan::nms_vlan_conf_to_np,
     },
@@ -17,6 +24,7 @@ pub(crate) fn nispor_apply(
     chg_net_state: &NetworkState,
     del_net_state: &NetworkState,
     cur_net_state: &NetworkState,
+    des_net_state: &NetworkState,
 ) -> Result<(), NmstateError> {
     let mut del_net_state = del_net_state.clone();
     only_delete_one_end_of_veth_peer(
@@ -26,40 +34,76 @@ pub(crate) fn nispor_apply(
     apply_single_state(&del_net_state)?;
     apply_single_state(add_net_state)?;
     apply_single_state(chg_net_state)?;
+    // Newly created bridge might get its existing ports attached in
+    // `chg_net_state`, hence apply port configurations after all.
+    apply_bridge_ports_conf(add_net_state)?;
+    apply_bridge_ports_conf(chg_net_state)?;
+    apply_routes(
+        &des_net_state.routes,
+        &cur_net_state.routes,
+        &del_net_state.interfaces,
+    )?;
+    apply_route_rules(&des_net_state.rules, &cur_net_state.rules)?;
     Ok(())
 }
 
-fn net_state_to_nispor(
-    net_state: &NetworkState,
-) -> Result<nispor::NetConf, NmstateError> {
-    let mut np_ifaces: Vec<nispor::IfaceConf> = Vec::new();
-
+// Nispor only knows the basic settings of interface, the rest like bond
+// options are applied by nmstate before or after nispor applying each
+// interface in the order of up priority.
+fn apply_single_state(net_state: &NetworkState) -> Result<(), NmstateError> {
     for iface in net_state.interfaces.to_vec() {
         if iface.is_up() {
-            let np_iface_type = nmstate_iface_type_to_np(&iface.iface_type());
-            if np_iface_type == nispor::IfaceType::Unknown {
-                warn!(
-                    "Unknown interface type {} for interface {}",
-                    iface.iface_type(),
-                    iface.name()
-                );
-                continue;
+            if np_cannot_create(iface) && !iface_exists(iface.name()) {
+                create_iface(iface)?;
+            }
+            if let Interface::Bond(bond_iface) = iface {
+                pre_apply_bond_mode(bond_iface)?;
             }
-            np_ifaces.push(nmstate_iface_to_np(iface, np_iface_type)?);
-        } else if iface.is_absent() {
-            let mut iface_conf = nispor::IfaceConf::default();
-            iface_conf.name = iface.name().to_string();
-            iface_conf.iface_type =
-                Some(nmstate_iface_type_to_np(&iface.iface_type()));
-            iface_conf.state = nispor::IfaceState::Absent;
-            np_ifaces.push(iface_conf);
+        }
+        if let Some(np_iface) = nmstate_iface_to_np_conf(iface)? {
+            let mut np_net_conf = nispor::NetConf::default();
+            np_net_conf.ifaces = Some(vec![np_iface]);
+            if let Err(e) = np_net_conf.apply() {
+                return Err(NmstateError::new(
+                    ErrorKind::PluginFailure,
+                    format!(
+                        "Unknown error from nipsor plugin: {}, {}",
+                        e.kind, e.msg
+                    ),
+                ));
+            }
+        }
+        if iface.is_up() {
+            post_apply_iface(iface)?;
         }
     }
+    Ok(())
+}
 
-    let mut net_conf = nispor::NetConf::default();
-    net_conf.ifaces = Some(np_ifaces);
-
-    Ok(net_conf)
+fn nmstate_iface_to_np_conf(
+    iface: &Interface,
+) -> Result<Option<nispor::IfaceConf>, NmstateError> {
+    if iface.is_up() {
+        let np_iface_type = nmstate_iface_type_to_np(&iface.iface_type());
+        if np_iface_type == nispor::IfaceType::Unknown {
+            warn!(
+                "Unknown interface type {} for interface {}",
+                iface.iface_type(),
+                iface.name()
+            );
+            return Ok(None);
+        }
+        Ok(Some(nmstate_iface_to_np(iface, np_iface_type)?))
+    } else if iface.is_absent() {
+        let mut iface_conf = nispor::IfaceConf::default();
+        iface_conf.name = iface.name().to_string();
+        iface_conf.iface_type =
+            Some(nmstate_iface_type_to_np(&iface.iface_type()));
+        iface_conf.state = nispor::IfaceState::Absent;
+        Ok(Some(iface_conf))
+    } else {
+        Ok(None)
+    }
 }
 
 fn nmstate_iface_type_to_np(
@@ -68,9 +112,14 @@ fn nmstate_iface_type_to_np(
     match nms_iface_type {
         InterfaceType::LinuxBridge => nispor::IfaceType::Bridge,
         InterfaceType::Bond => nispor::IfaceType::Bond,
+        InterfaceType::Dummy => nispor::IfaceType::Dummy,
         InterfaceType::Ethernet => nispor::IfaceType::Ethernet,
+        InterfaceType::MacVlan => nispor::IfaceType::MacVlan,
+        InterfaceType::MacVtap => nispor::IfaceType::MacVtap,
         InterfaceType::Veth => nispor::IfaceType::Veth,
         InterfaceType::Vlan => nispor::IfaceType::Vlan,
+        InterfaceType::Vrf => nispor::IfaceType::Vrf,
+        InterfaceType::Vxlan => nispor::IfaceType::Vxlan,
         _ => nispor::IfaceType::Unknown,
     }
 }
@@ -93,26 +142,67 @@ fn nmstate_iface_to_np(
     }
 
     np_iface.mac_address = base_iface.mac_address.clone();
+    if let Some(mtu) = base_iface.mtu {
+        np_iface.mtu = Some(u32::try_from(mtu).map_err(|_| {
+            NmstateError::new(
+                ErrorKind::InvalidArgument,
+                format!(
+                    "Invalid MTU {} for interface {}",
+                    mtu,
+                    nms_iface.name()
+                ),
+            )
+        })?);
+    }
 
-    if let Interface::Ethernet(eth_iface) = nms_iface {
-        np_iface.veth = nms_veth_conf_to_np(eth_iface.veth.as_ref());
-    } else if let Interface::Vlan(vlan_iface) = nms_iface {
-        np_iface.vlan = nms_vlan_conf_to_np(vlan_iface.vlan.as_ref());
+    match nms_iface {
+        Interface::Ethernet(eth_iface) => {
+            np_iface.veth = nms_veth_conf_to_np(eth_iface.veth.as_ref());
+        }
+        Interface::Vlan(vlan_iface) => {
+            np_iface.vlan = nms_vlan_conf_to_np(vlan_iface.vlan.as_ref());
+        }
+        Interface::Bond(bond_iface) => {
+            np_iface.bond = nms_bond_conf_to_np(bond_iface.bond.as_ref());
+        }
+        _ => (),
     }
 
     Ok(np_iface)
 }
 
-fn apply_single_state(net_state: &NetworkState) -> Result<(), NmstateError> {
-    let np_net_conf = net_state_to_nispor(net_state)?;
-    if let Err(e) = np_net_conf.apply() {
-        Err(NmstateError::new(
-            ErrorKind::PluginFailure,
-            format!("Unknown error from nipsor plugin: {}, {}", e.kind, e.msg),
-        ))
-    } else {
-        Ok(())
+fn post_apply_iface(iface: &Interface) -> Result<(), NmstateError> {
+    match iface {
+        Interface::Bond(bond_iface) => apply_bond_options(bond_iface)?,
+        Interface::LinuxBridge(br_iface) => apply_bridge_options(br_iface)?,
+        _ => (),
     }
+    if let Some(enabled) = iface.base_iface().accept_all_mac_addresses {
+        set_promiscuous(iface.name(), enabled)?;
+    }
+    Ok(())
+}
+
+fn apply_bridge_ports_conf(
+    net_state: &NetworkState,
+) -> Result<(), NmstateError> {
+    for iface in net_state.interfaces.kernel_ifaces.values() {
+        if let Interface::LinuxBridge(br_iface) = iface {
+            if !iface.is_up() {
+                continue;
+            }
+            for port_conf in br_iface
+                .bridge
+                .as_ref()
+                .and_then(|b| b.port.as_ref())
+                .map(|p| p.as_slice())
+                .unwrap_or_default()
+            {
+                apply_bridge_port_conf(port_conf)?;
+            }
+        }
+    }
+    Ok(())
 }
 
 // Deleting one end of veth peer is enough, remove other end from desire state
@@ -86,6 +86,16 @@ pub(crate) fn np_iface_to_base_iface(
         } else {
             None
         },
+        driver: if !running_config_only {
+            get_iface_driver(np_iface.name.as_str())
+        } else {
+            None
+        },
+        pci_address: if !running_config_only {
+            get_iface_pci_address(np_iface.name.as_str())
+        } else {
+            None
+        },
         accept_all_mac_addresses: if np_iface
             .flags
             .contains(&nispor::IfaceFlags::Promisc)
@@ -140,3 +150,21 @@ fn get_permanent_mac_address(iface: &nispor::Iface) -> Option<String> {
         Some(iface.permanent_mac_address.clone())
     }
 }
+
+fn get_iface_driver(iface_name: &str) -> Option<String> {
+    std::fs::read_link(format!("/sys/class/net/{}/device/driver", iface_name))
+        .ok()
+        .and_then(|p| p.file_name().map(|n| n.to_string_lossy().to_string()))
+}
+
+fn get_iface_pci_address(iface_name: &str) -> Option<String> {
+    let dev_path = format!("/sys/class/net/{}/device", iface_name);
+    let subsystem =
+        std::fs::read_link(format!("{}/subsystem", dev_path)).ok()?;
+    if subsystem.file_name().map(|n| n == "pci") != Some(true) {
+        return None;
+    }
+    std::fs::read_link(dev_path)
+        .ok()
+        .and_then(|p| p.file_name().map(|n| n.to_string_lossy().to_string()))
+}
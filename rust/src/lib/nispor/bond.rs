@@ -1,9 +1,14 @@
 use log::warn;
 
 use crate::{
+    nispor::{
+        link::set_link_down,
+        sysfs::{iface_exists as sysfs_iface_exists, read_sysfs, write_sysfs},
+    },
     BaseInterface, BondAdSelect, BondAllPortsActive, BondArpAllTargets,
     BondArpValidate, BondConfig, BondFailOverMac, BondInterface, BondLacpRate,
     BondMode, BondOptions, BondPrimaryReselect, BondXmitHashPolicy,
+    NmstateError,
 };
 
 pub(crate) fn np_bond_to_nmstate(
@@ -186,3 +191,98 @@ fn np_bond_options_to_nmstate(np_iface: &nispor::Iface) -> BondOptions {
     }
     options
 }
+
+pub(crate) fn nms_bond_conf_to_np(
+    nms_bond_conf: Option<&BondConfig>,
+) -> Option<nispor::BondConf> {
+    nms_bond_conf.and_then(|c| c.mode).map(|mode| {
+        let mut np_bond_conf = nispor::BondConf::default();
+        np_bond_conf.mode = Some(match mode {
+            BondMode::RoundRobin => nispor::BondMode::BalanceRoundRobin,
+            BondMode::ActiveBackup => nispor::BondMode::ActiveBackup,
+            BondMode::XOR => nispor::BondMode::BalanceXor,
+            BondMode::Broadcast => nispor::BondMode::Broadcast,
+            BondMode::LACP => nispor::BondMode::Ieee8021AD,
+            BondMode::TLB => nispor::BondMode::BalanceTlb,
+            BondMode::ALB => nispor::BondMode::BalanceAlb,
+            _ => nispor::BondMode::default(),
+        });
+        np_bond_conf
+    })
+}
+
+// Kernel only allows changing bond mode when bond is down and holding no
+// port, nispor will bring the bond up afterwards.
+pub(crate) fn pre_apply_bond_mode(
+    bond_iface: &BondInterface,
+) -> Result<(), NmstateError> {
+    let iface_name = bond_iface.base.name.as_str();
+    if let Some(mode) = bond_iface
+        .bond
+        .as_ref()
+        .and_then(|c| c.mode)
+        .filter(|m| *m != BondMode::Unknown)
+    {
+        if !sysfs_iface_exists(iface_name) {
+            return Ok(());
+        }
+        let mode = mode.to_string();
+        let cur_mode = read_sysfs(iface_name, "bonding/mode")?;
+        if !cur_mode.split_whitespace().any(|m| m == mode) {
+            set_link_down(iface_name)?;
+            write_sysfs(iface_name, "bonding/mode", mode.as_str())?;
+        }
+    }
+    Ok(())
+}
+
+pub(crate) fn apply_bond_options(
+    bond_iface: &BondInterface,
+) -> Result<(), NmstateError> {
+    let iface_name = bond_iface.base.name.as_str();
+    let opts = match bond_iface.bond.as_ref().and_then(|c| c.options.as_ref()) {
+        Some(o) => o,
+        None => return Ok(()),
+    };
+    for (name, value) in opts.to_kernel_options() {
+        if name == "arp_ip_target" {
+            apply_bond_arp_ip_target(iface_name, value.as_str())?;
+        } else {
+            write_sysfs(
+                iface_name,
+                format!("bonding/{}", name).as_str(),
+                value.as_str(),
+            )?;
+        }
+    }
+    Ok(())
+}
+
+// The sysfs `arp_ip_target` is modified by `+<ip>` and `-<ip>`.
+fn apply_bond_arp_ip_target(
+    iface_name: &str,
+    value: &str,
+) -> Result<(), NmstateError> {
+    let des_ips: Vec<&str> = value
+        .split(',')
+        .map(|i| i.trim())
+        .filter(|i| !i.is_empty())
+        .collect();
+    let cur_value = read_sysfs(iface_name, "bonding/arp_ip_target")?;
+    let cur_ips: Vec<&str> = cur_value.split_whitespace().collect();
+    for ip in cur_ips.iter().filter(|i| !des_ips.contains(i)) {
+        write_sysfs(
+            iface_name,
+            "bonding/arp_ip_target",
+            format!("-{}", ip).as_str(),
+        )?;
+    }
+    for ip in des_ips.iter().filter(|i| !cur_ips.contains(i)) {
+        write_sysfs(
+            iface_name,
+            "bonding/arp_ip_target",
+            format!("+{}", ip).as_str(),
+        )?;
+    }
+    Ok(())
+}
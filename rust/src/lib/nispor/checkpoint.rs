@@ -0,0 +1,269 @@
+use std::convert::TryFrom;
+use std::io::Write;
+use std::os::unix::fs::OpenOptionsExt;
+use std::path::{Component, Path};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    nispor::{apply::nispor_apply, show::nispor_retrieve},
+    snapshot::{checkpoint_error, now_secs},
+    CheckpointInfo, ErrorKind, NetworkState, NmstateError,
+};
+
+// Kernel mode has no daemon holding the checkpoint, the pre-apply state is
+// stored in this folder and the file path is used as checkpoint ID, so that
+// a separate process can commit or rollback it.
+// Without daemon, nothing rollbacks the checkpoint on timeout. The checkpoint
+// left by `--no-commit` stays until explicitly committed or rollbacked, and
+// commit of an expired one does the rollback instead.
+const KERNEL_CHECKPOINT_DIR: &str = "/run/nmstate/checkpoint";
+
+#[derive(Debug, Serialize, Deserialize)]
+pub(crate) struct KernelCheckpoint {
+    // Seconds since UNIX epoch
+    pub(crate) created: u64,
+    pub(crate) timeout: u32,
+    pub(crate) state: NetworkState,
+}
+
+impl KernelCheckpoint {
+    pub(crate) fn new(state: &NetworkState, timeout: u32) -> Self {
+        Self {
+            created: now_secs(),
+            timeout,
+            state: state.clone(),
+        }
+    }
+
+    fn is_expired(&self) -> bool {
+        now_secs() >= self.created + u64::from(self.timeout)
+    }
+
+    // Store as `<dir>/<created>-<pid>.json` and return the file path as
+    // checkpoint ID.
+    pub(crate) fn save(&self, dir: &str) -> Result<String, NmstateError> {
+        std::fs::create_dir_all(dir).map_err(|e| {
+            checkpoint_error(format!("Failed to create folder {}", dir), e)
+        })?;
+        let path =
+            format!("{}/{}-{}.json", dir, self.created, std::process::id());
+        let content = serde_json::to_string(self)?;
+        // The pre-apply state is holding secrets.
+        std::fs::OpenOptions::new()
+            .write(true)
+            .create_new(true)
+            .mode(0o600)
+            .open(&path)
+            .and_then(|mut fd| fd.write_all(content.as_bytes()))
+            .map_err(|e| {
+                checkpoint_error(format!("Failed to write {}", path), e)
+            })?;
+        Ok(path)
+    }
+
+    pub(crate) fn read(path: &str) -> Result<Self, NmstateError> {
+        let content = std::fs::read_to_string(path).map_err(|e| {
+            NmstateError::new(
+                ErrorKind::InvalidArgument,
+                format!("Failed to read checkpoint {}: {}", path, e),
+            )
+        })?;
+        Ok(serde_json::from_str(&content)?)
+    }
+}
+
+pub(crate) fn nispor_checkpoint_create(
+    pre_apply_state: &NetworkState,
+    timeout: u32,
+) -> Result<String, NmstateError> {
+    KernelCheckpoint::new(pre_apply_state, timeout).save(KERNEL_CHECKPOINT_DIR)
+}
+
+// Return the kernel checkpoint to use for specified checkpoint ID. Only
+// explicit kernel checkpoint path is accepted, so that leftover kernel
+// checkpoint never takes over the NetworkManager one.
+pub(crate) fn nispor_checkpoint_get(
+    checkpoint: &str,
+) -> Result<Option<String>, NmstateError> {
+    get_checkpoint_path(KERNEL_CHECKPOINT_DIR, checkpoint)
+}
+
+// Checkpoint ID under `dir` is only valid when it is a checkpoint file
+// directly in `dir`, so that commit or rollback never reads or removes other
+// files. Checkpoint ID not under `dir` is not kernel checkpoint.
+pub(crate) fn get_checkpoint_path(
+    dir: &str,
+    checkpoint: &str,
+) -> Result<Option<String>, NmstateError> {
+    if !Path::new(checkpoint).starts_with(dir) {
+        return Ok(None);
+    }
+    let path = Path::new(checkpoint);
+    let is_valid = !path.components().any(|c| c == Component::ParentDir)
+        && path
+            .file_name()
+            .and_then(|n| n.to_str())
+            .map(is_checkpoint_file_name)
+            .unwrap_or_default()
+        && match (path.canonicalize(), Path::new(dir).canonicalize()) {
+            (Ok(path), Ok(dir)) => path.parent() == Some(dir.as_path()),
+            _ => false,
+        };
+    if is_valid {
+        Ok(Some(checkpoint.to_string()))
+    } else {
+        let e = NmstateError::new(
+            ErrorKind::InvalidArgument,
+            format!("Invalid kernel checkpoint {}", checkpoint),
+        );
+        log::error!("{}", e);
+        Err(e)
+    }
+}
+
+// Checkpoint file is named as `<created>-<pid>.json`.
+fn is_checkpoint_file_name(name: &str) -> bool {
+    match name.strip_suffix(".json").and_then(|n| n.split_once('-')) {
+        Some((created, pid)) => {
+            !created.is_empty()
+                && !pid.is_empty()
+                && created.chars().all(|c| c.is_ascii_digit())
+                && pid.chars().all(|c| c.is_ascii_digit())
+        }
+        None => false,
+    }
+}
+
+pub(crate) fn nispor_checkpoint_list() -> Vec<CheckpointInfo> {
+    let mut ret = Vec::new();
+    let entries = match std::fs::read_dir(KERNEL_CHECKPOINT_DIR) {
+        Ok(e) => e,
+        Err(_) => return ret,
+    };
+    for path in entries
+        .filter_map(|e| e.ok())
+        .filter(|e| {
+            e.file_name()
+                .to_str()
+                .map(is_checkpoint_file_name)
+                .unwrap_or_default()
+        })
+        .filter_map(|e| e.path().to_str().map(|p| p.to_string()))
+    {
+        // Race: Checkpoint might just been committed or rollbacked
+        match KernelCheckpoint::read(&path) {
+            Ok(kernel_checkpoint) => {
+                let mut info = CheckpointInfo::new();
+                info.created = kernel_checkpoint.created;
+                info.rollback_timeout = kernel_checkpoint.timeout;
+                info.remaining_timeout = u32::try_from(
+                    (kernel_checkpoint.created
+                        + u64::from(kernel_checkpoint.timeout))
+                    .saturating_sub(now_secs()),
+                )
+                .unwrap_or_default();
+                info.devices = kernel_checkpoint
+                    .state
+                    .interfaces
+                    .kernel_ifaces
+                    .keys()
+                    .cloned()
+                    .collect();
+                info.devices.sort_unstable();
+                info.path = path;
+                ret.push(info);
+            }
+            Err(e) => log::debug!("{}", e),
+        }
+    }
+    ret.sort_unstable_by_key(|c| c.created);
+    ret
+}
+
+// Commit of expired checkpoint will rollback instead, as NetworkManager does
+// the rollback when checkpoint timeout.
+pub(crate) fn nispor_checkpoint_destroy(
+    checkpoint: &str,
+) -> Result<(), NmstateError> {
+    let kernel_checkpoint = KernelCheckpoint::read(checkpoint)?;
+    if kernel_checkpoint.is_expired() {
+        rollback(&kernel_checkpoint)?;
+        remove_checkpoint(checkpoint)?;
+        return Err(NmstateError::new(
+            ErrorKind::InvalidArgument,
+            format!(
+                "Checkpoint {} expired after {} seconds and has been \
+                rollbacked",
+                checkpoint, kernel_checkpoint.timeout
+            ),
+        ));
+    }
+    remove_checkpoint(checkpoint)
+}
+
+// Like NetworkManager, the checkpoint will expire `added_time_sec` seconds
+// from now.
+pub(crate) fn nispor_checkpoint_timeout_extend(
+    checkpoint: &str,
+    added_time_sec: u32,
+) -> Result<(), NmstateError> {
+    let mut kernel_checkpoint = KernelCheckpoint::read(checkpoint)?;
+    kernel_checkpoint.timeout = u32::try_from(
+        now_secs().saturating_sub(kernel_checkpoint.created)
+            + u64::from(added_time_sec),
+    )
+    .unwrap_or(u32::MAX);
+    let content = serde_json::to_string(&kernel_checkpoint)?;
+    // Write then rename, so that other process never reads partial file.
+    let tmp_path = format!("{}.tmp", checkpoint);
+    std::fs::OpenOptions::new()
+        .write(true)
+        .create(true)
+        .truncate(true)
+        .mode(0o600)
+        .open(&tmp_path)
+        .and_then(|mut fd| fd.write_all(content.as_bytes()))
+        .and_then(|_| std::fs::rename(&tmp_path, checkpoint))
+        .map_err(|e| {
+            checkpoint_error(format!("Failed to write {}", checkpoint), e)
+        })
+}
+
+pub(crate) fn nispor_checkpoint_rollback(
+    checkpoint: &str,
+) -> Result<(), NmstateError> {
+    rollback(&KernelCheckpoint::read(checkpoint)?)?;
+    remove_checkpoint(checkpoint)
+}
+
+// Apply the reverse of the changes done since checkpoint creation, check
+// `NetworkState::gen_revert_state()` for detail. Hostname is also restored.
+fn rollback(kernel_checkpoint: &KernelCheckpoint) -> Result<(), NmstateError> {
+    let cur_net_state = nispor_retrieve(false)?;
+    let des_net_state =
+        kernel_checkpoint.state.gen_revert_state(&cur_net_state);
+
+    let (add_net_state, chg_net_state, del_net_state) =
+        des_net_state.gen_state_for_apply(&cur_net_state)?;
+    nispor_apply(
+        &add_net_state,
+        &chg_net_state,
+        &del_net_state,
+        &cur_net_state,
+        &des_net_state,
+    )?;
+    if let Some(hostname) = des_net_state.hostname.as_ref() {
+        hostname.restore(true)?;
+    }
+    Ok(())
+}
+
+fn remove_checkpoint(checkpoint: &str) -> Result<(), NmstateError> {
+    std::fs::remove_file(checkpoint).map_err(|e| {
+        checkpoint_error(
+            format!("Failed to remove checkpoint {}", checkpoint),
+            e,
+        )
+    })
+}
@@ -90,3 +90,27 @@ pub(crate) fn set_running_hostname(hostname: &str) -> Result<(), NmstateError> {
     }
     Ok(())
 }
+
+// Empty hostname means no static hostname configured.
+pub(crate) fn set_config_hostname(hostname: &str) -> Result<(), NmstateError> {
+    let result = if hostname.is_empty() {
+        if std::path::Path::new(HOSTNAME_CONFIG_PATH).exists() {
+            std::fs::remove_file(HOSTNAME_CONFIG_PATH)
+        } else {
+            Ok(())
+        }
+    } else {
+        std::fs::write(HOSTNAME_CONFIG_PATH, format!("{}\n", hostname))
+    };
+    result.map_err(|e| {
+        let e = NmstateError::new(
+            ErrorKind::PluginFailure,
+            format!(
+                "Failed to set hostname config {} to {}: {}",
+                HOSTNAME_CONFIG_PATH, hostname, e
+            ),
+        );
+        log::error!("{}", e);
+        e
+    })
+}
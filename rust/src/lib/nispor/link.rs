@@ -0,0 +1,191 @@
+use std::future::Future;
+use std::net::IpAddr;
+
+use rtnetlink::{
+    packet_route::link::MacVlanMode, LinkDummy, LinkMacVlan, LinkMacVtap,
+    LinkUnspec, LinkVrf, LinkVxlan,
+};
+
+use crate::{
+    nispor::sysfs::get_iface_index, ErrorKind, Interface, MacVtapMode,
+    NmstateError,
+};
+
+// Nispor cannot create these interface types, nmstate create them via
+// netlink before handing over to nispor.
+pub(crate) fn np_cannot_create(iface: &Interface) -> bool {
+    matches!(
+        iface,
+        Interface::Dummy(_)
+            | Interface::Vrf(_)
+            | Interface::Vxlan(_)
+            | Interface::MacVlan(_)
+            | Interface::MacVtap(_)
+    )
+}
+
+pub(crate) fn create_iface(iface: &Interface) -> Result<(), NmstateError> {
+    let name = iface.name();
+    let msg = match iface {
+        Interface::Dummy(_) => LinkDummy::new(name).build(),
+        Interface::Vrf(vrf_iface) => {
+            let table_id = vrf_iface
+                .vrf
+                .as_ref()
+                .map(|v| v.table_id)
+                .unwrap_or_default();
+            LinkVrf::new(name, table_id).build()
+        }
+        Interface::Vxlan(vxlan_iface) => {
+            let vxlan_conf = get_conf(name, vxlan_iface.vxlan.as_ref())?;
+            let mut builder = LinkVxlan::new(name, vxlan_conf.id);
+            if !vxlan_conf.base_iface.is_empty() {
+                builder = builder.dev(get_iface_index(&vxlan_conf.base_iface)?);
+            }
+            if let Some(port) = vxlan_conf.dst_port {
+                builder = builder.port(port);
+            }
+            match vxlan_conf.remote {
+                Some(IpAddr::V4(addr)) => builder = builder.remote(addr),
+                Some(IpAddr::V6(addr)) => builder = builder.remote6(addr),
+                None => (),
+            }
+            builder.build()
+        }
+        Interface::MacVlan(mac_vlan_iface) => {
+            let mac_vlan_conf =
+                get_conf(name, mac_vlan_iface.mac_vlan.as_ref())?;
+            LinkMacVlan::new(
+                name,
+                get_iface_index(&mac_vlan_conf.base_iface)?,
+                np_mac_vlan_mode(mac_vlan_conf.mode),
+            )
+            .build()
+        }
+        Interface::MacVtap(mac_vtap_iface) => {
+            let mac_vtap_conf =
+                get_conf(name, mac_vtap_iface.mac_vtap.as_ref())?;
+            LinkMacVtap::new(
+                name,
+                get_iface_index(&mac_vtap_conf.base_iface)?,
+                np_mac_vtap_mode(mac_vtap_conf.mode),
+            )
+            .build()
+        }
+        _ => {
+            return Err(NmstateError::new(
+                ErrorKind::Bug,
+                format!(
+                    "create_iface() got unexpected interface {} type {}",
+                    name,
+                    iface.iface_type()
+                ),
+            ));
+        }
+    };
+    log::debug!("Creating interface {} type {}", name, iface.iface_type());
+    run_netlink(
+        format!("create interface {}", name).as_str(),
+        |handle| async move { handle.link().add(msg).execute().await },
+    )
+}
+
+// The `u32` of nmstate `MacVlanMode` is NetworkManager numbering which differs
+// from kernel `MACVLAN_MODE_*` flags, hence explicit match here.
+pub(crate) fn np_mac_vlan_mode(mode: crate::MacVlanMode) -> MacVlanMode {
+    match mode {
+        crate::MacVlanMode::Private => MacVlanMode::Private,
+        crate::MacVlanMode::Bridge => MacVlanMode::Bridge,
+        crate::MacVlanMode::Passthru => MacVlanMode::Passthrough,
+        crate::MacVlanMode::Source => MacVlanMode::Source,
+        // Kernel default
+        crate::MacVlanMode::Vepa | crate::MacVlanMode::Unknown => {
+            MacVlanMode::Vepa
+        }
+    }
+}
+
+pub(crate) fn np_mac_vtap_mode(mode: MacVtapMode) -> MacVlanMode {
+    match mode {
+        MacVtapMode::Private => MacVlanMode::Private,
+        MacVtapMode::Bridge => MacVlanMode::Bridge,
+        MacVtapMode::Passthru => MacVlanMode::Passthrough,
+        MacVtapMode::Source => MacVlanMode::Source,
+        // Kernel default
+        MacVtapMode::Vepa | MacVtapMode::Unknown => MacVlanMode::Vepa,
+    }
+}
+
+pub(crate) fn set_link_down(iface_name: &str) -> Result<(), NmstateError> {
+    let msg = LinkUnspec::new_with_name(iface_name).down().build();
+    run_netlink(
+        format!("bring interface {} down", iface_name).as_str(),
+        |handle| async move { handle.link().set(msg).execute().await },
+    )
+}
+
+pub(crate) fn set_promiscuous(
+    iface_name: &str,
+    enabled: bool,
+) -> Result<(), NmstateError> {
+    let msg = LinkUnspec::new_with_name(iface_name)
+        .promiscuous(enabled)
+        .build();
+    run_netlink(
+        format!("set promiscuous mode of interface {}", iface_name).as_str(),
+        |handle| async move { handle.link().set(msg).execute().await },
+    )
+}
+
+// Run single netlink request in a new tokio runtime. The `action` is used in
+// error message like `Failed to <action>: <error>`.
+pub(crate) fn run_netlink<F, Fut>(
+    action: &str,
+    request: F,
+) -> Result<(), NmstateError>
+where
+    F: FnOnce(rtnetlink::Handle) -> Fut,
+    Fut: Future<Output = Result<(), rtnetlink::Error>>,
+{
+    let rt = tokio::runtime::Builder::new_current_thread()
+        .enable_io()
+        .build()
+        .map_err(|e| {
+            NmstateError::new(
+                ErrorKind::Bug,
+                format!("Failed to create tokio runtime: {}", e),
+            )
+        })?;
+    rt.block_on(async {
+        let (connection, handle, _) =
+            rtnetlink::new_connection().map_err(|e| {
+                NmstateError::new(
+                    ErrorKind::PluginFailure,
+                    format!("Failed to create netlink connection: {}", e),
+                )
+            })?;
+        tokio::spawn(connection);
+        request(handle).await.map_err(|e| {
+            NmstateError::new(
+                ErrorKind::PluginFailure,
+                format!("Failed to {}: {}", action, e),
+            )
+        })
+    })
+}
+
+fn get_conf<'a, T>(
+    iface_name: &str,
+    conf: Option<&'a T>,
+) -> Result<&'a T, NmstateError> {
+    conf.ok_or_else(|| {
+        NmstateError::new(
+            ErrorKind::InvalidArgument,
+            format!(
+                "Interface {} is missing its type specific configuration \
+                for creation",
+                iface_name
+            ),
+        )
+    })
+}
@@ -3,10 +3,13 @@ use std::convert::TryFrom;
 use log::warn;
 
 use crate::{
-    nispor::linux_bridge_port_vlan::parse_port_vlan_conf, BaseInterface,
-    LinuxBridgeConfig, LinuxBridgeInterface, LinuxBridgeMulticastRouterType,
-    LinuxBridgeOptions, LinuxBridgePortConfig, LinuxBridgeStpOptions,
-    VlanProtocol,
+    nispor::{
+        linux_bridge_port_vlan::{apply_port_vlan_conf, parse_port_vlan_conf},
+        sysfs::write_sysfs,
+    },
+    BaseInterface, LinuxBridgeConfig, LinuxBridgeInterface,
+    LinuxBridgeMulticastRouterType, LinuxBridgeOptions, LinuxBridgePortConfig,
+    LinuxBridgeStpOptions, NmstateError, VlanProtocol,
 };
 
 pub(crate) fn np_bridge_to_nmstate(
@@ -171,3 +174,66 @@ fn get_stp_options(np_bridge: &nispor::BridgeInfo) -> LinuxBridgeStpOptions {
     stp_opt.priority = np_bridge.priority;
     stp_opt
 }
+
+pub(crate) fn apply_bridge_options(
+    br_iface: &LinuxBridgeInterface,
+) -> Result<(), NmstateError> {
+    let iface_name = br_iface.base.name.as_str();
+    let br_conf = match br_iface.bridge.as_ref() {
+        Some(c) => c,
+        None => return Ok(()),
+    };
+    if let Some(opts) = br_conf.options.as_ref() {
+        for (name, value) in opts.to_kernel_options() {
+            write_sysfs(
+                iface_name,
+                format!("bridge/{}", name).as_str(),
+                value.as_str(),
+            )?;
+        }
+    }
+    if let Some(port_confs) = br_conf.port.as_ref() {
+        let vlan_filtering = if port_confs
+            .iter()
+            .any(|p| p.vlan.as_ref().map(|v| !v.is_empty()).unwrap_or_default())
+        {
+            Some("1")
+        } else if !port_confs.is_empty()
+            && port_confs
+                .iter()
+                .all(|p| p.vlan.as_ref().map(|v| v.is_empty()) == Some(true))
+        {
+            Some("0")
+        } else {
+            None
+        };
+        if let Some(v) = vlan_filtering {
+            write_sysfs(iface_name, "bridge/vlan_filtering", v)?;
+        }
+    }
+    Ok(())
+}
+
+// Should be invoked after port attached to the bridge.
+pub(crate) fn apply_bridge_port_conf(
+    port_conf: &LinuxBridgePortConfig,
+) -> Result<(), NmstateError> {
+    let iface_name = port_conf.name.as_str();
+    if let Some(v) = port_conf.stp_path_cost {
+        write_sysfs(iface_name, "brport/path_cost", v.to_string().as_str())?;
+    }
+    if let Some(v) = port_conf.stp_priority {
+        write_sysfs(iface_name, "brport/priority", v.to_string().as_str())?;
+    }
+    if let Some(v) = port_conf.stp_hairpin_mode {
+        write_sysfs(
+            iface_name,
+            "brport/hairpin_mode",
+            u8::from(v).to_string().as_str(),
+        )?;
+    }
+    if let Some(vlan_conf) = port_conf.vlan.as_ref().filter(|v| !v.is_empty()) {
+        apply_port_vlan_conf(iface_name, vlan_conf)?;
+    }
+    Ok(())
+}
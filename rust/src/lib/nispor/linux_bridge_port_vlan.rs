@@ -1,8 +1,20 @@
+use rtnetlink::packet_route::{
+    link::{
+        AfSpecBridge, BridgeVlanInfo, BridgeVlanInfoFlags, LinkAttribute,
+        LinkMessage,
+    },
+    AddressFamily,
+};
+
 use crate::{
+    nispor::{link::run_netlink, sysfs::get_iface_index},
     BridgePortTunkTag, BridgePortVlanConfig, BridgePortVlanMode,
-    BridgePortVlanRange,
+    BridgePortVlanRange, NmstateError,
 };
 
+const BRIDGE_VLAN_ID_MIN: u16 = 1;
+const BRIDGE_VLAN_ID_MAX: u16 = 4094;
+
 pub(crate) fn parse_port_vlan_conf(
     np_vlan_entries: &[nispor::BridgeVlanEntry],
 ) -> Option<BridgePortVlanConfig> {
@@ -60,3 +72,96 @@ fn get_vlan_tag_range(np_vlan_entry: &nispor::BridgeVlanEntry) -> (u16, u16) {
         np_vlan_entry.vid.unwrap_or(1),
     ))
 }
+
+// Replace all VLANs of specified bridge port, including the default PVID 1
+// added by kernel when attaching port, with the desired ones.
+pub(crate) fn apply_port_vlan_conf(
+    port_name: &str,
+    vlan_conf: &BridgePortVlanConfig,
+) -> Result<(), NmstateError> {
+    let index = get_iface_index(port_name)?;
+    let del_vlan_infos =
+        gen_vlan_infos(BRIDGE_VLAN_ID_MIN, BRIDGE_VLAN_ID_MAX, false);
+    // Kernel ignores the VLANs not found during removal.
+    run_netlink(
+        format!("remove VLANs of bridge port {}", port_name).as_str(),
+        |handle| async move {
+            let mut req = handle.link().del(index);
+            set_af_spec_bridge(req.message_mut(), del_vlan_infos);
+            req.execute().await
+        },
+    )?;
+
+    let mut add_vlan_infos = Vec::new();
+    for (vid_min, vid_max, is_pvid) in nms_port_vlan_conf_to_np(vlan_conf) {
+        add_vlan_infos.extend(gen_vlan_infos(vid_min, vid_max, is_pvid));
+    }
+    if add_vlan_infos.is_empty() {
+        return Ok(());
+    }
+    let mut msg = LinkMessage::default();
+    msg.header.index = index;
+    set_af_spec_bridge(&mut msg, add_vlan_infos);
+    run_netlink(
+        format!("add VLANs to bridge port {}", port_name).as_str(),
+        |handle| async move { handle.link().set(msg).execute().await },
+    )
+}
+
+// Convert to list of (vid_min, vid_max, is_pvid), PVID is always egress
+// untagged.
+pub(crate) fn nms_port_vlan_conf_to_np(
+    vlan_conf: &BridgePortVlanConfig,
+) -> Vec<(u16, u16, bool)> {
+    let mut ret = Vec::new();
+    if let Some(tag) = vlan_conf.tag {
+        if vlan_conf.mode != Some(BridgePortVlanMode::Trunk)
+            || vlan_conf.enable_native == Some(true)
+        {
+            ret.push((tag, tag, true));
+        }
+    }
+    if vlan_conf.mode == Some(BridgePortVlanMode::Trunk) {
+        for trunk_tag in vlan_conf.trunk_tags.as_deref().unwrap_or_default() {
+            let (vid_min, vid_max) = trunk_tag.get_vlan_tag_range();
+            ret.push((vid_min, vid_max, false));
+        }
+    }
+    ret
+}
+
+fn gen_vlan_infos(
+    vid_min: u16,
+    vid_max: u16,
+    is_pvid: bool,
+) -> Vec<BridgeVlanInfo> {
+    let flags = if is_pvid {
+        BridgeVlanInfoFlags::Pvid | BridgeVlanInfoFlags::Untagged
+    } else {
+        BridgeVlanInfoFlags::empty()
+    };
+    if vid_min == vid_max {
+        vec![BridgeVlanInfo {
+            flags,
+            vid: vid_min,
+        }]
+    } else {
+        vec![
+            BridgeVlanInfo {
+                flags: flags | BridgeVlanInfoFlags::RangeBegin,
+                vid: vid_min,
+            },
+            BridgeVlanInfo {
+                flags: flags | BridgeVlanInfoFlags::RangeEnd,
+                vid: vid_max,
+            },
+        ]
+    }
+}
+
+fn set_af_spec_bridge(msg: &mut LinkMessage, vlan_infos: Vec<BridgeVlanInfo>) {
+    msg.header.interface_family = AddressFamily::Bridge;
+    msg.attributes.push(LinkAttribute::AfSpecBridge(
+        vlan_infos.into_iter().map(AfSpecBridge::VlanInfo).collect(),
+    ));
+}
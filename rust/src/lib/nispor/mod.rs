@@ -1,24 +1,42 @@
 mod apply;
 mod base_iface;
 mod bond;
+mod checkpoint;
 mod error;
 mod ethernet;
 mod ethtool;
 mod hostname;
 mod infiniband;
 mod ip;
+mod link;
 mod linux_bridge;
 mod linux_bridge_port_vlan;
 mod mac_vlan;
+mod monitor;
 mod mptcp;
 mod route;
 mod route_rule;
 mod show;
+mod sysfs;
 mod veth;
 mod vlan;
 mod vrf;
 mod vxlan;
 
 pub(crate) use apply::nispor_apply;
-pub(crate) use hostname::set_running_hostname;
+pub(crate) use checkpoint::{
+    nispor_checkpoint_create, nispor_checkpoint_destroy, nispor_checkpoint_get,
+    nispor_checkpoint_list, nispor_checkpoint_rollback,
+    nispor_checkpoint_timeout_extend,
+};
+#[cfg(test)]
+pub(crate) use checkpoint::{get_checkpoint_path, KernelCheckpoint};
+pub(crate) use hostname::{
+    get_hostname_state, set_config_hostname, set_running_hostname,
+};
+#[cfg(test)]
+pub(crate) use link::{np_mac_vlan_mode, np_mac_vtap_mode};
+pub(crate) use monitor::nispor_monitor;
+#[cfg(test)]
+pub(crate) use route::is_route_applied;
 pub(crate) use show::nispor_retrieve;
@@ -0,0 +1,115 @@
+use std::io::Read;
+use std::os::unix::io::AsRawFd;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::Sender;
+use std::sync::Arc;
+use std::time::Duration;
+
+use socket2::{Domain, Protocol, Socket, Type};
+
+use crate::{ErrorKind, NmstateError};
+
+// Multicast group bit masks defined in linux/rtnetlink.h
+const RTMGRP_LINK: u32 = 0x1;
+const RTMGRP_IPV4_IFADDR: u32 = 0x10;
+const RTMGRP_IPV4_ROUTE: u32 = 0x40;
+const RTMGRP_IPV4_RULE: u32 = 0x80;
+const RTMGRP_IPV6_IFADDR: u32 = 0x100;
+const RTMGRP_IPV6_ROUTE: u32 = 0x400;
+const RTMGRP_IPV6_RULE: u32 = 0x40000;
+
+const NETLINK_BUFFER_SIZE: usize = 65536;
+// How often to check the stop flag when no event received.
+const STOP_CHECK_INTERVAL_MILLISECONDS: u64 = 500;
+
+// Notify the sender on every link, address, route or route rule netlink
+// event, return when the receiver is gone or `stop` is set.
+pub(crate) fn nispor_monitor(
+    tx: Sender<()>,
+    stop: Arc<AtomicBool>,
+) -> Result<(), NmstateError> {
+    let mut socket = new_netlink_monitor_socket(
+        RTMGRP_LINK
+            | RTMGRP_IPV4_IFADDR
+            | RTMGRP_IPV4_ROUTE
+            | RTMGRP_IPV4_RULE
+            | RTMGRP_IPV6_IFADDR
+            | RTMGRP_IPV6_ROUTE
+            | RTMGRP_IPV6_RULE,
+    )?;
+    socket
+        .set_read_timeout(Some(Duration::from_millis(
+            STOP_CHECK_INTERVAL_MILLISECONDS,
+        )))
+        .map_err(|e| {
+            NmstateError::new(
+                ErrorKind::PluginFailure,
+                format!("Failed to set netlink socket timeout: {}", e),
+            )
+        })?;
+    let mut buffer = vec![0u8; NETLINK_BUFFER_SIZE];
+    loop {
+        if stop.load(Ordering::Relaxed) {
+            return Ok(());
+        }
+        match socket.read(&mut buffer) {
+            Ok(_) => (),
+            Err(e) if e.kind() == std::io::ErrorKind::Interrupted => continue,
+            Err(e)
+                if e.kind() == std::io::ErrorKind::WouldBlock
+                    || e.kind() == std::io::ErrorKind::TimedOut =>
+            {
+                continue
+            }
+            // Kernel dropped events as we are too slow, still a change.
+            Err(e) if e.raw_os_error() == Some(libc::ENOBUFS) => (),
+            Err(e) => {
+                let e = NmstateError::new(
+                    ErrorKind::PluginFailure,
+                    format!("Failed to receive netlink event: {}", e),
+                );
+                log::error!("{}", e);
+                return Err(e);
+            }
+        }
+        if tx.send(()).is_err() {
+            return Ok(());
+        }
+    }
+}
+
+fn new_netlink_monitor_socket(groups: u32) -> Result<Socket, NmstateError> {
+    let socket = Socket::new(
+        Domain::from(libc::AF_NETLINK),
+        Type::RAW,
+        Some(Protocol::from(libc::NETLINK_ROUTE)),
+    )
+    .map_err(|e| {
+        NmstateError::new(
+            ErrorKind::PluginFailure,
+            format!("Failed to create netlink socket: {}", e),
+        )
+    })?;
+    let mut addr: libc::sockaddr_nl = unsafe { std::mem::zeroed() };
+    addr.nl_family = libc::AF_NETLINK as libc::sa_family_t;
+    addr.nl_groups = groups;
+    let ret = unsafe {
+        libc::bind(
+            socket.as_raw_fd(),
+            &addr as *const libc::sockaddr_nl as *const libc::sockaddr,
+            std::mem::size_of::<libc::sockaddr_nl>() as libc::socklen_t,
+        )
+    };
+    if ret != 0 {
+        let e = NmstateError::new(
+            ErrorKind::PluginFailure,
+            format!(
+                "Failed to subscribe netlink multicast groups: {}",
+                std::io::Error::last_os_error()
+            ),
+        );
+        log::error!("{}", e);
+        return Err(e);
+    }
+    Ok(socket)
+}
@@ -1,6 +1,16 @@
+use std::convert::TryFrom;
+use std::net::IpAddr;
+
 use log::warn;
+use rtnetlink::{
+    packet_route::route::{RouteMessage, RouteProtocol},
+    RouteMessageBuilder,
+};
 
-use crate::{RouteEntry, Routes};
+use crate::{
+    nispor::{link::run_netlink, sysfs::get_iface_index},
+    ErrorKind, Interfaces, NmstateError, RouteEntry, Routes,
+};
 
 const SUPPORTED_ROUTE_SCOPE: [nispor::RouteScope; 2] =
     [nispor::RouteScope::Universe, nispor::RouteScope::Link];
@@ -9,6 +19,7 @@ const SUPPORTED_STATIC_ROUTE_PROTOCOL: [nispor::RouteProtocol; 2] =
     [nispor::RouteProtocol::Boot, nispor::RouteProtocol::Static];
 
 const LOCAL_ROUTE_TABLE: u32 = 255;
+const MAIN_ROUTE_TABLE: u32 = 254;
 const IPV4_DEFAULT_GATEWAY: &str = "0.0.0.0/0";
 const IPV6_DEFAULT_GATEWAY: &str = "::/0";
 const IPV4_EMPTY_NEXT_HOP_ADDRESS: &str = "0.0.0.0";
@@ -129,3 +140,111 @@ fn flat_multipath_route(np_route: &nispor::Route) -> Vec<nispor::Route> {
     }
     ret
 }
+
+// Apply the route changes of interfaces in desired state, routes on
+// interfaces of `del_ifaces` are ignored as kernel will remove them along.
+pub(crate) fn apply_routes(
+    des_routes: &Routes,
+    cur_routes: &Routes,
+    del_ifaces: &Interfaces,
+) -> Result<(), NmstateError> {
+    let changed_iface_routes =
+        des_routes.gen_changed_ifaces_and_routes(cur_routes)?;
+    let cur_routes = cur_routes.config.as_deref().unwrap_or_default();
+    let mut routes_to_del = Vec::new();
+    let mut routes_to_add = Vec::new();
+    for (iface_name, routes) in changed_iface_routes {
+        if del_ifaces.kernel_ifaces.contains_key(&iface_name) {
+            continue;
+        }
+        let cur_iface_routes: Vec<&RouteEntry> = cur_routes
+            .iter()
+            .filter(|r| r.next_hop_iface.as_deref() == Some(&iface_name))
+            .collect();
+        for cur_route in cur_iface_routes.as_slice() {
+            if !routes.iter().any(|r| is_route_applied(r, cur_route)) {
+                routes_to_del.push((*cur_route).clone());
+            }
+        }
+        for route in routes {
+            if !cur_iface_routes.iter().any(|r| is_route_applied(&route, r)) {
+                routes_to_add.push(route);
+            }
+        }
+    }
+
+    for route in routes_to_del {
+        log::debug!("Removing route {:?}", route);
+        let msg = nms_route_to_np(&route)?;
+        run_netlink(
+            format!("remove route {:?}", route).as_str(),
+            |handle| async move { handle.route().del(msg).execute().await },
+        )?;
+    }
+    for route in routes_to_add {
+        log::debug!("Adding route {:?}", route);
+        let msg = nms_route_to_np(&route)?;
+        run_netlink(
+            format!("add route {:?}", route).as_str(),
+            |handle| async move { handle.route().add(msg).execute().await },
+        )?;
+    }
+    Ok(())
+}
+
+// The `RouteEntry::is_match()` treats undefined table ID as wildcard and
+// ignores metric, both are required for deciding whether route should be
+// replaced.
+pub(crate) fn is_route_applied(des_route: &RouteEntry, cur_route: &RouteEntry) -> bool {
+    des_route.is_match(cur_route)
+        && (des_route.metric.is_none()
+            || des_route.metric == Some(RouteEntry::USE_DEFAULT_METRIC)
+            || des_route.metric == cur_route.metric)
+        && cur_route.table_id == Some(get_table_id(des_route))
+}
+
+fn get_table_id(route: &RouteEntry) -> u32 {
+    route
+        .table_id
+        .filter(|t| *t != RouteEntry::USE_DEFAULT_ROUTE_TABLE)
+        .unwrap_or(MAIN_ROUTE_TABLE)
+}
+
+fn nms_route_to_np(route: &RouteEntry) -> Result<RouteMessage, NmstateError> {
+    let dst: ipnet::IpNet = route
+        .destination
+        .as_deref()
+        .unwrap_or(IPV4_DEFAULT_GATEWAY)
+        .parse()?;
+    let mut builder = RouteMessageBuilder::<IpAddr>::new()
+        .destination_prefix(dst.addr(), dst.prefix_len())
+        .map_err(|e| route_error(route, e))?
+        .protocol(RouteProtocol::Static)
+        .table_id(get_table_id(route));
+    if let Some(iface_name) = route.next_hop_iface.as_deref() {
+        builder = builder.output_interface(get_iface_index(iface_name)?);
+    }
+    if let Some(via) = route.next_hop_addr.as_deref() {
+        let via: IpAddr = via.parse()?;
+        if !via.is_unspecified() {
+            builder =
+                builder.gateway(via).map_err(|e| route_error(route, e))?;
+        }
+    }
+    if let Some(metric) = route.metric.filter(|m| *m >= 0) {
+        builder = builder.priority(u32::try_from(metric).map_err(|_| {
+            NmstateError::new(
+                ErrorKind::InvalidArgument,
+                format!("Invalid route metric {}", metric),
+            )
+        })?);
+    }
+    Ok(builder.build())
+}
+
+fn route_error<E: std::fmt::Display>(route: &RouteEntry, e: E) -> NmstateError {
+    NmstateError::new(
+        ErrorKind::InvalidArgument,
+        format!("Invalid route {:?}: {}", route, e),
+    )
+}
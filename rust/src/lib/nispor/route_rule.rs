@@ -1,4 +1,14 @@
-use crate::{RouteRuleEntry, RouteRules};
+use std::convert::TryFrom;
+
+use rtnetlink::packet_route::{
+    rule::{RuleAction, RuleAttribute, RuleMessage},
+    AddressFamily,
+};
+
+use crate::{
+    ip::sanitize_ip_network, nispor::link::run_netlink, ErrorKind,
+    NmstateError, RouteRuleEntry, RouteRules,
+};
 
 pub(crate) fn get_route_rules(np_rules: &[nispor::RouteRule]) -> RouteRules {
     let mut ret = RouteRules::new();
@@ -29,3 +39,99 @@ pub(crate) fn get_route_rules(np_rules: &[nispor::RouteRule]) -> RouteRules {
 
     ret
 }
+
+// Apply the route rule changes of route tables in desired state.
+pub(crate) fn apply_route_rules(
+    des_rules: &RouteRules,
+    cur_rules: &RouteRules,
+) -> Result<(), NmstateError> {
+    let changed_table_rules =
+        des_rules.gen_rule_changed_table_ids(cur_rules)?;
+    let cur_rules = cur_rules.config.as_deref().unwrap_or_default();
+    let mut rules_to_del = Vec::new();
+    let mut rules_to_add = Vec::new();
+    for (table_id, rules) in changed_table_rules {
+        let cur_table_rules: Vec<&RouteRuleEntry> = cur_rules
+            .iter()
+            .filter(|r| {
+                r.table_id.unwrap_or(RouteRuleEntry::DEFAULR_ROUTE_TABLE_ID)
+                    == table_id
+            })
+            .collect();
+        for cur_rule in cur_table_rules.as_slice() {
+            if !rules.iter().any(|r| r.is_match(cur_rule)) {
+                rules_to_del.push((*cur_rule).clone());
+            }
+        }
+        for rule in rules {
+            if !cur_table_rules.iter().any(|r| rule.is_match(r)) {
+                rules_to_add.push(rule);
+            }
+        }
+    }
+
+    for rule in rules_to_del {
+        log::debug!("Removing route rule {:?}", rule);
+        let msg = nms_rule_to_np(&rule)?;
+        run_netlink(
+            format!("remove route rule {:?}", rule).as_str(),
+            |handle| async move { handle.rule().del(msg).execute().await },
+        )?;
+    }
+    for rule in rules_to_add {
+        log::debug!("Adding route rule {:?}", rule);
+        let msg = nms_rule_to_np(&rule)?;
+        run_netlink(
+            format!("add route rule {:?}", rule).as_str(),
+            |handle| async move {
+                let mut req = handle.rule().add();
+                *req.message_mut() = msg;
+                req.execute().await
+            },
+        )?;
+    }
+    Ok(())
+}
+
+fn nms_rule_to_np(rule: &RouteRuleEntry) -> Result<RuleMessage, NmstateError> {
+    let mut msg = RuleMessage::default();
+    msg.header.action = RuleAction::ToTable;
+    let table_id = rule
+        .table_id
+        .filter(|t| *t != RouteRuleEntry::USE_DEFAULT_ROUTE_TABLE)
+        .unwrap_or(RouteRuleEntry::DEFAULR_ROUTE_TABLE_ID);
+    // Table ID larger than 255 can only be stored in attribute.
+    msg.header.table = u8::try_from(table_id).unwrap_or_default();
+    msg.attributes.push(RuleAttribute::Table(table_id));
+    if let Some(ip_from) = rule.ip_from.as_deref() {
+        let ip_net: ipnet::IpNet = sanitize_ip_network(ip_from)?.parse()?;
+        msg.header.family = ip_family(&ip_net);
+        msg.header.src_len = ip_net.prefix_len();
+        msg.attributes.push(RuleAttribute::Source(ip_net.addr()));
+    }
+    if let Some(ip_to) = rule.ip_to.as_deref() {
+        let ip_net: ipnet::IpNet = sanitize_ip_network(ip_to)?.parse()?;
+        msg.header.family = ip_family(&ip_net);
+        msg.header.dst_len = ip_net.prefix_len();
+        msg.attributes
+            .push(RuleAttribute::Destination(ip_net.addr()));
+    }
+    if let Some(priority) = rule.priority.filter(|p| *p >= 0) {
+        msg.attributes.push(RuleAttribute::Priority(
+            u32::try_from(priority).map_err(|_| {
+                NmstateError::new(
+                    ErrorKind::InvalidArgument,
+                    format!("Invalid route rule priority {}", priority),
+                )
+            })?,
+        ));
+    }
+    Ok(msg)
+}
+
+fn ip_family(ip_net: &ipnet::IpNet) -> AddressFamily {
+    match ip_net {
+        ipnet::IpNet::V4(_) => AddressFamily::Inet,
+        ipnet::IpNet::V6(_) => AddressFamily::Inet6,
+    }
+}
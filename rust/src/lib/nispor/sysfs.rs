@@ -0,0 +1,62 @@
+use crate::{ErrorKind, NmstateError};
+
+const SYSFS_NET_DIR: &str = "/sys/class/net";
+
+pub(crate) fn iface_exists(iface_name: &str) -> bool {
+    std::path::Path::new(&format!("{}/{}", SYSFS_NET_DIR, iface_name)).exists()
+}
+
+pub(crate) fn get_iface_index(iface_name: &str) -> Result<u32, NmstateError> {
+    let content = read_sysfs(iface_name, "ifindex")?;
+    content.parse::<u32>().map_err(|e| {
+        NmstateError::new(
+            ErrorKind::PluginFailure,
+            format!(
+                "Invalid interface index {} of interface {}: {}",
+                content, iface_name, e
+            ),
+        )
+    })
+}
+
+// Read `/sys/class/net/<iface_name>/<path>` with trailing new line removed.
+pub(crate) fn read_sysfs(
+    iface_name: &str,
+    path: &str,
+) -> Result<String, NmstateError> {
+    let file_path = format!("{}/{}/{}", SYSFS_NET_DIR, iface_name, path);
+    std::fs::read_to_string(&file_path)
+        .map(|s| s.trim().to_string())
+        .map_err(|e| {
+            NmstateError::new(
+                ErrorKind::PluginFailure,
+                format!("Failed to read {}: {}", file_path, e),
+            )
+        })
+}
+
+// Write `value` to `/sys/class/net/<iface_name>/<path>` unless current value
+// is already the same. Kernel might show current value with extra
+// information, for example bond `mode` is shown as `active-backup 1`, hence
+// any whitespace separated word identical to `value` is treated as equal.
+pub(crate) fn write_sysfs(
+    iface_name: &str,
+    path: &str,
+    value: &str,
+) -> Result<(), NmstateError> {
+    if let Ok(cur_value) = read_sysfs(iface_name, path) {
+        if cur_value == value
+            || cur_value.split_whitespace().any(|v| v == value)
+        {
+            return Ok(());
+        }
+    }
+    let file_path = format!("{}/{}/{}", SYSFS_NET_DIR, iface_name, path);
+    log::debug!("Writing {} to {}", value, file_path);
+    std::fs::write(&file_path, value).map_err(|e| {
+        NmstateError::new(
+            ErrorKind::PluginFailure,
+            format!("Failed to write {} to {}: {}", value, file_path, e),
+        )
+    })
+}
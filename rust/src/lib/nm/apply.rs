@@ -141,6 +141,13 @@ fn delete_net_state(
     Ok(())
 }
 
+pub(crate) fn nm_set_config_hostname(
+    hostname: &str,
+) -> Result<(), NmstateError> {
+    let nm_api = NmApi::new().map_err(nm_error_to_nmstate)?;
+    nm_api.hostname_set(hostname).map_err(nm_error_to_nmstate)
+}
+
 fn apply_single_state(
     nm_api: &NmApi,
     net_state: &NetworkState,
@@ -1,7 +1,12 @@
-use crate::nm::nm_dbus::NmApi;
+use std::convert::TryFrom;
+
+use crate::nm::nm_dbus::{NmApi, NmCheckpoint};
 use log::warn;
 
-use crate::{nm::error::nm_error_to_nmstate, NmstateError};
+use crate::{
+    nm::error::nm_error_to_nmstate, snapshot::now_secs, CheckpointInfo,
+    ErrorKind, NmstateError,
+};
 
 // Wait maximum 60 seconds for rollback
 pub(crate) const CHECKPOINT_ROLLBACK_TIMEOUT: u32 = 60;
@@ -47,3 +52,49 @@ pub(crate) fn nm_checkpoint_timeout_extend(
         .checkpoint_timeout_extend(checkpoint, added_time_sec)
         .map_err(nm_error_to_nmstate)
 }
+
+pub(crate) fn nm_checkpoint_list() -> Result<Vec<CheckpointInfo>, NmstateError>
+{
+    let nm_api = NmApi::new().map_err(nm_error_to_nmstate)?;
+    let nm_checkpoints =
+        nm_api.checkpoints_get().map_err(nm_error_to_nmstate)?;
+    let boot_time_ms = get_boot_time_ms()?;
+    let now = now_secs();
+    Ok(nm_checkpoints
+        .iter()
+        .map(|c| nm_checkpoint_to_nmstate(c, boot_time_ms, now))
+        .collect())
+}
+
+// NetworkManager is using CLOCK_BOOTTIME for checkpoint creation time, it
+// is converted to seconds since UNIX epoch using current time `now`.
+pub(crate) fn nm_checkpoint_to_nmstate(
+    nm_checkpoint: &NmCheckpoint,
+    boot_time_ms: i64,
+    now: u64,
+) -> CheckpointInfo {
+    let age = u64::try_from(boot_time_ms - nm_checkpoint.created)
+        .unwrap_or_default()
+        / 1000;
+    let mut ret = CheckpointInfo::new();
+    ret.path = nm_checkpoint.obj_path.clone();
+    ret.created = now.saturating_sub(age);
+    ret.rollback_timeout = nm_checkpoint.rollback_timeout;
+    ret.remaining_timeout = u32::try_from(
+        u64::from(nm_checkpoint.rollback_timeout).saturating_sub(age),
+    )
+    .unwrap_or_default();
+    ret.devices = nm_checkpoint.devices.clone();
+    ret
+}
+
+fn get_boot_time_ms() -> Result<i64, NmstateError> {
+    let time = nix::time::clock_gettime(nix::time::ClockId::CLOCK_BOOTTIME)
+        .map_err(|e| {
+            NmstateError::new(
+                ErrorKind::Bug,
+                format!("Failed to get CLOCK_BOOTTIME: {}", e),
+            )
+        })?;
+    Ok(time.tv_sec() * 1000 + time.tv_nsec() / 1_000_000)
+}
@@ -0,0 +1,676 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::HashMap;
+use std::convert::TryFrom;
+use std::str::FromStr;
+
+use super::nm_dbus::{
+    NmConnection, NmIpRoute, NmIpRouteRule, NmSettingBridge,
+    NmSettingBridgePort, NmSettingBridgeVlanRange, NmSettingIp,
+    NmSettingIpMethod, NmVlanProtocol,
+};
+use super::settings::{
+    NM_SETTING_BOND_SETTING_NAME, NM_SETTING_BRIDGE_SETTING_NAME,
+    NM_SETTING_DUMMY_SETTING_NAME, NM_SETTING_INFINIBAND_SETTING_NAME,
+    NM_SETTING_MACVLAN_SETTING_NAME, NM_SETTING_VLAN_SETTING_NAME,
+    NM_SETTING_VRF_SETTING_NAME, NM_SETTING_VXLAN_SETTING_NAME,
+    NM_SETTING_WIRED_SETTING_NAME,
+};
+
+use crate::import::{
+    bridge_port_vlan_conf, parse_enum, set_bond_kernel_options,
+    ImportedNetState,
+};
+use crate::{
+    BaseInterface, BondConfig, BondInterface, BridgePortVlanConfig,
+    Dhcpv4ClientId, Dhcpv6Duid, DummyInterface, ErrorKind, EthernetConfig,
+    EthernetDuplex, EthernetInterface, InfiniBandConfig, InfiniBandInterface,
+    InfiniBandMode, Interface, InterfaceIpAddr, InterfaceIpv4, InterfaceIpv6,
+    InterfaceState, InterfaceType, Ipv6AddrGenMode, LinuxBridgeConfig,
+    LinuxBridgeInterface, LinuxBridgeMulticastRouterType, LinuxBridgeOptions,
+    LinuxBridgePortConfig, LinuxBridgeStpOptions, MacVlanConfig,
+    MacVlanInterface, MacVlanMode, MacVtapConfig, MacVtapInterface,
+    MacVtapMode, NetworkState, NmstateError, RouteEntry, RouteRuleEntry,
+    VlanConfig, VlanInterface, VlanProtocol, VrfConfig, VrfInterface,
+    VxlanConfig, VxlanInterface,
+};
+
+const ADDR_GEN_MODE_EUI64: i32 = 0;
+const ADDR_GEN_MODE_STABLE_PRIVACY: i32 = 1;
+
+// Convert the content of NetworkManager keyfiles to desired network state.
+// The `keyfiles` is list of (file_path, content).
+pub(crate) fn nm_import_keyfiles(
+    keyfiles: &[(String, String)],
+) -> Result<NetworkState, NmstateError> {
+    // Tuple of (file_path, content, NmConnection)
+    let mut nm_conns: Vec<(&str, &str, NmConnection)> = Vec::new();
+    for (file_path, content) in keyfiles {
+        match NmConnection::from_keyfile(content) {
+            Ok(nm_conn) => {
+                if nm_conn.iface_name().is_none() {
+                    log::warn!(
+                        "Ignoring {} as it does not bind to any interface name",
+                        file_path
+                    );
+                } else {
+                    nm_conns.push((file_path, content, nm_conn));
+                }
+            }
+            Err(e) => {
+                return Err(NmstateError::new(
+                    ErrorKind::InvalidArgument,
+                    format!(
+                        "Failed to parse NetworkManager keyfile {}: {}",
+                        file_path, e
+                    ),
+                ));
+            }
+        }
+    }
+
+    // The controller and parent could be referred by UUID, profile name
+    // or interface name.
+    let mut conn_to_iface: HashMap<&str, &str> = HashMap::new();
+    for (_, _, nm_conn) in nm_conns.iter() {
+        if let Some(iface_name) = nm_conn.iface_name() {
+            if let Some(id) = nm_conn.id() {
+                conn_to_iface.insert(id, iface_name);
+            }
+            if let Some(uuid) = nm_conn.uuid() {
+                conn_to_iface.insert(uuid, iface_name);
+            }
+        }
+    }
+    let resolve = |name: Option<&String>| -> Option<String> {
+        name.map(|n| {
+            conn_to_iface
+                .get(n.as_str())
+                .map(|i| i.to_string())
+                .unwrap_or_else(|| n.to_string())
+        })
+    };
+
+    let mut imported = ImportedNetState::new();
+    for (file_path, content, nm_conn) in nm_conns.iter() {
+        let mut iface =
+            match nm_conn_to_iface(nm_conn, file_path, content, &resolve)? {
+                Some(i) => i,
+                None => continue,
+            };
+        let iface_name = iface.name().to_string();
+
+        if let Some(ctrl) = resolve(
+            nm_conn
+                .connection
+                .as_ref()
+                .and_then(|c| c.controller.as_ref()),
+        ) {
+            imported.add_port(
+                &ctrl,
+                nm_br_port_to_nmstate(
+                    &iface_name,
+                    nm_conn.bridge_port.as_ref(),
+                ),
+            );
+        } else {
+            // NetworkManager use `auto` when IP setting is not defined
+            let nm_ip_set = nm_conn.ipv4.clone().unwrap_or_default();
+            iface.base_iface_mut().ipv4 =
+                Some(nm_ip_setting_to_nmstate4(&nm_ip_set)?);
+            nm_ip_setting_to_dns(&nm_ip_set, &mut imported);
+            for route in nm_ip_setting_to_routes(&nm_ip_set, &iface_name, false)
+            {
+                imported.add_route(route);
+            }
+            for rule in nm_ip_setting_to_rules(&nm_ip_set) {
+                imported.add_rule(rule);
+            }
+            let nm_ip_set = nm_conn.ipv6.clone().unwrap_or_default();
+            iface.base_iface_mut().ipv6 =
+                Some(nm_ip_setting_to_nmstate6(&nm_ip_set)?);
+            nm_ip_setting_to_dns(&nm_ip_set, &mut imported);
+            for route in nm_ip_setting_to_routes(&nm_ip_set, &iface_name, true)
+            {
+                imported.add_route(route);
+            }
+            for rule in nm_ip_setting_to_rules(&nm_ip_set) {
+                imported.add_rule(rule);
+            }
+        }
+        imported.add_iface(iface);
+    }
+    imported.build()
+}
+
+fn nm_conn_to_iface(
+    nm_conn: &NmConnection,
+    file_path: &str,
+    content: &str,
+    resolve: &dyn Fn(Option<&String>) -> Option<String>,
+) -> Result<Option<Interface>, NmstateError> {
+    let iface_name = match nm_conn.iface_name() {
+        Some(i) => i.to_string(),
+        None => return Ok(None),
+    };
+    let nm_iface_type = nm_conn.iface_type().unwrap_or_default();
+    let mut base = BaseInterface::new();
+    base.name = iface_name.clone();
+    base.state = InterfaceState::Up;
+
+    if let Some(nm_wired_set) = nm_conn.wired.as_ref() {
+        base.mtu = nm_wired_set.mtu.map(u64::from);
+        // The cloned-mac-address could also be special values like
+        // `permanent`, `preserve` or `random`.
+        base.mac_address = nm_wired_set
+            .cloned_mac_address
+            .as_ref()
+            .filter(|m| m.contains(':'))
+            .cloned();
+        base.accept_all_mac_addresses =
+            match nm_wired_set.accept_all_mac_addresses {
+                Some(1) => Some(true),
+                Some(0) => Some(false),
+                _ => None,
+            };
+    }
+
+    let iface = match nm_iface_type {
+        NM_SETTING_WIRED_SETTING_NAME => {
+            base.iface_type = InterfaceType::Ethernet;
+            let mut iface = EthernetInterface::new();
+            iface.base = base;
+            if let Some(nm_wired_set) = nm_conn.wired.as_ref() {
+                if nm_wired_set.speed.is_some()
+                    || nm_wired_set.duplex.is_some()
+                    || nm_wired_set.auto_negotiate.is_some()
+                {
+                    let mut eth_conf = EthernetConfig::new();
+                    eth_conf.auto_neg = nm_wired_set.auto_negotiate;
+                    eth_conf.speed = nm_wired_set.speed;
+                    eth_conf.duplex = nm_wired_set
+                        .duplex
+                        .as_deref()
+                        .map(|d| parse_enum::<EthernetDuplex>(d, "duplex"))
+                        .transpose()?;
+                    iface.ethernet = Some(eth_conf);
+                }
+            }
+            Interface::Ethernet(iface)
+        }
+        NM_SETTING_BOND_SETTING_NAME => {
+            base.iface_type = InterfaceType::Bond;
+            let mut iface = BondInterface::new();
+            iface.base = base;
+            let mut bond_conf = BondConfig::new();
+            bond_conf.port = Some(Vec::new());
+            iface.bond = Some(bond_conf);
+            let mut iface = Interface::Bond(iface);
+            if let Some(nm_bond_set) = nm_conn.bond.as_ref() {
+                let mut opts: Vec<(String, String)> = nm_bond_set
+                    .options
+                    .iter()
+                    .map(|(k, v)| (k.to_string(), v.to_string()))
+                    .collect();
+                opts.sort_unstable();
+                set_bond_kernel_options(
+                    &mut iface,
+                    &opts,
+                    file_path,
+                    get_keyfile_section_line(content, "bond"),
+                )?;
+            }
+            iface
+        }
+        NM_SETTING_BRIDGE_SETTING_NAME => {
+            base.iface_type = InterfaceType::LinuxBridge;
+            let mut iface = LinuxBridgeInterface::new();
+            iface.base = base;
+            let mut br_conf = LinuxBridgeConfig::new();
+            br_conf.port = Some(Vec::new());
+            if let Some(nm_br_set) = nm_conn.bridge.as_ref() {
+                br_conf.options = Some(nm_br_setting_to_nmstate(nm_br_set)?);
+            }
+            iface.bridge = Some(br_conf);
+            Interface::LinuxBridge(iface)
+        }
+        NM_SETTING_VLAN_SETTING_NAME => {
+            base.iface_type = InterfaceType::Vlan;
+            let mut iface = VlanInterface::new();
+            iface.base = base;
+            if let Some(nm_vlan_set) = nm_conn.vlan.as_ref() {
+                iface.vlan = Some(VlanConfig {
+                    base_iface: resolve(nm_vlan_set.parent.as_ref())
+                        .unwrap_or_default(),
+                    id: nm_vlan_set
+                        .id
+                        .map(|i| to_int::<u16>(i.into(), "vlan.id"))
+                        .transpose()?
+                        .unwrap_or_default(),
+                });
+            }
+            Interface::Vlan(iface)
+        }
+        NM_SETTING_VXLAN_SETTING_NAME => {
+            base.iface_type = InterfaceType::Vxlan;
+            let mut iface = VxlanInterface::new();
+            iface.base = base;
+            if let Some(nm_vxlan_set) = nm_conn.vxlan.as_ref() {
+                iface.vxlan = Some(VxlanConfig {
+                    base_iface: resolve(nm_vxlan_set.parent.as_ref())
+                        .unwrap_or_default(),
+                    id: nm_vxlan_set.id.unwrap_or_default(),
+                    remote: nm_vxlan_set
+                        .remote
+                        .as_deref()
+                        .map(|r| {
+                            r.parse().map_err(|_| {
+                                NmstateError::new(
+                                    ErrorKind::InvalidArgument,
+                                    format!(
+                                        "Invalid VXLAN remote address {}",
+                                        r
+                                    ),
+                                )
+                            })
+                        })
+                        .transpose()?,
+                    dst_port: nm_vxlan_set
+                        .dst_port
+                        .map(|p| {
+                            to_int::<u16>(p.into(), "vxlan.destination-port")
+                        })
+                        .transpose()?,
+                });
+            }
+            Interface::Vxlan(iface)
+        }
+        NM_SETTING_MACVLAN_SETTING_NAME => {
+            let nm_set = nm_conn.mac_vlan.clone().unwrap_or_default();
+            let parent = resolve(nm_set.parent.as_ref()).unwrap_or_default();
+            if nm_set.tap == Some(true) {
+                base.iface_type = InterfaceType::MacVtap;
+                let mut iface = MacVtapInterface::new();
+                iface.base = base;
+                iface.mac_vtap = Some(MacVtapConfig {
+                    base_iface: parent,
+                    mode: match nm_set.mode {
+                        Some(1) => MacVtapMode::Vepa,
+                        Some(2) => MacVtapMode::Bridge,
+                        Some(3) => MacVtapMode::Private,
+                        Some(4) => MacVtapMode::Passthru,
+                        Some(5) => MacVtapMode::Source,
+                        _ => MacVtapMode::Unknown,
+                    },
+                    accept_all_mac: nm_set.accept_all_mac,
+                });
+                Interface::MacVtap(iface)
+            } else {
+                base.iface_type = InterfaceType::MacVlan;
+                let mut iface = MacVlanInterface::new();
+                iface.base = base;
+                iface.mac_vlan = Some(MacVlanConfig {
+                    base_iface: parent,
+                    mode: match nm_set.mode {
+                        Some(1) => MacVlanMode::Vepa,
+                        Some(2) => MacVlanMode::Bridge,
+                        Some(3) => MacVlanMode::Private,
+                        Some(4) => MacVlanMode::Passthru,
+                        Some(5) => MacVlanMode::Source,
+                        _ => MacVlanMode::Unknown,
+                    },
+                    accept_all_mac: nm_set.accept_all_mac,
+                });
+                Interface::MacVlan(iface)
+            }
+        }
+        NM_SETTING_VRF_SETTING_NAME => {
+            base.iface_type = InterfaceType::Vrf;
+            let mut iface = VrfInterface::new();
+            iface.base = base;
+            iface.vrf = Some(VrfConfig {
+                port: Some(Vec::new()),
+                table_id: nm_conn
+                    .vrf
+                    .as_ref()
+                    .and_then(|v| v.table)
+                    .unwrap_or_default(),
+            });
+            Interface::Vrf(iface)
+        }
+        NM_SETTING_DUMMY_SETTING_NAME => {
+            base.iface_type = InterfaceType::Dummy;
+            let mut iface = DummyInterface::new();
+            iface.base = base;
+            Interface::Dummy(iface)
+        }
+        NM_SETTING_INFINIBAND_SETTING_NAME => {
+            base.iface_type = InterfaceType::InfiniBand;
+            let mut iface = InfiniBandInterface::default();
+            if let Some(nm_ib_set) = nm_conn.infiniband.as_ref() {
+                if let Some(mtu) = nm_ib_set.mtu {
+                    base.mtu = Some(mtu.into());
+                }
+                iface.ib = Some(InfiniBandConfig {
+                    mode: nm_ib_set
+                        .mode
+                        .as_deref()
+                        .map(|m| {
+                            parse_enum::<InfiniBandMode>(m, "transport-mode")
+                        })
+                        .transpose()?
+                        .unwrap_or_default(),
+                    base_iface: resolve(nm_ib_set.parent.as_ref()),
+                    // NetworkManager use -1 for no p-key
+                    pkey: match nm_ib_set.pkey {
+                        Some(p) if p >= 0 => {
+                            Some(to_int::<u16>(p as u64, "p-key")?)
+                        }
+                        _ => None,
+                    },
+                });
+            }
+            iface.base = base;
+            Interface::InfiniBand(iface)
+        }
+        _ => {
+            log::warn!(
+                "Ignoring interface {} with unsupported connection type {}",
+                iface_name,
+                nm_iface_type
+            );
+            return Ok(None);
+        }
+    };
+    Ok(Some(iface))
+}
+
+fn nm_br_port_to_nmstate(
+    port: &str,
+    nm_br_port_set: Option<&NmSettingBridgePort>,
+) -> LinuxBridgePortConfig {
+    let mut port_conf = LinuxBridgePortConfig::new();
+    port_conf.name = port.to_string();
+    if let Some(nm_set) = nm_br_port_set {
+        port_conf.stp_hairpin_mode = nm_set.hairpin_mode;
+        port_conf.stp_path_cost = nm_set.path_cost;
+        port_conf.stp_priority =
+            nm_set.priority.and_then(|p| u16::try_from(p).ok());
+        port_conf.vlan =
+            nm_set.vlans.as_deref().and_then(nm_vlan_ranges_to_nmstate);
+    }
+    port_conf
+}
+
+// Line number of the `[section]` header in keyfile, 0 if not found.
+fn get_keyfile_section_line(content: &str, section: &str) -> usize {
+    let header = format!("[{}]", section);
+    content
+        .lines()
+        .position(|l| l.trim() == header)
+        .map(|i| i + 1)
+        .unwrap_or_default()
+}
+
+fn nm_br_setting_to_nmstate(
+    nm_set: &NmSettingBridge,
+) -> Result<LinuxBridgeOptions, NmstateError> {
+    let stp_opts = LinuxBridgeStpOptions {
+        enabled: nm_set.stp,
+        forward_delay: nm_set
+            .forward_delay
+            .map(|v| to_int::<u8>(v.into(), "forward-delay"))
+            .transpose()?,
+        hello_time: nm_set
+            .hello_time
+            .map(|v| to_int::<u8>(v.into(), "hello-time"))
+            .transpose()?,
+        max_age: nm_set
+            .max_age
+            .map(|v| to_int::<u8>(v.into(), "max-age"))
+            .transpose()?,
+        priority: nm_set
+            .priority
+            .map(|v| to_int::<u16>(v.into(), "priority"))
+            .transpose()?,
+    };
+    Ok(LinuxBridgeOptions {
+        group_addr: nm_set.group_address.clone(),
+        group_forward_mask: nm_set
+            .group_forward_mask
+            .map(|v| to_int::<u16>(v.into(), "group-forward-mask"))
+            .transpose()?,
+        hash_max: nm_set.multicast_hash_max,
+        mac_ageing_time: nm_set.ageing_time,
+        multicast_last_member_count: nm_set.multicast_last_member_count,
+        multicast_last_member_interval: nm_set.multicast_last_member_interval,
+        multicast_membership_interval: nm_set.multicast_membership_interval,
+        multicast_querier: nm_set.multicast_querier,
+        multicast_querier_interval: nm_set.multicast_querier_interval,
+        multicast_query_interval: nm_set.multicast_query_interval,
+        multicast_query_response_interval: nm_set
+            .multicast_query_response_interval,
+        multicast_query_use_ifaddr: nm_set.multicast_query_use_ifaddr,
+        multicast_router: nm_set
+            .multicast_router
+            .as_deref()
+            .map(LinuxBridgeMulticastRouterType::from_str)
+            .transpose()?,
+        multicast_snooping: nm_set.multicast_snooping,
+        multicast_startup_query_count: nm_set.multicast_startup_query_count,
+        multicast_startup_query_interval: nm_set
+            .multicast_startup_query_interval,
+        stp: Some(stp_opts),
+        vlan_protocol: nm_set.vlan_protocol.map(|p| match p {
+            NmVlanProtocol::Dot1Q => VlanProtocol::Ieee8021Q,
+            NmVlanProtocol::Dot1Ad => VlanProtocol::Ieee8021Ad,
+        }),
+        ..Default::default()
+    })
+}
+
+fn nm_vlan_ranges_to_nmstate(
+    nm_vlans: &[NmSettingBridgeVlanRange],
+) -> Option<BridgePortVlanConfig> {
+    let mut pvid = None;
+    let mut vlans = Vec::new();
+    let mut untagged = Vec::new();
+    for nm_vlan in nm_vlans {
+        if nm_vlan.pvid
+            && nm_vlan.untagged
+            && nm_vlan.vid_start == nm_vlan.vid_end
+        {
+            pvid = Some(nm_vlan.vid_start);
+        } else if nm_vlan.untagged {
+            untagged.push((nm_vlan.vid_start, nm_vlan.vid_end));
+        } else {
+            vlans.push((nm_vlan.vid_start, nm_vlan.vid_end));
+        }
+    }
+    bridge_port_vlan_conf(&vlans, pvid, &untagged)
+}
+
+fn nm_ip_setting_to_nmstate4(
+    nm_set: &NmSettingIp,
+) -> Result<InterfaceIpv4, NmstateError> {
+    let mut ip = InterfaceIpv4::new();
+    ip.prop_list.push("enabled");
+    // NetworkManager use `auto` by default
+    match nm_set.method.as_ref().unwrap_or(&NmSettingIpMethod::Auto) {
+        NmSettingIpMethod::Disabled => {
+            ip.enabled = false;
+            return Ok(ip);
+        }
+        NmSettingIpMethod::Auto => {
+            ip.enabled = true;
+            ip.dhcp = Some(true);
+            ip.auto_dns = Some(!nm_set.ignore_auto_dns.unwrap_or_default());
+            ip.auto_gateway = Some(!nm_set.never_default.unwrap_or_default());
+            ip.auto_routes =
+                Some(!nm_set.ignore_auto_routes.unwrap_or_default());
+            ip.auto_table_id = nm_set.route_table;
+            ip.dhcp_client_id =
+                nm_set.dhcp_client_id.as_deref().map(|c| match c {
+                    "mac" => Dhcpv4ClientId::LinkLayerAddress,
+                    "duid" => Dhcpv4ClientId::IaidPlusDuid,
+                    _ => Dhcpv4ClientId::Other(c.to_string()),
+                });
+        }
+        method => {
+            if method != &NmSettingIpMethod::Manual {
+                log::warn!(
+                    "IPv4 method {} is not supported, treating as static",
+                    method
+                );
+            }
+            ip.enabled = true;
+            ip.dhcp = Some(false);
+        }
+    }
+    if ip.dhcp == Some(false) || !nm_set.addresses.is_empty() {
+        ip.addresses = Some(nm_addrs_to_nmstate(&nm_set.addresses)?);
+    }
+    Ok(ip)
+}
+
+fn nm_ip_setting_to_nmstate6(
+    nm_set: &NmSettingIp,
+) -> Result<InterfaceIpv6, NmstateError> {
+    let mut ip = InterfaceIpv6::new();
+    ip.prop_list.push("enabled");
+    let (dhcp, autoconf) =
+        match nm_set.method.as_ref().unwrap_or(&NmSettingIpMethod::Auto) {
+            NmSettingIpMethod::Disabled | NmSettingIpMethod::Ignore => {
+                ip.enabled = false;
+                return Ok(ip);
+            }
+            NmSettingIpMethod::Auto => (true, true),
+            NmSettingIpMethod::Dhcp => (true, false),
+            method => {
+                if method != &NmSettingIpMethod::Manual
+                    && method != &NmSettingIpMethod::LinkLocal
+                {
+                    log::warn!(
+                        "IPv6 method {} is not supported, treating as static",
+                        method
+                    );
+                }
+                (false, false)
+            }
+        };
+    ip.enabled = true;
+    ip.dhcp = Some(dhcp);
+    ip.autoconf = Some(autoconf);
+    if dhcp || autoconf {
+        ip.auto_dns = Some(!nm_set.ignore_auto_dns.unwrap_or_default());
+        ip.auto_gateway = Some(!nm_set.never_default.unwrap_or_default());
+        ip.auto_routes = Some(!nm_set.ignore_auto_routes.unwrap_or_default());
+        ip.auto_table_id = nm_set.route_table;
+        ip.dhcp_duid = nm_set.dhcp_duid.as_deref().map(|d| match d {
+            "ll" => Dhcpv6Duid::LinkLayerAddress,
+            "llt" => Dhcpv6Duid::LinkLayerAddressPlusTime,
+            "uuid" => Dhcpv6Duid::Uuid,
+            _ => Dhcpv6Duid::Other(d.to_string()),
+        });
+    }
+    ip.addr_gen_mode = match nm_set.addr_gen_mode {
+        Some(ADDR_GEN_MODE_EUI64) => Some(Ipv6AddrGenMode::Eui64),
+        Some(ADDR_GEN_MODE_STABLE_PRIVACY) => {
+            Some(Ipv6AddrGenMode::StablePrivacy)
+        }
+        _ => None,
+    };
+    if !(dhcp || autoconf) || !nm_set.addresses.is_empty() {
+        ip.addresses = Some(nm_addrs_to_nmstate(&nm_set.addresses)?);
+    }
+    Ok(ip)
+}
+
+fn nm_addrs_to_nmstate(
+    nm_addrs: &[String],
+) -> Result<Vec<InterfaceIpAddr>, NmstateError> {
+    let mut ret = Vec::new();
+    for nm_addr in nm_addrs {
+        ret.push(InterfaceIpAddr::try_from(nm_addr.as_str())?);
+    }
+    Ok(ret)
+}
+
+fn nm_ip_setting_to_dns(nm_set: &NmSettingIp, imported: &mut ImportedNetState) {
+    for srv in nm_set.dns.as_deref().unwrap_or_default() {
+        imported.add_dns_server(srv);
+    }
+    for sch in nm_set.dns_search.as_deref().unwrap_or_default() {
+        imported.add_dns_search(sch);
+    }
+}
+
+fn nm_ip_setting_to_routes(
+    nm_set: &NmSettingIp,
+    iface_name: &str,
+    is_ipv6: bool,
+) -> Vec<RouteEntry> {
+    let mut ret = Vec::new();
+    if let Some(gw) = nm_set.gateway.as_ref() {
+        let mut rt = RouteEntry::new();
+        rt.destination =
+            Some(if is_ipv6 { "::/0" } else { "0.0.0.0/0" }.to_string());
+        rt.next_hop_iface = Some(iface_name.to_string());
+        rt.next_hop_addr = Some(gw.to_string());
+        rt.table_id = nm_set.route_table.filter(|t| *t != 0);
+        ret.push(rt);
+    }
+    for nm_route in nm_set.routes.as_slice() {
+        ret.push(nm_route_to_nmstate(nm_route, nm_set, iface_name));
+    }
+    ret
+}
+
+fn nm_route_to_nmstate(
+    nm_route: &NmIpRoute,
+    nm_set: &NmSettingIp,
+    iface_name: &str,
+) -> RouteEntry {
+    let mut rt = RouteEntry::new();
+    rt.destination = match (nm_route.dest.as_ref(), nm_route.prefix) {
+        (Some(d), Some(p)) => Some(format!("{}/{}", d, p)),
+        (Some(d), None) => Some(d.to_string()),
+        _ => None,
+    };
+    rt.next_hop_iface = Some(iface_name.to_string());
+    rt.next_hop_addr = nm_route.next_hop.clone();
+    rt.metric = nm_route.metric.map(i64::from);
+    rt.table_id = nm_route.table.or(nm_set.route_table).filter(|t| *t != 0);
+    rt
+}
+
+fn nm_ip_setting_to_rules(nm_set: &NmSettingIp) -> Vec<RouteRuleEntry> {
+    nm_set
+        .route_rules
+        .iter()
+        .map(|nm_rule: &NmIpRouteRule| {
+            let mut rule = RouteRuleEntry::new();
+            rule.ip_from =
+                nm_rule.from.as_ref().map(|f| match nm_rule.from_len {
+                    Some(l) => format!("{}/{}", f, l),
+                    None => f.to_string(),
+                });
+            rule.ip_to = nm_rule.to.as_ref().map(|t| match nm_rule.to_len {
+                Some(l) => format!("{}/{}", t, l),
+                None => t.to_string(),
+            });
+            rule.priority = nm_rule.priority.map(i64::from);
+            rule.table_id = nm_rule.table;
+            rule
+        })
+        .collect()
+}
+
+fn to_int<T: TryFrom<u64>>(value: u64, prop: &str) -> Result<T, NmstateError> {
+    T::try_from(value).map_err(|_| {
+        NmstateError::new(
+            ErrorKind::InvalidArgument,
+            format!("Value {} is out of range for {}", value, prop),
+        )
+    })
+}
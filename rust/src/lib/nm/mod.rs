@@ -12,6 +12,10 @@ mod device;
 mod error;
 #[cfg(feature = "gen_conf")]
 mod gen_conf;
+#[cfg(feature = "gen_conf")]
+mod import;
+#[cfg(feature = "query_apply")]
+mod monitor;
 mod nm_dbus;
 #[cfg(feature = "query_apply")]
 mod profile;
@@ -25,13 +29,17 @@ mod unit_tests;
 mod version;
 
 #[cfg(feature = "query_apply")]
-pub(crate) use apply::nm_apply;
+pub(crate) use apply::{nm_apply, nm_set_config_hostname};
 #[cfg(feature = "query_apply")]
 pub(crate) use checkpoint::{
-    nm_checkpoint_create, nm_checkpoint_destroy, nm_checkpoint_rollback,
-    nm_checkpoint_timeout_extend,
+    nm_checkpoint_create, nm_checkpoint_destroy, nm_checkpoint_list,
+    nm_checkpoint_rollback, nm_checkpoint_timeout_extend,
 };
 #[cfg(feature = "gen_conf")]
 pub(crate) use gen_conf::nm_gen_conf;
+#[cfg(feature = "gen_conf")]
+pub(crate) use import::nm_import_keyfiles;
+#[cfg(feature = "query_apply")]
+pub(crate) use monitor::nm_monitor;
 #[cfg(feature = "query_apply")]
 pub(crate) use show::nm_retrieve;
@@ -0,0 +1,32 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::Sender;
+use std::sync::Arc;
+use std::time::Duration;
+
+use crate::nm::nm_dbus::NmApi;
+
+use crate::{nm::error::nm_error_to_nmstate, NmstateError};
+
+// How often to check the stop flag when no signal received.
+const STOP_CHECK_INTERVAL_MILLISECONDS: u64 = 500;
+
+// Notify the sender on every NetworkManager D-Bus signal, return when the
+// receiver is gone or `stop` is set.
+pub(crate) fn nm_monitor(
+    tx: Sender<()>,
+    stop: Arc<AtomicBool>,
+) -> Result<(), NmstateError> {
+    let nm_api = NmApi::new().map_err(nm_error_to_nmstate)?;
+    nm_api.subscribe_signals().map_err(nm_error_to_nmstate)?;
+    let timeout = Duration::from_millis(STOP_CHECK_INTERVAL_MILLISECONDS);
+    while !stop.load(Ordering::Relaxed) {
+        if nm_api.wait_signal(timeout).map_err(nm_error_to_nmstate)?
+            && tx.send(()).is_err()
+        {
+            return Ok(());
+        }
+    }
+    Ok(())
+}
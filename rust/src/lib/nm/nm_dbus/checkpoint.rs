@@ -0,0 +1,68 @@
+use super::{
+    dbus::{obj_path_to_string, NM_DBUS_INTERFACE_ROOT},
+    device::nm_dev_name_get,
+    ErrorKind, NmError,
+};
+
+const NM_DBUS_INTERFACE_CHECKPOINT: &str =
+    "org.freedesktop.NetworkManager.Checkpoint";
+
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct NmCheckpoint {
+    pub obj_path: String,
+    // Milliseconds in CLOCK_BOOTTIME
+    pub created: i64,
+    // Seconds, 0 means no automatic rollback
+    pub rollback_timeout: u32,
+    pub devices: Vec<String>,
+}
+
+fn nm_checkpoint_property_error(
+    obj_path: &str,
+    property: &str,
+    e: zbus::fdo::Error,
+) -> NmError {
+    NmError::new(
+        ErrorKind::Bug,
+        format!(
+            "Failed to retrieve {} of checkpoint {}: {}",
+            property, obj_path, e
+        ),
+    )
+}
+
+pub(crate) fn nm_checkpoint_from_obj_path(
+    dbus_conn: &zbus::Connection,
+    obj_path: &str,
+) -> Result<NmCheckpoint, NmError> {
+    let proxy = zbus::Proxy::new(
+        dbus_conn,
+        NM_DBUS_INTERFACE_ROOT,
+        obj_path,
+        NM_DBUS_INTERFACE_CHECKPOINT,
+    )?;
+    let created = proxy
+        .get_property::<i64>("Created")
+        .map_err(|e| nm_checkpoint_property_error(obj_path, "Created", e))?;
+    let rollback_timeout =
+        proxy.get_property::<u32>("RollbackTimeout").map_err(|e| {
+            nm_checkpoint_property_error(obj_path, "RollbackTimeout", e)
+        })?;
+    let mut devices = Vec::new();
+    for dev_obj_path in proxy
+        .get_property::<Vec<zvariant::OwnedObjectPath>>("Devices")
+        .map_err(|e| nm_checkpoint_property_error(obj_path, "Devices", e))?
+    {
+        let dev_obj_path = obj_path_to_string(dev_obj_path);
+        // Race: Device might just been removed, hence we ignore error here
+        if let Ok(name) = nm_dev_name_get(dbus_conn, &dev_obj_path) {
+            devices.push(name);
+        }
+    }
+    Ok(NmCheckpoint {
+        obj_path: obj_path.to_string(),
+        created,
+        rollback_timeout,
+        devices,
+    })
+}
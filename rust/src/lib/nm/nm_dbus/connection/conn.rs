@@ -30,6 +30,7 @@ use super::super::{
     connection::infiniband::NmSettingInfiniBand,
     connection::ip::NmSettingIp,
     connection::mac_vlan::NmSettingMacVlan,
+    connection::matching::NmSettingMatch,
     connection::ovs::{
         NmSettingOvsBridge, NmSettingOvsDpdk, NmSettingOvsExtIds,
         NmSettingOvsIface, NmSettingOvsPatch, NmSettingOvsPort,
@@ -112,6 +113,7 @@ pub struct NmConnection {
     pub user: Option<NmSettingUser>,
     pub ethtool: Option<NmSettingEthtool>,
     pub infiniband: Option<NmSettingInfiniBand>,
+    pub matching: Option<NmSettingMatch>,
     #[serde(skip)]
     pub(crate) obj_path: String,
     #[serde(skip)]
@@ -180,6 +182,7 @@ impl TryFrom<NmConnectionDbusOwnedValue> for NmConnection {
                 "infiniband",
                 NmSettingInfiniBand::try_from
             )?,
+            matching: _from_map!(v, "match", NmSettingMatch::try_from)?,
             _other: v,
             ..Default::default()
         })
@@ -279,6 +282,9 @@ impl NmConnection {
         if let Some(v) = &self.infiniband {
             ret.insert("infiniband", v.to_value()?);
         }
+        if let Some(v) = &self.matching {
+            ret.insert("match", v.to_value()?);
+        }
         for (key, setting_value) in &self._other {
             let mut other_setting_value: HashMap<&str, zvariant::Value> =
                 HashMap::new();
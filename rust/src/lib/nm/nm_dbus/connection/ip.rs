@@ -75,8 +75,14 @@ impl std::fmt::Display for NmSettingIpMethod {
 impl TryFrom<zvariant::OwnedValue> for NmSettingIpMethod {
     type Error = NmError;
     fn try_from(value: zvariant::OwnedValue) -> Result<Self, Self::Error> {
-        let str_value = String::try_from(value)?;
-        match str_value.as_str() {
+        Self::try_from(String::try_from(value)?.as_str())
+    }
+}
+
+impl TryFrom<&str> for NmSettingIpMethod {
+    type Error = NmError;
+    fn try_from(str_value: &str) -> Result<Self, Self::Error> {
+        match str_value {
             "auto" => Ok(Self::Auto),
             "disabled" => Ok(Self::Disabled),
             "link-local" => Ok(Self::LinkLocal),
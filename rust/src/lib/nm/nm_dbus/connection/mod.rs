@@ -25,6 +25,7 @@ mod ieee8021x;
 mod infiniband;
 mod ip;
 mod mac_vlan;
+mod matching;
 mod ovs;
 mod route;
 mod route_rule;
@@ -48,6 +49,7 @@ pub use self::ieee8021x::NmSetting8021X;
 pub use self::infiniband::NmSettingInfiniBand;
 pub use self::ip::{NmSettingIp, NmSettingIpMethod};
 pub use self::mac_vlan::NmSettingMacVlan;
+pub use self::matching::NmSettingMatch;
 pub use self::ovs::{
     NmSettingOvsBridge, NmSettingOvsDpdk, NmSettingOvsExtIds,
     NmSettingOvsIface, NmSettingOvsPatch, NmSettingOvsPort,
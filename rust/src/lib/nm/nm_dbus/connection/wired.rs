@@ -16,6 +16,7 @@ use super::super::{
 #[serde(try_from = "DbusDictionary")]
 #[non_exhaustive]
 pub struct NmSettingWired {
+    pub mac_address: Option<String>,
     pub cloned_mac_address: Option<String>,
     pub mtu: Option<u32>,
     pub accept_all_mac_addresses: Option<i32>,
@@ -29,6 +30,12 @@ impl TryFrom<DbusDictionary> for NmSettingWired {
     type Error = NmError;
     fn try_from(mut v: DbusDictionary) -> Result<Self, Self::Error> {
         Ok(Self {
+            mac_address: _from_map!(
+                v,
+                "mac-address",
+                own_value_to_bytes_array
+            )?
+            .map(u8_array_to_mac_string),
             cloned_mac_address: _from_map!(
                 v,
                 "cloned-mac-address",
@@ -52,6 +59,12 @@ impl TryFrom<DbusDictionary> for NmSettingWired {
 impl ToDbusValue for NmSettingWired {
     fn to_value(&self) -> Result<HashMap<&str, zvariant::Value>, NmError> {
         let mut ret = HashMap::new();
+        if let Some(v) = &self.mac_address {
+            ret.insert(
+                "mac-address",
+                zvariant::Value::new(mac_str_to_u8_array(v)),
+            );
+        }
         if let Some(v) = &self.cloned_mac_address {
             ret.insert(
                 "cloned-mac-address",
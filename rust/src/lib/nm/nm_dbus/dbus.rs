@@ -14,6 +14,8 @@
 
 use std::collections::HashMap;
 use std::convert::TryFrom;
+use std::os::unix::io::AsRawFd;
+use std::time::Duration;
 
 use log::debug;
 
@@ -49,6 +51,9 @@ const NM_SETTINGS_UPDATE2_FLAGS_TO_DISK: u32 = 1;
 const NM_SETTINGS_UPDATE2_FLAGS_IN_MEMORY: u32 = 2;
 const NM_SETTINGS_UPDATE2_FLAGS_BLOCK_AUTOCONNECT: u32 = 32;
 
+const NM_SIGNAL_MATCH_RULE: &str =
+    "type='signal',sender='org.freedesktop.NetworkManager'";
+
 pub(crate) struct NmDbus<'a> {
     pub(crate) connection: zbus::Connection,
     proxy: NetworkManagerProxy<'a>,
@@ -75,6 +80,51 @@ impl<'a> NmDbus<'a> {
         Ok(self.proxy.version()?)
     }
 
+    pub(crate) fn subscribe_signals(&self) -> Result<(), NmError> {
+        let dbus_proxy = zbus::fdo::DBusProxy::new(&self.connection)?;
+        dbus_proxy.add_match(NM_SIGNAL_MATCH_RULE).map_err(|e| {
+            NmError::new(
+                ErrorKind::DbusConnectionError,
+                format!("Failed to subscribe NetworkManager signals: {}", e),
+            )
+        })
+    }
+
+    // Block till any signal received or timeout, return false on timeout.
+    pub(crate) fn wait_signal(
+        &self,
+        timeout: Duration,
+    ) -> Result<bool, NmError> {
+        loop {
+            let mut poll_fd = libc::pollfd {
+                fd: self.connection.as_raw_fd(),
+                events: libc::POLLIN,
+                revents: 0,
+            };
+            let ret = unsafe {
+                libc::poll(&mut poll_fd, 1, timeout.as_millis() as libc::c_int)
+            };
+            if ret == 0 {
+                return Ok(false);
+            } else if ret < 0 {
+                let e = std::io::Error::last_os_error();
+                if e.kind() == std::io::ErrorKind::Interrupted {
+                    continue;
+                }
+                return Err(NmError::new(
+                    ErrorKind::DbusConnectionError,
+                    format!("Failed to wait on D-Bus connection: {}", e),
+                ));
+            }
+            let msg = self.connection.receive_message()?;
+            if msg.primary_header().map(|h| h.msg_type()).ok()
+                == Some(zbus::MessageType::Signal)
+            {
+                return Ok(true);
+            }
+        }
+    }
+
     pub(crate) fn checkpoint_create(
         &self,
         timeout: u32,
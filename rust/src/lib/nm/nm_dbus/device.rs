@@ -390,7 +390,7 @@ pub struct NmDevice {
     pub real: bool,
 }
 
-fn nm_dev_name_get(
+pub(crate) fn nm_dev_name_get(
     dbus_conn: &zbus::Connection,
     obj_path: &str,
 ) -> Result<String, NmError> {
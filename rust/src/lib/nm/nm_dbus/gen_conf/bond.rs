@@ -3,6 +3,7 @@ use std::collections::HashMap;
 use zvariant::Value;
 
 use super::super::{NmError, NmSettingBond, ToKeyfile};
+use super::keyfile::{FromKeyfile, KeyfileSection};
 
 impl ToKeyfile for NmSettingBond {
     fn to_keyfile(&self) -> Result<HashMap<String, zvariant::Value>, NmError> {
@@ -13,3 +14,16 @@ impl ToKeyfile for NmSettingBond {
         Ok(ret)
     }
 }
+
+impl FromKeyfile for NmSettingBond {
+    fn from_keyfile(section: &KeyfileSection) -> Result<Self, NmError> {
+        let mut ret = Self::default();
+        for (key, value) in section.iter() {
+            // The `interface-name` is deprecated property of bond setting
+            if key != "interface-name" && !value.is_empty() {
+                ret.options.insert(key.to_string(), value.to_string());
+            }
+        }
+        Ok(ret)
+    }
+}
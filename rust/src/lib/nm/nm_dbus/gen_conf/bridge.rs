@@ -1,12 +1,17 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use std::collections::HashMap;
+use std::convert::TryFrom;
 
 use zvariant::Value;
 
 use super::super::{
-    NmError, NmSettingBridge, NmSettingBridgePort, NmSettingBridgeVlanRange,
-    ToDbusValue, ToKeyfile,
+    ErrorKind, NmError, NmSettingBridge, NmSettingBridgePort,
+    NmSettingBridgeVlanRange, NmVlanProtocol, ToDbusValue, ToKeyfile,
+};
+use super::keyfile::{
+    keyfile_get_bool, keyfile_get_num, keyfile_get_str, FromKeyfile,
+    KeyfileSection,
 };
 
 impl ToKeyfile for NmSettingBridge {
@@ -71,3 +76,110 @@ impl ToKeyfile for NmSettingBridgePort {
         Ok(ret)
     }
 }
+
+impl FromKeyfile for NmSettingBridge {
+    fn from_keyfile(section: &KeyfileSection) -> Result<Self, NmError> {
+        let mut ret = Self::default();
+        ret.ageing_time = keyfile_get_num(section, "ageing-time")?;
+        ret.forward_delay = keyfile_get_num(section, "forward-delay")?;
+        ret.group_address = keyfile_get_str(section, "group-address");
+        ret.group_forward_mask =
+            keyfile_get_num(section, "group-forward-mask")?;
+        ret.hello_time = keyfile_get_num(section, "hello-time")?;
+        ret.max_age = keyfile_get_num(section, "max-age")?;
+        ret.multicast_hash_max =
+            keyfile_get_num(section, "multicast-hash-max")?;
+        ret.multicast_last_member_count =
+            keyfile_get_num(section, "multicast-last-member-count")?;
+        ret.multicast_last_member_interval =
+            keyfile_get_num(section, "multicast-last-member-interval")?;
+        ret.multicast_membership_interval =
+            keyfile_get_num(section, "multicast-membership-interval")?;
+        ret.multicast_querier = keyfile_get_bool(section, "multicast-querier")?;
+        ret.multicast_querier_interval =
+            keyfile_get_num(section, "multicast-querier-interval")?;
+        ret.multicast_query_interval =
+            keyfile_get_num(section, "multicast-query-interval")?;
+        ret.multicast_query_response_interval =
+            keyfile_get_num(section, "multicast-query-response-interval")?;
+        ret.multicast_query_use_ifaddr =
+            keyfile_get_bool(section, "multicast-query-use-ifaddr")?;
+        ret.multicast_router = keyfile_get_str(section, "multicast-router");
+        ret.multicast_snooping =
+            keyfile_get_bool(section, "multicast-snooping")?;
+        ret.multicast_startup_query_count =
+            keyfile_get_num(section, "multicast-startup-query-count")?;
+        ret.multicast_startup_query_interval =
+            keyfile_get_num(section, "multicast-startup-query-interval")?;
+        ret.priority = keyfile_get_num(section, "priority")?;
+        // NetworkManager enables STP by default
+        ret.stp = keyfile_get_bool(section, "stp")?.or(Some(true));
+        ret.vlan_default_pvid = keyfile_get_num(section, "vlan-default-pvid")?;
+        ret.vlan_filtering = keyfile_get_bool(section, "vlan-filtering")?;
+        ret.vlan_protocol = keyfile_get_str(section, "vlan-protocol")
+            .map(NmVlanProtocol::try_from)
+            .transpose()?;
+        ret.vlan_stats_enabled =
+            keyfile_get_bool(section, "vlan-stats-enabled")?;
+        ret.vlans = keyfile_get_str(section, "vlans")
+            .map(|v| vlan_ranges_from_keyfile(&v))
+            .transpose()?;
+        Ok(ret)
+    }
+}
+
+impl FromKeyfile for NmSettingBridgePort {
+    fn from_keyfile(section: &KeyfileSection) -> Result<Self, NmError> {
+        let mut ret = Self::default();
+        ret.hairpin_mode = keyfile_get_bool(section, "hairpin-mode")?;
+        ret.path_cost = keyfile_get_num(section, "path-cost")?;
+        ret.priority = keyfile_get_num(section, "priority")?;
+        ret.vlans = keyfile_get_str(section, "vlans")
+            .map(|v| vlan_ranges_from_keyfile(&v))
+            .transpose()?;
+        Ok(ret)
+    }
+}
+
+// The keyfile format is `vid[-vid_end] [pvid] [untagged]` separated by comma
+fn vlan_ranges_from_keyfile(
+    value: &str,
+) -> Result<Vec<NmSettingBridgeVlanRange>, NmError> {
+    let mut ret = Vec::new();
+    for vlan_str in value.split(',').filter(|v| !v.trim().is_empty()) {
+        let mut vlan = NmSettingBridgeVlanRange::default();
+        let mut items = vlan_str.split_whitespace();
+        let range = items.next().unwrap_or_default();
+        let mut vids = range.splitn(2, '-').map(|v| v.parse::<u16>());
+        match (vids.next(), vids.next()) {
+            (Some(Ok(start)), None) => {
+                vlan.vid_start = start;
+                vlan.vid_end = start;
+            }
+            (Some(Ok(start)), Some(Ok(end))) => {
+                vlan.vid_start = start;
+                vlan.vid_end = end;
+            }
+            _ => {
+                return Err(NmError::new(
+                    ErrorKind::InvalidArgument,
+                    format!("Invalid bridge VLAN range {}", vlan_str),
+                ));
+            }
+        }
+        for flag in items {
+            match flag {
+                "pvid" => vlan.pvid = true,
+                "untagged" => vlan.untagged = true,
+                _ => {
+                    return Err(NmError::new(
+                        ErrorKind::InvalidArgument,
+                        format!("Invalid bridge VLAN flag {}", flag),
+                    ));
+                }
+            }
+        }
+        ret.push(vlan);
+    }
+    Ok(ret)
+}
@@ -3,12 +3,45 @@
 use std::collections::HashMap;
 
 use super::{
-    super::{NmConnection, NmError, NmSettingConnection, ToKeyfile},
-    keyfile::keyfile_sections_to_string,
+    super::{
+        NmConnection, NmError, NmSettingBond, NmSettingBridge,
+        NmSettingBridgePort, NmSettingConnection, NmSettingInfiniBand,
+        NmSettingIp, NmSettingMacVlan, NmSettingMatch, NmSettingVlan,
+        NmSettingVrf, NmSettingVxlan, NmSettingWired, ToKeyfile,
+    },
+    keyfile::{
+        keyfile_get_bool, keyfile_get_str, keyfile_sections_to_string,
+        keyfile_string_to_sections, FromKeyfile, KeyfileSection,
+    },
 };
 
 impl ToKeyfile for NmSettingConnection {}
 
+impl FromKeyfile for NmSettingConnection {
+    fn from_keyfile(section: &KeyfileSection) -> Result<Self, NmError> {
+        let mut ret = Self::default();
+        ret.id = keyfile_get_str(section, "id");
+        ret.uuid = keyfile_get_str(section, "uuid");
+        // Keyfile allows alias of setting name as connection type
+        ret.iface_type =
+            keyfile_get_str(section, "type").map(|t| match t.as_str() {
+                "ethernet" => "802-3-ethernet".to_string(),
+                "wifi" => "802-11-wireless".to_string(),
+                _ => t,
+            });
+        ret.iface_name = keyfile_get_str(section, "interface-name");
+        ret.controller = keyfile_get_str(section, "master")
+            .or_else(|| keyfile_get_str(section, "controller"));
+        ret.controller_type = keyfile_get_str(section, "slave-type")
+            .or_else(|| keyfile_get_str(section, "port-type"));
+        ret.autoconnect = keyfile_get_bool(section, "autoconnect")?;
+        ret.autoconnect_ports =
+            keyfile_get_bool(section, "autoconnect-slaves")?
+                .or(keyfile_get_bool(section, "autoconnect-ports")?);
+        Ok(ret)
+    }
+}
+
 impl NmConnection {
     pub fn to_keyfile(&self) -> Result<String, NmError> {
         let mut sections: Vec<(&str, HashMap<String, zvariant::Value>)> =
@@ -82,7 +115,66 @@ impl NmConnection {
         if let Some(ovs_eids) = &self.ovs_ext_ids {
             sections.push(("ovs-external-ids", ovs_eids.to_keyfile()?));
         }
+        if let Some(matching) = &self.matching {
+            sections.push(("match", matching.to_keyfile()?));
+        }
 
         keyfile_sections_to_string(&sections)
     }
 }
+
+impl NmConnection {
+    pub fn from_keyfile(content: &str) -> Result<Self, NmError> {
+        let mut ret = Self::default();
+        for (name, section) in keyfile_string_to_sections(content)? {
+            match name.as_str() {
+                "connection" => {
+                    ret.connection =
+                        Some(NmSettingConnection::from_keyfile(&section)?)
+                }
+                "bond" => {
+                    ret.bond = Some(NmSettingBond::from_keyfile(&section)?)
+                }
+                "bridge" => {
+                    ret.bridge = Some(NmSettingBridge::from_keyfile(&section)?)
+                }
+                "bridge-port" => {
+                    ret.bridge_port =
+                        Some(NmSettingBridgePort::from_keyfile(&section)?)
+                }
+                "ipv4" => ret.ipv4 = Some(NmSettingIp::from_keyfile(&section)?),
+                "ipv6" => ret.ipv6 = Some(NmSettingIp::from_keyfile(&section)?),
+                "ethernet" | "802-3-ethernet" => {
+                    ret.wired = Some(NmSettingWired::from_keyfile(&section)?)
+                }
+                "vlan" => {
+                    ret.vlan = Some(NmSettingVlan::from_keyfile(&section)?)
+                }
+                "vxlan" => {
+                    ret.vxlan = Some(NmSettingVxlan::from_keyfile(&section)?)
+                }
+                "macvlan" => {
+                    ret.mac_vlan =
+                        Some(NmSettingMacVlan::from_keyfile(&section)?)
+                }
+                "vrf" => ret.vrf = Some(NmSettingVrf::from_keyfile(&section)?),
+                "infiniband" => {
+                    ret.infiniband =
+                        Some(NmSettingInfiniBand::from_keyfile(&section)?)
+                }
+                "match" => {
+                    ret.matching = Some(NmSettingMatch::from_keyfile(&section)?)
+                }
+                _ => {
+                    if !section.is_empty() {
+                        log::warn!(
+                            "Ignoring unsupported keyfile section [{}]",
+                            name
+                        );
+                    }
+                }
+            }
+        }
+        Ok(ret)
+    }
+}
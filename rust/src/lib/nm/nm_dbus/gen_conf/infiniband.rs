@@ -1,5 +1,34 @@
 // SPDX-License-Identifier: Apache-2.0
 
-use super::super::{NmSettingInfiniBand, ToKeyfile};
+use super::super::{ErrorKind, NmError, NmSettingInfiniBand, ToKeyfile};
+use super::keyfile::{
+    keyfile_get_num, keyfile_get_str, FromKeyfile, KeyfileSection,
+};
 
 impl ToKeyfile for NmSettingInfiniBand {}
+
+impl FromKeyfile for NmSettingInfiniBand {
+    fn from_keyfile(section: &KeyfileSection) -> Result<Self, NmError> {
+        let mut ret = Self::default();
+        ret.parent = keyfile_get_str(section, "parent");
+        ret.mode = keyfile_get_str(section, "transport-mode");
+        ret.mtu = keyfile_get_num(section, "mtu")?;
+        // The p-key could be stored in hex format
+        ret.pkey = match keyfile_get_str(section, "p-key") {
+            Some(v) => Some(
+                match v.strip_prefix("0x") {
+                    Some(hex) => i32::from_str_radix(hex, 16),
+                    None => v.parse::<i32>(),
+                }
+                .map_err(|_| {
+                    NmError::new(
+                        ErrorKind::InvalidArgument,
+                        format!("Invalid infiniband p-key {}", v),
+                    )
+                })?,
+            ),
+            None => None,
+        };
+        Ok(ret)
+    }
+}
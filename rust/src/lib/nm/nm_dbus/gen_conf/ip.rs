@@ -1,14 +1,24 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use std::collections::HashMap;
+use std::convert::TryFrom;
 
-use super::super::{NmError, NmSettingIp, ToDbusValue, ToKeyfile};
+use super::super::{
+    ErrorKind, NmError, NmIpRoute, NmIpRouteRule, NmSettingIp,
+    NmSettingIpMethod, ToDbusValue, ToKeyfile,
+};
+use super::keyfile::{
+    keyfile_get_bool, keyfile_get_indexed, keyfile_get_list, keyfile_get_num,
+    keyfile_get_str, FromKeyfile, KeyfileSection,
+};
 
 impl ToKeyfile for NmSettingIp {
     fn to_keyfile(&self) -> Result<HashMap<String, zvariant::Value>, NmError> {
         let mut ret = HashMap::new();
         for (k, v) in self.to_value()?.drain() {
-            if !vec!["address-data", "route-data", "dns"].contains(&k) {
+            if !["address-data", "route-data", "routing-rules", "dns"]
+                .contains(&k)
+            {
                 ret.insert(k.to_string(), v);
             }
         }
@@ -28,6 +38,12 @@ impl ToKeyfile for NmSettingIp {
                 );
             }
         }
+        for (i, rule) in self.route_rules.as_slice().iter().enumerate() {
+            ret.insert(
+                format!("routing-rule{}", i + 1),
+                zvariant::Value::new(rule.to_keyfile()),
+            );
+        }
         if let Some(dns) = self.dns.as_ref() {
             ret.insert("dns".to_string(), zvariant::Value::new(dns));
         }
@@ -35,3 +51,67 @@ impl ToKeyfile for NmSettingIp {
         Ok(ret)
     }
 }
+
+impl FromKeyfile for NmSettingIp {
+    fn from_keyfile(section: &KeyfileSection) -> Result<Self, NmError> {
+        let mut ret = Self::default();
+        ret.method = keyfile_get_str(section, "method")
+            .map(|m| NmSettingIpMethod::try_from(m.as_str()))
+            .transpose()?;
+        ret.gateway = keyfile_get_str(section, "gateway");
+        // NetworkManager accepts both `addressN` and `addressesN`, each
+        // holding `ip/prefix[,gateway]` separated by semicolon.
+        let mut addr_lines = keyfile_get_indexed(section, "address");
+        addr_lines.extend(keyfile_get_indexed(section, "addresses"));
+        for (_, line) in addr_lines {
+            for addr in line.split(';').filter(|a| !a.is_empty()) {
+                let mut items = addr.split(',');
+                if let Some(ip) = items.next() {
+                    ret.addresses.push(ip.trim().to_string());
+                }
+                if let Some(gw) = items.next() {
+                    if ret.gateway.is_none() && !gw.trim().is_empty() {
+                        ret.gateway = Some(gw.trim().to_string());
+                    }
+                }
+            }
+        }
+        for (i, line) in keyfile_get_indexed(section, "route") {
+            let opts = section.get(&format!("route{}_options", i));
+            ret.routes
+                .push(NmIpRoute::from_keyfile(line, opts.map(|s| s.as_str()))?);
+        }
+        for (_, line) in keyfile_get_indexed(section, "routing-rule") {
+            ret.route_rules.push(NmIpRouteRule::from_keyfile(line)?);
+        }
+        ret.dns = keyfile_get_list(section, "dns");
+        ret.dns_search = keyfile_get_list(section, "dns-search");
+        ret.dns_priority = keyfile_get_num(section, "dns-priority")?;
+        ret.ignore_auto_dns = keyfile_get_bool(section, "ignore-auto-dns")?;
+        ret.ignore_auto_routes =
+            keyfile_get_bool(section, "ignore-auto-routes")?;
+        ret.never_default = keyfile_get_bool(section, "never-default")?;
+        ret.route_table = keyfile_get_num(section, "route-table")?;
+        ret.dhcp_client_id = keyfile_get_str(section, "dhcp-client-id");
+        ret.dhcp_timeout = keyfile_get_num(section, "dhcp-timeout")?;
+        ret.may_fail = keyfile_get_bool(section, "may-fail")?;
+        ret.ra_timeout = keyfile_get_num(section, "ra-timeout")?;
+        ret.dhcp_duid = keyfile_get_str(section, "dhcp-duid");
+        ret.dhcp_iaid = keyfile_get_str(section, "dhcp-iaid");
+        ret.addr_gen_mode =
+            match keyfile_get_str(section, "addr-gen-mode").as_deref() {
+                Some("eui64") => Some(0),
+                Some("stable-privacy") => Some(1),
+                Some("default-or-eui64") => Some(2),
+                Some("default") => Some(3),
+                Some(v) => Some(v.parse::<i32>().map_err(|_| {
+                    NmError::new(
+                        ErrorKind::InvalidArgument,
+                        format!("Invalid addr-gen-mode {}", v),
+                    )
+                })?),
+                None => None,
+            };
+        Ok(ret)
+    }
+}
@@ -1,5 +1,6 @@
 use std::collections::HashMap;
 use std::fmt::Write;
+use std::str::FromStr;
 
 use log::error;
 
@@ -86,3 +87,144 @@ pub(crate) trait ToKeyfile: ToDbusValue {
         Ok(ret)
     }
 }
+
+pub(crate) type KeyfileSection = HashMap<String, String>;
+
+pub(crate) trait FromKeyfile: Sized {
+    fn from_keyfile(section: &KeyfileSection) -> Result<Self, NmError>;
+}
+
+// Parse the keyfile content into sections with the order preserved.
+pub(crate) fn keyfile_string_to_sections(
+    content: &str,
+) -> Result<Vec<(String, KeyfileSection)>, NmError> {
+    let mut ret: Vec<(String, KeyfileSection)> = Vec::new();
+    for (i, line) in content.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        if line.starts_with('[') && line.ends_with(']') {
+            ret.push((
+                line[1..line.len() - 1].trim().to_string(),
+                HashMap::new(),
+            ));
+            continue;
+        }
+        let mut kv = line.splitn(2, '=');
+        let (key, value) = match (kv.next(), kv.next()) {
+            (Some(k), Some(v)) => (k.trim(), v.trim()),
+            _ => {
+                return Err(NmError::new(
+                    ErrorKind::InvalidArgument,
+                    format!("Line {}: invalid keyfile line '{}'", i + 1, line),
+                ));
+            }
+        };
+        if let Some((_, section)) = ret.last_mut() {
+            section.insert(key.to_string(), keyfile_unescape(value));
+        } else {
+            return Err(NmError::new(
+                ErrorKind::InvalidArgument,
+                format!(
+                    "Line {}: key '{}' is not inside any section",
+                    i + 1,
+                    key
+                ),
+            ));
+        }
+    }
+    Ok(ret)
+}
+
+fn keyfile_unescape(value: &str) -> String {
+    let mut ret = String::new();
+    let mut chars = value.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            ret.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('s') => ret.push(' '),
+            Some('n') => ret.push('\n'),
+            Some('t') => ret.push('\t'),
+            Some('r') => ret.push('\r'),
+            Some(c) => ret.push(c),
+            None => ret.push('\\'),
+        }
+    }
+    ret
+}
+
+pub(crate) fn keyfile_get_str(
+    section: &KeyfileSection,
+    key: &str,
+) -> Option<String> {
+    section.get(key).filter(|v| !v.is_empty()).cloned()
+}
+
+pub(crate) fn keyfile_get_num<T: FromStr>(
+    section: &KeyfileSection,
+    key: &str,
+) -> Result<Option<T>, NmError> {
+    match section.get(key).filter(|v| !v.is_empty()) {
+        Some(v) => v.parse::<T>().map(Some).map_err(|_| {
+            NmError::new(
+                ErrorKind::InvalidArgument,
+                format!("Invalid number '{}' for key {}", v, key),
+            )
+        }),
+        None => Ok(None),
+    }
+}
+
+pub(crate) fn keyfile_get_bool(
+    section: &KeyfileSection,
+    key: &str,
+) -> Result<Option<bool>, NmError> {
+    match section.get(key).map(|v| v.to_lowercase()).as_deref() {
+        Some("true") | Some("yes") | Some("1") => Ok(Some(true)),
+        Some("false") | Some("no") | Some("0") => Ok(Some(false)),
+        // NetworkManager use -1 for default value
+        Some("") | Some("-1") | None => Ok(None),
+        Some(v) => Err(NmError::new(
+            ErrorKind::InvalidArgument,
+            format!("Invalid boolean '{}' for key {}", v, key),
+        )),
+    }
+}
+
+pub(crate) fn keyfile_get_list(
+    section: &KeyfileSection,
+    key: &str,
+) -> Option<Vec<String>> {
+    section.get(key).map(|v| {
+        v.split([';', ','])
+            .map(|s| s.trim())
+            .filter(|s| !s.is_empty())
+            .map(|s| s.to_string())
+            .collect()
+    })
+}
+
+// Return the (index, value) of numbered keys like `address1` or `route2`
+// sorted by index. The key without index is treated as index 0.
+pub(crate) fn keyfile_get_indexed<'a>(
+    section: &'a KeyfileSection,
+    prefix: &str,
+) -> Vec<(u32, &'a str)> {
+    let mut ret: Vec<(u32, &str)> = section
+        .iter()
+        .filter_map(|(k, v)| {
+            let index = k.strip_prefix(prefix)?;
+            if index.is_empty() {
+                Some((0, v.as_str()))
+            } else {
+                index.parse::<u32>().ok().map(|i| (i, v.as_str()))
+            }
+        })
+        .collect();
+    ret.sort_unstable_by_key(|(i, _)| *i);
+    ret
+}
@@ -1,5 +1,20 @@
 // SPDX-License-Identifier: Apache-2.0
 
-use super::super::{NmSettingMacVlan, ToKeyfile};
+use super::super::{NmError, NmSettingMacVlan, ToKeyfile};
+use super::keyfile::{
+    keyfile_get_bool, keyfile_get_num, keyfile_get_str, FromKeyfile,
+    KeyfileSection,
+};
 
 impl ToKeyfile for NmSettingMacVlan {}
+
+impl FromKeyfile for NmSettingMacVlan {
+    fn from_keyfile(section: &KeyfileSection) -> Result<Self, NmError> {
+        let mut ret = Self::default();
+        ret.parent = keyfile_get_str(section, "parent");
+        ret.mode = keyfile_get_num(section, "mode")?;
+        ret.accept_all_mac = keyfile_get_bool(section, "promiscuous")?;
+        ret.tap = keyfile_get_bool(section, "tap")?;
+        Ok(ret)
+    }
+}
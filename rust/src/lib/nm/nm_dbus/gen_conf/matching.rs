@@ -0,0 +1,14 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use super::super::{NmError, NmSettingMatch, ToKeyfile};
+use super::keyfile::{keyfile_get_list, FromKeyfile, KeyfileSection};
+
+impl ToKeyfile for NmSettingMatch {}
+
+impl FromKeyfile for NmSettingMatch {
+    fn from_keyfile(section: &KeyfileSection) -> Result<Self, NmError> {
+        let mut ret = Self::default();
+        ret.path = keyfile_get_list(section, "path");
+        Ok(ret)
+    }
+}
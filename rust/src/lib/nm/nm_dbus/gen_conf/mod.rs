@@ -9,6 +9,7 @@ mod infiniband;
 mod ip;
 mod keyfile;
 mod mac_vlan;
+mod matching;
 mod ovs;
 mod route;
 mod route_rule;
@@ -2,7 +2,7 @@
 
 use std::collections::HashMap;
 
-use super::super::NmIpRoute;
+use super::super::{ErrorKind, NmError, NmIpRoute};
 
 const DEFAULT_ROUTE_TABLE: u32 = 254;
 
@@ -28,3 +28,50 @@ impl NmIpRoute {
         ret
     }
 }
+
+impl NmIpRoute {
+    // The keyfile format is `dest/prefix[,next_hop[,metric]]` with optional
+    // `routeN_options` like `table=100,onlink=true`.
+    pub(crate) fn from_keyfile(
+        line: &str,
+        options: Option<&str>,
+    ) -> Result<Self, NmError> {
+        let mut ret = Self::default();
+        let items: Vec<&str> = line.split(',').map(|s| s.trim()).collect();
+        let mut dest = items[0].splitn(2, '/');
+        ret.dest = dest.next().map(|s| s.to_string());
+        ret.prefix = match dest.next() {
+            Some(p) => Some(p.parse::<u32>().map_err(|_| {
+                invalid_route_error(line, "invalid prefix length")
+            })?),
+            None => None,
+        };
+        ret.next_hop = items
+            .get(1)
+            .filter(|n| !n.is_empty())
+            .map(|n| n.to_string());
+        ret.metric = match items.get(2).filter(|m| !m.is_empty()) {
+            Some(m) => Some(
+                m.parse::<u32>()
+                    .map_err(|_| invalid_route_error(line, "invalid metric"))?,
+            ),
+            None => None,
+        };
+        for opt in options.unwrap_or_default().split(',') {
+            let mut kv = opt.trim().splitn(2, '=');
+            if let (Some("table"), Some(v)) = (kv.next(), kv.next()) {
+                ret.table = Some(v.parse::<u32>().map_err(|_| {
+                    invalid_route_error(line, "invalid route table")
+                })?);
+            }
+        }
+        Ok(ret)
+    }
+}
+
+fn invalid_route_error(line: &str, reason: &str) -> NmError {
+    NmError::new(
+        ErrorKind::InvalidArgument,
+        format!("Invalid route {}: {}", line, reason),
+    )
+}
@@ -1,3 +1,93 @@
 // SPDX-License-Identifier: Apache-2.0
 
-// TODO
+use super::super::{ErrorKind, NmError, NmIpRouteRule};
+
+impl NmIpRouteRule {
+    pub(crate) fn to_keyfile(&self) -> String {
+        let mut items = Vec::new();
+        if let Some(v) = self.priority {
+            items.push(format!("priority {}", v));
+        }
+        if let Some(v) = self.from.as_ref() {
+            items.push(match self.from_len {
+                Some(l) => format!("from {}/{}", v, l),
+                None => format!("from {}", v),
+            });
+        }
+        if let Some(v) = self.to.as_ref() {
+            items.push(match self.to_len {
+                Some(l) => format!("to {}/{}", v, l),
+                None => format!("to {}", v),
+            });
+        }
+        if let Some(v) = self.table {
+            items.push(format!("table {}", v));
+        }
+        items.join(" ")
+    }
+
+    // The keyfile format is the same as `ip rule` arguments, for example:
+    //  `priority 100 from 192.0.2.0/24 to 198.51.100.0/24 table 100`
+    pub(crate) fn from_keyfile(line: &str) -> Result<Self, NmError> {
+        let mut ret = Self::default();
+        let mut items = line.split_whitespace();
+        while let Some(key) = items.next() {
+            let value = items.next().ok_or_else(|| {
+                invalid_rule_error(line, &format!("no value for {}", key))
+            })?;
+            match key {
+                "priority" => {
+                    ret.priority =
+                        Some(value.parse::<u32>().map_err(|_| {
+                            invalid_rule_error(line, "invalid priority")
+                        })?);
+                }
+                "table" | "lookup" => {
+                    ret.table = Some(value.parse::<u32>().map_err(|_| {
+                        invalid_rule_error(line, "invalid table")
+                    })?);
+                }
+                "from" => {
+                    let (ip, len) = parse_ip_prefix(line, value)?;
+                    ret.from = Some(ip);
+                    ret.from_len = len;
+                }
+                "to" => {
+                    let (ip, len) = parse_ip_prefix(line, value)?;
+                    ret.to = Some(ip);
+                    ret.to_len = len;
+                }
+                _ => {
+                    return Err(invalid_rule_error(
+                        line,
+                        &format!("unsupported property {}", key),
+                    ));
+                }
+            }
+        }
+        Ok(ret)
+    }
+}
+
+fn parse_ip_prefix(
+    line: &str,
+    value: &str,
+) -> Result<(String, Option<u8>), NmError> {
+    let mut items = value.splitn(2, '/');
+    let ip = items.next().unwrap_or_default().to_string();
+    let len =
+        match items.next() {
+            Some(l) => Some(l.parse::<u8>().map_err(|_| {
+                invalid_rule_error(line, "invalid prefix length")
+            })?),
+            None => None,
+        };
+    Ok((ip, len))
+}
+
+fn invalid_rule_error(line: &str, reason: &str) -> NmError {
+    NmError::new(
+        ErrorKind::InvalidArgument,
+        format!("Invalid routing rule {}: {}", line, reason),
+    )
+}
@@ -1,5 +1,17 @@
 // SPDX-License-Identifier: Apache-2.0
 
-use super::super::{NmSettingVlan, ToKeyfile};
+use super::super::{NmError, NmSettingVlan, ToKeyfile};
+use super::keyfile::{
+    keyfile_get_num, keyfile_get_str, FromKeyfile, KeyfileSection,
+};
 
 impl ToKeyfile for NmSettingVlan {}
+
+impl FromKeyfile for NmSettingVlan {
+    fn from_keyfile(section: &KeyfileSection) -> Result<Self, NmError> {
+        let mut ret = Self::default();
+        ret.parent = keyfile_get_str(section, "parent");
+        ret.id = keyfile_get_num(section, "id")?;
+        Ok(ret)
+    }
+}
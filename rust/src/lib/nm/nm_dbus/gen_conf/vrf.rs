@@ -1,5 +1,14 @@
 // SPDX-License-Identifier: Apache-2.0
 
-use super::super::{NmSettingVrf, ToKeyfile};
+use super::super::{NmError, NmSettingVrf, ToKeyfile};
+use super::keyfile::{keyfile_get_num, FromKeyfile, KeyfileSection};
 
 impl ToKeyfile for NmSettingVrf {}
+
+impl FromKeyfile for NmSettingVrf {
+    fn from_keyfile(section: &KeyfileSection) -> Result<Self, NmError> {
+        let mut ret = Self::default();
+        ret.table = keyfile_get_num(section, "table")?;
+        Ok(ret)
+    }
+}
@@ -1,5 +1,19 @@
 // SPDX-License-Identifier: Apache-2.0
 
-use super::super::{NmSettingVxlan, ToKeyfile};
+use super::super::{NmError, NmSettingVxlan, ToKeyfile};
+use super::keyfile::{
+    keyfile_get_num, keyfile_get_str, FromKeyfile, KeyfileSection,
+};
 
 impl ToKeyfile for NmSettingVxlan {}
+
+impl FromKeyfile for NmSettingVxlan {
+    fn from_keyfile(section: &KeyfileSection) -> Result<Self, NmError> {
+        let mut ret = Self::default();
+        ret.parent = keyfile_get_str(section, "parent");
+        ret.id = keyfile_get_num(section, "id")?;
+        ret.remote = keyfile_get_str(section, "remote");
+        ret.dst_port = keyfile_get_num(section, "destination-port")?;
+        Ok(ret)
+    }
+}
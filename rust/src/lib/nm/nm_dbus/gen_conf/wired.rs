@@ -4,18 +4,40 @@ use std::collections::HashMap;
 use zvariant::Value;
 
 use super::super::{NmError, NmSettingWired, ToDbusValue, ToKeyfile};
+use super::keyfile::{
+    keyfile_get_bool, keyfile_get_num, keyfile_get_str, FromKeyfile,
+    KeyfileSection,
+};
 
 impl ToKeyfile for NmSettingWired {
     fn to_keyfile(&self) -> Result<HashMap<String, zvariant::Value>, NmError> {
         let mut ret = HashMap::new();
         for (k, v) in self.to_value()?.drain() {
-            if k != "cloned-mac-address" {
+            if k != "cloned-mac-address" && k != "mac-address" {
                 ret.insert(k.to_string(), v);
             }
         }
+        if let Some(v) = &self.mac_address {
+            ret.insert("mac-address".to_string(), Value::new(v));
+        }
         if let Some(v) = &self.cloned_mac_address {
             ret.insert("cloned-mac-address".to_string(), Value::new(v));
         }
         Ok(ret)
     }
 }
+
+impl FromKeyfile for NmSettingWired {
+    fn from_keyfile(section: &KeyfileSection) -> Result<Self, NmError> {
+        let mut ret = Self::default();
+        ret.mac_address = keyfile_get_str(section, "mac-address");
+        ret.cloned_mac_address = keyfile_get_str(section, "cloned-mac-address");
+        ret.mtu = keyfile_get_num(section, "mtu")?;
+        ret.accept_all_mac_addresses =
+            keyfile_get_num(section, "accept-all-mac-addresses")?;
+        ret.speed = keyfile_get_num(section, "speed")?;
+        ret.duplex = keyfile_get_str(section, "duplex");
+        ret.auto_negotiate = keyfile_get_bool(section, "auto-negotiate")?;
+        Ok(ret)
+    }
+}
@@ -2,6 +2,8 @@
 
 #[cfg(feature = "query_apply")]
 mod active_connection;
+#[cfg(feature = "query_apply")]
+mod checkpoint;
 mod connection;
 mod convert;
 #[cfg(feature = "query_apply")]
@@ -25,15 +27,17 @@ mod gen_conf;
 pub use self::active_connection::{
     NmActiveConnection, NM_ACTIVATION_STATE_FLAG_EXTERNAL,
 };
+#[cfg(feature = "query_apply")]
+pub use self::checkpoint::NmCheckpoint;
 pub use self::connection::{
     NmConnection, NmIpRoute, NmIpRouteRule, NmSetting8021X, NmSettingBond,
     NmSettingBridge, NmSettingBridgePort, NmSettingBridgeVlanRange,
     NmSettingConnection, NmSettingEthtool, NmSettingInfiniBand, NmSettingIp,
-    NmSettingIpMethod, NmSettingMacVlan, NmSettingOvsBridge, NmSettingOvsDpdk,
-    NmSettingOvsExtIds, NmSettingOvsIface, NmSettingOvsPatch, NmSettingOvsPort,
-    NmSettingSriov, NmSettingSriovVf, NmSettingSriovVfVlan, NmSettingUser,
-    NmSettingVeth, NmSettingVlan, NmSettingVrf, NmSettingVxlan, NmSettingWired,
-    NmSettingsConnectionFlag, NmVlanProtocol,
+    NmSettingIpMethod, NmSettingMacVlan, NmSettingMatch, NmSettingOvsBridge,
+    NmSettingOvsDpdk, NmSettingOvsExtIds, NmSettingOvsIface, NmSettingOvsPatch,
+    NmSettingOvsPort, NmSettingSriov, NmSettingSriovVf, NmSettingSriovVfVlan,
+    NmSettingUser, NmSettingVeth, NmSettingVlan, NmSettingVrf, NmSettingVxlan,
+    NmSettingWired, NmSettingsConnectionFlag, NmVlanProtocol,
 };
 #[cfg(feature = "query_apply")]
 pub use self::device::{NmDevice, NmDeviceState, NmDeviceStateReason};
@@ -9,6 +9,7 @@ use super::{
     active_connection::{
         get_nm_ac_by_obj_path, nm_ac_obj_path_uuid_get, NmActiveConnection,
     },
+    checkpoint::{nm_checkpoint_from_obj_path, NmCheckpoint},
     connection::{nm_con_get_from_obj_path, NmConnection},
     dbus::NmDbus,
     device::{
@@ -38,6 +39,16 @@ impl<'a> NmApi<'a> {
         self.dbus.version()
     }
 
+    // Subscribe to all signals of NetworkManager, use `wait_signal()` to
+    // wait on them.
+    pub fn subscribe_signals(&self) -> Result<(), NmError> {
+        self.dbus.subscribe_signals()
+    }
+
+    pub fn wait_signal(&self, timeout: Duration) -> Result<bool, NmError> {
+        self.dbus.wait_signal(timeout)
+    }
+
     pub fn checkpoint_create(&self, timeout: u32) -> Result<String, NmError> {
         debug!("checkpoint_create");
         let cp = self.dbus.checkpoint_create(timeout)?;
@@ -64,6 +75,22 @@ impl<'a> NmApi<'a> {
             .checkpoint_rollback(checkpoint_to_rollback.as_str())
     }
 
+    pub fn checkpoints_get(&self) -> Result<Vec<NmCheckpoint>, NmError> {
+        debug!("checkpoints_get");
+        let mut ret = Vec::new();
+        for obj_path in self.dbus.checkpoints()? {
+            // Race: Checkpoint might just been destroyed or rollbacked
+            match nm_checkpoint_from_obj_path(&self.dbus.connection, &obj_path)
+            {
+                Ok(checkpoint) => ret.push(checkpoint),
+                Err(e) => {
+                    debug!("Failed to retrieve checkpoint {} {}", obj_path, e)
+                }
+            }
+        }
+        Ok(ret)
+    }
+
     fn last_active_checkpoint(&self) -> Result<String, NmError> {
         debug!("last_active_checkpoint");
         let mut checkpoints = self.dbus.checkpoints()?;
@@ -22,7 +22,10 @@ use super::{
     wired::gen_nm_wired_setting,
 };
 
-use crate::{ErrorKind, Interface, InterfaceType, NetworkState, NmstateError};
+use crate::{
+    BaseInterface, ErrorKind, Interface, InterfaceIdentifier, InterfaceType,
+    NetworkState, NmstateError,
+};
 
 pub(crate) const NM_SETTING_BRIDGE_SETTING_NAME: &str = "bridge";
 pub(crate) const NM_SETTING_WIRED_SETTING_NAME: &str = "802-3-ethernet";
@@ -58,7 +61,8 @@ pub(crate) fn iface_to_nm_connections(
         &base_iface.name,
         &base_iface.iface_type,
         nm_ac_uuids,
-    );
+    )
+    .or_else(|| get_exist_profile_by_id(exist_nm_conns, base_iface));
     if iface.is_up_exist_config() {
         if let Some(nm_conn) = exist_nm_conn {
             if !iface.is_userspace()
@@ -269,6 +273,11 @@ pub(crate) fn gen_nm_conn_setting(
     nm_conn: &mut NmConnection,
     stable_uuid: bool,
 ) -> Result<(), NmstateError> {
+    let base_iface = iface.base_iface();
+    let profile_name = base_iface
+        .profile_name
+        .as_deref()
+        .unwrap_or_else(|| iface.name());
     let mut nm_conn_set = if let Some(cur_nm_conn_set) = &nm_conn.connection {
         cur_nm_conn_set.clone()
     } else {
@@ -285,12 +294,12 @@ pub(crate) fn gen_nm_conn_setting(
             InterfaceType::OvsInterface => {
                 format!("{}-if", iface.name())
             }
-            _ => iface.name().to_string(),
+            _ => profile_name.to_string(),
         };
 
         new_nm_conn_set.id = Some(conn_name);
         new_nm_conn_set.uuid = Some(if stable_uuid {
-            uuid_from_name_and_type(iface.name(), &iface.iface_type())
+            uuid_from_name_and_type(profile_name, &iface.iface_type())
         } else {
             // Use Linux random number generator (RNG) to generate UUID
             uuid::Uuid::new_v4().hyphenated().to_string()
@@ -306,7 +315,21 @@ pub(crate) fn gen_nm_conn_setting(
         new_nm_conn_set
     };
 
-    nm_conn_set.iface_name = Some(iface.name().to_string());
+    // Interface identified by MAC address or PCI address should be bound
+    // to the device by `ethernet.mac-address` or `match.path`.
+    if base_iface.is_identified_by_name() {
+        nm_conn_set.iface_name = Some(iface.name().to_string());
+    } else {
+        nm_conn_set.iface_name = None;
+    }
+    if base_iface.identifier == Some(InterfaceIdentifier::PciAddress) {
+        if let Some(pci_address) = base_iface.pci_address.as_deref() {
+            let mut nm_match_set =
+                nm_conn.matching.as_ref().cloned().unwrap_or_default();
+            nm_match_set.path = Some(vec![format!("pci-{}", pci_address)]);
+            nm_conn.matching = Some(nm_match_set);
+        }
+    }
     nm_conn_set.autoconnect = Some(true);
     nm_conn_set.autoconnect_ports = if iface.is_controller() {
         Some(true)
@@ -362,6 +385,21 @@ fn uuid_from_name_and_type(
     .to_string()
 }
 
+// For interface identified by MAC address or PCI address, the existing
+// profile has no interface name but using the profile name as ID.
+fn get_exist_profile_by_id<'a>(
+    exist_nm_conns: &'a [NmConnection],
+    base_iface: &BaseInterface,
+) -> Option<&'a NmConnection> {
+    let profile_name = base_iface.profile_name.as_deref()?;
+    let nm_iface_type = iface_type_to_nm(&base_iface.iface_type).ok()?;
+    exist_nm_conns.iter().rev().find(|nm_conn| {
+        nm_conn.iface_name().is_none()
+            && nm_conn.id() == Some(profile_name)
+            && nm_conn.iface_type() == Some(&nm_iface_type)
+    })
+}
+
 // Found existing profile, prefer the activated one
 pub(crate) fn get_exist_profile<'a>(
     exist_nm_conns: &'a [NmConnection],
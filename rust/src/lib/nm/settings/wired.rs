@@ -2,6 +2,7 @@ use crate::nm::nm_dbus::NmConnection;
 
 use crate::{
     nm::version::nm_supports_accept_all_mac_addresses_mode, Interface,
+    InterfaceIdentifier,
 };
 
 pub(crate) fn gen_nm_wired_setting(
@@ -15,7 +16,13 @@ pub(crate) fn gen_nm_wired_setting(
     let base_iface = iface.base_iface();
 
     if let Some(mac) = &base_iface.mac_address {
-        nm_wired_set.cloned_mac_address = Some(mac.to_string());
+        // MAC address used as identifier is the permanent MAC address of
+        // the device to bind to.
+        if base_iface.identifier == Some(InterfaceIdentifier::MacAddress) {
+            nm_wired_set.mac_address = Some(mac.to_string());
+        } else {
+            nm_wired_set.cloned_mac_address = Some(mac.to_string());
+        }
         flag_need_wired = true;
     }
     if let Some(mtu) = &base_iface.mtu {
@@ -0,0 +1,47 @@
+use crate::nm::{checkpoint::nm_checkpoint_to_nmstate, nm_dbus::NmCheckpoint};
+
+const TEST_NOW: u64 = 1700000000;
+
+fn gen_test_nm_checkpoint(created: i64, rollback_timeout: u32) -> NmCheckpoint {
+    NmCheckpoint {
+        obj_path: "/org/freedesktop/NetworkManager/Checkpoint/1".to_string(),
+        created,
+        rollback_timeout,
+        devices: vec!["eth1".to_string()],
+    }
+}
+
+#[test]
+fn test_nm_checkpoint_to_nmstate_timestamps() {
+    // Created 15.5 seconds ago on a host booted 1000 seconds ago
+    let nm_checkpoint = gen_test_nm_checkpoint(984_500, 60);
+
+    let info = nm_checkpoint_to_nmstate(&nm_checkpoint, 1_000_000, TEST_NOW);
+
+    assert_eq!(info.path, nm_checkpoint.obj_path);
+    assert_eq!(info.created, TEST_NOW - 15);
+    assert_eq!(info.rollback_timeout, 60);
+    assert_eq!(info.remaining_timeout, 45);
+    assert_eq!(info.devices, vec!["eth1".to_string()]);
+}
+
+#[test]
+fn test_nm_checkpoint_to_nmstate_expired() {
+    let nm_checkpoint = gen_test_nm_checkpoint(100_000, 60);
+
+    let info = nm_checkpoint_to_nmstate(&nm_checkpoint, 1_000_000, TEST_NOW);
+
+    assert_eq!(info.created, TEST_NOW - 900);
+    assert_eq!(info.remaining_timeout, 0);
+}
+
+#[test]
+fn test_nm_checkpoint_to_nmstate_created_in_future() {
+    // Clock skew between NetworkManager and nmstate should not underflow
+    let nm_checkpoint = gen_test_nm_checkpoint(1_000_500, 60);
+
+    let info = nm_checkpoint_to_nmstate(&nm_checkpoint, 1_000_000, TEST_NOW);
+
+    assert_eq!(info.created, TEST_NOW);
+    assert_eq!(info.remaining_timeout, 60);
+}
@@ -0,0 +1,210 @@
+use crate::nm::nm_import_keyfiles;
+use crate::{BondLacpRate, BondMode, ErrorKind, Interface, InterfaceType};
+
+const BOND0_KEYFILE: &str = r#"[connection]
+id=bond0
+uuid=2ee5d3b6-1f0a-4e2d-9a4c-3d5d7b1f7a11
+type=bond
+interface-name=bond0
+
+[bond]
+mode=active-backup
+miimon=100
+
+[ipv4]
+method=manual
+address1=192.0.2.2/24,192.0.2.1
+dns=192.0.2.53;
+route1=198.51.100.0/24,192.0.2.254,100
+route1_options=table=200
+routing-rule1=priority 1000 from 192.0.2.0/24 table 200
+
+[ipv6]
+method=disabled
+"#;
+
+const ETH1_KEYFILE: &str = r#"[connection]
+id=eth1
+type=ethernet
+interface-name=eth1
+master=2ee5d3b6-1f0a-4e2d-9a4c-3d5d7b1f7a11
+slave-type=bond
+
+[ethernet]
+mtu=9000
+"#;
+
+#[test]
+fn test_nm_import_bond_with_static_ip() {
+    let net_state = nm_import_keyfiles(&[
+        ("bond0.nmconnection".to_string(), BOND0_KEYFILE.to_string()),
+        ("eth1.nmconnection".to_string(), ETH1_KEYFILE.to_string()),
+    ])
+    .unwrap();
+
+    let bond_iface = if let Interface::Bond(i) = net_state
+        .interfaces
+        .get_iface("bond0", InterfaceType::Bond)
+        .unwrap()
+    {
+        i
+    } else {
+        panic!("bond0 should be bond interface");
+    };
+    let bond_conf = bond_iface.bond.as_ref().unwrap();
+    assert_eq!(bond_conf.port, Some(vec!["eth1".to_string()]));
+    assert_eq!(bond_conf.options.as_ref().and_then(|o| o.miimon), Some(100));
+    let ipv4 = bond_iface.base.ipv4.as_ref().unwrap();
+    assert!(ipv4.enabled);
+    assert_eq!(ipv4.dhcp, Some(false));
+    assert_eq!(ipv4.addresses.as_ref().map(|a| a.len()), Some(1));
+    assert!(!bond_iface.base.ipv6.as_ref().unwrap().enabled);
+
+    let eth1_iface = net_state
+        .interfaces
+        .get_iface("eth1", InterfaceType::Ethernet)
+        .unwrap();
+    assert_eq!(eth1_iface.base_iface().mtu, Some(9000));
+    assert!(eth1_iface.base_iface().ipv4.is_none());
+
+    let routes = net_state.routes.config.as_ref().unwrap();
+    assert_eq!(routes.len(), 2);
+    assert_eq!(routes[0].destination.as_deref(), Some("0.0.0.0/0"));
+    assert_eq!(routes[0].next_hop_addr.as_deref(), Some("192.0.2.1"));
+    assert_eq!(routes[1].destination.as_deref(), Some("198.51.100.0/24"));
+    assert_eq!(routes[1].metric, Some(100));
+    assert_eq!(routes[1].table_id, Some(200));
+
+    let rules = net_state.rules.config.as_ref().unwrap();
+    assert_eq!(rules[0].ip_from.as_deref(), Some("192.0.2.0/24"));
+    assert_eq!(rules[0].priority, Some(1000));
+    assert_eq!(rules[0].table_id, Some(200));
+
+    assert_eq!(
+        net_state
+            .dns
+            .config
+            .as_ref()
+            .and_then(|c| c.server.as_ref()),
+        Some(&vec!["192.0.2.53".to_string()])
+    );
+}
+
+#[test]
+fn test_nm_import_bridge_port_vlan() {
+    let net_state = nm_import_keyfiles(&[
+        (
+            "br0.nmconnection".to_string(),
+            r#"[connection]
+id=br0
+type=bridge
+interface-name=br0
+
+[bridge]
+stp=false
+
+[ipv4]
+method=auto
+"#
+            .to_string(),
+        ),
+        (
+            "eth2.nmconnection".to_string(),
+            r#"[connection]
+id=eth2
+type=ethernet
+interface-name=eth2
+master=br0
+slave-type=bridge
+
+[bridge-port]
+vlans=1 pvid untagged,100-200
+"#
+            .to_string(),
+        ),
+    ])
+    .unwrap();
+
+    let br_iface = if let Interface::LinuxBridge(i) = net_state
+        .interfaces
+        .get_iface("br0", InterfaceType::LinuxBridge)
+        .unwrap()
+    {
+        i
+    } else {
+        panic!("br0 should be linux bridge interface");
+    };
+    let br_conf = br_iface.bridge.as_ref().unwrap();
+    assert_eq!(
+        br_conf
+            .options
+            .as_ref()
+            .and_then(|o| o.stp.as_ref())
+            .and_then(|s| s.enabled),
+        Some(false)
+    );
+    let port_conf = &br_conf.port.as_ref().unwrap()[0];
+    assert_eq!(port_conf.name, "eth2");
+    let vlan_conf = port_conf.vlan.as_ref().unwrap();
+    assert_eq!(vlan_conf.tag, Some(1));
+    assert_eq!(vlan_conf.enable_native, Some(true));
+    assert_eq!(vlan_conf.trunk_tags.as_ref().map(|t| t.len()), Some(1));
+    assert_eq!(br_iface.base.ipv4.as_ref().unwrap().dhcp, Some(true));
+}
+
+#[test]
+fn test_nm_import_bond_ignore_unsupported_option() {
+    let net_state = nm_import_keyfiles(&[(
+        "bond1.nmconnection".to_string(),
+        r#"[connection]
+id=bond1
+type=bond
+interface-name=bond1
+
+[bond]
+mode=802.3ad
+arp_missed_max=2
+lacp_rate=fast
+
+[ipv4]
+method=disabled
+
+[ipv6]
+method=disabled
+"#
+        .to_string(),
+    )])
+    .unwrap();
+
+    let bond_iface = if let Interface::Bond(i) = net_state
+        .interfaces
+        .get_iface("bond1", InterfaceType::Bond)
+        .unwrap()
+    {
+        i
+    } else {
+        panic!("bond1 should be bond interface");
+    };
+    let bond_conf = bond_iface.bond.as_ref().unwrap();
+    assert_eq!(bond_conf.mode, Some(BondMode::LACP));
+    assert_eq!(
+        bond_conf
+            .options
+            .as_ref()
+            .and_then(|o| o.lacp_rate.as_ref()),
+        Some(&BondLacpRate::Fast)
+    );
+}
+
+#[test]
+fn test_nm_import_invalid_keyfile() {
+    let result = nm_import_keyfiles(&[(
+        "bad.nmconnection".to_string(),
+        "id=no_section\n".to_string(),
+    )]);
+    assert!(result.is_err());
+    if let Err(e) = result {
+        assert_eq!(e.kind(), ErrorKind::InvalidArgument);
+        assert!(e.msg().contains("bad.nmconnection"));
+    }
+}
@@ -1,2 +1,6 @@
+#[cfg(all(test, feature = "query_apply"))]
+mod checkpoint;
+#[cfg(all(test, feature = "gen_conf"))]
+mod import;
 #[cfg(test)]
 mod profiles;
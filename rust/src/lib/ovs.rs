@@ -23,6 +23,32 @@ impl OvsDbGlobalConfig {
     pub fn is_none(&self) -> bool {
         self.external_ids.is_none() && self.other_config.is_none()
     }
+
+    #[cfg(any(feature = "query_apply", feature = "gen_conf"))]
+    pub(crate) fn get_other_config(&self) -> HashMap<&str, &str> {
+        let mut ret = HashMap::new();
+        if let Some(ocfg) = self.other_config.as_ref() {
+            for (k, v) in ocfg.iter() {
+                if let Some(v) = v {
+                    ret.insert(k.as_str(), v.as_str());
+                }
+            }
+        }
+        ret
+    }
+
+    #[cfg(any(feature = "query_apply", feature = "gen_conf"))]
+    pub(crate) fn get_external_ids(&self) -> HashMap<&str, &str> {
+        let mut ret = HashMap::new();
+        if let Some(eids) = self.external_ids.as_ref() {
+            for (k, v) in eids {
+                if let Some(v) = v {
+                    ret.insert(k.as_str(), v.as_str());
+                }
+            }
+        }
+        ret
+    }
 }
 
 impl<'de> Deserialize<'de> for OvsDbGlobalConfig {
@@ -0,0 +1,128 @@
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+use crate::{
+    ovsdb::{
+        db::{
+            OvsDbCondition, OvsDbConnection, OvsDbUpdate, GLOBAL_CONFIG_TABLE,
+        },
+        show::ovsdb_is_running,
+    },
+    snapshot::{
+        snapshot_get_path, snapshot_read, snapshot_remove, snapshot_save,
+    },
+    NmstateError,
+};
+
+// Snapshot of the `external_ids` and `other_config` changed by nmstate
+// directly
+const OVSDB_CHECKPOINT_DIR: &str = "/run/nmstate/ovsdb_checkpoint";
+
+const OVSDB_CHECKPOINT_TABLES: [&str; 4] =
+    [GLOBAL_CONFIG_TABLE, "Bridge", "Port", "Interface"];
+const OVSDB_CHECKPOINT_COLUMNS: [&str; 2] = ["external_ids", "other_config"];
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub(crate) struct OvsDbSnapshotRow {
+    table: String,
+    // The `Open_vSwitch` table has no name column and holds single row only
+    name: Option<String>,
+    columns: HashMap<String, Value>,
+}
+
+impl OvsDbSnapshotRow {
+    pub(crate) fn new(table: &str, mut row: Map<String, Value>) -> Self {
+        let name = row
+            .remove("name")
+            .and_then(|n| n.as_str().map(|n| n.to_string()));
+        Self {
+            table: table.to_string(),
+            name,
+            columns: row.into_iter().collect(),
+        }
+    }
+}
+
+impl From<&OvsDbSnapshotRow> for OvsDbUpdate {
+    fn from(row: &OvsDbSnapshotRow) -> Self {
+        OvsDbUpdate {
+            table: row.table.clone(),
+            conditions: row
+                .name
+                .as_ref()
+                .map(|n| {
+                    vec![OvsDbCondition::new(
+                        "name",
+                        "==",
+                        Value::String(n.to_string()),
+                    )]
+                })
+                .unwrap_or_default(),
+            row: row.columns.clone(),
+        }
+    }
+}
+
+pub(crate) fn ovsdb_checkpoint_create(
+    checkpoint: &str,
+) -> Result<(), NmstateError> {
+    let mut cli = OvsDbConnection::new()?;
+    let mut snapshot: Vec<OvsDbSnapshotRow> = Vec::new();
+    for table in OVSDB_CHECKPOINT_TABLES {
+        let mut columns = OVSDB_CHECKPOINT_COLUMNS.to_vec();
+        if table != GLOBAL_CONFIG_TABLE {
+            columns.push("name");
+        }
+        for row in cli.get_rows(table, columns.as_slice())? {
+            snapshot.push(OvsDbSnapshotRow::new(table, row));
+        }
+    }
+
+    snapshot_save(
+        OVSDB_CHECKPOINT_DIR,
+        checkpoint,
+        &serde_json::to_string(&snapshot)?,
+    )?;
+    log::info!("Stored OVSDB snapshot of checkpoint {}", checkpoint);
+    Ok(())
+}
+
+// Restore the OVSDB snapshot of specified NetworkManager checkpoint if any.
+// Empty checkpoint means the last snapshot.
+pub(crate) fn ovsdb_checkpoint_rollback(
+    checkpoint: &str,
+) -> Result<(), NmstateError> {
+    let path = match snapshot_get_path(OVSDB_CHECKPOINT_DIR, checkpoint) {
+        Some(p) => p,
+        None => return Ok(()),
+    };
+    if !ovsdb_is_running() {
+        log::warn!(
+            "OVSDB is not running, cannot restore OVSDB snapshot of \
+            checkpoint {}",
+            checkpoint
+        );
+        return snapshot_remove(&path);
+    }
+    let snapshot: Vec<OvsDbSnapshotRow> =
+        serde_json::from_str(&snapshot_read(&path)?)?;
+    let updates: Vec<OvsDbUpdate> =
+        snapshot.iter().map(OvsDbUpdate::from).collect();
+    // Rows created after snapshot are removed by NetworkManager rollback,
+    // the update on rows not exist any more is no-op.
+    let mut cli = OvsDbConnection::new()?;
+    cli.apply_updates(updates.as_slice())?;
+    log::info!("Restored OVSDB snapshot of checkpoint {}", checkpoint);
+    snapshot_remove(&path)
+}
+
+pub(crate) fn ovsdb_checkpoint_destroy(
+    checkpoint: &str,
+) -> Result<(), NmstateError> {
+    match snapshot_get_path(OVSDB_CHECKPOINT_DIR, checkpoint) {
+        Some(path) => snapshot_remove(&path),
+        None => Ok(()),
+    }
+}
@@ -1,24 +1,31 @@
 use std::collections::HashMap;
+#[cfg(feature = "query_apply")]
 use std::convert::TryFrom;
+#[cfg(feature = "query_apply")]
 use std::convert::TryInto;
 
 use serde_json::{Map, Value};
 
+#[cfg(feature = "query_apply")]
 use crate::{
     ovsdb::json_rpc::OvsDbJsonRpc, ErrorKind, NmstateError, OvsDbGlobalConfig,
 };
 
-const OVS_DB_NAME: &str = "Open_vSwitch";
+pub(crate) const OVS_DB_NAME: &str = "Open_vSwitch";
 pub(crate) const GLOBAL_CONFIG_TABLE: &str = "Open_vSwitch";
+#[cfg(feature = "query_apply")]
 const NM_RESERVED_EXTERNAL_ID: &str = "NM.connection.uuid";
 
+#[cfg(feature = "query_apply")]
 const DEFAULT_OVS_DB_SOCKET_PATH: &str = "/run/openvswitch/db.sock";
 
+#[cfg(feature = "query_apply")]
 #[derive(Debug)]
 pub(crate) struct OvsDbConnection {
     rpc: OvsDbJsonRpc,
 }
 
+#[cfg(feature = "query_apply")]
 #[derive(Debug, Clone, Default, PartialEq, Eq)]
 pub(crate) struct OvsDbSelect {
     table: String,
@@ -34,6 +41,15 @@ pub(crate) struct OvsDbCondition {
 }
 
 impl OvsDbCondition {
+    #[cfg(any(feature = "query_apply", feature = "gen_conf"))]
+    pub(crate) fn new(column: &str, function: &str, value: Value) -> Self {
+        Self {
+            column: column.to_string(),
+            function: function.to_string(),
+            value,
+        }
+    }
+
     fn to_value(&self) -> Value {
         Value::Array(vec![
             Value::String(self.column.to_string()),
@@ -43,6 +59,7 @@ impl OvsDbCondition {
     }
 }
 
+#[cfg(feature = "query_apply")]
 impl OvsDbSelect {
     fn to_value(&self) -> Value {
         let mut ret = Map::new();
@@ -67,6 +84,7 @@ impl OvsDbSelect {
     }
 }
 
+#[cfg(feature = "query_apply")]
 impl OvsDbConnection {
     // TODO: support environment variable OVS_DB_UNIX_SOCKET_PATH
     pub(crate) fn new() -> Result<Self, NmstateError> {
@@ -204,6 +222,56 @@ impl OvsDbConnection {
             }
         }
     }
+
+    // Query specified columns of all rows in specified table.
+    pub(crate) fn get_rows(
+        &mut self,
+        table_name: &str,
+        columns: &[&'static str],
+    ) -> Result<Vec<Map<String, Value>>, NmstateError> {
+        let select = OvsDbSelect {
+            table: table_name.to_string(),
+            conditions: vec![],
+            columns: Some(columns.to_vec()),
+        };
+        let reply = self.rpc.exec(
+            "transact",
+            &Value::Array(vec![
+                Value::String(OVS_DB_NAME.to_string()),
+                select.to_value(),
+            ]),
+        )?;
+        if let Some(rows) = reply
+            .as_array()
+            .and_then(|v| v.first())
+            .and_then(|v| v.as_object())
+            .and_then(|v| v.get("rows"))
+            .and_then(|v| v.as_array())
+        {
+            Ok(rows.iter().filter_map(|r| r.as_object()).cloned().collect())
+        } else {
+            let e = NmstateError::new(
+                ErrorKind::PluginFailure,
+                format!(
+                    "Invalid reply from OVSDB for querying {} table: {:?}",
+                    table_name, reply
+                ),
+            );
+            log::error!("{}", e);
+            Err(e)
+        }
+    }
+
+    pub(crate) fn apply_updates(
+        &mut self,
+        updates: &[OvsDbUpdate],
+    ) -> Result<(), NmstateError> {
+        let mut params = vec![Value::String(OVS_DB_NAME.to_string())];
+        params.extend(updates.iter().map(|u| u.to_value()));
+        self.rpc.exec("transact", &Value::Array(params))?;
+        Ok(())
+    }
+
     pub(crate) fn apply_global_conf(
         &mut self,
         ovs_conf: &OvsDbGlobalConfig,
@@ -220,12 +288,14 @@ impl OvsDbConnection {
     }
 }
 
+#[cfg(feature = "query_apply")]
 #[derive(Debug, Default)]
 pub(crate) struct OvsDbIface {
     pub(crate) name: String,
     pub(crate) external_ids: HashMap<String, String>,
 }
 
+#[cfg(feature = "query_apply")]
 impl TryFrom<&Value> for OvsDbIface {
     type Error = NmstateError;
     fn try_from(v: &Value) -> Result<OvsDbIface, Self::Error> {
@@ -248,6 +318,7 @@ impl TryFrom<&Value> for OvsDbIface {
     }
 }
 
+#[cfg(feature = "query_apply")]
 pub(crate) fn parse_str_map(v: &[Value]) -> HashMap<String, String> {
     let mut ret = HashMap::new();
     if let Some(ids) = v.get(1).and_then(|i| i.as_array()) {
@@ -275,7 +346,7 @@ pub(crate) struct OvsDbUpdate {
 }
 
 impl OvsDbUpdate {
-    fn to_value(&self) -> Value {
+    pub(crate) fn to_value(&self) -> Value {
         let mut ret = Map::new();
         ret.insert("op".to_string(), Value::String("update".to_string()));
         ret.insert("table".to_string(), Value::String(self.table.clone()));
@@ -290,3 +361,73 @@ impl OvsDbUpdate {
         Value::Object(ret)
     }
 }
+
+// Unlike `OvsDbUpdate` replacing the whole column, the `mutate` operation
+// changes the map or set column in place, so that keys owned by others are
+// preserved.
+#[cfg(feature = "gen_conf")]
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub(crate) struct OvsDbMutate {
+    pub(crate) table: String,
+    pub(crate) conditions: Vec<OvsDbCondition>,
+    // Column, mutator and value, applied in order
+    pub(crate) mutations: Vec<(String, String, Value)>,
+}
+
+#[cfg(feature = "gen_conf")]
+impl OvsDbMutate {
+    pub(crate) fn to_value(&self) -> Value {
+        let mut ret = Map::new();
+        ret.insert("op".to_string(), Value::String("mutate".to_string()));
+        ret.insert("table".to_string(), Value::String(self.table.clone()));
+        let condition_values: Vec<Value> =
+            self.conditions.iter().map(|c| c.to_value()).collect();
+        ret.insert("where".to_string(), Value::Array(condition_values));
+        let mutation_values: Vec<Value> = self
+            .mutations
+            .iter()
+            .map(|(column, mutator, value)| {
+                Value::Array(vec![
+                    Value::String(column.to_string()),
+                    Value::String(mutator.to_string()),
+                    value.clone(),
+                ])
+            })
+            .collect();
+        ret.insert("mutations".to_string(), Value::Array(mutation_values));
+        Value::Object(ret)
+    }
+}
+
+// Convert string map to the OVSDB `map` type, sorted for stable output.
+pub(crate) fn str_map_to_value(m: &HashMap<&str, &str>) -> Value {
+    let mut pairs: Vec<(&&str, &&str)> = m.iter().collect();
+    pairs.sort_unstable();
+    Value::Array(vec![
+        Value::String("map".to_string()),
+        Value::Array(
+            pairs
+                .iter()
+                .map(|(k, v)| {
+                    Value::Array(vec![
+                        Value::String(k.to_string()),
+                        Value::String(v.to_string()),
+                    ])
+                })
+                .collect(),
+        ),
+    ])
+}
+
+// Convert strings to the OVSDB `set` type, sorted for stable output.
+#[cfg(feature = "gen_conf")]
+pub(crate) fn str_set_to_value(items: &[&str]) -> Value {
+    let mut items = items.to_vec();
+    items.sort_unstable();
+    Value::Array(vec![
+        Value::String("set".to_string()),
+        Value::Array(
+            items.iter().map(|i| Value::String(i.to_string())).collect(),
+        ),
+    ])
+}
@@ -0,0 +1,104 @@
+use serde_json::Value;
+
+use crate::{
+    ovsdb::db::{
+        str_map_to_value, str_set_to_value, OvsDbCondition, OvsDbMutate,
+        OvsDbUpdate, OVS_DB_NAME,
+    },
+    ErrorKind, Interface, InterfaceType, NetworkState, NmstateError,
+};
+
+const OVSDB_TRANSACT_FILE_NAME: &str = "ovsdb_transact.json";
+
+// Generate the parameters of OVSDB `transact` method holding the global
+// `ovs-db` configuration and the external_ids of OVS bridges and interfaces.
+// It can be applied by `ovsdb-client transact "$(cat ovsdb_transact.json)"`
+// once the OVS bridges and interfaces are created.
+pub(crate) fn ovsdb_gen_conf(
+    net_state: &NetworkState,
+) -> Result<Vec<(String, String)>, NmstateError> {
+    let mut ops = Vec::new();
+    if !net_state.ovsdb.is_none() {
+        let mut update: OvsDbUpdate = (&net_state.ovsdb).into();
+        // Columns not mentioned in desire state are left untouched.
+        if net_state.ovsdb.external_ids.is_none() {
+            update.row.remove("external_ids");
+        }
+        if net_state.ovsdb.other_config.is_none() {
+            update.row.remove("other_config");
+        }
+        ops.push(update.to_value());
+    }
+
+    for iface in net_state.interfaces.to_vec() {
+        let ovsdb_conf = match iface.base_iface().ovsdb.as_ref() {
+            Some(c) if iface.is_up() => c,
+            _ => continue,
+        };
+        let table = match iface {
+            Interface::OvsBridge(_) => "Bridge",
+            Interface::OvsInterface(_) => "Interface",
+            _ if iface.base_iface().controller_type
+                == Some(InterfaceType::OvsBridge) =>
+            {
+                "Interface"
+            }
+            _ => {
+                log::warn!(
+                    "Property interfaces.{}.ovs-db is only supported by OVS \
+                    bridge and its ports, ignoring",
+                    iface.name()
+                );
+                continue;
+            }
+        };
+        // NetworkManager stores its connection UUID in `external_ids` of the
+        // same row, hence only the desired keys are removed and inserted
+        // instead of replacing the whole map. Keys not mentioned in desire
+        // state are left untouched.
+        let keys: Vec<&str> = ovsdb_conf
+            .external_ids
+            .as_ref()
+            .map(|eids| eids.keys().map(|k| k.as_str()).collect())
+            .unwrap_or_default();
+        if keys.is_empty() {
+            continue;
+        }
+        ops.push(
+            OvsDbMutate {
+                table: table.to_string(),
+                conditions: vec![OvsDbCondition::new(
+                    "name",
+                    "==",
+                    Value::String(iface.name().to_string()),
+                )],
+                mutations: vec![
+                    (
+                        "external_ids".to_string(),
+                        "delete".to_string(),
+                        str_set_to_value(keys.as_slice()),
+                    ),
+                    (
+                        "external_ids".to_string(),
+                        "insert".to_string(),
+                        str_map_to_value(&ovsdb_conf.get_external_ids()),
+                    ),
+                ],
+            }
+            .to_value(),
+        );
+    }
+
+    if ops.is_empty() {
+        return Ok(Vec::new());
+    }
+    ops.insert(0, Value::String(OVS_DB_NAME.to_string()));
+    let content =
+        serde_json::to_string_pretty(&Value::Array(ops)).map_err(|e| {
+            NmstateError::new(
+                ErrorKind::Bug,
+                format!("Failed to generate OVSDB transaction: {}", e),
+            )
+        })?;
+    Ok(vec![(OVSDB_TRANSACT_FILE_NAME.to_string(), content + "\n")])
+}
@@ -1,12 +1,16 @@
 use std::collections::HashMap;
 
+#[cfg(feature = "query_apply")]
 use serde_json::{Map, Value};
 
+#[cfg(feature = "query_apply")]
+use crate::ovsdb::db::parse_str_map;
 use crate::{
-    ovsdb::db::{parse_str_map, OvsDbUpdate, GLOBAL_CONFIG_TABLE},
+    ovsdb::db::{str_map_to_value, OvsDbUpdate, GLOBAL_CONFIG_TABLE},
     OvsDbGlobalConfig,
 };
 
+#[cfg(feature = "query_apply")]
 impl From<&Map<std::string::String, Value>> for OvsDbGlobalConfig {
     fn from(m: &Map<std::string::String, Value>) -> Self {
         let mut ret = Self::default();
@@ -21,6 +25,7 @@ impl From<&Map<std::string::String, Value>> for OvsDbGlobalConfig {
 }
 
 // Convert HashMap<String, String> to HashMap<String, Option<String>>
+#[cfg(feature = "query_apply")]
 fn convert_map(
     mut m: HashMap<String, String>,
 ) -> HashMap<String, Option<String>> {
@@ -34,33 +39,13 @@ fn convert_map(
 impl From<&OvsDbGlobalConfig> for OvsDbUpdate {
     fn from(ovs_conf: &OvsDbGlobalConfig) -> Self {
         let mut row = HashMap::new();
-        let mut value_array = Vec::new();
-        for (k, v) in ovs_conf.get_external_ids().iter() {
-            value_array.push(Value::Array(vec![
-                Value::String(k.to_string()),
-                Value::String(v.to_string()),
-            ]));
-        }
         row.insert(
             "external_ids".to_string(),
-            Value::Array(vec![
-                Value::String("map".to_string()),
-                Value::Array(value_array),
-            ]),
+            str_map_to_value(&ovs_conf.get_external_ids()),
         );
-        let mut value_array = Vec::new();
-        for (k, v) in ovs_conf.get_other_config().iter() {
-            value_array.push(Value::Array(vec![
-                Value::String(k.to_string()),
-                Value::String(v.to_string()),
-            ]));
-        }
         row.insert(
             "other_config".to_string(),
-            Value::Array(vec![
-                Value::String("map".to_string()),
-                Value::Array(value_array),
-            ]),
+            str_map_to_value(&ovs_conf.get_other_config()),
         );
 
         OvsDbUpdate {
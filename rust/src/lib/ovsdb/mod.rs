@@ -1,9 +1,30 @@
+#[cfg(feature = "query_apply")]
 mod apply;
+#[cfg(feature = "query_apply")]
+mod checkpoint;
 mod db;
+#[cfg(feature = "gen_conf")]
+mod gen_conf;
 mod global_conf;
+#[cfg(feature = "query_apply")]
 mod json_rpc;
+#[cfg(feature = "query_apply")]
 mod show;
 
+#[cfg(feature = "query_apply")]
 pub(crate) use apply::ovsdb_apply;
+#[cfg(feature = "query_apply")]
+pub(crate) use checkpoint::{
+    ovsdb_checkpoint_create, ovsdb_checkpoint_destroy,
+    ovsdb_checkpoint_rollback,
+};
+#[cfg(all(test, feature = "query_apply"))]
+pub(crate) use checkpoint::OvsDbSnapshotRow;
+#[cfg(all(test, feature = "query_apply"))]
+pub(crate) use db::OvsDbUpdate;
+#[cfg(feature = "gen_conf")]
+pub(crate) use gen_conf::ovsdb_gen_conf;
+#[cfg(feature = "query_apply")]
 pub(crate) use show::ovsdb_is_running;
+#[cfg(feature = "query_apply")]
 pub(crate) use show::ovsdb_retrieve;
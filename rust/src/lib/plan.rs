@@ -0,0 +1,82 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    DnsClientState, Interface, InterfaceType, OvsDbGlobalConfig, RouteEntry,
+    RouteRuleEntry,
+};
+
+// The changes `NetworkState::apply()` would do, generated by
+// `NetworkState::plan()` without touching the system.
+#[derive(Debug, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+#[non_exhaustive]
+pub struct NetworkStatePlan {
+    #[serde(skip_serializing_if = "Vec::is_empty", default)]
+    pub add_interfaces: Vec<Interface>,
+    #[serde(skip_serializing_if = "Vec::is_empty", default)]
+    pub change_interfaces: Vec<Interface>,
+    #[serde(skip_serializing_if = "Vec::is_empty", default)]
+    pub delete_interfaces: Vec<PlanProfile>,
+    // Profiles of interfaces been brought up, empty in kernel only mode.
+    #[serde(skip_serializing_if = "Vec::is_empty", default)]
+    pub activate_profiles: Vec<PlanProfile>,
+    // Profiles of interfaces been brought down, empty in kernel only mode.
+    #[serde(skip_serializing_if = "Vec::is_empty", default)]
+    pub deactivate_profiles: Vec<PlanProfile>,
+    #[serde(skip_serializing_if = "Vec::is_empty", default)]
+    pub add_routes: Vec<RouteEntry>,
+    #[serde(skip_serializing_if = "Vec::is_empty", default)]
+    pub remove_routes: Vec<RouteEntry>,
+    #[serde(skip_serializing_if = "Vec::is_empty", default)]
+    pub add_route_rules: Vec<RouteRuleEntry>,
+    #[serde(skip_serializing_if = "Vec::is_empty", default)]
+    pub remove_route_rules: Vec<RouteRuleEntry>,
+    // Desired DNS configuration when changed.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub dns: Option<DnsClientState>,
+    // Desired static hostname when changed.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub hostname: Option<String>,
+    // Desired OVSDB global configuration when changed.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ovsdb: Option<OvsDbGlobalConfig>,
+}
+
+impl NetworkStatePlan {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self == &Self::default()
+    }
+
+    pub fn hide_secrets(&mut self) {
+        for iface in self
+            .add_interfaces
+            .iter_mut()
+            .chain(self.change_interfaces.iter_mut())
+        {
+            iface.base_iface_mut().hide_secrets();
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[non_exhaustive]
+pub struct PlanProfile {
+    pub name: String,
+    #[serde(rename = "type")]
+    pub iface_type: InterfaceType,
+}
+
+impl From<&Interface> for PlanProfile {
+    fn from(iface: &Interface) -> Self {
+        Self {
+            name: iface.name().to_string(),
+            iface_type: iface.iface_type(),
+        }
+    }
+}
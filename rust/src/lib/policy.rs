@@ -0,0 +1,303 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::{ErrorKind, NetworkState, NmstateError};
+
+const CAPTURE_PREFIX: &str = "capture.";
+const TEMPLATE_START: &str = "{{";
+const TEMPLATE_END: &str = "}}";
+
+// Network policy holding a `capture` section selecting properties from
+// current network state and a `desired-state` section which could reference
+// captured values using `{{ capture.<name>.<path> }}` templates.
+//
+// Each capture is an expression like
+// `routes.running.destination=="0.0.0.0/0"` which keeps the list entries
+// matching the value. The value could also be a reference to other capture,
+// for example
+// `interfaces.name==capture.gw.routes.running.0.next-hop-interface`.
+#[derive(Debug, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case", deny_unknown_fields)]
+#[non_exhaustive]
+pub struct NetworkPolicy {
+    #[serde(default)]
+    pub capture: HashMap<String, String>,
+    #[serde(default)]
+    pub desired_state: Value,
+}
+
+impl NetworkPolicy {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl NetworkState {
+    // Render the network policy against specified current network state.
+    pub fn new_from_policy(
+        policy: &NetworkPolicy,
+        current: &Self,
+    ) -> Result<Self, NmstateError> {
+        let mut captures = PolicyCaptures::new(
+            &policy.capture,
+            serde_json::to_value(current)?,
+        );
+        // Evaluate all captures so invalid ones are reported even not used
+        for name in policy.capture.keys() {
+            captures.get(name)?;
+        }
+        let desired = render_template(&policy.desired_state, &mut captures)?;
+        if desired.is_null() {
+            return Ok(Self::new());
+        }
+        match serde_json::from_value(desired) {
+            Ok(s) => Ok(s),
+            Err(e) => Err(NmstateError::new(
+                ErrorKind::InvalidArgument,
+                format!("Invalid desired state rendered from policy: {}", e),
+            )),
+        }
+    }
+}
+
+struct PolicyCaptures<'a> {
+    exprs: &'a HashMap<String, String>,
+    current: Value,
+    results: HashMap<String, Value>,
+    // Captures been resolved, used to detect circular reference.
+    resolving: Vec<String>,
+}
+
+impl<'a> PolicyCaptures<'a> {
+    fn new(exprs: &'a HashMap<String, String>, current: Value) -> Self {
+        Self {
+            exprs,
+            current,
+            results: HashMap::new(),
+            resolving: Vec::new(),
+        }
+    }
+
+    fn get(&mut self, name: &str) -> Result<Value, NmstateError> {
+        if let Some(result) = self.results.get(name) {
+            return Ok(result.clone());
+        }
+        let exprs = self.exprs;
+        let expr = match exprs.get(name) {
+            Some(e) => e,
+            None => {
+                return Err(NmstateError::new(
+                    ErrorKind::InvalidArgument,
+                    format!("Capture {} is not defined", name),
+                ));
+            }
+        };
+        if self.resolving.iter().any(|n| n == name) {
+            return Err(NmstateError::new(
+                ErrorKind::InvalidArgument,
+                format!(
+                    "Circular reference in capture: {} -> {}",
+                    self.resolving.join(" -> "),
+                    name
+                ),
+            ));
+        }
+        self.resolving.push(name.to_string());
+        let result = self.eval(expr);
+        self.resolving.pop();
+        let result = result?;
+        self.results.insert(name.to_string(), result.clone());
+        Ok(result)
+    }
+
+    fn eval(&mut self, expr: &str) -> Result<Value, NmstateError> {
+        let (path, value) = match expr.split_once("==") {
+            Some((p, v)) => (p.trim(), v.trim()),
+            None => {
+                return Err(NmstateError::new(
+                    ErrorKind::InvalidArgument,
+                    format!(
+                        "Invalid capture expression '{}', expecting \
+                        '<path>==<value>'",
+                        expr
+                    ),
+                ));
+            }
+        };
+        let value = if value.starts_with(CAPTURE_PREFIX) {
+            self.resolve(value)?
+        } else {
+            parse_literal(value)?
+        };
+        let path: Vec<&str> = path.split('.').collect();
+        Ok(filter_value(&self.current, path.as_slice(), &value))
+    }
+
+    // Resolve reference like `capture.<name>.<path>`
+    fn resolve(&mut self, reference: &str) -> Result<Value, NmstateError> {
+        let mut path = match reference.strip_prefix(CAPTURE_PREFIX) {
+            Some(p) => p.split('.'),
+            None => {
+                return Err(NmstateError::new(
+                    ErrorKind::InvalidArgument,
+                    format!(
+                        "Invalid capture reference '{}', expecting \
+                        'capture.<name>.<path>'",
+                        reference
+                    ),
+                ));
+            }
+        };
+        let name = path.next().unwrap_or_default();
+        let captured = self.get(name)?;
+        let mut value = &captured;
+        for key in path {
+            let next_value = match value {
+                Value::Object(m) => m.get(key),
+                Value::Array(items) => {
+                    key.parse::<usize>().ok().and_then(|i| items.get(i))
+                }
+                _ => None,
+            };
+            value = match next_value {
+                Some(v) => v,
+                None => {
+                    return Err(NmstateError::new(
+                        ErrorKind::InvalidArgument,
+                        format!(
+                            "Capture reference '{}' not found: no '{}' in \
+                            {}",
+                            reference, key, value
+                        ),
+                    ));
+                }
+            };
+        }
+        Ok(value.clone())
+    }
+}
+
+fn parse_literal(value: &str) -> Result<Value, NmstateError> {
+    if let Some(s) = value.strip_prefix('"').and_then(|s| s.strip_suffix('"')) {
+        Ok(Value::String(s.to_string()))
+    } else if let Ok(v) = serde_json::from_str::<Value>(value) {
+        Ok(v)
+    } else {
+        Err(NmstateError::new(
+            ErrorKind::InvalidArgument,
+            format!(
+                "Invalid value '{}' in capture expression, string should \
+                be double quoted",
+                value
+            ),
+        ))
+    }
+}
+
+// Walk down the object by path, the first list found will be filtered to
+// only contain entries with specified value at the rest of the path.
+fn filter_value(current: &Value, path: &[&str], value: &Value) -> Value {
+    match current {
+        Value::Object(m) => {
+            let (key, rest) = match path.split_first() {
+                Some(p) => p,
+                None => return Value::Null,
+            };
+            let mut ret = serde_json::Map::new();
+            if let Some(v) = m.get(*key) {
+                ret.insert(key.to_string(), filter_value(v, rest, value));
+            }
+            Value::Object(ret)
+        }
+        Value::Array(items) => Value::Array(
+            items
+                .iter()
+                .filter(|i| is_value_match(i, path, value))
+                .cloned()
+                .collect(),
+        ),
+        _ => Value::Null,
+    }
+}
+
+fn is_value_match(current: &Value, path: &[&str], value: &Value) -> bool {
+    match (current, path.split_first()) {
+        (Value::Array(items), _) => {
+            items.iter().any(|i| is_value_match(i, path, value))
+        }
+        (Value::Object(m), Some((key, rest))) => m
+            .get(*key)
+            .map(|v| is_value_match(v, rest, value))
+            .unwrap_or_default(),
+        (_, None) => current == value,
+        _ => false,
+    }
+}
+
+fn render_template(
+    template: &Value,
+    captures: &mut PolicyCaptures,
+) -> Result<Value, NmstateError> {
+    Ok(match template {
+        Value::Object(m) => {
+            let mut ret = serde_json::Map::new();
+            for (key, value) in m.iter() {
+                ret.insert(key.to_string(), render_template(value, captures)?);
+            }
+            Value::Object(ret)
+        }
+        Value::Array(items) => {
+            let mut ret = Vec::new();
+            for item in items {
+                ret.push(render_template(item, captures)?);
+            }
+            Value::Array(ret)
+        }
+        Value::String(s) => render_string(s, captures)?,
+        _ => template.clone(),
+    })
+}
+
+// String only holding a single template is replaced by the captured value
+// as it is, so non-string properties like `mtu` could be templated also.
+fn render_string(
+    template: &str,
+    captures: &mut PolicyCaptures,
+) -> Result<Value, NmstateError> {
+    let trimmed = template.trim();
+    if let Some(reference) = trimmed
+        .strip_prefix(TEMPLATE_START)
+        .and_then(|s| s.strip_suffix(TEMPLATE_END))
+    {
+        if !reference.contains(TEMPLATE_START) {
+            return captures.resolve(reference.trim());
+        }
+    }
+
+    let mut ret = String::new();
+    let mut remains = template;
+    while let Some(start) = remains.find(TEMPLATE_START) {
+        ret += &remains[..start];
+        remains = &remains[start + TEMPLATE_START.len()..];
+        let end = match remains.find(TEMPLATE_END) {
+            Some(e) => e,
+            None => {
+                return Err(NmstateError::new(
+                    ErrorKind::InvalidArgument,
+                    format!("Unclosed template in '{}'", template),
+                ));
+            }
+        };
+        match captures.resolve(remains[..end].trim())? {
+            Value::String(s) => ret += &s,
+            v => ret += &v.to_string(),
+        }
+        remains = &remains[end + TEMPLATE_END.len()..];
+    }
+    ret += remains;
+    Ok(Value::String(ret))
+}
@@ -0,0 +1,65 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use std::net::IpAddr;
+
+use serde::{Deserialize, Serialize};
+
+// Connectivity check to run after verification of desired state, any failure
+// will rollback the changes.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(tag = "type", rename_all = "kebab-case")]
+#[non_exhaustive]
+pub enum NetworkProbe {
+    // ICMP echo to specified address
+    Ping(PingProbe),
+    // TCP connection to specified address and port
+    Tcp(TcpProbe),
+    // Resolve specified DNS name
+    Dns(DnsProbe),
+}
+
+impl NetworkProbe {
+    pub(crate) const DEFAULT_TIMEOUT: u32 = 5;
+
+    // Timeout in seconds
+    pub fn timeout(&self) -> u32 {
+        match self {
+            Self::Ping(p) => p.timeout,
+            Self::Tcp(p) => p.timeout,
+            Self::Dns(p) => p.timeout,
+        }
+        .unwrap_or(Self::DEFAULT_TIMEOUT)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[non_exhaustive]
+#[serde(deny_unknown_fields)]
+pub struct PingProbe {
+    pub address: IpAddr,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub timeout: Option<u32>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[non_exhaustive]
+#[serde(deny_unknown_fields)]
+pub struct TcpProbe {
+    pub address: IpAddr,
+    pub port: u16,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub timeout: Option<u32>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[non_exhaustive]
+#[serde(deny_unknown_fields)]
+pub struct DnsProbe {
+    pub name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub timeout: Option<u32>,
+}
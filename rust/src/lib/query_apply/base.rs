@@ -1,7 +1,8 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::{
-    query_apply::mptcp::mptcp_pre_verify_cleanup, BaseInterface, InterfaceType,
+    query_apply::mptcp::mptcp_pre_verify_cleanup, BaseInterface,
+    InterfaceIdentifier, InterfaceType,
 };
 
 impl BaseInterface {
@@ -29,6 +30,18 @@ impl BaseInterface {
         if other.prop_list.contains(&"max_mtu") {
             self.max_mtu = other.max_mtu;
         }
+        if other.prop_list.contains(&"driver") {
+            self.driver = other.driver.clone();
+        }
+        if other.prop_list.contains(&"identifier") {
+            self.identifier = other.identifier;
+        }
+        if other.prop_list.contains(&"pci_address") {
+            self.pci_address = other.pci_address.clone();
+        }
+        if other.profile_name.is_some() {
+            self.profile_name = other.profile_name.clone();
+        }
         if other.prop_list.contains(&"controller") {
             self.controller = other.controller.clone();
         }
@@ -90,9 +103,17 @@ impl BaseInterface {
         &mut self,
         pre_apply_current: Option<&Self>,
     ) {
-        // Ignore min_mtu and max_mtu as they are not changeable
+        // Ignore min_mtu, max_mtu and driver as they are not changeable
         self.min_mtu = None;
         self.max_mtu = None;
+        self.driver = None;
+        // The identifier properties has been resolved to interface name.
+        // The MAC address used as identifier is permanent MAC address which
+        // might differ from current MAC address.
+        if self.identifier == Some(InterfaceIdentifier::MacAddress) {
+            self.mac_address = None;
+        }
+        self.identifier = None;
         // * If cannot have IP, set ip: none
         if !self.can_have_ip() {
             self.ipv4 = None;
@@ -2,10 +2,13 @@
 
 use std::net::{Ipv4Addr, Ipv6Addr};
 
-use crate::{ip::is_ipv6_addr, DnsState, ErrorKind, NmstateError};
+use crate::{
+    ip::is_ipv6_addr, DnsState, ErrorKind, NmstateError, VerificationDifference,
+};
 
 impl DnsState {
     pub(crate) fn verify(&self, current: &Self) -> Result<(), NmstateError> {
+        let mut errors: Vec<NmstateError> = Vec::new();
         if let Some(conf) = self.config.as_ref() {
             if let Some(srvs) = conf.server.as_ref() {
                 let cur_conf = current.config.as_ref().ok_or_else(|| {
@@ -32,14 +35,18 @@ impl DnsState {
                 if cur_conf.server != Some(canonicalized_srvs)
                     && !(cur_conf.server.is_none() && srvs.is_empty())
                 {
-                    return Err(NmstateError::new(
-                        ErrorKind::VerificationError,
+                    errors.push(NmstateError::new_verification_error(
                         format!(
                             "Failed to apply DNS config: desire name servers \
                             {:?}, got {:?}",
                             srvs,
                             cur_conf.server.as_ref()
                         ),
+                        vec![VerificationDifference::new(
+                            "dns-resolver.config.server".to_string(),
+                            serde_json::to_value(srvs)?,
+                            serde_json::to_value(&cur_conf.server)?,
+                        )],
                     ));
                 }
             }
@@ -56,18 +63,22 @@ impl DnsState {
                 if cur_conf.search != Some(schs.to_vec())
                     && !(cur_conf.search.is_none() && schs.is_empty())
                 {
-                    return Err(NmstateError::new(
-                        ErrorKind::VerificationError,
+                    errors.push(NmstateError::new_verification_error(
                         format!(
                             "Failed to apply DNS config: desire searches \
                             {:?}, got {:?}",
                             schs,
                             cur_conf.search.as_ref()
                         ),
+                        vec![VerificationDifference::new(
+                            "dns-resolver.config.search".to_string(),
+                            serde_json::to_value(schs)?,
+                            serde_json::to_value(&cur_conf.search)?,
+                        )],
                     ));
                 }
             }
         }
-        Ok(())
+        NmstateError::merge_verification_errors(errors)
     }
 }
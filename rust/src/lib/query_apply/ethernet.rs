@@ -2,10 +2,19 @@
 
 use crate::{
     EthernetConfig, EthernetInterface, InterfaceType, Interfaces, NmstateError,
-    VethConfig,
+    SrIovConfig, VethConfig,
 };
 
 impl EthernetInterface {
+    pub(crate) fn sriov_is_enabled(&self) -> bool {
+        self.ethernet
+            .as_ref()
+            .and_then(|eth_conf| {
+                eth_conf.sr_iov.as_ref().map(SrIovConfig::sriov_is_enabled)
+            })
+            .unwrap_or_default()
+    }
+
     pub(crate) fn update_ethernet(&mut self, other: &EthernetInterface) {
         if let Some(eth_conf) = &mut self.ethernet {
             eth_conf.update(other.ethernet.as_ref())
@@ -1,6 +1,13 @@
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::{ErrorKind, HostNameState, NmstateError};
+use crate::{
+    nispor::{get_hostname_state, set_config_hostname, set_running_hostname},
+    nm::nm_set_config_hostname,
+    snapshot::{
+        snapshot_get_path, snapshot_read, snapshot_remove, snapshot_save,
+    },
+    ErrorKind, HostNameState, NmstateError, VerificationDifference,
+};
 
 impl HostNameState {
     pub(crate) fn update(&mut self, other: &Self) {
@@ -28,36 +35,112 @@ impl HostNameState {
             return Err(e);
         };
 
+        let mut errors: Vec<NmstateError> = Vec::new();
         if let Some(running) = self.running.as_ref() {
             if Some(running) != current.running.as_ref() {
-                let e = NmstateError::new(
-                    ErrorKind::VerificationError,
+                let e = NmstateError::new_verification_error(
                     format!(
                         "Verification fail, desire hostname.running: \
                         {}, current: {:?}",
                         running,
                         current.running.as_ref()
                     ),
+                    vec![VerificationDifference::new(
+                        "hostname.running".to_string(),
+                        serde_json::to_value(running)?,
+                        serde_json::to_value(&current.running)?,
+                    )],
                 );
                 log::error!("{}", e);
-                return Err(e);
+                errors.push(e);
             }
         }
         if let Some(config) = self.config.as_ref() {
             if Some(config) != current.config.as_ref() {
-                let e = NmstateError::new(
-                    ErrorKind::VerificationError,
+                let e = NmstateError::new_verification_error(
                     format!(
                         "Verification fail, desire hostname.config: \
                         {}, current: {:?}",
                         config,
                         current.config.as_ref()
                     ),
+                    vec![VerificationDifference::new(
+                        "hostname.config".to_string(),
+                        serde_json::to_value(config)?,
+                        serde_json::to_value(&current.config)?,
+                    )],
                 );
                 log::error!("{}", e);
-                return Err(e);
+                errors.push(e);
+            }
+        }
+        NmstateError::merge_verification_errors(errors)
+    }
+
+    // Restore the hostname to the pre-apply state stored in `self`. The
+    // config hostname is changed via NetworkManager unless in kernel only
+    // mode.
+    pub(crate) fn restore(
+        &self,
+        kernel_only: bool,
+    ) -> Result<(), NmstateError> {
+        let current = get_hostname_state().unwrap_or_default();
+        if let Some(running) = self.running.as_ref() {
+            if !running.is_empty() && Some(running) != current.running.as_ref()
+            {
+                log::info!("Restoring running hostname to {}", running);
+                set_running_hostname(running)?;
+            }
+        }
+        if let Some(config) = self.config.as_ref() {
+            if Some(config) != current.config.as_ref() {
+                log::info!("Restoring config hostname to {:?}", config);
+                if kernel_only {
+                    set_config_hostname(config)?;
+                } else {
+                    nm_set_config_hostname(config)?;
+                }
             }
         }
         Ok(())
     }
 }
+
+// Snapshot of the pre-apply hostname
+const HOSTNAME_CHECKPOINT_DIR: &str = "/run/nmstate/hostname_checkpoint";
+
+pub(crate) fn hostname_checkpoint_create(
+    checkpoint: &str,
+    pre_apply: &HostNameState,
+) -> Result<(), NmstateError> {
+    snapshot_save(
+        HOSTNAME_CHECKPOINT_DIR,
+        checkpoint,
+        &serde_json::to_string(pre_apply)?,
+    )?;
+    log::info!("Stored hostname of checkpoint {}", checkpoint);
+    Ok(())
+}
+
+pub(crate) fn hostname_checkpoint_rollback(
+    checkpoint: &str,
+) -> Result<(), NmstateError> {
+    let path = match snapshot_get_path(HOSTNAME_CHECKPOINT_DIR, checkpoint) {
+        Some(p) => p,
+        None => return Ok(()),
+    };
+    let pre_apply: HostNameState =
+        serde_json::from_str(&snapshot_read(&path)?)?;
+    pre_apply.restore(false)?;
+    log::info!("Restored hostname of checkpoint {}", checkpoint);
+    snapshot_remove(&path)
+}
+
+pub(crate) fn hostname_checkpoint_destroy(
+    checkpoint: &str,
+) -> Result<(), NmstateError> {
+    match snapshot_get_path(HOSTNAME_CHECKPOINT_DIR, checkpoint) {
+        Some(path) => snapshot_remove(&path),
+        None => Ok(()),
+    }
+}
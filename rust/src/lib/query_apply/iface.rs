@@ -1,8 +1,11 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::{
-    state::get_json_value_difference, ErrorKind, Interface, InterfaceType,
-    LinuxBridgeInterface, NmstateError,
+    state::{
+        gen_verification_differences, gen_verification_error,
+        get_json_value_differences,
+    },
+    Interface, InterfaceType, LinuxBridgeInterface, NmstateError,
 };
 
 impl Interface {
@@ -70,13 +73,19 @@ impl Interface {
         let self_value = serde_json::to_value(&self_clone)?;
         let current_value = serde_json::to_value(&current_clone)?;
 
-        if let Some((reference, desire, current)) = get_json_value_difference(
+        let differences = get_json_value_differences(
             format!("{}.interface", self.name()),
             &self_value,
             &current_value,
-        ) {
-            // Linux Bridge on 250 kernel HZ and 100 user HZ system(e.g.
-            // Ubuntu) will have round up which lead to 1 difference.
+        );
+        if differences.is_empty() {
+            return Ok(());
+        }
+        // Linux Bridge on 250 kernel HZ and 100 user HZ system(e.g.
+        // Ubuntu) will have round up which lead to 1 difference.
+        // Only report it when it is the only difference, otherwise the full
+        // verification report is more useful.
+        if let [(reference, desire, current)] = differences.as_slice() {
             if let (
                 serde_json::Value::Number(des),
                 serde_json::Value::Number(cur),
@@ -85,10 +94,9 @@ impl Interface {
                 if desire.as_u64().unwrap_or(0) as i128
                     - cur.as_u64().unwrap_or(0) as i128
                     == 1
-                    && LinuxBridgeInterface::is_interger_rounded_up(&reference)
+                    && LinuxBridgeInterface::is_interger_rounded_up(reference)
                 {
-                    let e = NmstateError::new(
-                        ErrorKind::KernelIntegerRoundedError,
+                    let e = NmstateError::new_kernel_integer_rounded_error(
                         format!(
                             "Linux kernel configured with 250 HZ \
                                 will round up/down the integer in linux \
@@ -98,22 +106,14 @@ impl Interface {
                             des,
                             cur
                         ),
+                        gen_verification_differences(differences.as_slice()),
                     );
                     log::error!("{}", e);
                     return Err(e);
                 }
             }
-
-            Err(NmstateError::new(
-                ErrorKind::VerificationError,
-                format!(
-                    "Verification failure: {} desire '{}', current '{}'",
-                    reference, desire, current
-                ),
-            ))
-        } else {
-            Ok(())
         }
+        Err(gen_verification_error(differences.as_slice()))
     }
 
     pub fn update(&mut self, other: &Interface) {
@@ -248,6 +248,10 @@ impl Interface {
             iface.change_port_name(org_port_name, new_port_name);
         } else if let Interface::Bond(iface) = self {
             iface.change_port_name(org_port_name, new_port_name);
+        } else if let Interface::Vrf(iface) = self {
+            iface.change_port_name(org_port_name, new_port_name);
+        } else if let Interface::Vlan(iface) = self {
+            iface.change_parent_name(org_port_name, new_port_name);
         }
     }
 }
@@ -6,8 +6,8 @@ use std::iter::FromIterator;
 use serde::Deserialize;
 
 use crate::{
-    ErrorKind, Interface, InterfaceState, InterfaceType, Interfaces,
-    NmstateError,
+    ErrorKind, Interface, InterfaceIdentifier, InterfaceState, InterfaceType,
+    Interfaces, NmstateError, VerificationDifference,
 };
 
 impl Interfaces {
@@ -123,14 +123,18 @@ impl Interfaces {
         cur_clone.remove_unknown_type_port();
         self_clone.resolve_sriov_reference(pre_apply_current)?;
 
+        // Collect failures of all interfaces for full verification report
+        let mut errors: Vec<NmstateError> = Vec::new();
         for iface in self_clone.to_vec() {
-            if iface.is_absent() || (iface.is_virtual() && iface.is_down()) {
+            let result = if iface.is_absent()
+                || (iface.is_virtual() && iface.is_down())
+            {
                 if let Some(cur_iface) =
                     cur_clone.get_iface(iface.name(), iface.iface_type())
                 {
-                    verify_desire_absent_but_found_in_current(
-                        iface, cur_iface,
-                    )?;
+                    verify_desire_absent_but_found_in_current(iface, cur_iface)
+                } else {
+                    Ok(())
                 }
             } else if let Some(cur_iface) =
                 cur_clone.get_iface(iface.name(), iface.iface_type())
@@ -139,25 +143,38 @@ impl Interfaces {
                     .get_iface(iface.name(), iface.iface_type());
                 // Do not verify physical interface with state:down
                 if !iface.is_down() {
-                    iface.verify(pre_apply_cur_iface, cur_iface)?;
-                    if let Interface::Ethernet(eth_iface) = iface {
-                        if eth_iface.sriov_is_enabled() {
-                            eth_iface.verify_sriov(cur_ifaces)?;
-                        }
-                    }
+                    iface.verify(pre_apply_cur_iface, cur_iface).and_then(
+                        |()| match iface {
+                            Interface::Ethernet(eth_iface)
+                                if eth_iface.sriov_is_enabled() =>
+                            {
+                                eth_iface.verify_sriov(cur_ifaces)
+                            }
+                            _ => Ok(()),
+                        },
+                    )
+                } else {
+                    Ok(())
                 }
             } else {
-                return Err(NmstateError::new(
-                    ErrorKind::VerificationError,
+                Err(NmstateError::new_verification_error(
                     format!(
                         "Failed to find desired interface {} {:?}",
                         iface.name(),
                         iface.iface_type()
                     ),
-                ));
+                    vec![VerificationDifference::new(
+                        format!("{}.interface", iface.name()),
+                        serde_json::to_value(iface)?,
+                        serde_json::Value::Null,
+                    )],
+                ))
+            };
+            if let Err(e) = result {
+                errors.push(e);
             }
         }
-        Ok(())
+        NmstateError::merge_verification_errors(errors)
     }
 
     fn remove_unknown_type_port(&mut self) {
@@ -261,10 +278,13 @@ impl Interfaces {
         })
     }
 
+    // Return the interfaces renamed by `resolve_iface_identifiers()` as
+    // (original name, kernel name).
     pub(crate) fn resolve_unknown_ifaces(
         &mut self,
         cur_ifaces: &Self,
-    ) -> Result<(), NmstateError> {
+    ) -> Result<Vec<(String, String)>, NmstateError> {
+        let renames = self.resolve_iface_identifiers(cur_ifaces)?;
         let mut resolved_ifaces: Vec<Interface> = Vec::new();
         for (iface_name, iface) in self.kernel_ifaces.iter() {
             if iface.iface_type() != InterfaceType::Unknown || iface.is_ignore()
@@ -333,7 +353,162 @@ impl Interfaces {
             self.kernel_ifaces.remove(new_iface.name());
             self.push(new_iface);
         }
-        Ok(())
+        Ok(renames)
+    }
+
+    // Rename interfaces identified by MAC address or PCI address to the
+    // kernel interface name found in current state. The original name is
+    // stored as profile name.
+    fn resolve_iface_identifiers(
+        &mut self,
+        cur_ifaces: &Self,
+    ) -> Result<Vec<(String, String)>, NmstateError> {
+        let mut renames: Vec<(String, String)> = Vec::new();
+        for iface in self.kernel_ifaces.values() {
+            if iface.base_iface().is_identified_by_name() {
+                continue;
+            }
+            let cur_iface = find_iface_by_identifier(iface, cur_ifaces)?;
+            if cur_iface.name() != iface.name() {
+                if self.kernel_ifaces.contains_key(cur_iface.name()) {
+                    let e = NmstateError::new(
+                        ErrorKind::InvalidArgument,
+                        format!(
+                            "Interface {} is resolved to {} which is also \
+                            defined in desire state",
+                            iface.name(),
+                            cur_iface.name()
+                        ),
+                    );
+                    log::error!("{}", e);
+                    return Err(e);
+                }
+                renames.push((
+                    iface.name().to_string(),
+                    cur_iface.name().to_string(),
+                ));
+            }
+        }
+        for (org_name, new_name) in renames.iter() {
+            log::info!(
+                "Interface {} resolved to kernel interface {}",
+                org_name,
+                new_name
+            );
+            if let Some(mut iface) = self.kernel_ifaces.remove(org_name) {
+                iface.base_iface_mut().name = new_name.clone();
+                iface.base_iface_mut().profile_name = Some(org_name.clone());
+                self.kernel_ifaces.insert(new_name.clone(), iface);
+            }
+            for (name, _) in self.insert_order.iter_mut() {
+                if name == org_name {
+                    *name = new_name.clone();
+                }
+            }
+            for iface in self
+                .kernel_ifaces
+                .values_mut()
+                .chain(self.user_ifaces.values_mut())
+            {
+                iface.change_port_name(org_name, new_name.clone());
+                if iface.base_iface().controller.as_ref() == Some(org_name) {
+                    iface.base_iface_mut().controller = Some(new_name.clone());
+                }
+            }
+        }
+        Ok(renames)
+    }
+}
+
+fn find_iface_by_identifier<'a>(
+    iface: &Interface,
+    cur_ifaces: &'a Interfaces,
+) -> Result<&'a Interface, NmstateError> {
+    let base_iface = iface.base_iface();
+    let (prop_name, founds) = match base_iface.identifier {
+        Some(InterfaceIdentifier::MacAddress) => (
+            "mac-address",
+            find_ifaces_by_mac(
+                cur_ifaces,
+                base_iface.mac_address.as_deref().unwrap_or_default(),
+            ),
+        ),
+        Some(InterfaceIdentifier::PciAddress) => (
+            "pci-address",
+            cur_ifaces
+                .kernel_ifaces
+                .values()
+                .filter(|i| {
+                    i.base_iface().pci_address.is_some()
+                        && i.base_iface().pci_address == base_iface.pci_address
+                })
+                .collect(),
+        ),
+        _ => (
+            "name",
+            cur_ifaces
+                .kernel_ifaces
+                .get(iface.name())
+                .into_iter()
+                .collect(),
+        ),
+    };
+    match founds.as_slice() {
+        [cur_iface] => Ok(*cur_iface),
+        [] => {
+            let e = NmstateError::new(
+                ErrorKind::InvalidArgument,
+                format!(
+                    "Failed to find interface {} identified by {} in \
+                    current state",
+                    iface.name(),
+                    prop_name
+                ),
+            );
+            log::error!("{}", e);
+            Err(e)
+        }
+        _ => {
+            let e = NmstateError::new(
+                ErrorKind::InvalidArgument,
+                format!(
+                    "Found 2+ interfaces matching {} of interface {}: {:?}",
+                    prop_name,
+                    iface.name(),
+                    founds.iter().map(|i| i.name()).collect::<Vec<&str>>()
+                ),
+            );
+            log::error!("{}", e);
+            Err(e)
+        }
+    }
+}
+
+// Prefer permanent MAC address as MAC address of bond port or VLAN might be
+// identical to the one we are searching for.
+fn find_ifaces_by_mac<'a>(
+    cur_ifaces: &'a Interfaces,
+    mac: &str,
+) -> Vec<&'a Interface> {
+    let is_mac_match = |m: Option<&String>| {
+        m.map(|m| m.eq_ignore_ascii_case(mac)).unwrap_or_default()
+    };
+    let founds: Vec<&Interface> = cur_ifaces
+        .kernel_ifaces
+        .values()
+        .filter(|i| is_mac_match(i.base_iface().permanent_mac_address.as_ref()))
+        .collect();
+    if founds.is_empty() {
+        cur_ifaces
+            .kernel_ifaces
+            .values()
+            .filter(|i| {
+                i.iface_type() == InterfaceType::Ethernet
+                    && is_mac_match(i.base_iface().mac_address.as_ref())
+            })
+            .collect()
+    } else {
+        founds
     }
 }
 
@@ -365,14 +540,18 @@ fn verify_desire_absent_but_found_in_current(
 ) -> Result<(), NmstateError> {
     if cur_iface.is_virtual() {
         // Virtual interface should be deleted by absent action
-        let e = NmstateError::new(
-            ErrorKind::VerificationError,
+        let e = NmstateError::new_verification_error(
             format!(
                 "Absent/Down interface {}/{} still found as {:?}",
                 des_iface.name(),
                 des_iface.iface_type(),
                 cur_iface
             ),
+            vec![VerificationDifference::new(
+                format!("{}.interface.state", des_iface.name()),
+                serde_json::to_value(&des_iface.base_iface().state)?,
+                serde_json::to_value(&cur_iface.base_iface().state)?,
+            )],
         );
         log::error!("{}", e);
         Err(e)
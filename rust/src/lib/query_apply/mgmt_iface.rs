@@ -0,0 +1,250 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use std::net::IpAddr;
+use std::str::FromStr;
+
+use crate::{
+    ip::is_ipv6_unicast_link_local, ErrorKind, Interface, InterfaceIpAddr,
+    InterfaceIpv4, InterfaceIpv6, NetworkState, NmstateError, RouteEntry,
+};
+
+const IPV4_DEFAULT_ROUTE: &str = "0.0.0.0/0";
+const IPV6_DEFAULT_ROUTE: &str = "::/0";
+const MAIN_ROUTE_TABLE_ID: u32 = 254;
+
+// Refuse the change set which will take down, remove or readdress the
+// interface holding management connectivity:
+//  * Interface holding default route of main route table.
+//  * Interface holding the server address of current SSH session.
+pub(crate) fn validate_mgmt_iface_changes(
+    chg_net_state: &NetworkState,
+    del_net_state: &NetworkState,
+    cur_net_state: &NetworkState,
+) -> Result<(), NmstateError> {
+    let mgmt_ifaces = get_mgmt_ifaces(cur_net_state, get_ssh_server_ip());
+    check_mgmt_ifaces(
+        mgmt_ifaces.as_slice(),
+        chg_net_state,
+        del_net_state,
+        cur_net_state,
+    )
+}
+
+// The `SSH_CONNECTION` environment is in the format of
+// `<client_ip> <client_port> <server_ip> <server_port>`.
+// Please be aware that `sudo` might reset this environment.
+fn get_ssh_server_ip() -> Option<IpAddr> {
+    let ssh_conn = std::env::var("SSH_CONNECTION").ok()?;
+    IpAddr::from_str(ssh_conn.split_whitespace().nth(2)?).ok()
+}
+
+// Return list of (interface name, reason).
+pub(crate) fn get_mgmt_ifaces(
+    cur_net_state: &NetworkState,
+    ssh_server_ip: Option<IpAddr>,
+) -> Vec<(String, &'static str)> {
+    let mut ret: Vec<(String, &'static str)> = Vec::new();
+    for route in cur_net_state
+        .routes
+        .running
+        .as_deref()
+        .unwrap_or_default()
+        .iter()
+        .chain(cur_net_state.routes.config.as_deref().unwrap_or_default())
+    {
+        if let Some(iface_name) = get_default_route_iface(route) {
+            if !ret.iter().any(|(n, _)| n == iface_name) {
+                ret.push((iface_name.to_string(), "default route"));
+            }
+        }
+    }
+
+    if let Some(ssh_server_ip) = ssh_server_ip {
+        for iface in cur_net_state.interfaces.kernel_ifaces.values() {
+            if get_iface_ip_addrs(iface).any(|a| a.ip == ssh_server_ip)
+                && !ret.iter().any(|(n, _)| n == iface.name())
+            {
+                ret.push((iface.name().to_string(), "current SSH session"));
+            }
+        }
+    }
+    ret.sort_unstable();
+    ret
+}
+
+fn get_default_route_iface(route: &RouteEntry) -> Option<&str> {
+    let is_main_table = match route.table_id {
+        None | Some(RouteEntry::USE_DEFAULT_ROUTE_TABLE) => true,
+        Some(t) => t == MAIN_ROUTE_TABLE_ID,
+    };
+    if is_main_table
+        && (route.destination.as_deref() == Some(IPV4_DEFAULT_ROUTE)
+            || route.destination.as_deref() == Some(IPV6_DEFAULT_ROUTE))
+    {
+        route.next_hop_iface.as_deref().filter(|n| !n.is_empty())
+    } else {
+        None
+    }
+}
+
+pub(crate) fn check_mgmt_ifaces(
+    mgmt_ifaces: &[(String, &'static str)],
+    chg_net_state: &NetworkState,
+    del_net_state: &NetworkState,
+    cur_net_state: &NetworkState,
+) -> Result<(), NmstateError> {
+    for (iface_name, reason) in mgmt_ifaces {
+        let prop = if del_net_state
+            .interfaces
+            .kernel_ifaces
+            .contains_key(iface_name)
+        {
+            Some("state: absent".to_string())
+        } else if let (Some(chg_iface), Some(cur_iface)) = (
+            chg_net_state.interfaces.kernel_ifaces.get(iface_name),
+            cur_net_state.interfaces.kernel_ifaces.get(iface_name),
+        ) {
+            get_disruptive_prop(chg_iface, cur_iface)
+        } else {
+            None
+        };
+        if let Some(prop) = prop {
+            let e = NmstateError::new(
+                ErrorKind::InvalidArgument,
+                format!(
+                    "Refusing to apply `{}` to interface {} holding the {}, \
+                    which will disrupt the management connectivity, \
+                    please set allow_mgmt_disruption (nmstatectl --force) \
+                    to override",
+                    prop, iface_name, reason
+                ),
+            );
+            log::error!("{}", e);
+            return Err(e);
+        }
+    }
+    Ok(())
+}
+
+fn get_disruptive_prop(
+    chg_iface: &Interface,
+    cur_iface: &Interface,
+) -> Option<String> {
+    if chg_iface.is_down() {
+        return Some("state: down".to_string());
+    }
+    let chg_base = chg_iface.base_iface();
+    let cur_base = cur_iface.base_iface();
+    if let Some(ctrl) = chg_base.controller.as_deref() {
+        if ctrl != cur_base.controller.as_deref().unwrap_or_default() {
+            return Some(format!("controller: {}", ctrl));
+        }
+    }
+    if let (Some(chg_ip), Some(cur_ip)) =
+        (chg_base.ipv4.as_ref(), cur_base.ipv4.as_ref())
+    {
+        if let Some(prop) =
+            get_disruptive_ip_prop("ipv4", &chg_ip.into(), &cur_ip.into())
+        {
+            return Some(prop);
+        }
+    }
+    if let (Some(chg_ip), Some(cur_ip)) =
+        (chg_base.ipv6.as_ref(), cur_base.ipv6.as_ref())
+    {
+        if let Some(prop) =
+            get_disruptive_ip_prop("ipv6", &chg_ip.into(), &cur_ip.into())
+        {
+            return Some(prop);
+        }
+    }
+    None
+}
+
+// Properties shared by InterfaceIpv4 and InterfaceIpv6 for the check
+struct IpConf<'a> {
+    // Name of the properties controlling dynamic IP
+    auto_prop: &'static str,
+    enabled: bool,
+    is_auto: bool,
+    addresses: Option<&'a [InterfaceIpAddr]>,
+}
+
+impl<'a> From<&'a InterfaceIpv4> for IpConf<'a> {
+    fn from(ip: &'a InterfaceIpv4) -> Self {
+        Self {
+            auto_prop: "dhcp",
+            enabled: ip.enabled,
+            is_auto: ip.is_auto(),
+            addresses: ip.addresses.as_deref(),
+        }
+    }
+}
+
+impl<'a> From<&'a InterfaceIpv6> for IpConf<'a> {
+    fn from(ip: &'a InterfaceIpv6) -> Self {
+        Self {
+            auto_prop: "dhcp/autoconf",
+            enabled: ip.enabled,
+            is_auto: ip.is_auto(),
+            addresses: ip.addresses.as_deref(),
+        }
+    }
+}
+
+fn get_disruptive_ip_prop(
+    family: &str,
+    chg_ip: &IpConf,
+    cur_ip: &IpConf,
+) -> Option<String> {
+    if !cur_ip.enabled {
+        return None;
+    }
+    if !chg_ip.enabled {
+        return Some(format!("{}.enabled: false", family));
+    }
+    if chg_ip.is_auto != cur_ip.is_auto {
+        return Some(format!(
+            "{}.{}: {}",
+            family, chg_ip.auto_prop, chg_ip.is_auto
+        ));
+    }
+    // Dynamic addresses are managed by DHCP or autoconf
+    if chg_ip.is_auto {
+        return None;
+    }
+    let chg_addrs = chg_ip.addresses?;
+    for cur_addr in cur_ip.addresses.unwrap_or_default() {
+        if let IpAddr::V6(ip) = cur_addr.ip {
+            if is_ipv6_unicast_link_local(&ip) {
+                continue;
+            }
+        }
+        if !chg_addrs.iter().any(|a| a.ip == cur_addr.ip) {
+            return Some(format!(
+                "{}.address: removing {}/{}",
+                family, cur_addr.ip, cur_addr.prefix_length
+            ));
+        }
+    }
+    None
+}
+
+fn get_iface_ip_addrs(
+    iface: &Interface,
+) -> impl Iterator<Item = &InterfaceIpAddr> {
+    let base_iface = iface.base_iface();
+    base_iface
+        .ipv4
+        .as_ref()
+        .and_then(|i| i.addresses.as_deref())
+        .unwrap_or_default()
+        .iter()
+        .chain(
+            base_iface
+                .ipv6
+                .as_ref()
+                .and_then(|i| i.addresses.as_deref())
+                .unwrap_or_default(),
+        )
+}
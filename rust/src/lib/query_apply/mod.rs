@@ -14,13 +14,28 @@ mod linux_bridge;
 mod lldp;
 mod mac_vlan;
 mod mac_vtap;
+mod mgmt_iface;
+mod monitor;
 mod mptcp;
 mod net_state;
 mod ovs;
+mod plan;
+mod probe;
 mod route;
 mod sriov;
 mod vlan;
 mod vrf;
 mod vxlan;
 
+pub(crate) use self::hostname::{
+    hostname_checkpoint_create, hostname_checkpoint_destroy,
+    hostname_checkpoint_rollback,
+};
 pub(crate) use self::inter_ifaces::get_ignored_ifaces;
+pub(crate) use self::mgmt_iface::validate_mgmt_iface_changes;
+#[cfg(test)]
+pub(crate) use self::mgmt_iface::{check_mgmt_ifaces, get_mgmt_ifaces};
+#[cfg(test)]
+pub(crate) use self::plan::gen_plan;
+pub(crate) use self::probe::run_probes;
+pub(crate) use self::sriov::check_sriov_capability;
@@ -0,0 +1,127 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{channel, Receiver, RecvTimeoutError};
+use std::sync::Arc;
+use std::thread::JoinHandle;
+use std::time::{Duration, Instant};
+
+use crate::{
+    nispor::nispor_monitor, nm::nm_monitor, ErrorKind, NetworkState,
+    NetworkStateDiff, NmstateError,
+};
+
+// Wait for burst of events to settle down before retrieving the state.
+const MONITOR_DEBOUNCE_MILLISECONDS: u64 = 500;
+// Retrieve the state anyway when events keep coming for this long.
+const MONITOR_MAX_DEBOUNCE_MILLISECONDS: u64 = 5000;
+
+impl NetworkState {
+    // Watch kernel netlink events and NetworkManager D-Bus signals, invoke
+    // the callback with the new full state and the changes compared to
+    // previous state whenever any property changed. The callback is first
+    // invoked with the initial state and empty changes, so it could be used
+    // as baseline. The retrieve options of this state, like
+    // `set_kernel_only()`, are used for retrieving.
+    // Return when callback returns false.
+    pub fn monitor<F>(&self, callback: F) -> Result<(), NmstateError>
+    where
+        F: FnMut(&NetworkState, &NetworkStateDiff) -> bool,
+    {
+        let (tx, rx) = channel();
+        let stop = Arc::new(AtomicBool::new(false));
+        let mut threads: Vec<JoinHandle<()>> = Vec::new();
+        let nl_tx = tx.clone();
+        let nl_stop = stop.clone();
+        threads.push(std::thread::spawn(move || {
+            if let Err(e) = nispor_monitor(nl_tx, nl_stop) {
+                log::error!("Kernel netlink monitor stopped: {}", e);
+            }
+        }));
+        if !self.kernel_only {
+            let nm_tx = tx.clone();
+            let nm_stop = stop.clone();
+            threads.push(std::thread::spawn(move || {
+                if let Err(e) = nm_monitor(nm_tx, nm_stop) {
+                    log::warn!("NetworkManager monitor stopped: {}", e);
+                }
+            }));
+        }
+        drop(tx);
+
+        let result = self.monitor_changes(&rx, callback);
+        stop.store(true, Ordering::Relaxed);
+        drop(rx);
+        for thread in threads {
+            if thread.join().is_err() {
+                log::error!("Network state monitor thread panicked");
+            }
+        }
+        result
+    }
+
+    fn monitor_changes<F>(
+        &self,
+        rx: &Receiver<()>,
+        mut callback: F,
+    ) -> Result<(), NmstateError>
+    where
+        F: FnMut(&NetworkState, &NetworkStateDiff) -> bool,
+    {
+        let mut cur_state = self.new_for_monitor();
+        cur_state.retrieve()?;
+        if !callback(&cur_state, &NetworkStateDiff::new()) {
+            return Ok(());
+        }
+        loop {
+            if rx.recv().is_err() {
+                let e = NmstateError::new(
+                    ErrorKind::PluginFailure,
+                    "All network state monitors stopped".to_string(),
+                );
+                log::error!("{}", e);
+                return Err(e);
+            }
+            let debounce = Duration::from_millis(MONITOR_DEBOUNCE_MILLISECONDS);
+            let deadline = Instant::now()
+                + Duration::from_millis(MONITOR_MAX_DEBOUNCE_MILLISECONDS);
+            loop {
+                let remaining =
+                    deadline.saturating_duration_since(Instant::now());
+                if remaining.is_zero() {
+                    break;
+                }
+                match rx.recv_timeout(debounce.min(remaining)) {
+                    Ok(()) => continue,
+                    Err(RecvTimeoutError::Timeout) => break,
+                    // Let the outer `recv()` report the error
+                    Err(RecvTimeoutError::Disconnected) => break,
+                }
+            }
+            let mut new_state = self.new_for_monitor();
+            // Interface might be removed during retrieving, try again on
+            // next event.
+            if let Err(e) = new_state.retrieve() {
+                log::warn!("Failed to retrieve network state: {}", e);
+                continue;
+            }
+            let changes = cur_state.get_changes(&new_state)?;
+            if !changes.is_empty() {
+                log::debug!("Network state changed: {:?}", changes);
+                if !callback(&new_state, &changes) {
+                    return Ok(());
+                }
+            }
+            cur_state = new_state;
+        }
+    }
+
+    fn new_for_monitor(&self) -> Self {
+        let mut state = Self::new();
+        state.kernel_only = self.kernel_only;
+        state.include_secrets = self.include_secrets;
+        state.include_status_data = self.include_status_data;
+        state.running_config_only = self.running_config_only;
+        state
+    }
+}
@@ -1,14 +1,30 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::{
-    nispor::{nispor_apply, nispor_retrieve, set_running_hostname},
+    history::history_record,
+    nispor::{
+        nispor_apply, nispor_checkpoint_create, nispor_checkpoint_destroy,
+        nispor_checkpoint_get, nispor_checkpoint_list,
+        nispor_checkpoint_rollback, nispor_checkpoint_timeout_extend,
+        nispor_retrieve, set_running_hostname,
+    },
     nm::{
         nm_apply, nm_checkpoint_create, nm_checkpoint_destroy,
-        nm_checkpoint_rollback, nm_checkpoint_timeout_extend, nm_retrieve,
+        nm_checkpoint_list, nm_checkpoint_rollback,
+        nm_checkpoint_timeout_extend, nm_retrieve,
+    },
+    ovsdb::{
+        ovsdb_apply, ovsdb_checkpoint_create, ovsdb_checkpoint_destroy,
+        ovsdb_checkpoint_rollback, ovsdb_is_running, ovsdb_retrieve,
+    },
+    query_apply::{
+        check_sriov_capability, get_ignored_ifaces, hostname_checkpoint_create,
+        hostname_checkpoint_destroy, hostname_checkpoint_rollback, run_probes,
+        validate_mgmt_iface_changes,
     },
-    ovsdb::{ovsdb_apply, ovsdb_is_running, ovsdb_retrieve},
-    query_apply::get_ignored_ifaces,
-    NetworkState, NmstateError,
+    snapshot::now_secs,
+    CheckpointInfo, InterfaceState, Interfaces, NetworkState, NmstateError,
+    RouteRuleState, RouteState,
 };
 
 const DEFAULT_ROLLBACK_TIMEOUT: u32 = 60;
@@ -20,12 +36,44 @@ const VERIFY_RETRY_NM: usize = 2;
 const MAX_SUPPORTED_INTERFACES: usize = 1000;
 
 impl NetworkState {
+    // The OVSDB and hostname snapshots are restored even when NetworkManager
+    // failed to rollback, for example NetworkManager already did the
+    // rollback on checkpoint timeout.
     pub fn checkpoint_rollback(checkpoint: &str) -> Result<(), NmstateError> {
-        nm_checkpoint_rollback(checkpoint)
+        if let Some(kernel_checkpoint) = nispor_checkpoint_get(checkpoint)? {
+            nispor_checkpoint_rollback(&kernel_checkpoint)
+        } else {
+            get_first_error(vec![
+                nm_checkpoint_rollback(checkpoint),
+                ovsdb_checkpoint_rollback(checkpoint),
+                hostname_checkpoint_rollback(checkpoint),
+            ])
+        }
     }
 
     pub fn checkpoint_commit(checkpoint: &str) -> Result<(), NmstateError> {
-        nm_checkpoint_destroy(checkpoint)
+        if let Some(kernel_checkpoint) = nispor_checkpoint_get(checkpoint)? {
+            nispor_checkpoint_destroy(&kernel_checkpoint)
+        } else {
+            get_first_error(vec![
+                nm_checkpoint_destroy(checkpoint),
+                ovsdb_checkpoint_destroy(checkpoint),
+                hostname_checkpoint_destroy(checkpoint),
+            ])
+        }
+    }
+
+    // List both kernel and NetworkManager checkpoints, sorted by creation
+    // time.
+    pub fn checkpoint_list() -> Result<Vec<CheckpointInfo>, NmstateError> {
+        let mut ret = nispor_checkpoint_list();
+        match nm_checkpoint_list() {
+            Ok(nm_checkpoints) => ret.extend(nm_checkpoints),
+            // Kernel only host might not have NetworkManager running.
+            Err(e) => log::warn!("Failed to list NM checkpoints: {}", e),
+        }
+        ret.sort_by_key(|c| c.created);
+        Ok(ret)
     }
 
     pub fn retrieve(&mut self) -> Result<&mut Self, NmstateError> {
@@ -66,7 +114,10 @@ impl NetworkState {
         Ok(self)
     }
 
-    pub fn apply(&self) -> Result<(), NmstateError> {
+    // Return the desired states to verify and to apply with the current state
+    // retrieved, all resolved against each other and ignored interfaces
+    // removed.
+    pub(crate) fn pre_apply(&self) -> Result<(Self, Self, Self), NmstateError> {
         let mut desire_state_to_verify = self.clone();
         let mut desire_state_to_apply = self.clone();
         let mut cur_net_state = NetworkState::new();
@@ -120,16 +171,31 @@ impl NetworkState {
             .remove_ignored_iface_routes(ignored_kernel_ifaces.as_slice());
 
         desire_state_to_verify
-            .interfaces
             .resolve_unknown_ifaces(&cur_net_state.interfaces)?;
         desire_state_to_apply
-            .interfaces
             .resolve_unknown_ifaces(&cur_net_state.interfaces)?;
 
         desire_state_to_apply
             .interfaces
             .resolve_sriov_reference(&cur_net_state.interfaces)?;
 
+        Ok((desire_state_to_verify, desire_state_to_apply, cur_net_state))
+    }
+
+    // Verify current network state against this desired state without
+    // applying anything. The returned `ErrorKind::VerificationError` holds
+    // all the differences found.
+    pub fn verify_current(&self) -> Result<(), NmstateError> {
+        let (desire_state_to_verify, _, cur_net_state) = self.pre_apply()?;
+        desire_state_to_verify.verify(&cur_net_state, &cur_net_state)
+    }
+
+    pub fn apply(&self) -> Result<(), NmstateError> {
+        let started = now_secs();
+        let (desire_state_to_verify, desire_state_to_apply, cur_net_state) =
+            self.pre_apply()?;
+
+        check_sriov_capability(&desire_state_to_apply.interfaces)?;
         let (add_net_state, chg_net_state, del_net_state) =
             desire_state_to_apply.gen_state_for_apply(&cur_net_state)?;
 
@@ -137,6 +203,14 @@ impl NetworkState {
         log::debug!("Changing net state {:?}", &chg_net_state);
         log::debug!("Deleting net state {:?}", &del_net_state);
 
+        if !self.allow_mgmt_disruption {
+            validate_mgmt_iface_changes(
+                &chg_net_state,
+                &del_net_state,
+                &cur_net_state,
+            )?;
+        }
+
         if !self.kernel_only {
             let retry_count =
                 if desire_state_to_apply.interfaces.has_sriov_enabled() {
@@ -149,7 +223,35 @@ impl NetworkState {
             let checkpoint = nm_checkpoint_create(timeout)?;
             log::info!("Created checkpoint {}", &checkpoint);
 
-            with_nm_checkpoint(&checkpoint, self.no_commit, || {
+            let apply_ovsdb =
+                desire_state_to_apply.prop_list.contains(&"ovsdb")
+                    && ovsdb_is_running();
+            if apply_ovsdb {
+                if let Err(e) = ovsdb_checkpoint_create(&checkpoint) {
+                    if let Err(e) = nm_checkpoint_destroy(&checkpoint) {
+                        log::warn!("nm_checkpoint_destroy() failed: {}", e);
+                    }
+                    return Err(e);
+                }
+            }
+
+            // NM checkpoint does not cover hostname changes.
+            if let Some(pre_hostname) =
+                self.hostname.as_ref().and(cur_net_state.hostname.as_ref())
+            {
+                if let Err(e) =
+                    hostname_checkpoint_create(&checkpoint, pre_hostname)
+                {
+                    // Destroy the checkpoint along with the OVSDB snapshot
+                    if let Err(e) = NetworkState::checkpoint_commit(&checkpoint)
+                    {
+                        log::warn!("checkpoint_commit() failed: {}", e);
+                    }
+                    return Err(e);
+                }
+            }
+
+            with_checkpoint(&checkpoint, self.no_commit, || {
                 // NM might have unknown race problem found by verify stage,
                 // we try to apply the state again if so.
                 with_retry(
@@ -168,9 +270,7 @@ impl NetworkState {
                             &checkpoint,
                             self.memory_only,
                         )?;
-                        if desire_state_to_apply.prop_list.contains(&"ovsdb")
-                            && ovsdb_is_running()
-                        {
+                        if apply_ovsdb {
                             ovsdb_apply(
                                 &desire_state_to_apply,
                                 &cur_net_state,
@@ -206,34 +306,98 @@ impl NetworkState {
                             Ok(())
                         }
                     },
-                )
-            })
+                )?;
+                if !self.probes.is_empty() {
+                    nm_checkpoint_timeout_extend(
+                        &checkpoint,
+                        self.probes.iter().map(|p| p.timeout()).sum(),
+                    )?;
+                }
+                run_probes(self.probes.as_slice())
+            })?;
+            self.record_history(&cur_net_state, &checkpoint, started);
+            Ok(())
         } else {
-            // TODO: Need checkpoint for kernel only mode
-            nispor_apply(
-                &add_net_state,
-                &chg_net_state,
-                &del_net_state,
-                &cur_net_state,
-            )?;
-            if let Some(running_hostname) =
-                self.hostname.as_ref().and_then(|c| c.running.as_ref())
-            {
-                set_running_hostname(running_hostname)?;
+            let timeout = self.timeout.unwrap_or(DEFAULT_ROLLBACK_TIMEOUT);
+            // Only restore hostname on rollback when desired state changed
+            // it, the kernel checkpoint rollback will do the restore.
+            let mut pre_apply_state = cur_net_state.clone();
+            if self.hostname.is_none() {
+                pre_apply_state.hostname = None;
             }
-            if !self.no_verify {
-                with_retry(
-                    VERIFY_RETRY_INTERVAL_MILLISECONDS,
-                    VERIFY_RETRY_COUNT_KERNEL_MODE,
-                    || {
-                        let mut new_cur_net_state = cur_net_state.clone();
-                        new_cur_net_state.retrieve()?;
-                        desire_state_to_verify
-                            .verify(&cur_net_state, &new_cur_net_state)
-                    },
-                )
-            } else {
-                Ok(())
+            let checkpoint =
+                nispor_checkpoint_create(&pre_apply_state, timeout)?;
+            log::info!("Created checkpoint {}", &checkpoint);
+
+            with_checkpoint(&checkpoint, self.no_commit, || {
+                nispor_apply(
+                    &add_net_state,
+                    &chg_net_state,
+                    &del_net_state,
+                    &cur_net_state,
+                    &desire_state_to_apply,
+                )?;
+                if let Some(running_hostname) =
+                    self.hostname.as_ref().and_then(|c| c.running.as_ref())
+                {
+                    set_running_hostname(running_hostname)?;
+                }
+                if !self.no_verify {
+                    with_retry(
+                        VERIFY_RETRY_INTERVAL_MILLISECONDS,
+                        VERIFY_RETRY_COUNT_KERNEL_MODE,
+                        || {
+                            let mut new_cur_net_state = cur_net_state.clone();
+                            new_cur_net_state.retrieve()?;
+                            desire_state_to_verify
+                                .verify(&cur_net_state, &new_cur_net_state)
+                        },
+                    )?;
+                }
+                if !self.probes.is_empty() {
+                    nispor_checkpoint_timeout_extend(
+                        &checkpoint,
+                        self.probes.iter().map(|p| p.timeout()).sum(),
+                    )?;
+                }
+                run_probes(self.probes.as_slice())
+            })?;
+            self.record_history(&cur_net_state, &checkpoint, started);
+            Ok(())
+        }
+    }
+
+    // Bring the network back to `target` state, for example the state
+    // retrieved before earlier applies, using the apply options of this state.
+    // Virtual interfaces, routes and route rules not found in `target` are
+    // removed. Return the state applied.
+    pub fn revert_to(&self, target: &Self) -> Result<Self, NmstateError> {
+        let mut cur_state = Self::new();
+        cur_state.set_kernel_only(self.kernel_only);
+        cur_state.set_running_config_only(true);
+        cur_state.retrieve()?;
+
+        let revert_state = target.gen_revert_state(&cur_state);
+        let mut net_state = self.clone();
+        net_state.hostname = revert_state.hostname;
+        net_state.dns = revert_state.dns;
+        net_state.rules = revert_state.rules;
+        net_state.routes = revert_state.routes;
+        net_state.interfaces = revert_state.interfaces;
+        net_state.ovsdb = revert_state.ovsdb;
+        net_state.prop_list = revert_state.prop_list;
+        net_state.apply()?;
+        Ok(net_state)
+    }
+
+    // Done after checkpoint commit, so that the result recorded is the final
+    // one. The network state is already applied, hence failure on recording
+    // the history is not treated as apply failure.
+    fn record_history(&self, pre_apply: &Self, checkpoint: &str, started: u64) {
+        if self.record_history {
+            if let Err(e) = history_record(self, pre_apply, checkpoint, started)
+            {
+                log::warn!("Failed to record apply history: {}", e);
             }
         }
     }
@@ -243,18 +407,38 @@ impl NetworkState {
         pre_apply_current: &Self,
         current: &Self,
     ) -> Result<(), NmstateError> {
-        if let Some(desired_hostname) = self.hostname.as_ref() {
-            desired_hostname.verify(current.hostname.as_ref())?;
-        }
-        self.interfaces
-            .verify(&pre_apply_current.interfaces, &current.interfaces)?;
         let (ignored_kernel_ifaces, _) =
             get_ignored_ifaces(&self.interfaces, &current.interfaces);
-        self.routes
-            .verify(&current.routes, &ignored_kernel_ifaces)?;
-        self.rules.verify(&current.rules)?;
-        self.dns.verify(&current.dns)?;
-        self.ovsdb.verify(&current.ovsdb)
+        // Verify all sections for full verification report
+        let results = vec![
+            self.hostname
+                .as_ref()
+                .map(|h| h.verify(current.hostname.as_ref()))
+                .unwrap_or(Ok(())),
+            self.interfaces
+                .verify(&pre_apply_current.interfaces, &current.interfaces),
+            self.routes.verify(&current.routes, &ignored_kernel_ifaces),
+            self.rules.verify(&current.rules),
+            self.dns.verify(&current.dns),
+            self.ovsdb.verify(&current.ovsdb),
+        ];
+        NmstateError::merge_verification_errors(
+            results.into_iter().filter_map(|r| r.err()).collect(),
+        )
+    }
+
+    // Besides interfaces, the routes referring interfaces renamed by
+    // interface identifier are also updated to use kernel interface name.
+    pub(crate) fn resolve_unknown_ifaces(
+        &mut self,
+        cur_ifaces: &Interfaces,
+    ) -> Result<(), NmstateError> {
+        for (org_name, new_name) in
+            self.interfaces.resolve_unknown_ifaces(cur_ifaces)?
+        {
+            self.routes.change_iface_name(&org_name, &new_name);
+        }
+        Ok(())
     }
 
     pub(crate) fn update_state(&mut self, other: &Self) {
@@ -277,9 +461,56 @@ impl NetworkState {
             self.ovsdb = other.ovsdb.clone();
         }
     }
+
+    // Generate the state reverting `current` back to this state:
+    //  * Virtual interfaces not found in this state are removed.
+    //  * Routes and route rules not found in this state are removed.
+    //  * Everything else is restored to this state.
+    pub(crate) fn gen_revert_state(&self, current: &Self) -> Self {
+        let mut revert_state = self.clone();
+
+        for cur_iface in current.interfaces.to_vec() {
+            if cur_iface.is_virtual()
+                && revert_state
+                    .interfaces
+                    .get_iface(cur_iface.name(), cur_iface.iface_type())
+                    .is_none()
+            {
+                let mut iface = cur_iface.clone();
+                iface.base_iface_mut().state = InterfaceState::Absent;
+                revert_state.interfaces.push(iface);
+            }
+        }
+
+        let revert_routes =
+            revert_state.routes.config.get_or_insert_with(Vec::new);
+        for cur_route in current.routes.config.as_deref().unwrap_or_default() {
+            if !revert_routes.iter().any(|r| {
+                r.is_match(cur_route)
+                    && r.metric == cur_route.metric
+                    && r.table_id == cur_route.table_id
+            }) {
+                let mut route = cur_route.clone();
+                route.state = Some(RouteState::Absent);
+                revert_routes.push(route);
+            }
+        }
+
+        let revert_rules =
+            revert_state.rules.config.get_or_insert_with(Vec::new);
+        for cur_rule in current.rules.config.as_deref().unwrap_or_default() {
+            if !revert_rules.iter().any(|r| r.is_match(cur_rule)) {
+                let mut rule = cur_rule.clone();
+                rule.state = Some(RouteRuleState::Absent);
+                revert_rules.push(rule);
+            }
+        }
+        revert_state
+    }
 }
 
-fn with_nm_checkpoint<T>(
+// Both NetworkManager and kernel checkpoint are supported.
+fn with_checkpoint<T>(
     checkpoint: &str,
     no_commit: bool,
     func: T,
@@ -290,7 +521,7 @@ where
     match func() {
         Ok(()) => {
             if !no_commit {
-                nm_checkpoint_destroy(checkpoint)?;
+                NetworkState::checkpoint_commit(checkpoint)?;
 
                 log::info!("Destroyed checkpoint {}", checkpoint);
             } else {
@@ -299,8 +530,8 @@ where
             Ok(())
         }
         Err(e) => {
-            if let Err(e) = nm_checkpoint_rollback(checkpoint) {
-                log::warn!("nm_checkpoint_rollback() failed: {}", e);
+            if let Err(e) = NetworkState::checkpoint_rollback(checkpoint) {
+                log::warn!("checkpoint_rollback() failed: {}", e);
             }
             log::info!("Rollbacked to checkpoint {}", checkpoint);
             Err(e)
@@ -335,3 +566,19 @@ where
     }
     Ok(())
 }
+
+// Log all the errors, return the first one.
+fn get_first_error(
+    results: Vec<Result<(), NmstateError>>,
+) -> Result<(), NmstateError> {
+    let mut ret = Ok(());
+    for result in results {
+        if let Err(e) = result {
+            log::error!("{}", e);
+            if ret.is_ok() {
+                ret = Err(e);
+            }
+        }
+    }
+    ret
+}
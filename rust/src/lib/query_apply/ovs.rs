@@ -3,8 +3,8 @@
 use std::collections::HashMap;
 
 use crate::{
-    state::get_json_value_difference, ErrorKind, NmstateError,
-    OvsBridgeBondConfig, OvsBridgeConfig, OvsBridgeInterface,
+    state::{gen_verification_error, get_json_value_differences},
+    NmstateError, OvsBridgeBondConfig, OvsBridgeConfig, OvsBridgeInterface,
     OvsDbGlobalConfig,
 };
 
@@ -13,22 +13,17 @@ impl OvsDbGlobalConfig {
         let self_value = serde_json::to_value(self)?;
         let current_value = serde_json::to_value(current)?;
 
-        if let Some((reference, desire, current)) = get_json_value_difference(
+        let differences = get_json_value_differences(
             "ovsdb".to_string(),
             &self_value,
             &current_value,
-        ) {
-            let e = NmstateError::new(
-                ErrorKind::VerificationError,
-                format!(
-                    "Verification failure: {} desire '{}', current '{}'",
-                    reference, desire, current
-                ),
-            );
+        );
+        if differences.is_empty() {
+            Ok(())
+        } else {
+            let e = gen_verification_error(differences.as_slice());
             log::error!("{}", e);
             Err(e)
-        } else {
-            Ok(())
         }
     }
 
@@ -68,30 +63,6 @@ impl OvsDbGlobalConfig {
             self.other_config = current.other_config.clone();
         }
     }
-
-    pub(crate) fn get_other_config(&self) -> HashMap<&str, &str> {
-        let mut ret = HashMap::new();
-        if let Some(ocfg) = self.other_config.as_ref() {
-            for (k, v) in ocfg.iter() {
-                if let Some(v) = v {
-                    ret.insert(k.as_str(), v.as_str());
-                }
-            }
-        }
-        ret
-    }
-
-    pub(crate) fn get_external_ids(&self) -> HashMap<&str, &str> {
-        let mut ret = HashMap::new();
-        if let Some(eids) = self.external_ids.as_ref() {
-            for (k, v) in eids {
-                if let Some(v) = v {
-                    ret.insert(k.as_str(), v.as_str());
-                }
-            }
-        }
-        ret
-    }
 }
 
 fn merge_hashmap(
@@ -0,0 +1,93 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    dns::is_dns_changed, query_apply::check_sriov_capability, NetworkState,
+    NetworkStatePlan, NmstateError,
+};
+
+impl NetworkState {
+    // Generate the changes `apply()` would do without touching the system or
+    // creating checkpoint.
+    pub fn plan(&self) -> Result<NetworkStatePlan, NmstateError> {
+        let (_, desire_state_to_apply, cur_net_state) = self.pre_apply()?;
+        check_sriov_capability(&desire_state_to_apply.interfaces)?;
+        let (add_net_state, chg_net_state, del_net_state) =
+            desire_state_to_apply.gen_state_for_apply(&cur_net_state)?;
+        Ok(gen_plan(
+            &desire_state_to_apply,
+            &cur_net_state,
+            &add_net_state,
+            &chg_net_state,
+            &del_net_state,
+        ))
+    }
+}
+
+pub(crate) fn gen_plan(
+    desired: &NetworkState,
+    current: &NetworkState,
+    add_net_state: &NetworkState,
+    chg_net_state: &NetworkState,
+    del_net_state: &NetworkState,
+) -> NetworkStatePlan {
+    let mut plan = NetworkStatePlan::new();
+    for iface in add_net_state.interfaces.to_vec() {
+        plan.add_interfaces.push(iface.clone());
+    }
+    for iface in chg_net_state.interfaces.to_vec() {
+        plan.change_interfaces.push(iface.clone());
+    }
+    for iface in del_net_state.interfaces.to_vec() {
+        plan.delete_interfaces.push(iface.into());
+    }
+    if !desired.kernel_only {
+        for iface in plan
+            .add_interfaces
+            .iter()
+            .chain(plan.change_interfaces.iter())
+        {
+            if iface.is_up() {
+                plan.activate_profiles.push(iface.into());
+            } else if iface.is_down() {
+                plan.deactivate_profiles.push(iface.into());
+            }
+        }
+    }
+
+    let (add_routes, remove_routes) =
+        desired
+            .routes
+            .gen_add_and_remove(&current.routes, |iface_name| {
+                del_net_state
+                    .interfaces
+                    .kernel_ifaces
+                    .contains_key(iface_name)
+            });
+    plan.add_routes = add_routes;
+    plan.remove_routes = remove_routes;
+    let (add_rules, remove_rules) =
+        desired.rules.gen_add_and_remove(&current.rules);
+    plan.add_route_rules = add_rules;
+    plan.remove_route_rules = remove_rules;
+
+    let mut desired_dns = desired.clone();
+    desired_dns.dns.merge_current(&current.dns);
+    if is_dns_changed(&desired_dns, current) {
+        plan.dns = desired_dns.dns.config;
+    }
+
+    if let Some(hostname) =
+        desired.hostname.as_ref().and_then(|h| h.config.as_ref())
+    {
+        if current.hostname.as_ref().and_then(|h| h.config.as_ref())
+            != Some(hostname)
+        {
+            plan.hostname = Some(hostname.to_string());
+        }
+    }
+
+    if desired.prop_list.contains(&"ovsdb") && desired.ovsdb != current.ovsdb {
+        plan.ovsdb = Some(desired.ovsdb.clone());
+    }
+    plan
+}
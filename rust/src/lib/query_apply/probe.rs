@@ -0,0 +1,174 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use std::io::Read;
+use std::net::{IpAddr, SocketAddr, TcpStream, ToSocketAddrs};
+use std::time::{Duration, Instant};
+
+use socket2::{Domain, Protocol, SockAddr, Socket, Type};
+
+use crate::{
+    DnsProbe, ErrorKind, NetworkProbe, NmstateError, PingProbe, TcpProbe,
+};
+
+const ICMP_ECHO_REQUEST: u8 = 8;
+const ICMP_ECHO_REPLY: u8 = 0;
+const ICMPV6_ECHO_REQUEST: u8 = 128;
+const ICMPV6_ECHO_REPLY: u8 = 129;
+const ICMP_ECHO_SEQ: u16 = 1;
+const ICMP_HEADER_LEN: usize = 8;
+
+pub(crate) fn run_probes(probes: &[NetworkProbe]) -> Result<(), NmstateError> {
+    for probe in probes {
+        log::info!("Running probe {:?}", probe);
+        probe.run()?;
+    }
+    Ok(())
+}
+
+impl NetworkProbe {
+    pub(crate) fn run(&self) -> Result<(), NmstateError> {
+        let timeout = Duration::from_secs(self.timeout().into());
+        match self {
+            Self::Ping(p) => p.run(timeout),
+            Self::Tcp(p) => p.run(timeout),
+            Self::Dns(p) => p.run(timeout),
+        }
+        .map_err(|e| {
+            let e = NmstateError::new(
+                ErrorKind::VerificationError,
+                format!("Probe {:?} failed: {}", self, e),
+            );
+            log::error!("{}", e);
+            e
+        })
+    }
+}
+
+impl PingProbe {
+    fn run(&self, timeout: Duration) -> Result<(), String> {
+        let (domain, protocol, request_type, reply_type) = match self.address {
+            IpAddr::V4(_) => (
+                Domain::IPV4,
+                Protocol::ICMPV4,
+                ICMP_ECHO_REQUEST,
+                ICMP_ECHO_REPLY,
+            ),
+            IpAddr::V6(_) => (
+                Domain::IPV6,
+                Protocol::ICMPV6,
+                ICMPV6_ECHO_REQUEST,
+                ICMPV6_ECHO_REPLY,
+            ),
+        };
+        let mut socket = Socket::new(domain, Type::RAW, Some(protocol))
+            .map_err(|e| format!("Failed to create ICMP socket: {}", e))?;
+        // Raw socket receives all ICMP packets, use PID to identify our reply.
+        let id = (std::process::id() & 0xffff) as u16;
+        let request = gen_icmp_echo_request(request_type, id);
+        socket
+            .send_to(
+                &request,
+                &SockAddr::from(SocketAddr::new(self.address, 0)),
+            )
+            .map_err(|e| format!("Failed to send ICMP echo request: {}", e))?;
+
+        let deadline = Instant::now() + timeout;
+        let mut buffer = [0u8; 1500];
+        while let Some(remain) = deadline.checked_duration_since(Instant::now())
+        {
+            if remain.is_zero() {
+                break;
+            }
+            socket
+                .set_read_timeout(Some(remain))
+                .map_err(|e| format!("Failed to set socket timeout: {}", e))?;
+            let len = match socket.read(&mut buffer) {
+                Ok(l) => l,
+                Err(e)
+                    if e.kind() == std::io::ErrorKind::WouldBlock
+                        || e.kind() == std::io::ErrorKind::TimedOut =>
+                {
+                    break;
+                }
+                Err(e) => {
+                    return Err(format!("Failed to receive ICMP reply: {}", e))
+                }
+            };
+            // IPv4 raw socket includes the IP header.
+            let icmp = if self.address.is_ipv4() {
+                let ip_header_len = usize::from(buffer[0] & 0x0f) * 4;
+                buffer.get(ip_header_len..len).unwrap_or_default()
+            } else {
+                buffer.get(..len).unwrap_or_default()
+            };
+            if icmp.len() >= ICMP_HEADER_LEN
+                && icmp[0] == reply_type
+                && u16::from_be_bytes([icmp[4], icmp[5]]) == id
+                && u16::from_be_bytes([icmp[6], icmp[7]]) == ICMP_ECHO_SEQ
+            {
+                return Ok(());
+            }
+        }
+        Err(format!("No ICMP echo reply within {:?}", timeout))
+    }
+}
+
+// The kernel will fill the checksum of ICMPv6, hence the checksum is only
+// required by ICMPv4, it is harmless to set it for ICMPv6.
+fn gen_icmp_echo_request(request_type: u8, id: u16) -> Vec<u8> {
+    let mut packet = vec![0u8; ICMP_HEADER_LEN];
+    packet[0] = request_type;
+    packet[4..6].copy_from_slice(&id.to_be_bytes());
+    packet[6..8].copy_from_slice(&ICMP_ECHO_SEQ.to_be_bytes());
+    packet.extend_from_slice(b"nmstate!");
+    let checksum = icmp_checksum(&packet);
+    packet[2..4].copy_from_slice(&checksum.to_be_bytes());
+    packet
+}
+
+fn icmp_checksum(data: &[u8]) -> u16 {
+    let mut sum: u32 = data
+        .chunks(2)
+        .map(|c| (u32::from(c[0]) << 8) + u32::from(*c.get(1).unwrap_or(&0)))
+        .sum();
+    while sum >> 16 != 0 {
+        sum = (sum & 0xffff) + (sum >> 16);
+    }
+    !(sum as u16)
+}
+
+impl TcpProbe {
+    fn run(&self, timeout: Duration) -> Result<(), String> {
+        TcpStream::connect_timeout(
+            &SocketAddr::new(self.address, self.port),
+            timeout,
+        )
+        .map(|_| ())
+        .map_err(|e| e.to_string())
+    }
+}
+
+impl DnsProbe {
+    // The libc resolver has no timeout argument, resolve in a thread instead.
+    // The resolver cannot be cancelled, on timeout the thread is detached and
+    // left to finish by the resolver's own timeout, so that the probe never
+    // waits longer than its timeout covered by the checkpoint.
+    fn run(&self, timeout: Duration) -> Result<(), String> {
+        let (sender, receiver) = std::sync::mpsc::channel();
+        let name = self.name.clone();
+        std::thread::spawn(move || {
+            let result = (name.as_str(), 0)
+                .to_socket_addrs()
+                .map(|mut addrs| addrs.next().is_some());
+            // The receiver might be gone on timeout.
+            sender.send(result).ok();
+        });
+        let result = receiver.recv_timeout(timeout);
+        match result {
+            Ok(Ok(true)) => Ok(()),
+            Ok(Ok(false)) => Err("No address resolved".to_string()),
+            Ok(Err(e)) => Err(e.to_string()),
+            Err(_) => Err(format!("No DNS reply within {:?}", timeout)),
+        }
+    }
+}
@@ -1,8 +1,16 @@
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::{ErrorKind, NmstateError, Routes};
+use crate::{NmstateError, Routes, VerificationDifference};
 
 impl Routes {
+    pub(crate) fn change_iface_name(&mut self, org_name: &str, new_name: &str) {
+        for route in self.config.iter_mut().flatten() {
+            if route.next_hop_iface.as_deref() == Some(org_name) {
+                route.next_hop_iface = Some(new_name.to_string());
+            }
+        }
+    }
+
     // Kernel might append additional routes. For example, IPv6 default
     // gateway will generate /128 static direct route.
     // Hence, we only check:
@@ -29,18 +37,23 @@ impl Routes {
                 }
                 None => Vec::new(),
             };
+            let mut errors: Vec<NmstateError> = Vec::new();
             for desire_route in config_routes.iter().filter(|r| !r.is_absent())
             {
                 if !cur_config_routes.iter().any(|r| desire_route.is_match(r)) {
-                    let e = NmstateError::new(
-                        ErrorKind::VerificationError,
+                    let e = NmstateError::new_verification_error(
                         format!(
                             "Desired route {:?} not found after apply",
                             desire_route
                         ),
+                        vec![VerificationDifference::new(
+                            "routes.config".to_string(),
+                            serde_json::to_value(desire_route)?,
+                            serde_json::Value::Null,
+                        )],
                     );
                     log::error!("{}", e);
-                    return Err(e);
+                    errors.push(e);
                 }
             }
 
@@ -64,20 +77,26 @@ impl Routes {
                             true
                         } && absent_route.is_match(r))
                 {
-                    let e = NmstateError::new(
-                        ErrorKind::VerificationError,
+                    let e = NmstateError::new_verification_error(
                         format!(
                             "Desired absent route {:?} still found \
                             after apply: {:?}",
                             absent_route, cur_route
                         ),
+                        vec![VerificationDifference::new(
+                            "routes.config".to_string(),
+                            serde_json::to_value(absent_route)?,
+                            serde_json::to_value(cur_route)?,
+                        )],
                     );
                     log::error!("{}", e);
-                    return Err(e);
+                    errors.push(e);
                 }
             }
+            NmstateError::merge_verification_errors(errors)
+        } else {
+            Ok(())
         }
-        Ok(())
     }
 
     pub(crate) fn remove_ignored_iface_routes(
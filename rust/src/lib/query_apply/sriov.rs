@@ -19,6 +19,10 @@ impl SrIovConfig {
         }
     }
 
+    pub(crate) fn sriov_is_enabled(&self) -> bool {
+        matches!(self.total_vfs, Some(i) if i > 0)
+    }
+
     // Convert VF MAC address to upper case
     // Ignore 'vfs: []' which is just reverting all VF config to default.
     pub(crate) fn pre_verify_cleanup(&mut self) {
@@ -277,3 +281,32 @@ pub(crate) fn get_sriov_vf_iface_name(
     }
     None
 }
+
+pub(crate) fn check_sriov_capability(
+    ifaces: &Interfaces,
+) -> Result<(), NmstateError> {
+    for iface in ifaces.kernel_ifaces.values() {
+        if let Interface::Ethernet(eth_iface) = iface {
+            if eth_iface.sriov_is_enabled() && !is_sriov_supported(iface.name())
+            {
+                let e = NmstateError::new(
+                    ErrorKind::NotSupportedError,
+                    format!(
+                        "SR-IOV is not supported by interface {}",
+                        iface.name()
+                    ),
+                );
+                log::error!("{}", e);
+                return Err(e);
+            }
+        }
+    }
+    Ok(())
+}
+
+// Checking existence of file:
+//      /sys/class/net/<iface_name>/device/sriov_numvfs
+fn is_sriov_supported(iface_name: &str) -> bool {
+    let path = format!("/sys/class/net/{}/device/sriov_numvfs", iface_name);
+    std::path::Path::new(&path).exists()
+}
@@ -11,6 +11,18 @@ impl VlanInterface {
             self.vlan = other.vlan.clone();
         }
     }
+
+    pub(crate) fn change_parent_name(
+        &mut self,
+        origin_name: &str,
+        new_name: String,
+    ) {
+        if let Some(vlan_conf) = self.vlan.as_mut() {
+            if vlan_conf.base_iface == origin_name {
+                vlan_conf.base_iface = new_name;
+            }
+        }
+    }
 }
 
 impl VlanConfig {
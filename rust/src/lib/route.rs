@@ -9,6 +9,7 @@ use crate::{
 };
 
 #[derive(Debug, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 #[non_exhaustive]
 #[serde(deny_unknown_fields)]
 pub struct Routes {
@@ -23,6 +24,48 @@ impl Routes {
         Self::default()
     }
 
+    // Compare config routes of desired state `self` against `current`,
+    // return (routes to add, current routes to remove). Routes with next hop
+    // interface `skip_iface()` returns true for are ignored.
+    pub(crate) fn gen_add_and_remove<F>(
+        &self,
+        current: &Self,
+        skip_iface: F,
+    ) -> (Vec<RouteEntry>, Vec<RouteEntry>)
+    where
+        F: Fn(&str) -> bool,
+    {
+        let is_skipped = |route: &RouteEntry| {
+            route
+                .next_hop_iface
+                .as_deref()
+                .map(&skip_iface)
+                .unwrap_or_default()
+        };
+        let cur_routes = current.config.as_deref().unwrap_or_default();
+        let mut add_routes = Vec::new();
+        let mut remove_routes: Vec<RouteEntry> = Vec::new();
+        for route in self.config.as_deref().unwrap_or_default() {
+            if route.is_absent() {
+                for cur_route in cur_routes
+                    .iter()
+                    .filter(|r| route.is_match(r) && !is_skipped(r))
+                {
+                    let mut cur_route = cur_route.clone();
+                    cur_route.state = Some(RouteState::Absent);
+                    if !remove_routes.contains(&cur_route) {
+                        remove_routes.push(cur_route);
+                    }
+                }
+            } else if !is_skipped(route)
+                && !cur_routes.iter().any(|r| route.is_match(r))
+            {
+                add_routes.push(route.clone());
+            }
+        }
+        (add_routes, remove_routes)
+    }
+
     pub fn validate(&self) -> Result<(), NmstateError> {
         // All desire non-absent route should have next hop interface
         if let Some(config_routes) = self.config.as_ref() {
@@ -157,6 +200,7 @@ impl Routes {
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 #[serde(rename_all = "kebab-case")]
 #[non_exhaustive]
 pub enum RouteState {
@@ -170,6 +214,7 @@ impl Default for RouteState {
 }
 
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 #[serde(rename_all = "kebab-case")]
 #[non_exhaustive]
 #[serde(deny_unknown_fields)]
@@ -5,10 +5,11 @@ use serde::{Deserialize, Serialize};
 
 use crate::{
     ip::{is_ipv6_addr, sanitize_ip_network},
-    ErrorKind, InterfaceIpAddr, NmstateError,
+    ErrorKind, InterfaceIpAddr, NmstateError, VerificationDifference,
 };
 
 #[derive(Debug, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 #[non_exhaustive]
 #[serde(deny_unknown_fields)]
 pub struct RouteRules {
@@ -21,6 +22,31 @@ impl RouteRules {
         Self::default()
     }
 
+    // Compare config route rules of desired state `self` against `current`,
+    // return (route rules to add, current route rules to remove).
+    pub(crate) fn gen_add_and_remove(
+        &self,
+        current: &Self,
+    ) -> (Vec<RouteRuleEntry>, Vec<RouteRuleEntry>) {
+        let cur_rules = current.config.as_deref().unwrap_or_default();
+        let mut add_rules = Vec::new();
+        let mut remove_rules: Vec<RouteRuleEntry> = Vec::new();
+        for rule in self.config.as_deref().unwrap_or_default() {
+            if rule.is_absent() {
+                for cur_rule in cur_rules.iter().filter(|r| rule.is_match(r)) {
+                    let mut cur_rule = cur_rule.clone();
+                    cur_rule.state = Some(RouteRuleState::Absent);
+                    if !remove_rules.contains(&cur_rule) {
+                        remove_rules.push(cur_rule);
+                    }
+                }
+            } else if !cur_rules.iter().any(|r| rule.is_match(r)) {
+                add_rules.push(rule.clone());
+            }
+        }
+        (add_rules, remove_rules)
+    }
+
     // * Neither ip_from nor ip_to should be defined
     pub(crate) fn validate(&self) -> Result<(), NmstateError> {
         if let Some(rules) = self.config.as_ref() {
@@ -46,17 +72,22 @@ impl RouteRules {
                 Some(c) => c,
                 None => empty_vec.as_slice(),
             };
+            let mut errors: Vec<NmstateError> = Vec::new();
             for rule in rules.iter().filter(|r| !r.is_absent()) {
                 if !cur_rules.iter().any(|r| rule.is_match(r)) {
-                    let e = NmstateError::new(
-                        ErrorKind::VerificationError,
+                    let e = NmstateError::new_verification_error(
                         format!(
                             "Desired route rule {:?} not found after apply",
                             rule
                         ),
+                        vec![VerificationDifference::new(
+                            "route-rules.config".to_string(),
+                            serde_json::to_value(rule)?,
+                            serde_json::Value::Null,
+                        )],
                     );
                     log::error!("{}", e);
-                    return Err(e);
+                    errors.push(e);
                 }
             }
 
@@ -73,20 +104,26 @@ impl RouteRules {
                 if let Some(cur_rule) =
                     cur_rules.iter().find(|r| absent_rule.is_match(r))
                 {
-                    let e = NmstateError::new(
-                        ErrorKind::VerificationError,
+                    let e = NmstateError::new_verification_error(
                         format!(
                             "Desired absent route rule {:?} still found \
                             after apply: {:?}",
                             absent_rule, cur_rule
                         ),
+                        vec![VerificationDifference::new(
+                            "route-rules.config".to_string(),
+                            serde_json::to_value(absent_rule)?,
+                            serde_json::to_value(cur_rule)?,
+                        )],
                     );
                     log::error!("{}", e);
-                    return Err(e);
+                    errors.push(e);
                 }
             }
+            NmstateError::merge_verification_errors(errors)
+        } else {
+            Ok(())
         }
-        Ok(())
     }
 
     // RouteRuleEntry been added/removed for specific table id , all(including
@@ -188,6 +225,7 @@ impl RouteRules {
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 #[serde(rename_all = "kebab-case")]
 #[non_exhaustive]
 pub enum RouteRuleState {
@@ -201,6 +239,7 @@ impl Default for RouteRuleState {
 }
 
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 #[serde(rename_all = "kebab-case")]
 #[non_exhaustive]
 #[serde(deny_unknown_fields)]
@@ -251,11 +290,11 @@ impl RouteRuleEntry {
         Ok(())
     }
 
-    fn is_absent(&self) -> bool {
+    pub(crate) fn is_absent(&self) -> bool {
         matches!(self.state, Some(RouteRuleState::Absent))
     }
 
-    fn is_match(&self, other: &Self) -> bool {
+    pub(crate) fn is_match(&self, other: &Self) -> bool {
         if let Some(ip_from) = self.ip_from.as_deref() {
             let ip_from = if !ip_from.contains('/') {
                 match InterfaceIpAddr::try_from(ip_from) {
@@ -0,0 +1,578 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use serde::Deserialize;
+
+use crate::{ErrorKind, Interface, NetworkState, NmstateError};
+
+const TOP_LEVEL_PROPERTIES: [&str; 7] = [
+    "hostname",
+    "dns-resolver",
+    "route-rules",
+    "routes",
+    "interfaces",
+    "ovs-db",
+    "probes",
+];
+const OVSDB_GLOBAL_PROPERTIES: [&str; 2] = ["external_ids", "other_config"];
+const OVSDB_IFACE_PROPERTIES: [&str; 1] = ["external_ids"];
+// Properties of interface without known type
+const UNKNOWN_IFACE_PROPERTIES: [&str; 2] = ["name", "state"];
+
+impl NetworkState {
+    /// Parse the JSON string like [NetworkState::new_from_json()] but also
+    /// reject any property not consumed by nmstate, for example typo in
+    /// property name. Interface without known `type` is only allowed to
+    /// hold `name` and `state`.
+    /// No access to running system is required.
+    pub fn validate_offline(
+        net_state_json: &str,
+    ) -> Result<Self, NmstateError> {
+        let value: serde_json::Value = serde_json::from_str(net_state_json)
+            .map_err(|e| {
+                NmstateError::new(
+                    ErrorKind::InvalidArgument,
+                    format!("Invalid json string: {}", e),
+                )
+            })?;
+        let errors = get_unknown_properties(&value)?;
+        if errors.is_empty() {
+            Self::new_from_json(net_state_json)
+        } else {
+            for error in &errors {
+                log::error!("{}", error);
+            }
+            Err(NmstateError::new(
+                ErrorKind::InvalidArgument,
+                errors.join("; "),
+            ))
+        }
+    }
+}
+
+// Return error message for each unknown property with full property path.
+fn get_unknown_properties(
+    value: &serde_json::Value,
+) -> Result<Vec<String>, NmstateError> {
+    let mut ret = Vec::new();
+    let top = if let Some(top) = value.as_object() {
+        top
+    } else {
+        return Err(NmstateError::new(
+            ErrorKind::InvalidArgument,
+            format!("Expecting dict/HashMap, but got {}", value),
+        ));
+    };
+    ret.extend(get_unknown_keys(value, "", &TOP_LEVEL_PROPERTIES));
+    // Deserialize each section individually to include section name in
+    // error message of nested structs
+    for (key, section_value) in top.iter() {
+        if key == "interfaces" || !TOP_LEVEL_PROPERTIES.contains(&key.as_str())
+        {
+            continue;
+        }
+        let mut section = serde_json::Map::new();
+        section.insert(key.to_string(), section_value.clone());
+        if let Err(e) = deserialize_collect_unknown::<NetworkState>(
+            serde_json::Value::Object(section),
+            "",
+            &mut ret,
+        ) {
+            ret.push(format!("Invalid {}: {}", key, e));
+        }
+    }
+    if let Some(ovsdb_value) = top.get("ovs-db") {
+        ret.extend(get_unknown_keys(
+            ovsdb_value,
+            "ovs-db",
+            &OVSDB_GLOBAL_PROPERTIES,
+        ));
+    }
+    let iface_values = top
+        .get("interfaces")
+        .and_then(|v| v.as_array())
+        .map(|v| v.as_slice())
+        .unwrap_or_default();
+    for (i, iface_value) in iface_values.iter().enumerate() {
+        let path = format!("interfaces[{}]", i);
+        let iface = match deserialize_collect_unknown::<Interface>(
+            iface_value.clone(),
+            &path,
+            &mut ret,
+        ) {
+            Ok(i) => i,
+            Err(e) => {
+                ret.push(format!("Invalid {}: {}", path, e));
+                continue;
+            }
+        };
+        // Properties of absent interface are ignored on purpose
+        if iface.is_absent() {
+            continue;
+        }
+        if let Interface::Unknown(_) = iface {
+            if let Some(iface_type) = iface_value.get("type") {
+                ret.push(format!(
+                    "Unsupported interface type {} of {}.type",
+                    iface_type, path
+                ));
+            } else {
+                ret.extend(
+                    get_unknown_keys(
+                        iface_value,
+                        &path,
+                        &UNKNOWN_IFACE_PROPERTIES,
+                    )
+                    .into_iter()
+                    .map(|e| format!("{}, interface type is required", e)),
+                );
+            }
+            continue;
+        }
+        for key in iface.base_iface()._other.keys() {
+            ret.push(format!("Unknown property {}.{}", path, key));
+        }
+        if let Some(ovsdb_value) = iface_value.get("ovs-db") {
+            ret.extend(get_unknown_keys(
+                ovsdb_value,
+                &format!("{}.ovs-db", path),
+                &OVSDB_IFACE_PROPERTIES,
+            ));
+        }
+    }
+    Ok(ret)
+}
+
+// Nested structs refuse unknown property without telling where it is, while
+// the same property name might be valid in other nested struct. Check each
+// property holding the refused name by keeping it as the only one, record the
+// full path of those still refused and remove them, then retry till
+// deserialize succeeded or failed with other error, which is returned as
+// string.
+fn deserialize_collect_unknown<T>(
+    mut value: serde_json::Value,
+    path: &str,
+    errors: &mut Vec<String>,
+) -> Result<T, String>
+where
+    T: for<'de> Deserialize<'de>,
+{
+    loop {
+        let e = match T::deserialize(&value) {
+            Ok(v) => return Ok(v),
+            Err(e) => e.to_string(),
+        };
+        let key = match get_serde_unknown_field(&e) {
+            Some(k) => k.to_string(),
+            None => return Err(e),
+        };
+        let mut holders = Vec::new();
+        get_key_holders(&value, &key, "", path, &mut holders);
+        let unknown_holders: Vec<&(String, String)> = holders
+            .iter()
+            .filter(|(pointer, _)| {
+                let mut test_value = value.clone();
+                for (other, _) in holders.iter().filter(|(p, _)| p != pointer) {
+                    remove_key(&mut test_value, other, &key);
+                }
+                is_unknown_field::<T>(&test_value, &key)
+            })
+            .collect();
+        if unknown_holders.is_empty() {
+            return Err(e);
+        }
+        for (pointer, key_path) in unknown_holders {
+            errors.push(format!("Unknown property {}", key_path));
+            remove_key(&mut value, pointer, &key);
+        }
+    }
+}
+
+fn is_unknown_field<T>(value: &serde_json::Value, key: &str) -> bool
+where
+    T: for<'de> Deserialize<'de>,
+{
+    match T::deserialize(value) {
+        Ok(_) => false,
+        Err(e) => get_serde_unknown_field(&e.to_string()) == Some(key),
+    }
+}
+
+// Extract field name from serde error message like:
+//  unknown field `mtuu`, expected one of ...
+fn get_serde_unknown_field(msg: &str) -> Option<&str> {
+    msg.strip_prefix("unknown field `")
+        .and_then(|s| s.split_once('`'))
+        .map(|(key, _)| key)
+}
+
+// Collect JSON pointer and full property path of each object holding
+// property named `key`.
+fn get_key_holders(
+    value: &serde_json::Value,
+    key: &str,
+    pointer: &str,
+    path: &str,
+    holders: &mut Vec<(String, String)>,
+) {
+    match value {
+        serde_json::Value::Object(obj) => {
+            if obj.contains_key(key) {
+                holders.push((pointer.to_string(), join_path(path, key)));
+            }
+            for (cur_key, cur_value) in obj.iter() {
+                get_key_holders(
+                    cur_value,
+                    key,
+                    &format!(
+                        "{}/{}",
+                        pointer,
+                        cur_key.replace('~', "~0").replace('/', "~1")
+                    ),
+                    &join_path(path, cur_key),
+                    holders,
+                );
+            }
+        }
+        serde_json::Value::Array(items) => {
+            for (i, item) in items.iter().enumerate() {
+                get_key_holders(
+                    item,
+                    key,
+                    &format!("{}/{}", pointer, i),
+                    &format!("{}[{}]", path, i),
+                    holders,
+                );
+            }
+        }
+        _ => (),
+    }
+}
+
+fn remove_key(value: &mut serde_json::Value, pointer: &str, key: &str) {
+    if let Some(obj) =
+        value.pointer_mut(pointer).and_then(|v| v.as_object_mut())
+    {
+        obj.remove(key);
+    }
+}
+
+fn join_path(path: &str, key: &str) -> String {
+    if path.is_empty() {
+        key.to_string()
+    } else {
+        format!("{}.{}", path, key)
+    }
+}
+
+fn get_unknown_keys(
+    value: &serde_json::Value,
+    path: &str,
+    known_keys: &[&str],
+) -> Vec<String> {
+    let mut ret = Vec::new();
+    if let Some(obj) = value.as_object() {
+        for key in obj.keys() {
+            if !known_keys.contains(&key.as_str()) {
+                if path.is_empty() {
+                    ret.push(format!("Unknown property {}", key));
+                } else {
+                    ret.push(format!("Unknown property {}.{}", path, key));
+                }
+            }
+        }
+    }
+    ret
+}
+
+#[cfg(feature = "schema")]
+pub(crate) mod json_schema {
+    use schemars::{gen::SchemaGenerator, schema::Schema, JsonSchema};
+    use serde_json::json;
+
+    use crate::{
+        ip::InterfaceIp, BondInterface, Dhcpv4ClientId, Dhcpv6Duid,
+        DummyInterface, EthernetInterface, InfiniBandInterface, Interface,
+        InterfaceIpv4, InterfaceIpv6, InterfaceState, InterfaceType,
+        Interfaces, Ipv6AddrGenMode, LinuxBridgeInterface,
+        LinuxBridgeMulticastRouterType, MacVlanInterface, MacVtapInterface,
+        NetworkState, NmstateError, OvsBridgeInterface, OvsDbGlobalConfig,
+        OvsDbIfaceConfig, OvsInterface, UnknownInterface, VlanInterface,
+        VrfInterface, VxlanInterface,
+    };
+
+    impl NetworkState {
+        /// JSON Schema(draft 7) of network state in pretty JSON string.
+        /// Unknown properties are not allowed, just like
+        /// [NetworkState::validate_offline()]. Number or boolean in string
+        /// format, which is accepted by nmstate, is not allowed by schema.
+        pub fn json_schema() -> Result<String, NmstateError> {
+            let mut schema = schemars::schema_for!(NetworkState);
+            close_object(&mut schema.schema);
+            for def in schema.definitions.values_mut() {
+                if let Schema::Object(obj) = def {
+                    close_object(obj);
+                }
+            }
+            Ok(serde_json::to_string_pretty(&schema)?)
+        }
+    }
+
+    // Interface structs flatten `BaseInterface` which does not allow
+    // unknown properties as `deny_unknown_fields` is not inherited.
+    fn close_object(obj: &mut schemars::schema::SchemaObject) {
+        if let Some(obj_valid) = obj.object.as_mut() {
+            if !obj_valid.properties.is_empty()
+                && obj_valid.additional_properties.is_none()
+            {
+                obj_valid.additional_properties = Some(Box::new(false.into()));
+            }
+        }
+    }
+
+    fn to_schema(value: serde_json::Value) -> Schema {
+        serde_json::from_value(value).unwrap_or(Schema::Bool(true))
+    }
+
+    fn typed_iface_schema<T: JsonSchema>(
+        gen: &mut SchemaGenerator,
+        iface_types: &[InterfaceType],
+    ) -> serde_json::Value {
+        let iface_types: Vec<String> =
+            iface_types.iter().map(|t| t.to_string()).collect();
+        json!({
+            "allOf": [
+                gen.subschema_for::<T>(),
+                {
+                    "required": ["type"],
+                    "properties": {"type": {"enum": iface_types}}
+                }
+            ]
+        })
+    }
+
+    impl JsonSchema for Interface {
+        fn schema_name() -> String {
+            "Interface".to_string()
+        }
+
+        fn json_schema(gen: &mut SchemaGenerator) -> Schema {
+            to_schema(json!({
+                "anyOf": [
+                    typed_iface_schema::<BondInterface>(
+                        gen,
+                        &[InterfaceType::Bond],
+                    ),
+                    typed_iface_schema::<DummyInterface>(
+                        gen,
+                        &[InterfaceType::Dummy],
+                    ),
+                    typed_iface_schema::<EthernetInterface>(
+                        gen,
+                        &[InterfaceType::Ethernet, InterfaceType::Veth],
+                    ),
+                    typed_iface_schema::<LinuxBridgeInterface>(
+                        gen,
+                        &[InterfaceType::LinuxBridge],
+                    ),
+                    typed_iface_schema::<OvsBridgeInterface>(
+                        gen,
+                        &[InterfaceType::OvsBridge],
+                    ),
+                    typed_iface_schema::<OvsInterface>(
+                        gen,
+                        &[InterfaceType::OvsInterface],
+                    ),
+                    typed_iface_schema::<VlanInterface>(
+                        gen,
+                        &[InterfaceType::Vlan],
+                    ),
+                    typed_iface_schema::<VxlanInterface>(
+                        gen,
+                        &[InterfaceType::Vxlan],
+                    ),
+                    typed_iface_schema::<MacVlanInterface>(
+                        gen,
+                        &[InterfaceType::MacVlan],
+                    ),
+                    typed_iface_schema::<MacVtapInterface>(
+                        gen,
+                        &[InterfaceType::MacVtap],
+                    ),
+                    typed_iface_schema::<VrfInterface>(
+                        gen,
+                        &[InterfaceType::Vrf],
+                    ),
+                    typed_iface_schema::<InfiniBandInterface>(
+                        gen,
+                        &[InterfaceType::InfiniBand],
+                    ),
+                    gen.subschema_for::<UnknownInterface>(),
+                ]
+            }))
+        }
+    }
+
+    // Interface without type, only allowed to change state of existing
+    // interface.
+    impl JsonSchema for UnknownInterface {
+        fn schema_name() -> String {
+            "UnknownInterface".to_string()
+        }
+
+        fn json_schema(gen: &mut SchemaGenerator) -> Schema {
+            to_schema(json!({
+                "type": "object",
+                "required": ["name"],
+                "properties": {
+                    "name": {"type": "string"},
+                    "state": gen.subschema_for::<InterfaceState>(),
+                },
+                "additionalProperties": false
+            }))
+        }
+    }
+
+    impl JsonSchema for InterfaceType {
+        fn schema_name() -> String {
+            "InterfaceType".to_string()
+        }
+
+        fn json_schema(_gen: &mut SchemaGenerator) -> Schema {
+            to_schema(json!({"type": "string"}))
+        }
+    }
+
+    impl JsonSchema for Interfaces {
+        fn schema_name() -> String {
+            "Interfaces".to_string()
+        }
+
+        fn json_schema(gen: &mut SchemaGenerator) -> Schema {
+            to_schema(json!({
+                "type": "array",
+                "items": gen.subschema_for::<Interface>()
+            }))
+        }
+    }
+
+    impl JsonSchema for InterfaceIpv4 {
+        fn schema_name() -> String {
+            "InterfaceIpv4".to_string()
+        }
+
+        fn json_schema(gen: &mut SchemaGenerator) -> Schema {
+            ip_schema(gen, &["autoconf", "dhcp-duid", "addr-gen-mode"])
+        }
+    }
+
+    impl JsonSchema for InterfaceIpv6 {
+        fn schema_name() -> String {
+            "InterfaceIpv6".to_string()
+        }
+
+        fn json_schema(gen: &mut SchemaGenerator) -> Schema {
+            ip_schema(gen, &["dhcp-client-id"])
+        }
+    }
+
+    // IPv4 and IPv6 share the same serde struct `InterfaceIp`.
+    fn ip_schema(gen: &mut SchemaGenerator, excludes: &[&str]) -> Schema {
+        let mut schema = InterfaceIp::json_schema(gen);
+        if let Schema::Object(obj) = &mut schema {
+            if let Some(obj_valid) = obj.object.as_mut() {
+                for exclude in excludes {
+                    obj_valid.properties.remove(*exclude);
+                }
+            }
+        }
+        schema
+    }
+
+    impl JsonSchema for OvsDbGlobalConfig {
+        fn schema_name() -> String {
+            "OvsDbGlobalConfig".to_string()
+        }
+
+        fn json_schema(_gen: &mut SchemaGenerator) -> Schema {
+            to_schema(json!({
+                "type": "object",
+                "properties": {
+                    "external_ids": ovsdb_map_schema(),
+                    "other_config": ovsdb_map_schema(),
+                },
+                "additionalProperties": false
+            }))
+        }
+    }
+
+    impl JsonSchema for OvsDbIfaceConfig {
+        fn schema_name() -> String {
+            "OvsDbIfaceConfig".to_string()
+        }
+
+        fn json_schema(_gen: &mut SchemaGenerator) -> Schema {
+            to_schema(json!({
+                "type": "object",
+                "properties": {
+                    "external_ids": ovsdb_map_schema(),
+                },
+                "additionalProperties": false
+            }))
+        }
+    }
+
+    impl JsonSchema for LinuxBridgeMulticastRouterType {
+        fn schema_name() -> String {
+            "LinuxBridgeMulticastRouterType".to_string()
+        }
+
+        fn json_schema(_gen: &mut SchemaGenerator) -> Schema {
+            to_schema(json!({
+                "anyOf": [
+                    {"enum": ["auto", "disabled", "enabled"]},
+                    {"type": "integer", "minimum": 0, "maximum": 2}
+                ]
+            }))
+        }
+    }
+
+    // Integer also accepted in hex string format, like `0x80ff`
+    pub(crate) struct IntegerOrString;
+
+    impl JsonSchema for IntegerOrString {
+        fn schema_name() -> String {
+            "IntegerOrString".to_string()
+        }
+
+        fn json_schema(_gen: &mut SchemaGenerator) -> Schema {
+            to_schema(json!({"type": ["integer", "string"]}))
+        }
+    }
+
+    // Null value means removing the key
+    fn ovsdb_map_schema() -> serde_json::Value {
+        json!({
+            "type": "object",
+            "additionalProperties": {"type": ["string", "number", "null"]}
+        })
+    }
+
+    // Types serialized as string with free form allowed
+    macro_rules! impl_string_schema {
+        ($($t:ident),*) => {
+            $(
+                impl JsonSchema for $t {
+                    fn schema_name() -> String {
+                        stringify!($t).to_string()
+                    }
+
+                    fn json_schema(_gen: &mut SchemaGenerator) -> Schema {
+                        to_schema(json!({"type": "string"}))
+                    }
+                }
+            )*
+        };
+    }
+
+    impl_string_schema!(Dhcpv4ClientId, Dhcpv6Duid, Ipv6AddrGenMode);
+}
@@ -0,0 +1,89 @@
+// SPDX-License-Identifier: Apache-2.0
+
+// NetworkManager checkpoint does not cover some changes done by nmstate
+// directly, like OVSDB `external_ids` and hostname. Their pre-apply values are
+// stored as snapshot files in a folder per backend, named by NetworkManager
+// checkpoint, so that a separate process can commit or rollback them.
+
+use std::io::Write;
+use std::os::unix::fs::OpenOptionsExt;
+use std::time::SystemTime;
+
+use crate::{ErrorKind, NmstateError};
+
+// Store the snapshot content of specified checkpoint, readable by root only
+// as it might hold secrets.
+pub(crate) fn snapshot_save(
+    dir: &str,
+    checkpoint: &str,
+    content: &str,
+) -> Result<(), NmstateError> {
+    std::fs::create_dir_all(dir).map_err(|e| {
+        checkpoint_error(format!("Failed to create folder {}", dir), e)
+    })?;
+    let path = gen_snapshot_path(dir, checkpoint);
+    std::fs::OpenOptions::new()
+        .write(true)
+        .create(true)
+        .truncate(true)
+        .mode(0o600)
+        .open(&path)
+        .and_then(|mut fd| fd.write_all(content.as_bytes()))
+        .map_err(|e| checkpoint_error(format!("Failed to write {}", path), e))
+}
+
+// Return the snapshot file path of specified checkpoint if exists.
+// Empty checkpoint means the last checkpoint, hence the newest snapshot.
+pub(crate) fn snapshot_get_path(dir: &str, checkpoint: &str) -> Option<String> {
+    if checkpoint.is_empty() {
+        let mut snapshots: Vec<(SystemTime, String)> = std::fs::read_dir(dir)
+            .ok()?
+            .filter_map(|e| e.ok())
+            .filter_map(|e| {
+                let modified = e.metadata().ok()?.modified().ok()?;
+                Some((modified, e.path().to_str()?.to_string()))
+            })
+            .collect();
+        snapshots.sort_unstable();
+        snapshots.pop().map(|(_, path)| path)
+    } else {
+        let path = gen_snapshot_path(dir, checkpoint);
+        if std::path::Path::new(&path).exists() {
+            Some(path)
+        } else {
+            None
+        }
+    }
+}
+
+pub(crate) fn snapshot_read(path: &str) -> Result<String, NmstateError> {
+    std::fs::read_to_string(path)
+        .map_err(|e| checkpoint_error(format!("Failed to read {}", path), e))
+}
+
+pub(crate) fn snapshot_remove(path: &str) -> Result<(), NmstateError> {
+    std::fs::remove_file(path)
+        .map_err(|e| checkpoint_error(format!("Failed to remove {}", path), e))
+}
+
+fn gen_snapshot_path(dir: &str, checkpoint: &str) -> String {
+    format!(
+        "{}/{}.json",
+        dir,
+        checkpoint.trim_start_matches('/').replace('/', "_")
+    )
+}
+
+pub(crate) fn checkpoint_error(
+    action: String,
+    e: std::io::Error,
+) -> NmstateError {
+    NmstateError::new(ErrorKind::PluginFailure, format!("{}: {}", action, e))
+}
+
+pub(crate) fn now_secs() -> u64 {
+    SystemTime::now()
+        .duration_since(SystemTime::UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or_default()
+}
@@ -1,94 +1,110 @@
 use serde_json::Value;
 
-use crate::NetworkState;
+use crate::{NetworkState, NmstateError, VerificationDifference};
 
-fn _get_json_value_difference<'a, 'b>(
+fn _get_json_value_differences<'a, 'b>(
     reference: String,
     desire: &'a Value,
     current: &'b Value,
-) -> Option<(String, &'a Value, &'b Value)> {
+    differences: &mut Vec<(String, &'a Value, &'b Value)>,
+) {
     match (desire, current) {
         (Value::Bool(des), Value::Bool(cur)) => {
             if des != cur {
-                Some((reference, desire, current))
-            } else {
-                None
+                differences.push((reference, desire, current));
             }
         }
         (Value::Number(des), Value::Number(cur)) => {
             if des != cur {
-                Some((reference, desire, current))
-            } else {
-                None
+                differences.push((reference, desire, current));
             }
         }
         (Value::String(des), Value::String(cur)) => {
-            if des != cur {
-                if des == NetworkState::PASSWORD_HID_BY_NMSTATE {
-                    None
-                } else {
-                    Some((reference, desire, current))
-                }
-            } else {
-                None
+            if des != cur && des != NetworkState::PASSWORD_HID_BY_NMSTATE {
+                differences.push((reference, desire, current));
             }
         }
         (Value::Array(des), Value::Array(cur)) => {
             if des.len() != cur.len() {
-                Some((reference, desire, current))
+                differences.push((reference, desire, current));
             } else {
                 for (index, des_element) in des.iter().enumerate() {
                     // The [] is safe as we already checked the length
                     let cur_element = &cur[index];
-                    if let Some(difference) = get_json_value_difference(
+                    _get_json_value_differences(
                         format!("{}[{}]", &reference, index),
                         des_element,
                         cur_element,
-                    ) {
-                        return Some(difference);
-                    }
+                        differences,
+                    );
                 }
-                None
             }
         }
         (Value::Object(des), Value::Object(cur)) => {
             for (key, des_value) in des.iter() {
                 let reference = format!("{}.{}", reference, key);
                 if let Some(cur_value) = cur.get(key) {
-                    if let Some(difference) = get_json_value_difference(
-                        reference.clone(),
+                    _get_json_value_differences(
+                        reference,
                         des_value,
                         cur_value,
-                    ) {
-                        return Some(difference);
-                    }
+                        differences,
+                    );
                 } else if des_value != &Value::Null {
-                    return Some((reference, des_value, &Value::Null));
+                    differences.push((reference, des_value, &Value::Null));
                 }
             }
-            None
         }
-        (Value::Null, _) => None,
-        (_, _) => Some((reference, desire, current)),
+        (Value::Null, _) => (),
+        (_, _) => differences.push((reference, desire, current)),
     }
 }
 
-pub(crate) fn get_json_value_difference<'a, 'b>(
+// Return all the differences in the order of desire properties.
+pub(crate) fn get_json_value_differences<'a, 'b>(
     reference: String,
     desire: &'a Value,
     current: &'b Value,
-) -> Option<(String, &'a Value, &'b Value)> {
-    if let Some((reference, desire, current)) =
-        _get_json_value_difference(reference, desire, current)
-    {
-        if should_ignore(reference.as_str(), desire, current) {
-            None
-        } else {
-            Some((reference, desire, current))
-        }
-    } else {
-        None
-    }
+) -> Vec<(String, &'a Value, &'b Value)> {
+    let mut differences = Vec::new();
+    _get_json_value_differences(reference, desire, current, &mut differences);
+    differences.retain(|(reference, desire, current)| {
+        !should_ignore(reference.as_str(), desire, current)
+    });
+    differences
+}
+
+pub(crate) fn gen_verification_error(
+    differences: &[(String, &Value, &Value)],
+) -> NmstateError {
+    NmstateError::new_verification_error(
+        differences
+            .iter()
+            .map(|(reference, desire, current)| {
+                format!(
+                    "Verification failure: {} desire '{}', current '{}'",
+                    reference, desire, current
+                )
+            })
+            .collect::<Vec<String>>()
+            .join("\n"),
+        gen_verification_differences(differences),
+    )
+}
+
+pub(crate) fn gen_verification_differences(
+    differences: &[(String, &Value, &Value)],
+) -> Vec<VerificationDifference> {
+    differences
+        .iter()
+        .map(|(reference, desire, current)| {
+            VerificationDifference::new(
+                reference.to_string(),
+                (*desire).clone(),
+                (*current).clone(),
+            )
+        })
+        .collect()
 }
 
 fn should_ignore(reference: &str, desire: &Value, current: &Value) -> bool {
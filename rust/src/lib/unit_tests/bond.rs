@@ -430,3 +430,65 @@ fn test_bond_ports() {
     .unwrap();
     assert_eq!(ifaces.to_vec()[0].ports(), Some(vec!["eth1", "eth2"]));
 }
+
+#[cfg(feature = "gen_conf")]
+#[test]
+fn test_bond_opts_from_kernel_options() {
+    use crate::BondOptions;
+
+    let opts = [
+        ("miimon".to_string(), "100".to_string()),
+        ("peer_notif_delay".to_string(), "0".to_string()),
+        ("xmit_hash_policy".to_string(), "layer3+4".to_string()),
+    ];
+    let (bond_opts, unsupported) =
+        BondOptions::from_kernel_options(&opts).unwrap();
+
+    assert_eq!(bond_opts.miimon, Some(100));
+    assert_eq!(
+        bond_opts.xmit_hash_policy,
+        Some(BondXmitHashPolicy::Layer34)
+    );
+    assert_eq!(unsupported, ["peer_notif_delay"]);
+
+    let result = BondOptions::from_kernel_options(&[(
+        "miimon".to_string(),
+        "fast".to_string(),
+    )]);
+    assert!(result.is_err());
+    if let Err(e) = result {
+        assert_eq!(e.kind(), ErrorKind::InvalidArgument);
+    }
+}
+
+#[cfg(feature = "gen_conf")]
+#[test]
+fn test_bond_opts_kernel_options_round_trip() {
+    use crate::BondOptions;
+
+    let bond_opts: BondOptions = serde_yaml::from_str(
+        r#"---
+ad_actor_sys_prio: 100
+ad_actor_system: 00:23:45:67:89:1a
+ad_select: bandwidth
+all_slaves_active: delivered
+lacp_rate: fast
+lp_interval: 2
+miimon: 100
+min_links: 1
+primary_reselect: better
+resend_igmp: 3
+tlb_dynamic_lb: false
+use_carrier: true
+xmit_hash_policy: layer3+4
+"#,
+    )
+    .unwrap();
+
+    let (new_bond_opts, unsupported) =
+        BondOptions::from_kernel_options(&bond_opts.to_kernel_options())
+            .unwrap();
+
+    assert!(unsupported.is_empty());
+    assert_eq!(new_bond_opts, bond_opts);
+}
@@ -334,3 +334,34 @@ bridge:
 
     assert_eq!(desired.get_config_changed_ports(&current), vec!["eth1"])
 }
+
+#[cfg(feature = "query_apply")]
+#[test]
+fn test_linux_bridge_options_to_kernel_options() {
+    let opts = serde_yaml::from_str::<crate::LinuxBridgeOptions>(
+        r#"---
+stp:
+  enabled: true
+  forward-delay: 15
+  priority: 32768
+mac-ageing-time: 300
+multicast-snooping: false
+multicast-router: enabled
+vlan-protocol: 802.1ad
+"#,
+    )
+    .unwrap();
+
+    assert_eq!(
+        opts.to_kernel_options(),
+        vec![
+            ("ageing_time".to_string(), "30000".to_string()),
+            ("forward_delay".to_string(), "1500".to_string()),
+            ("multicast_router".to_string(), "2".to_string()),
+            ("multicast_snooping".to_string(), "0".to_string()),
+            ("priority".to_string(), "32768".to_string()),
+            ("stp_state".to_string(), "1".to_string()),
+            ("vlan_protocol".to_string(), "0x88a8".to_string()),
+        ]
+    );
+}
@@ -0,0 +1,172 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use std::path::PathBuf;
+
+use crate::{
+    nispor::{get_checkpoint_path, KernelCheckpoint},
+    InterfaceState, InterfaceType, NetworkState, RouteRuleState, RouteState,
+};
+
+fn gen_test_dir() -> PathBuf {
+    std::env::temp_dir().join(format!("nmstate-test-{}", uuid::Uuid::new_v4()))
+}
+
+fn gen_test_pre_apply_state() -> NetworkState {
+    serde_yaml::from_str(
+        r#"---
+interfaces:
+- name: eth1
+  type: ethernet
+  state: up
+  mtu: 1500
+routes:
+  config:
+  - destination: 192.0.2.0/24
+    next-hop-interface: eth1
+    metric: 100
+    table-id: 254
+"#,
+    )
+    .unwrap()
+}
+
+#[test]
+fn test_gen_revert_state() {
+    let pre_apply_state = gen_test_pre_apply_state();
+    let cur_state: NetworkState = serde_yaml::from_str(
+        r#"---
+interfaces:
+- name: eth1
+  type: ethernet
+  state: up
+  mtu: 9000
+- name: dummy1
+  type: dummy
+  state: up
+routes:
+  config:
+  - destination: 192.0.2.0/24
+    next-hop-interface: eth1
+    metric: 100
+    table-id: 254
+  - destination: 198.51.100.0/24
+    next-hop-interface: dummy1
+    metric: 100
+    table-id: 254
+route-rules:
+  config:
+  - ip-from: 192.0.2.1/32
+    route-table: 100
+"#,
+    )
+    .unwrap();
+
+    let revert_state = pre_apply_state.gen_revert_state(&cur_state);
+
+    let eth1 = revert_state
+        .interfaces
+        .get_iface("eth1", InterfaceType::Ethernet)
+        .unwrap();
+    assert_eq!(eth1.base_iface().state, InterfaceState::Up);
+    assert_eq!(eth1.base_iface().mtu, Some(1500));
+    let dummy1 = revert_state
+        .interfaces
+        .get_iface("dummy1", InterfaceType::Dummy)
+        .unwrap();
+    assert_eq!(dummy1.base_iface().state, InterfaceState::Absent);
+
+    let routes = revert_state.routes.config.as_ref().unwrap();
+    assert_eq!(routes.len(), 2);
+    assert_eq!(routes[0].destination.as_deref(), Some("192.0.2.0/24"));
+    assert_eq!(routes[0].state, None);
+    assert_eq!(routes[1].destination.as_deref(), Some("198.51.100.0/24"));
+    assert_eq!(routes[1].state, Some(RouteState::Absent));
+
+    let rules = revert_state.rules.config.as_ref().unwrap();
+    assert_eq!(rules.len(), 1);
+    assert_eq!(rules[0].ip_from.as_deref(), Some("192.0.2.1/32"));
+    assert_eq!(rules[0].state, Some(RouteRuleState::Absent));
+}
+
+#[test]
+fn test_gen_revert_state_no_change() {
+    let pre_apply_state = gen_test_pre_apply_state();
+
+    let revert_state = pre_apply_state.gen_revert_state(&pre_apply_state);
+
+    assert_eq!(revert_state.interfaces, pre_apply_state.interfaces);
+    assert_eq!(revert_state.routes, pre_apply_state.routes);
+    assert_eq!(revert_state.rules.config, Some(Vec::new()));
+}
+
+#[test]
+fn test_kernel_checkpoint_save_and_read() {
+    let dir = gen_test_dir();
+    let dir_str = dir.to_str().unwrap();
+    let pre_apply_state = gen_test_pre_apply_state();
+
+    let checkpoint = KernelCheckpoint::new(&pre_apply_state, 60)
+        .save(dir_str)
+        .unwrap();
+
+    assert_eq!(
+        get_checkpoint_path(dir_str, &checkpoint).unwrap(),
+        Some(checkpoint.clone())
+    );
+    let kernel_checkpoint = KernelCheckpoint::read(&checkpoint).unwrap();
+    std::fs::remove_dir_all(&dir).unwrap();
+
+    assert_eq!(kernel_checkpoint.timeout, 60);
+    assert!(kernel_checkpoint.created > 0);
+    assert_eq!(
+        kernel_checkpoint.state.interfaces,
+        pre_apply_state.interfaces
+    );
+    assert_eq!(kernel_checkpoint.state.routes, pre_apply_state.routes);
+}
+
+#[test]
+fn test_kernel_checkpoint_path_not_kernel() {
+    let dir = gen_test_dir();
+    let dir_str = dir.to_str().unwrap();
+
+    assert_eq!(
+        get_checkpoint_path(
+            dir_str,
+            "/org/freedesktop/NetworkManager/Checkpoint/1"
+        )
+        .unwrap(),
+        None
+    );
+    assert_eq!(
+        get_checkpoint_path(dir_str, &format!("{}-other/1-1.json", dir_str))
+            .unwrap(),
+        None
+    );
+}
+
+#[test]
+fn test_kernel_checkpoint_path_invalid() {
+    let dir = gen_test_dir();
+    let dir_str = dir.to_str().unwrap();
+    std::fs::create_dir_all(dir.join("sub")).unwrap();
+    for name in ["other.json", "1-1.json.tmp", "sub/1-1.json"] {
+        std::fs::write(dir.join(name), "{}").unwrap();
+    }
+
+    let results: Vec<bool> = [
+        "other.json",
+        "1-1.json.tmp",
+        "sub/1-1.json",
+        "sub/../1-1.json",
+        "2-2.json",
+    ]
+    .iter()
+    .map(|name| {
+        get_checkpoint_path(dir_str, &format!("{}/{}", dir_str, name)).is_err()
+    })
+    .collect();
+    std::fs::remove_dir_all(&dir).unwrap();
+
+    assert_eq!(results, vec![true; 5]);
+}
@@ -0,0 +1,311 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{InterfaceType, NetworkState, RouteState};
+
+#[test]
+fn test_diff_ifaces() {
+    let old: NetworkState = serde_yaml::from_str(
+        r#"---
+interfaces:
+- name: eth1
+  type: ethernet
+  state: up
+  mtu: 1500
+  ipv4:
+    enabled: true
+    address:
+    - ip: 192.0.2.1
+      prefix-length: 24
+- name: dummy1
+  type: dummy
+  state: up
+- name: dummy2
+  type: dummy
+  state: up
+"#,
+    )
+    .unwrap();
+    let new: NetworkState = serde_yaml::from_str(
+        r#"---
+interfaces:
+- name: eth1
+  type: ethernet
+  state: up
+  mtu: 9000
+  ipv4:
+    enabled: true
+    address:
+    - ip: 192.0.2.2
+      prefix-length: 24
+- name: dummy1
+  type: dummy
+  state: absent
+- name: dummy3
+  type: dummy
+  state: up
+"#,
+    )
+    .unwrap();
+
+    let diff = old.diff(&new).unwrap();
+
+    assert_eq!(diff.add_interfaces.len(), 1);
+    assert_eq!(diff.add_interfaces[0].name(), "dummy3");
+    assert_eq!(diff.remove_interfaces.len(), 2);
+    assert_eq!(diff.remove_interfaces[0].name, "dummy1");
+    assert_eq!(diff.remove_interfaces[0].iface_type, InterfaceType::Dummy);
+    assert_eq!(diff.remove_interfaces[1].name, "dummy2");
+    assert_eq!(diff.change_interfaces.len(), 1);
+    assert_eq!(diff.change_interfaces[0].name, "eth1");
+    let changes = &diff.change_interfaces[0].changes;
+    assert_eq!(changes.len(), 2);
+    assert_eq!(changes[0].path, "ipv4.address[0].ip");
+    assert_eq!(changes[0].old, serde_json::json!("192.0.2.1"));
+    assert_eq!(changes[0].new, serde_json::json!("192.0.2.2"));
+    assert_eq!(changes[1].path, "mtu");
+    assert_eq!(changes[1].old, serde_json::json!(1500));
+    assert_eq!(changes[1].new, serde_json::json!(9000));
+}
+
+#[test]
+fn test_diff_routes_dns_hostname() {
+    let old: NetworkState = serde_yaml::from_str(
+        r#"---
+hostname:
+  config: host-a
+dns-resolver:
+  config:
+    server:
+    - 192.0.2.250
+routes:
+  config:
+  - destination: 198.51.100.0/24
+    next-hop-address: 192.0.2.254
+    next-hop-interface: eth1
+route-rules:
+  config:
+  - ip-from: 192.0.2.0/24
+    route-table: 500
+"#,
+    )
+    .unwrap();
+    let new: NetworkState = serde_yaml::from_str(
+        r#"---
+hostname:
+  config: host-b
+dns-resolver:
+  config:
+    server:
+    - 192.0.2.251
+routes:
+  config:
+  - destination: 198.51.100.0/24
+    state: absent
+  - destination: 203.0.113.0/24
+    next-hop-address: 192.0.2.254
+    next-hop-interface: eth1
+route-rules:
+  config:
+  - ip-from: 192.0.2.0/24
+    route-table: 500
+"#,
+    )
+    .unwrap();
+
+    let diff = old.diff(&new).unwrap();
+
+    assert!(diff.add_interfaces.is_empty());
+    assert_eq!(diff.remove_routes.len(), 1);
+    assert_eq!(
+        diff.remove_routes[0].destination.as_deref(),
+        Some("198.51.100.0/24")
+    );
+    assert_eq!(diff.add_routes.len(), 1);
+    assert_eq!(
+        diff.add_routes[0].destination.as_deref(),
+        Some("203.0.113.0/24")
+    );
+    assert!(diff.add_route_rules.is_empty());
+    assert!(diff.remove_route_rules.is_empty());
+    assert_eq!(diff.dns.len(), 1);
+    assert_eq!(diff.dns[0].path, "dns-resolver.config.server[0]");
+    let hostname = diff.hostname.unwrap();
+    assert_eq!(hostname.old, serde_json::json!("host-a"));
+    assert_eq!(hostname.new, serde_json::json!("host-b"));
+}
+
+#[test]
+fn test_diff_missing_iface_and_route() {
+    let old: NetworkState = serde_yaml::from_str(
+        r#"---
+interfaces:
+- name: eth1
+  type: ethernet
+  state: up
+- name: bond0
+  type: bond
+  state: up
+  link-aggregation:
+    mode: balance-rr
+routes:
+  config:
+  - destination: 198.51.100.0/24
+    next-hop-address: 192.0.2.254
+    next-hop-interface: eth1
+  - destination: 203.0.113.0/24
+    next-hop-address: 192.0.2.254
+    next-hop-interface: eth1
+"#,
+    )
+    .unwrap();
+    let new: NetworkState = serde_yaml::from_str(
+        r#"---
+interfaces:
+- name: eth1
+  type: ethernet
+  state: up
+routes:
+  config:
+  - destination: 198.51.100.0/24
+    next-hop-address: 192.0.2.254
+    next-hop-interface: eth1
+"#,
+    )
+    .unwrap();
+
+    let diff = old.diff(&new).unwrap();
+
+    assert!(diff.add_interfaces.is_empty());
+    assert!(diff.change_interfaces.is_empty());
+    assert_eq!(diff.remove_interfaces.len(), 1);
+    assert_eq!(diff.remove_interfaces[0].name, "bond0");
+    assert_eq!(diff.remove_interfaces[0].iface_type, InterfaceType::Bond);
+    assert!(diff.add_routes.is_empty());
+    assert_eq!(diff.remove_routes.len(), 1);
+    assert_eq!(
+        diff.remove_routes[0].destination.as_deref(),
+        Some("203.0.113.0/24")
+    );
+    assert_eq!(diff.remove_routes[0].state, Some(RouteState::Absent));
+
+    // Missing entries in desired state are untouched by apply
+    assert!(old.diff_desired(&new).unwrap().is_empty());
+}
+
+#[test]
+fn test_diff_same_state_is_empty() {
+    let state: NetworkState = serde_yaml::from_str(
+        r#"---
+interfaces:
+- name: eth1
+  type: ethernet
+  state: up
+"#,
+    )
+    .unwrap();
+
+    assert!(state.diff(&state).unwrap().is_empty());
+}
+
+#[cfg(feature = "query_apply")]
+#[test]
+fn test_get_changes_report_removed() {
+    let old: NetworkState = serde_yaml::from_str(
+        r#"---
+interfaces:
+- name: eth1
+  type: ethernet
+  state: up
+  mtu: 1500
+- name: dummy1
+  type: dummy
+  state: up
+routes:
+  running:
+  - destination: 198.51.100.0/24
+    next-hop-address: 192.0.2.254
+    next-hop-interface: eth1
+    table-id: 254
+"#,
+    )
+    .unwrap();
+    let new: NetworkState = serde_yaml::from_str(
+        r#"---
+interfaces:
+- name: eth1
+  type: ethernet
+  state: up
+  mtu: 9000
+routes:
+  running:
+  - destination: 203.0.113.0/24
+    next-hop-address: 192.0.2.254
+    next-hop-interface: eth1
+    table-id: 254
+"#,
+    )
+    .unwrap();
+
+    let changes = old.get_changes(&new).unwrap();
+
+    assert_eq!(changes.remove_interfaces.len(), 1);
+    assert_eq!(changes.remove_interfaces[0].name, "dummy1");
+    assert_eq!(changes.change_interfaces.len(), 1);
+    assert_eq!(changes.change_interfaces[0].changes[0].path, "mtu");
+    assert_eq!(changes.add_routes.len(), 1);
+    assert_eq!(
+        changes.add_routes[0].destination.as_deref(),
+        Some("203.0.113.0/24")
+    );
+    assert_eq!(changes.remove_routes.len(), 1);
+    assert_eq!(
+        changes.remove_routes[0].destination.as_deref(),
+        Some("198.51.100.0/24")
+    );
+    assert!(new.get_changes(&new).unwrap().is_empty());
+}
+
+#[cfg(feature = "query_apply")]
+#[test]
+fn test_get_changes_dns_hostname_running() {
+    let old: NetworkState = serde_yaml::from_str(
+        r#"---
+dns-resolver:
+  running:
+    server:
+    - 192.0.2.1
+  config:
+    server:
+    - 192.0.2.1
+hostname:
+  running: host-a
+  config: host-a
+"#,
+    )
+    .unwrap();
+    let new: NetworkState = serde_yaml::from_str(
+        r#"---
+dns-resolver:
+  running:
+    server:
+    - 192.0.2.2
+  config:
+    server:
+    - 192.0.2.1
+hostname:
+  running: host-b
+  config: host-a
+"#,
+    )
+    .unwrap();
+
+    let changes = old.get_changes(&new).unwrap();
+
+    assert_eq!(changes.dns.len(), 1);
+    assert_eq!(changes.dns[0].path, "dns-resolver.running.server[0]");
+    assert_eq!(
+        changes.hostname.map(|h| h.path).as_deref(),
+        Some("hostname")
+    );
+    assert!(new.get_changes(&new).unwrap().is_empty());
+}
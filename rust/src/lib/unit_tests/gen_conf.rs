@@ -0,0 +1,361 @@
+use crate::NetworkState;
+
+fn get_conf<'a>(
+    confs: &'a std::collections::HashMap<String, Vec<(String, String)>>,
+    backend: &str,
+    file_name: &str,
+) -> &'a str {
+    confs[backend]
+        .iter()
+        .find(|(name, _)| name == file_name)
+        .map(|(_, content)| content.as_str())
+        .unwrap()
+}
+
+#[test]
+fn test_gen_conf_ifcfg_bond_with_static_ip_and_route() {
+    let desired: NetworkState = serde_yaml::from_str(
+        r#"---
+interfaces:
+- name: bond0
+  type: bond
+  state: up
+  ipv4:
+    enabled: true
+    address:
+    - ip: 192.0.2.1
+      prefix-length: 24
+  link-aggregation:
+    mode: active-backup
+    options:
+      miimon: 100
+    port:
+    - eth1
+routes:
+  config:
+  - destination: 198.51.100.0/24
+    next-hop-address: 192.0.2.254
+    next-hop-interface: bond0
+    table-id: 100
+"#,
+    )
+    .unwrap();
+    let confs = desired.gen_conf().unwrap();
+
+    let bond_conf = get_conf(&confs, "ifcfg-rh", "ifcfg-bond0");
+    assert!(bond_conf.contains("DEVICE=bond0\n"));
+    assert!(bond_conf.contains("TYPE=Bond\n"));
+    assert!(
+        bond_conf.contains("BONDING_OPTS=\"mode=active-backup miimon=100\"")
+    );
+    assert!(bond_conf.contains("IPADDR0=192.0.2.1\n"));
+    assert!(bond_conf.contains("PREFIX0=24\n"));
+
+    let port_conf = get_conf(&confs, "ifcfg-rh", "ifcfg-eth1");
+    assert!(port_conf.contains("MASTER=bond0\n"));
+    assert!(port_conf.contains("SLAVE=yes\n"));
+
+    assert_eq!(
+        get_conf(&confs, "ifcfg-rh", "route-bond0"),
+        "198.51.100.0/24 via 192.0.2.254 dev bond0 table 100\n"
+    );
+}
+
+#[test]
+fn test_gen_conf_ifupdown_bridge_with_dhcp() {
+    let desired: NetworkState = serde_yaml::from_str(
+        r#"---
+interfaces:
+- name: br0
+  type: linux-bridge
+  state: up
+  ipv4:
+    enabled: true
+    dhcp: true
+  bridge:
+    options:
+      stp:
+        enabled: false
+    port:
+    - name: eth1
+      stp-path-cost: 100
+"#,
+    )
+    .unwrap();
+    let confs = desired.gen_conf().unwrap();
+
+    assert_eq!(
+        get_conf(&confs, "ifupdown", "br0"),
+        "auto br0\n\
+        iface br0 inet dhcp\n    \
+        bridge_ports eth1\n    \
+        bridge_stp off\n    \
+        bridge_pathcost eth1 100\n"
+    );
+    assert_eq!(
+        get_conf(&confs, "ifupdown", "eth1"),
+        "auto eth1\niface eth1 inet manual\n"
+    );
+}
+
+#[test]
+fn test_gen_conf_ifupdown_vlan_with_custom_name() {
+    let desired: NetworkState = serde_yaml::from_str(
+        r#"---
+interfaces:
+- name: prod
+  type: vlan
+  state: up
+  vlan:
+    base-iface: eth1
+    id: 101
+"#,
+    )
+    .unwrap();
+    let confs = desired.gen_conf().unwrap();
+
+    let vlan_conf = get_conf(&confs, "ifupdown", "prod");
+    assert!(vlan_conf
+        .contains("pre-up ip link add link eth1 name prod type vlan id 101\n"));
+}
+
+#[test]
+fn test_gen_conf_shell_ports_after_controller() {
+    let desired: NetworkState = serde_yaml::from_str(
+        r#"---
+interfaces:
+- name: eth1
+  type: ethernet
+  state: up
+- name: br0
+  type: linux-bridge
+  state: up
+  ipv4:
+    enabled: true
+    address:
+    - ip: 192.0.2.1
+      prefix-length: 24
+  bridge:
+    options:
+      stp:
+        enabled: false
+    port:
+    - name: bond0
+      vlan:
+        mode: access
+        tag: 10
+- name: bond0
+  type: bond
+  state: up
+  link-aggregation:
+    mode: active-backup
+    options:
+      miimon: 100
+    port:
+    - eth1
+routes:
+  config:
+  - destination: 198.51.100.0/24
+    next-hop-address: 192.0.2.254
+    next-hop-interface: br0
+"#,
+    )
+    .unwrap();
+    let confs = desired.gen_conf().unwrap();
+
+    assert_eq!(
+        get_conf(&confs, "shell", "nmstate.sh"),
+        "#!/bin/sh\n\
+        # Generated by nmstate\n\
+        set -e\n\
+        \n\
+        # br0\n\
+        ip link add br0 type bridge stp_state 0 vlan_filtering 1\n\
+        ip link set dev br0 up\n\
+        ip addr add 192.0.2.1/24 dev br0\n\
+        \n\
+        # bond0\n\
+        ip link add bond0 type bond mode active-backup miimon 100\n\
+        ip link set dev bond0 down\n\
+        ip link set dev bond0 master br0\n\
+        bridge vlan del dev bond0 vid 1\n\
+        bridge vlan add dev bond0 vid 10 pvid untagged\n\
+        ip link set dev bond0 up\n\
+        \n\
+        # eth1\n\
+        ip link set dev eth1 down\n\
+        ip link set dev eth1 master bond0\n\
+        ip link set dev eth1 up\n\
+        \n\
+        # Routes\n\
+        ip route add 198.51.100.0/24 via 192.0.2.254 dev br0\n"
+    );
+}
+
+#[test]
+fn test_gen_conf_ovsdb_global_and_iface_external_ids() {
+    let desired: NetworkState = serde_yaml::from_str(
+        r#"---
+ovs-db:
+  external_ids:
+    hostname: host1
+interfaces:
+- name: br0
+  type: ovs-bridge
+  state: up
+  ovs-db:
+    external_ids:
+      role: uplink
+  bridge:
+    port:
+    - name: ovs0
+- name: ovs0
+  type: ovs-interface
+  state: up
+  ovs-db:
+    external_ids:
+      owner: nmstate
+      color: red
+"#,
+    )
+    .unwrap();
+    let confs = desired.gen_conf().unwrap();
+    let content: serde_json::Value = serde_json::from_str(get_conf(
+        &confs,
+        "OpenvSwitch",
+        "ovsdb_transact.json",
+    ))
+    .unwrap();
+
+    assert_eq!(
+        content,
+        serde_json::json!([
+            "Open_vSwitch",
+            {
+                "op": "update",
+                "table": "Open_vSwitch",
+                "where": [],
+                "row": {
+                    "external_ids": ["map", [["hostname", "host1"]]]
+                }
+            },
+            {
+                "op": "mutate",
+                "table": "Bridge",
+                "where": [["name", "==", "br0"]],
+                "mutations": [
+                    ["external_ids", "delete", ["set", ["role"]]],
+                    ["external_ids", "insert", ["map", [["role", "uplink"]]]]
+                ]
+            },
+            {
+                "op": "mutate",
+                "table": "Interface",
+                "where": [["name", "==", "ovs0"]],
+                "mutations": [
+                    ["external_ids", "delete", ["set", ["color", "owner"]]],
+                    [
+                        "external_ids",
+                        "insert",
+                        ["map", [["color", "red"], ["owner", "nmstate"]]]
+                    ]
+                ]
+            }
+        ])
+    );
+}
+
+// The `NM.connection.uuid` stored by NetworkManager in the same
+// `external_ids` column should never be removed.
+#[test]
+fn test_gen_conf_ovsdb_iface_external_ids_keep_others() {
+    let desired: NetworkState = serde_yaml::from_str(
+        r#"---
+interfaces:
+- name: br0
+  type: ovs-bridge
+  state: up
+  bridge:
+    port:
+    - name: ovs0
+- name: ovs0
+  type: ovs-interface
+  state: up
+  ovs-db:
+    external_ids:
+      owner: nmstate
+      color: null
+"#,
+    )
+    .unwrap();
+    let confs = desired.gen_conf().unwrap();
+    let content: serde_json::Value = serde_json::from_str(get_conf(
+        &confs,
+        "OpenvSwitch",
+        "ovsdb_transact.json",
+    ))
+    .unwrap();
+
+    assert_eq!(
+        content,
+        serde_json::json!([
+            "Open_vSwitch",
+            {
+                "op": "mutate",
+                "table": "Interface",
+                "where": [["name", "==", "ovs0"]],
+                "mutations": [
+                    ["external_ids", "delete", ["set", ["color", "owner"]]],
+                    ["external_ids", "insert", ["map", [["owner", "nmstate"]]]]
+                ]
+            }
+        ])
+    );
+    assert!(!content.to_string().contains("NM.connection.uuid"));
+}
+
+#[test]
+fn test_gen_conf_ovsdb_empty_without_ovs() {
+    let desired: NetworkState = serde_yaml::from_str(
+        r#"---
+interfaces:
+- name: eth1
+  type: ethernet
+  state: up
+"#,
+    )
+    .unwrap();
+    let confs = desired.gen_conf().unwrap();
+
+    assert!(confs["OpenvSwitch"].is_empty());
+}
+
+#[test]
+fn test_gen_conf_nm_bind_by_identifier() {
+    let desired: NetworkState = serde_yaml::from_str(
+        r#"---
+interfaces:
+- name: wan0
+  type: ethernet
+  state: up
+  identifier: mac-address
+  mac-address: 00:23:45:67:89:1A
+- name: lan0
+  type: ethernet
+  state: up
+  identifier: pci-address
+  pci-address: "0000:03:00.0"
+"#,
+    )
+    .unwrap();
+    let confs = desired.gen_conf().unwrap();
+
+    let wan_conf = get_conf(&confs, "NetworkManager", "wan0.nmconnection");
+    assert!(!wan_conf.contains("interface-name="));
+    assert!(wan_conf.contains("mac-address=00:23:45:67:89:1A\n"));
+    assert!(!wan_conf.contains("cloned-mac-address="));
+
+    let lan_conf = get_conf(&confs, "NetworkManager", "lan0.nmconnection");
+    assert!(!lan_conf.contains("interface-name="));
+    assert!(lan_conf.contains("[match]\npath=pci-0000:03:00.0\n"));
+}
@@ -0,0 +1,166 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use std::path::PathBuf;
+
+use crate::{
+    history::{history_get_from_dir, history_list_from_dir},
+    ErrorKind, NetworkStateRevision,
+};
+
+fn gen_test_dir() -> PathBuf {
+    std::env::temp_dir().join(format!("nmstate-test-{}", uuid::Uuid::new_v4()))
+}
+
+fn gen_test_revision(id: u64) -> NetworkStateRevision {
+    let mut rev = NetworkStateRevision::new();
+    rev.id = id;
+    rev.user = "root".to_string();
+    rev.started = 1700000000;
+    rev.finished = 1700000005;
+    rev.checkpoint = "/run/nmstate/checkpoint/1700000000-1.json".to_string();
+    rev.desired = serde_yaml::from_str(
+        r#"---
+interfaces:
+- name: eth1
+  type: ethernet
+  state: up
+  mtu: 9000
+"#,
+    )
+    .unwrap();
+    rev.result = rev.desired.clone();
+    rev
+}
+
+fn write_test_revision(dir: &std::path::Path, rev: &NetworkStateRevision) {
+    std::fs::write(
+        dir.join(format!("{}.json", rev.id)),
+        serde_json::to_string(rev).unwrap(),
+    )
+    .unwrap();
+}
+
+#[test]
+fn test_history_revision_serde() {
+    let rev = gen_test_revision(1);
+    let new_rev: NetworkStateRevision =
+        serde_json::from_str(&serde_json::to_string(&rev).unwrap()).unwrap();
+
+    assert_eq!(new_rev.id, 1);
+    assert_eq!(new_rev.user, "root");
+    assert_eq!(new_rev.checkpoint, rev.checkpoint);
+    assert_eq!(new_rev.desired.interfaces.to_vec()[0].name(), "eth1");
+    assert_eq!(
+        new_rev.result.interfaces.to_vec()[0].base_iface().mtu,
+        Some(9000)
+    );
+}
+
+#[test]
+fn test_history_list_sorted_by_id() {
+    let dir = gen_test_dir();
+    std::fs::create_dir_all(&dir).unwrap();
+    for id in [10, 2, 1] {
+        write_test_revision(&dir, &gen_test_revision(id));
+    }
+    std::fs::write(dir.join("README"), "not a revision").unwrap();
+    std::fs::write(dir.join("3.json"), "{}").unwrap();
+
+    let revs = history_list_from_dir(&dir).unwrap();
+    std::fs::remove_dir_all(&dir).unwrap();
+
+    assert_eq!(revs.iter().map(|r| r.id).collect::<Vec<u64>>(), [1, 2, 10]);
+}
+
+#[test]
+fn test_history_list_without_dir() {
+    assert!(history_list_from_dir(&gen_test_dir()).unwrap().is_empty());
+}
+
+#[test]
+fn test_history_get_not_found() {
+    let dir = gen_test_dir();
+    std::fs::create_dir_all(&dir).unwrap();
+    write_test_revision(&dir, &gen_test_revision(1));
+
+    let result = history_get_from_dir(&dir, 2);
+    let rev = history_get_from_dir(&dir, 1);
+    std::fs::remove_dir_all(&dir).unwrap();
+
+    assert_eq!(rev.unwrap().id, 1);
+    assert!(result.is_err());
+    if let Err(e) = result {
+        assert_eq!(e.kind(), ErrorKind::InvalidArgument);
+    }
+}
+
+#[cfg(feature = "query_apply")]
+#[test]
+fn test_history_save_next_id() {
+    use crate::history::history_save_to_dir;
+    use std::os::unix::fs::PermissionsExt;
+
+    let dir = gen_test_dir();
+    let first = history_save_to_dir(&dir, gen_test_revision(0)).unwrap();
+    let second = history_save_to_dir(&dir, gen_test_revision(0)).unwrap();
+    let mode = std::fs::metadata(dir.join("2.json"))
+        .unwrap()
+        .permissions()
+        .mode();
+    let revs = history_list_from_dir(&dir).unwrap();
+    let mut file_names: Vec<String> = std::fs::read_dir(&dir)
+        .unwrap()
+        .map(|e| e.unwrap().file_name().to_string_lossy().to_string())
+        .collect();
+    file_names.sort_unstable();
+    std::fs::remove_dir_all(&dir).unwrap();
+
+    assert_eq!((first, second), (1, 2));
+    // Temporary file is removed
+    assert_eq!(file_names, ["1.json", "2.json"]);
+    assert_eq!(mode & 0o777, 0o600);
+    assert_eq!(revs.len(), 2);
+    assert_eq!(
+        revs[1].desired.interfaces,
+        gen_test_revision(0).desired.interfaces
+    );
+}
+
+// Broken revision file still holds its ID
+#[cfg(feature = "query_apply")]
+#[test]
+fn test_history_save_next_id_from_file_name() {
+    use crate::history::history_save_to_dir;
+
+    let dir = gen_test_dir();
+    std::fs::create_dir_all(&dir).unwrap();
+    write_test_revision(&dir, &gen_test_revision(2));
+    std::fs::write(dir.join("5.json"), "{}").unwrap();
+    std::fs::write(dir.join("9.json.tmp"), "{}").unwrap();
+
+    let id = history_save_to_dir(&dir, gen_test_revision(0));
+    std::fs::remove_dir_all(&dir).unwrap();
+
+    assert_eq!(id.unwrap(), 6);
+}
+
+#[cfg(feature = "query_apply")]
+#[test]
+fn test_history_prune_keep_newest() {
+    use crate::history::history_prune_dir;
+
+    let dir = gen_test_dir();
+    std::fs::create_dir_all(&dir).unwrap();
+    for id in [1, 2, 3, 10] {
+        write_test_revision(&dir, &gen_test_revision(id));
+    }
+    std::fs::write(dir.join("README"), "not a revision").unwrap();
+
+    history_prune_dir(&dir, 2).unwrap();
+    let revs = history_list_from_dir(&dir).unwrap();
+    let has_readme = dir.join("README").exists();
+    std::fs::remove_dir_all(&dir).unwrap();
+
+    assert_eq!(revs.iter().map(|r| r.id).collect::<Vec<u64>>(), [3, 10]);
+    assert!(has_readme);
+}
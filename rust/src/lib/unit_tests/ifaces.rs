@@ -4,7 +4,7 @@ use crate::{
         new_eth_iface, new_ovs_br_iface, new_ovs_iface, new_unknown_iface,
         new_vlan_iface,
     },
-    BondMode, Interface, InterfaceState, InterfaceType, Interfaces,
+    BondMode, ErrorKind, Interface, InterfaceState, InterfaceType, Interfaces,
 };
 
 #[test]
@@ -241,3 +241,264 @@ fn test_ifaces_ignore_iface_in_current_but_desired() {
         vec![("br0".to_string(), InterfaceType::OvsBridge)]
     );
 }
+
+#[cfg(feature = "query_apply")]
+#[test]
+fn test_ifaces_verify_report_all_differences() {
+    let des_ifaces: Interfaces = serde_yaml::from_str(
+        r#"---
+- name: eth1
+  type: ethernet
+  state: up
+  mtu: 1400
+  ipv4:
+    enabled: false
+- name: eth2
+  type: ethernet
+  state: up
+  mtu: 1400
+"#,
+    )
+    .unwrap();
+    let cur_ifaces: Interfaces = serde_yaml::from_str(
+        r#"---
+- name: eth1
+  type: ethernet
+  state: up
+  mtu: 1500
+  ipv4:
+    enabled: true
+    dhcp: false
+    address:
+    - ip: 192.0.2.1
+      prefix-length: 24
+- name: eth2
+  type: ethernet
+  state: up
+  mtu: 1500
+"#,
+    )
+    .unwrap();
+
+    let result = des_ifaces.verify(&Interfaces::new(), &cur_ifaces);
+    assert!(result.is_err());
+    if let Err(e) = result {
+        assert_eq!(e.kind(), ErrorKind::VerificationError);
+        let paths: Vec<&str> =
+            e.differences().iter().map(|d| d.path.as_str()).collect();
+        assert_eq!(paths.len(), 3);
+        assert!(paths.contains(&"eth1.interface.mtu"));
+        assert!(paths.contains(&"eth1.interface.ipv4.enabled"));
+        assert!(paths.contains(&"eth2.interface.mtu"));
+    }
+}
+
+#[cfg(feature = "query_apply")]
+#[test]
+fn test_ifaces_verify_kernel_integer_rounded_only() {
+    let des_ifaces: Interfaces = serde_yaml::from_str(
+        r#"---
+- name: br0
+  type: linux-bridge
+  state: up
+  bridge:
+    options:
+      multicast-startup-query-interval: 3125
+- name: br1
+  type: linux-bridge
+  state: up
+  mtu: 1400
+  bridge:
+    options:
+      multicast-startup-query-interval: 3125
+"#,
+    )
+    .unwrap();
+    let cur_ifaces: Interfaces = serde_yaml::from_str(
+        r#"---
+- name: br0
+  type: linux-bridge
+  state: up
+  bridge:
+    options:
+      multicast-startup-query-interval: 3124
+- name: br1
+  type: linux-bridge
+  state: up
+  mtu: 1500
+  bridge:
+    options:
+      multicast-startup-query-interval: 3124
+"#,
+    )
+    .unwrap();
+
+    let mut br0_ifaces = Interfaces::new();
+    br0_ifaces.push(
+        des_ifaces
+            .get_iface("br0", InterfaceType::LinuxBridge)
+            .unwrap()
+            .clone(),
+    );
+    let result = br0_ifaces.verify(&Interfaces::new(), &cur_ifaces);
+    assert!(result.is_err());
+    if let Err(e) = result {
+        assert_eq!(e.kind(), ErrorKind::KernelIntegerRoundedError);
+        assert_eq!(e.differences().len(), 1);
+    }
+
+    let result = des_ifaces.verify(&Interfaces::new(), &cur_ifaces);
+    assert!(result.is_err());
+    if let Err(e) = result {
+        assert_eq!(e.kind(), ErrorKind::VerificationError);
+        let paths: Vec<&str> =
+            e.differences().iter().map(|d| d.path.as_str()).collect();
+        assert_eq!(paths.len(), 3);
+        assert!(paths.contains(&"br1.interface.mtu"));
+        assert!(paths.contains(
+            &"br1.interface.bridge.options.multicast-startup-query-interval"
+        ));
+    }
+}
+
+#[test]
+fn test_ifaces_resolve_identifier_mac_address() {
+    let current = serde_yaml::from_str::<Interfaces>(
+        r#"---
+- name: eth1
+  type: ethernet
+  state: up
+  mac-address: 00:23:45:67:89:1A
+- name: eth2
+  type: ethernet
+  state: up
+  mac-address: 00:23:45:67:89:1B
+"#,
+    )
+    .unwrap();
+    let mut desired = serde_yaml::from_str::<Interfaces>(
+        r#"---
+- name: wan0
+  type: ethernet
+  state: up
+  identifier: mac-address
+  mac-address: 00:23:45:67:89:1b
+- name: bond0
+  type: bond
+  state: up
+  link-aggregation:
+    mode: active-backup
+    port:
+    - wan0
+"#,
+    )
+    .unwrap();
+    desired.resolve_unknown_ifaces(&current).unwrap();
+
+    assert!(!desired.kernel_ifaces.contains_key("wan0"));
+    let iface = &desired.kernel_ifaces["eth2"];
+    assert_eq!(iface.name(), "eth2");
+    assert_eq!(iface.base_iface().profile_name.as_deref(), Some("wan0"));
+    assert_eq!(desired.kernel_ifaces["bond0"].ports(), Some(vec!["eth2"]));
+}
+
+#[test]
+fn test_ifaces_resolve_identifier_pci_address() {
+    let current = serde_yaml::from_str::<Interfaces>(
+        r#"---
+- name: enp3s0
+  type: ethernet
+  state: up
+  pci-address: "0000:03:00.0"
+- name: enp4s0
+  type: ethernet
+  state: up
+  pci-address: "0000:04:00.0"
+"#,
+    )
+    .unwrap();
+    let mut desired = serde_yaml::from_str::<Interfaces>(
+        r#"---
+- name: lan0
+  type: ethernet
+  state: up
+  identifier: pci-address
+  pci-address: "0000:04:00.0"
+  mtu: 9000
+"#,
+    )
+    .unwrap();
+    desired.resolve_unknown_ifaces(&current).unwrap();
+
+    let iface = &desired.kernel_ifaces["enp4s0"];
+    assert_eq!(iface.base_iface().profile_name.as_deref(), Some("lan0"));
+    assert_eq!(iface.base_iface().mtu, Some(9000));
+}
+
+#[cfg(feature = "query_apply")]
+#[test]
+fn test_net_state_resolve_identifier_route() {
+    let current = serde_yaml::from_str::<Interfaces>(
+        r#"---
+- name: eth1
+  type: ethernet
+  state: up
+  mac-address: 00:23:45:67:89:1A
+"#,
+    )
+    .unwrap();
+    let mut desired = serde_yaml::from_str::<crate::NetworkState>(
+        r#"---
+interfaces:
+- name: wan0
+  type: ethernet
+  state: up
+  identifier: mac-address
+  mac-address: 00:23:45:67:89:1A
+routes:
+  config:
+  - destination: 0.0.0.0/0
+    next-hop-address: 192.0.2.1
+    next-hop-interface: wan0
+  - destination: 198.51.100.0/24
+    next-hop-address: 192.0.2.2
+    next-hop-interface: eth2
+"#,
+    )
+    .unwrap();
+    desired.resolve_unknown_ifaces(&current).unwrap();
+
+    assert!(desired.interfaces.kernel_ifaces.contains_key("eth1"));
+    let routes = desired.routes.config.unwrap();
+    assert_eq!(routes[0].next_hop_iface.as_deref(), Some("eth1"));
+    assert_eq!(routes[1].next_hop_iface.as_deref(), Some("eth2"));
+}
+
+#[test]
+fn test_ifaces_resolve_identifier_not_found() {
+    let current = serde_yaml::from_str::<Interfaces>(
+        r#"---
+- name: eth1
+  type: ethernet
+  state: up
+  mac-address: 00:23:45:67:89:1A
+"#,
+    )
+    .unwrap();
+    let mut desired = serde_yaml::from_str::<Interfaces>(
+        r#"---
+- name: wan0
+  type: ethernet
+  state: up
+  identifier: mac-address
+  mac-address: 00:23:45:67:89:1C
+"#,
+    )
+    .unwrap();
+    let result = desired.resolve_unknown_ifaces(&current);
+
+    assert!(result.is_err());
+    if let Err(e) = result {
+        assert_eq!(e.kind(), ErrorKind::InvalidArgument);
+    }
+}
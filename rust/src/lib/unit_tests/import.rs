@@ -0,0 +1,297 @@
+use crate::ifcfg::ifcfg_import;
+use crate::import::parse_time_span_ms;
+use crate::netplan::netplan_import;
+use crate::networkd::networkd_import;
+use crate::{
+    BondMode, BridgePortVlanMode, ErrorKind, Interface, InterfaceType,
+    NetworkState,
+};
+
+fn to_files(files: &[(&str, &str)]) -> Vec<(String, String)> {
+    files
+        .iter()
+        .map(|(p, c)| (p.to_string(), c.to_string()))
+        .collect()
+}
+
+fn get_iface<'a>(
+    net_state: &'a NetworkState,
+    name: &str,
+    iface_type: InterfaceType,
+) -> &'a Interface {
+    net_state.interfaces.get_iface(name, iface_type).unwrap()
+}
+
+#[test]
+fn test_netplan_import_bond_vlan_with_static_ip() {
+    let net_state = netplan_import(&to_files(&[(
+        "01-netcfg.yaml",
+        r#"network:
+  version: 2
+  renderer: networkd
+  ethernets:
+    eth1: {}
+    eth2: {}
+  bonds:
+    bond0:
+      interfaces: [eth1, eth2]
+      parameters:
+        mode: 802.3ad
+        mii-monitor-interval: 100
+        lacp-rate: fast
+  vlans:
+    bond0.100:
+      id: 100
+      link: bond0
+      addresses: [192.0.2.2/24, "2001:db8::2/64"]
+      gateway4: 192.0.2.1
+      nameservers:
+        addresses: [192.0.2.53]
+        search: [example.org]
+      routes:
+      - to: 198.51.100.0/24
+        via: 192.0.2.254
+        metric: 100
+        table: 200
+      routing-policy:
+      - from: 192.0.2.0/24
+        table: 200
+        priority: 1000
+"#,
+    )]))
+    .unwrap();
+
+    if let Interface::Bond(bond_iface) =
+        get_iface(&net_state, "bond0", InterfaceType::Bond)
+    {
+        let bond_conf = bond_iface.bond.as_ref().unwrap();
+        assert_eq!(bond_conf.mode, Some(BondMode::LACP));
+        assert_eq!(
+            bond_conf.port,
+            Some(vec!["eth1".to_string(), "eth2".to_string()])
+        );
+        assert_eq!(
+            bond_conf.options.as_ref().and_then(|o| o.miimon),
+            Some(100)
+        );
+        assert!(!bond_iface.base.ipv4.as_ref().unwrap().enabled);
+    } else {
+        panic!("bond0 should be bond interface");
+    }
+    let eth1 = get_iface(&net_state, "eth1", InterfaceType::Ethernet);
+    assert!(eth1.base_iface().ipv4.is_none());
+
+    if let Interface::Vlan(vlan_iface) =
+        get_iface(&net_state, "bond0.100", InterfaceType::Vlan)
+    {
+        let vlan_conf = vlan_iface.vlan.as_ref().unwrap();
+        assert_eq!(vlan_conf.base_iface, "bond0");
+        assert_eq!(vlan_conf.id, 100);
+        let ipv4 = vlan_iface.base.ipv4.as_ref().unwrap();
+        assert_eq!(ipv4.dhcp, Some(false));
+        assert_eq!(ipv4.addresses.as_ref().map(|a| a.len()), Some(1));
+        let ipv6 = vlan_iface.base.ipv6.as_ref().unwrap();
+        assert_eq!(ipv6.addresses.as_ref().map(|a| a.len()), Some(1));
+    } else {
+        panic!("bond0.100 should be VLAN interface");
+    }
+
+    let routes = net_state.routes.config.as_ref().unwrap();
+    assert_eq!(routes.len(), 2);
+    assert_eq!(routes[0].destination.as_deref(), Some("0.0.0.0/0"));
+    assert_eq!(routes[1].table_id, Some(200));
+    let rules = net_state.rules.config.as_ref().unwrap();
+    assert_eq!(rules[0].priority, Some(1000));
+    let dns_conf = net_state.dns.config.as_ref().unwrap();
+    assert_eq!(dns_conf.server, Some(vec!["192.0.2.53".to_string()]));
+    assert_eq!(dns_conf.search, Some(vec!["example.org".to_string()]));
+}
+
+#[test]
+fn test_netplan_import_later_file_override() {
+    let net_state = netplan_import(&to_files(&[
+        (
+            "01-base.yaml",
+            r#"network:
+  version: 2
+  ethernets:
+    eth0:
+      dhcp4: true
+      mtu: 1500
+"#,
+        ),
+        (
+            "99-override.yaml",
+            r#"network:
+  ethernets:
+    eth0:
+      mtu: 9000
+      dhcp4-overrides:
+        use-dns: false
+"#,
+        ),
+    ]))
+    .unwrap();
+
+    let iface = get_iface(&net_state, "eth0", InterfaceType::Ethernet);
+    assert_eq!(iface.base_iface().mtu, Some(9000));
+    let ipv4 = iface.base_iface().ipv4.as_ref().unwrap();
+    assert_eq!(ipv4.dhcp, Some(true));
+    assert_eq!(ipv4.auto_dns, Some(false));
+}
+
+#[test]
+fn test_netplan_import_invalid_value_with_line() {
+    let result = netplan_import(&to_files(&[(
+        "01-netcfg.yaml",
+        r#"network:
+  vlans:
+    vlan10:
+      link: eth0
+      id: abc
+"#,
+    )]));
+
+    assert!(result.is_err());
+    if let Err(e) = result {
+        assert_eq!(e.kind(), ErrorKind::InvalidArgument);
+        assert!(e.msg().starts_with("01-netcfg.yaml:5:"));
+    }
+}
+
+#[test]
+fn test_ifcfg_import_bond_with_route_file() {
+    let net_state = ifcfg_import(&to_files(&[
+        (
+            "/etc/sysconfig/network-scripts/ifcfg-bond0",
+            r#"DEVICE=bond0
+TYPE=Bond
+BONDING_MASTER=yes
+BONDING_OPTS="mode=active-backup miimon=100"
+BOOTPROTO=none
+IPADDR=192.0.2.2
+NETMASK=255.255.255.0
+GATEWAY=192.0.2.1
+DNS1=192.0.2.53
+ONBOOT=yes
+"#,
+        ),
+        (
+            "/etc/sysconfig/network-scripts/ifcfg-eth1",
+            r#"DEVICE=eth1
+TYPE=Ethernet
+MASTER=bond0
+SLAVE=yes
+"#,
+        ),
+        (
+            "/etc/sysconfig/network-scripts/route-bond0",
+            "198.51.100.0/24 via 192.0.2.254 metric 100 table 200\n",
+        ),
+        (
+            "/etc/sysconfig/network-scripts/rule-bond0",
+            "from 192.0.2.0/24 priority 1000 table 200\n",
+        ),
+    ]))
+    .unwrap();
+
+    if let Interface::Bond(bond_iface) =
+        get_iface(&net_state, "bond0", InterfaceType::Bond)
+    {
+        let bond_conf = bond_iface.bond.as_ref().unwrap();
+        assert_eq!(bond_conf.mode, Some(BondMode::ActiveBackup));
+        assert_eq!(bond_conf.port, Some(vec!["eth1".to_string()]));
+        let ipv4 = bond_iface.base.ipv4.as_ref().unwrap();
+        assert_eq!(ipv4.addresses.as_ref().unwrap()[0].prefix_length, 24);
+        assert!(!bond_iface.base.ipv6.as_ref().unwrap().enabled);
+    } else {
+        panic!("bond0 should be bond interface");
+    }
+    let routes = net_state.routes.config.as_ref().unwrap();
+    assert_eq!(routes.len(), 2);
+    assert_eq!(routes[1].destination.as_deref(), Some("198.51.100.0/24"));
+    assert_eq!(routes[1].next_hop_iface.as_deref(), Some("bond0"));
+    assert_eq!(routes[1].metric, Some(100));
+    let rules = net_state.rules.config.as_ref().unwrap();
+    assert_eq!(rules[0].ip_from.as_deref(), Some("192.0.2.0/24"));
+    assert_eq!(rules[0].table_id, Some(200));
+}
+
+#[test]
+fn test_networkd_import_bridge_with_port_vlan() {
+    let net_state = networkd_import(&to_files(&[
+        (
+            "10-br0.netdev",
+            r#"[NetDev]
+Name=br0
+Kind=bridge
+
+[Bridge]
+STP=yes
+ForwardDelaySec=15
+"#,
+        ),
+        (
+            "10-br0.network",
+            r#"[Match]
+Name=br0
+
+[Network]
+DHCP=ipv4
+"#,
+        ),
+        (
+            "20-eth1.network",
+            r#"[Match]
+Name=eth1
+
+[Network]
+Bridge=br0
+
+[Bridge]
+Cost=100
+
+[BridgeVLAN]
+VLAN=100-200
+
+[BridgeVLAN]
+PVID=10
+EgressUntagged=10
+"#,
+        ),
+    ]))
+    .unwrap();
+
+    if let Interface::LinuxBridge(br_iface) =
+        get_iface(&net_state, "br0", InterfaceType::LinuxBridge)
+    {
+        let br_conf = br_iface.bridge.as_ref().unwrap();
+        let stp = br_conf
+            .options
+            .as_ref()
+            .and_then(|o| o.stp.as_ref())
+            .unwrap();
+        assert_eq!(stp.enabled, Some(true));
+        assert_eq!(stp.forward_delay, Some(15));
+        let port_conf = &br_conf.port.as_ref().unwrap()[0];
+        assert_eq!(port_conf.name, "eth1");
+        assert_eq!(port_conf.stp_path_cost, Some(100));
+        let vlan_conf = port_conf.vlan.as_ref().unwrap();
+        assert_eq!(vlan_conf.mode, Some(BridgePortVlanMode::Trunk));
+        assert_eq!(vlan_conf.tag, Some(10));
+        assert_eq!(vlan_conf.enable_native, Some(true));
+        let ipv4 = br_iface.base.ipv4.as_ref().unwrap();
+        assert_eq!(ipv4.dhcp, Some(true));
+    } else {
+        panic!("br0 should be linux bridge interface");
+    }
+}
+
+#[test]
+fn test_parse_time_span_ms() {
+    assert_eq!(parse_time_span_ms("100ms", 1000), Some(100));
+    assert_eq!(parse_time_span_ms("1.5s", 1000), Some(1500));
+    assert_eq!(parse_time_span_ms("1min 30s", 1000), Some(90000));
+    assert_eq!(parse_time_span_ms("2", 1000), Some(2000));
+    assert_eq!(parse_time_span_ms("2 days", 1000), None);
+}
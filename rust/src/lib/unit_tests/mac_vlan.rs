@@ -18,3 +18,22 @@ mac-vlan:
     let mac_conf = iface.mac_vlan.unwrap();
     assert_eq!(mac_conf.accept_all_mac, Some(true));
 }
+
+#[cfg(feature = "query_apply")]
+#[test]
+fn test_mac_vlan_mode_to_kernel() {
+    use rtnetlink::packet_route::link::MacVlanMode as KernelMode;
+
+    use crate::{nispor::np_mac_vlan_mode, MacVlanMode};
+
+    for (mode, kernel_mode, kernel_value) in [
+        (MacVlanMode::Private, KernelMode::Private, 1u32),
+        (MacVlanMode::Vepa, KernelMode::Vepa, 2),
+        (MacVlanMode::Bridge, KernelMode::Bridge, 4),
+        (MacVlanMode::Passthru, KernelMode::Passthrough, 8),
+        (MacVlanMode::Source, KernelMode::Source, 16),
+    ] {
+        assert_eq!(np_mac_vlan_mode(mode), kernel_mode);
+        assert_eq!(u32::from(np_mac_vlan_mode(mode)), kernel_value);
+    }
+}
@@ -18,3 +18,22 @@ mac-vtap:
     let mac_conf = iface.mac_vtap.unwrap();
     assert_eq!(mac_conf.accept_all_mac, Some(true));
 }
+
+#[cfg(feature = "query_apply")]
+#[test]
+fn test_mac_vtap_mode_to_kernel() {
+    use rtnetlink::packet_route::link::MacVlanMode as KernelMode;
+
+    use crate::{nispor::np_mac_vtap_mode, MacVtapMode};
+
+    for (mode, kernel_mode, kernel_value) in [
+        (MacVtapMode::Private, KernelMode::Private, 1u32),
+        (MacVtapMode::Vepa, KernelMode::Vepa, 2),
+        (MacVtapMode::Bridge, KernelMode::Bridge, 4),
+        (MacVtapMode::Passthru, KernelMode::Passthrough, 8),
+        (MacVtapMode::Source, KernelMode::Source, 16),
+    ] {
+        assert_eq!(np_mac_vtap_mode(mode), kernel_mode);
+        assert_eq!(u32::from(np_mac_vtap_mode(mode)), kernel_value);
+    }
+}
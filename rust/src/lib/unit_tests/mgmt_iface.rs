@@ -0,0 +1,184 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use std::net::IpAddr;
+use std::str::FromStr;
+
+use crate::{
+    query_apply::{check_mgmt_ifaces, get_mgmt_ifaces},
+    ErrorKind, NetworkState,
+};
+
+fn gen_cur_net_state() -> NetworkState {
+    serde_yaml::from_str(
+        r#"---
+interfaces:
+- name: eth1
+  type: ethernet
+  state: up
+  ipv4:
+    enabled: true
+    dhcp: false
+    address:
+    - ip: 192.0.2.10
+      prefix-length: 24
+  ipv6:
+    enabled: true
+    dhcp: false
+    autoconf: false
+    address:
+    - ip: fe80::1
+      prefix-length: 64
+- name: eth2
+  type: ethernet
+  state: up
+  ipv4:
+    enabled: true
+    dhcp: false
+    address:
+    - ip: 198.51.100.10
+      prefix-length: 24
+routes:
+  running:
+  - destination: 0.0.0.0/0
+    next-hop-interface: eth1
+    next-hop-address: 192.0.2.1
+    table-id: 254
+  - destination: 203.0.113.0/24
+    next-hop-interface: eth2
+    next-hop-address: 198.51.100.1
+    table-id: 254
+"#,
+    )
+    .unwrap()
+}
+
+fn check_desire_state(
+    desire_yaml: &str,
+    ssh_server_ip: Option<IpAddr>,
+) -> Result<(), crate::NmstateError> {
+    let cur_net_state = gen_cur_net_state();
+    let desire_net_state: NetworkState =
+        serde_yaml::from_str(desire_yaml).unwrap();
+    let (_, chg_net_state, del_net_state) =
+        desire_net_state.gen_state_for_apply(&cur_net_state)?;
+    let mgmt_ifaces = get_mgmt_ifaces(&cur_net_state, ssh_server_ip);
+    check_mgmt_ifaces(
+        mgmt_ifaces.as_slice(),
+        &chg_net_state,
+        &del_net_state,
+        &cur_net_state,
+    )
+}
+
+#[test]
+fn test_mgmt_ifaces_default_route_and_ssh() {
+    let cur_net_state = gen_cur_net_state();
+    assert_eq!(
+        get_mgmt_ifaces(&cur_net_state, None),
+        vec![("eth1".to_string(), "default route")]
+    );
+    assert_eq!(
+        get_mgmt_ifaces(
+            &cur_net_state,
+            Some(IpAddr::from_str("198.51.100.10").unwrap())
+        ),
+        vec![
+            ("eth1".to_string(), "default route"),
+            ("eth2".to_string(), "current SSH session"),
+        ]
+    );
+}
+
+#[test]
+fn test_mgmt_iface_down_or_absent_refused() {
+    for state in ["down", "absent"] {
+        let result = check_desire_state(
+            &format!(
+                r#"---
+interfaces:
+- name: eth1
+  type: ethernet
+  state: {}
+"#,
+                state
+            ),
+            None,
+        );
+        assert!(result.is_err());
+        if let Err(e) = result {
+            assert_eq!(e.kind(), ErrorKind::InvalidArgument);
+            assert!(e.msg().contains("eth1"));
+            assert!(e.msg().contains(&format!("state: {}", state)));
+        }
+    }
+}
+
+#[test]
+fn test_mgmt_iface_readdress_refused() {
+    let result = check_desire_state(
+        r#"---
+interfaces:
+- name: eth1
+  type: ethernet
+  state: up
+  ipv4:
+    enabled: true
+    dhcp: false
+    address:
+    - ip: 192.0.2.11
+      prefix-length: 24
+"#,
+        None,
+    );
+    assert!(result.is_err());
+    if let Err(e) = result {
+        assert_eq!(e.kind(), ErrorKind::InvalidArgument);
+        assert!(e.msg().contains("ipv4.address: removing 192.0.2.10/24"));
+    }
+}
+
+#[test]
+fn test_mgmt_iface_ssh_disable_ip_refused() {
+    let desire_yaml = r#"---
+interfaces:
+- name: eth2
+  type: ethernet
+  state: up
+  ipv4:
+    enabled: false
+"#;
+    assert!(check_desire_state(desire_yaml, None).is_ok());
+    let result = check_desire_state(
+        desire_yaml,
+        Some(IpAddr::from_str("198.51.100.10").unwrap()),
+    );
+    assert!(result.is_err());
+    if let Err(e) = result {
+        assert!(e.msg().contains("eth2"));
+        assert!(e.msg().contains("ipv4.enabled: false"));
+        assert!(e.msg().contains("current SSH session"));
+    }
+}
+
+#[test]
+fn test_mgmt_iface_add_address_allowed() {
+    assert!(check_desire_state(
+        r#"---
+interfaces:
+- name: eth1
+  type: ethernet
+  state: up
+  mtu: 1400
+  ipv4:
+    enabled: true
+    dhcp: false
+    address:
+    - ip: 192.0.2.10
+      prefix-length: 24
+    - ip: 192.0.2.11
+      prefix-length: 24
+"#,
+        None,
+    )
+    .is_ok());
+}
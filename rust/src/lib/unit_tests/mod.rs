@@ -4,16 +4,26 @@ mod base;
 mod bond;
 #[cfg(test)]
 mod bridge;
+#[cfg(all(test, feature = "query_apply"))]
+mod checkpoint;
+#[cfg(test)]
+mod diff;
 #[cfg(test)]
 mod dns;
 #[cfg(test)]
 mod ethernet;
 #[cfg(test)]
 mod ethtool;
+#[cfg(all(test, feature = "gen_conf"))]
+mod gen_conf;
+#[cfg(test)]
+mod history;
 #[cfg(test)]
 mod ifaces;
 #[cfg(test)]
 mod ifaces_ctrller;
+#[cfg(all(test, feature = "gen_conf"))]
+mod import;
 #[cfg(test)]
 mod infiniband;
 #[cfg(test)]
@@ -24,20 +34,34 @@ mod lldp;
 mod mac_vlan;
 #[cfg(test)]
 mod mac_vtap;
+#[cfg(all(test, feature = "query_apply"))]
+mod mgmt_iface;
 #[cfg(test)]
 mod mptcp;
 #[cfg(test)]
+mod nic_names;
+#[cfg(test)]
 mod ovs;
 #[cfg(test)]
 mod ovsdb;
+#[cfg(all(test, feature = "query_apply"))]
+mod plan;
+#[cfg(test)]
+mod policy;
+#[cfg(test)]
+mod probe;
 #[cfg(test)]
 mod route;
 #[cfg(test)]
 mod route_rule;
 #[cfg(test)]
+mod schema;
+#[cfg(test)]
 mod sriov;
 #[cfg(test)]
 mod testlib;
+#[cfg(all(test, feature = "gen_conf"))]
+mod validate;
 #[cfg(test)]
 mod vlan;
 #[cfg(test)]
@@ -0,0 +1,104 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{unit_tests::testlib::new_eth_iface, NetworkState};
+
+fn new_cur_state() -> NetworkState {
+    let mut state = NetworkState::new();
+    let mut iface = new_eth_iface("eth1");
+    iface.base_iface_mut().permanent_mac_address =
+        Some("00:23:45:67:89:1A".to_string());
+    state.interfaces.push(iface);
+    let mut iface = new_eth_iface("eth2");
+    iface.base_iface_mut().permanent_mac_address =
+        Some("00:23:45:67:89:1B".to_string());
+    state.interfaces.push(iface);
+    // No permanent MAC address
+    state.interfaces.push(new_eth_iface("eth3"));
+    state
+}
+
+#[test]
+fn test_gen_nic_name_link_files() {
+    let files = new_cur_state().gen_nic_name_link_files();
+
+    assert_eq!(files.len(), 2);
+    assert_eq!(files[0].0, "98-nmstate-eth1.link");
+    assert_eq!(
+        files[0].1,
+        "# Generated by nmstate\n[Match]\n\
+        PermanentMACAddress=00:23:45:67:89:1A\n\n[Link]\nName=eth1\n"
+    );
+    assert_eq!(files[1].0, "98-nmstate-eth2.link");
+}
+
+#[test]
+fn test_gen_nic_name_link_files_from_identifier() {
+    let desired: NetworkState = serde_yaml::from_str(
+        r#"---
+interfaces:
+- name: wan0
+  type: ethernet
+  state: up
+  identifier: mac-address
+  mac-address: 00:23:45:67:89:1C
+- name: eth1
+  type: ethernet
+  state: up
+  mac-address: 00:23:45:67:89:1D
+"#,
+    )
+    .unwrap();
+    let files = desired.gen_nic_name_link_files();
+
+    assert_eq!(files.len(), 1);
+    let (file_name, content) = &files[0];
+    assert_eq!(file_name, "98-nmstate-wan0.link");
+    assert!(content.contains("PermanentMACAddress=00:23:45:67:89:1C\n"));
+    assert!(content.contains("Name=wan0\n"));
+}
+
+#[test]
+fn test_get_stale_nic_name_link_files() {
+    let state = new_cur_state();
+    let mut exist_files = state.gen_nic_name_link_files();
+    exist_files.push((
+        "98-nmstate-eth9.link".to_string(),
+        "# Generated by nmstate\n[Match]\n\
+        PermanentMACAddress=00:23:45:67:89:ff\n\n[Link]\nName=eth9\n"
+            .to_string(),
+    ));
+    // Not generated by nmstate
+    exist_files.push((
+        "10-eth8.link".to_string(),
+        "[Match]\nPermanentMACAddress=00:23:45:67:89:fe\n".to_string(),
+    ));
+
+    assert_eq!(
+        state.get_stale_nic_name_link_files(&exist_files),
+        vec!["98-nmstate-eth9.link".to_string()]
+    );
+}
+
+#[test]
+fn test_get_stale_nic_name_link_files_old_name() {
+    let state = new_cur_state();
+    let mut exist_files = state.gen_nic_name_link_files();
+    // File of old name left beside the file of current name
+    exist_files.push((
+        "98-nmstate-lan0.link".to_string(),
+        exist_files[1].1.replace("eth2", "lan0"),
+    ));
+    // File name does not match `Name=`
+    exist_files
+        .push(("98-nmstate-lan1.link".to_string(), exist_files[0].1.clone()));
+    // MAC address is case insensitive
+    exist_files[0].1 = exist_files[0].1.replace("1A", "1a");
+
+    assert_eq!(
+        state.get_stale_nic_name_link_files(&exist_files),
+        vec![
+            "98-nmstate-lan0.link".to_string(),
+            "98-nmstate-lan1.link".to_string()
+        ]
+    );
+}
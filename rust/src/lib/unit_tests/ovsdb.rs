@@ -131,3 +131,62 @@ other_config: {}
     expect.prop_list = Vec::new();
     assert_eq!(desired, expect);
 }
+
+#[cfg(feature = "query_apply")]
+#[test]
+fn test_ovsdb_snapshot_row_to_update() {
+    use crate::ovsdb::{OvsDbSnapshotRow, OvsDbUpdate};
+
+    let row: serde_json::Map<String, serde_json::Value> =
+        serde_json::from_value(serde_json::json!({
+            "name": "br0",
+            "external_ids": ["map", [["a", "A0"]]],
+            "other_config": ["map", []],
+        }))
+        .unwrap();
+    let snapshot_row = OvsDbSnapshotRow::new("Bridge", row);
+    // Restore is done by another process reading the snapshot file.
+    let snapshot_row: OvsDbSnapshotRow =
+        serde_json::from_str(&serde_json::to_string(&snapshot_row).unwrap())
+            .unwrap();
+
+    assert_eq!(
+        OvsDbUpdate::from(&snapshot_row).to_value(),
+        serde_json::json!({
+            "op": "update",
+            "table": "Bridge",
+            "where": [["name", "==", "br0"]],
+            "row": {
+                "external_ids": ["map", [["a", "A0"]]],
+                "other_config": ["map", []],
+            },
+        })
+    );
+}
+
+#[cfg(feature = "query_apply")]
+#[test]
+fn test_ovsdb_snapshot_global_row_to_update() {
+    use crate::ovsdb::{OvsDbSnapshotRow, OvsDbUpdate};
+
+    let row: serde_json::Map<String, serde_json::Value> =
+        serde_json::from_value(serde_json::json!({
+            "external_ids": ["map", [["hostname", "host1"]]],
+            "other_config": ["map", [["stats-update-interval", "1000"]]],
+        }))
+        .unwrap();
+
+    assert_eq!(
+        OvsDbUpdate::from(&OvsDbSnapshotRow::new("Open_vSwitch", row))
+            .to_value(),
+        serde_json::json!({
+            "op": "update",
+            "table": "Open_vSwitch",
+            "where": [],
+            "row": {
+                "external_ids": ["map", [["hostname", "host1"]]],
+                "other_config": ["map", [["stats-update-interval", "1000"]]],
+            },
+        })
+    );
+}
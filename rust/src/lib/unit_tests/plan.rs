@@ -0,0 +1,137 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    query_apply::gen_plan, InterfaceType, NetworkState, NetworkStatePlan,
+    RouteState,
+};
+
+fn gen_test_plan(cur_yaml: &str, desire_yaml: &str) -> NetworkStatePlan {
+    let cur_net_state: NetworkState = serde_yaml::from_str(cur_yaml).unwrap();
+    let desire_net_state: NetworkState =
+        serde_yaml::from_str(desire_yaml).unwrap();
+    let (add_net_state, chg_net_state, del_net_state) = desire_net_state
+        .gen_state_for_apply(&cur_net_state)
+        .unwrap();
+    gen_plan(
+        &desire_net_state,
+        &cur_net_state,
+        &add_net_state,
+        &chg_net_state,
+        &del_net_state,
+    )
+}
+
+#[test]
+fn test_plan_ifaces() {
+    let plan = gen_test_plan(
+        r#"---
+interfaces:
+- name: eth1
+  type: ethernet
+  state: up
+- name: dummy1
+  type: dummy
+  state: up
+"#,
+        r#"---
+interfaces:
+- name: eth1
+  type: ethernet
+  state: down
+- name: dummy1
+  type: dummy
+  state: absent
+- name: dummy2
+  type: dummy
+  state: up
+"#,
+    );
+    assert_eq!(plan.add_interfaces.len(), 1);
+    assert_eq!(plan.add_interfaces[0].name(), "dummy2");
+    assert_eq!(plan.change_interfaces.len(), 1);
+    assert_eq!(plan.change_interfaces[0].name(), "eth1");
+    assert_eq!(plan.delete_interfaces.len(), 1);
+    assert_eq!(plan.delete_interfaces[0].name, "dummy1");
+    assert_eq!(plan.delete_interfaces[0].iface_type, InterfaceType::Dummy);
+    assert_eq!(plan.activate_profiles.len(), 1);
+    assert_eq!(plan.activate_profiles[0].name, "dummy2");
+    assert_eq!(plan.deactivate_profiles.len(), 1);
+    assert_eq!(plan.deactivate_profiles[0].name, "eth1");
+    assert!(plan.add_routes.is_empty());
+    assert!(plan.dns.is_none());
+}
+
+#[test]
+fn test_plan_routes() {
+    let plan = gen_test_plan(
+        r#"---
+interfaces:
+- name: eth1
+  type: ethernet
+  state: up
+routes:
+  config:
+  - destination: 198.51.100.0/24
+    next-hop-interface: eth1
+    next-hop-address: 192.0.2.1
+"#,
+        r#"---
+routes:
+  config:
+  - destination: 198.51.100.0/24
+    state: absent
+  - destination: 203.0.113.0/24
+    next-hop-interface: eth1
+    next-hop-address: 192.0.2.1
+"#,
+    );
+    assert_eq!(plan.add_routes.len(), 1);
+    assert_eq!(
+        plan.add_routes[0].destination.as_deref(),
+        Some("203.0.113.0/24")
+    );
+    assert_eq!(plan.remove_routes.len(), 1);
+    assert_eq!(
+        plan.remove_routes[0].destination.as_deref(),
+        Some("198.51.100.0/24")
+    );
+    assert_eq!(plan.remove_routes[0].state, Some(RouteState::Absent));
+    assert!(plan.delete_interfaces.is_empty());
+}
+
+#[test]
+fn test_plan_hide_secrets() {
+    let mut plan = gen_test_plan(
+        r#"---
+interfaces:
+- name: eth1
+  type: ethernet
+  state: up
+"#,
+        r#"---
+interfaces:
+- name: eth1
+  type: ethernet
+  state: up
+  802.1x:
+    identity: client.example.org
+    eap-methods:
+    - tls
+    private-key: /etc/pki/client.key
+    private-key-password: password
+    client-cert: /etc/pki/client.crt
+    ca-cert: /etc/pki/ca.crt
+"#,
+    );
+    plan.hide_secrets();
+
+    assert_eq!(plan.change_interfaces.len(), 1);
+    assert_eq!(
+        plan.change_interfaces[0]
+            .base_iface()
+            .ieee8021x
+            .as_ref()
+            .and_then(|c| c.private_key_password.as_deref()),
+        Some(NetworkState::PASSWORD_HID_BY_NMSTATE)
+    );
+}
@@ -0,0 +1,158 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    ErrorKind, InterfaceType, NetworkPolicy, NetworkState, NmstateError,
+};
+
+const CURRENT_STATE: &str = r#"---
+routes:
+  running:
+  - destination: 0.0.0.0/0
+    next-hop-address: 192.0.2.254
+    next-hop-interface: eth1
+    table-id: 254
+  - destination: 198.51.100.0/24
+    next-hop-address: 192.0.2.253
+    next-hop-interface: eth2
+    table-id: 254
+interfaces:
+- name: eth1
+  type: ethernet
+  state: up
+  driver: virtio_net
+  mtu: 1500
+- name: eth2
+  type: ethernet
+  state: up
+  driver: mlx5_core
+  mtu: 9000
+- name: eth3
+  type: ethernet
+  state: up
+  driver: mlx5_core
+  mtu: 9000
+"#;
+
+fn render_policy(policy_yaml: &str) -> Result<NetworkState, NmstateError> {
+    let current: NetworkState = serde_yaml::from_str(CURRENT_STATE).unwrap();
+    let policy: NetworkPolicy = serde_yaml::from_str(policy_yaml).unwrap();
+    NetworkState::new_from_policy(&policy, &current)
+}
+
+#[test]
+fn test_policy_capture_default_gw_iface() {
+    let state = render_policy(
+        r#"---
+capture:
+  default-gw: routes.running.destination=="0.0.0.0/0"
+  base-iface: >-
+    interfaces.name==capture.default-gw.routes.running.0.next-hop-interface
+desired-state:
+  interfaces:
+  - name: br0
+    type: linux-bridge
+    state: up
+    mtu: "{{ capture.base-iface.interfaces.0.mtu }}"
+    bridge:
+      port:
+      - name: "{{ capture.base-iface.interfaces.0.name }}"
+"#,
+    )
+    .unwrap();
+
+    let iface = state
+        .interfaces
+        .get_iface("br0", InterfaceType::LinuxBridge)
+        .unwrap();
+    assert_eq!(iface.base_iface().mtu, Some(1500));
+    assert_eq!(iface.ports(), Some(vec!["eth1"]));
+}
+
+#[test]
+fn test_policy_capture_by_driver() {
+    let state = render_policy(
+        r#"---
+capture:
+  mlx: interfaces.driver=="mlx5_core"
+desired-state:
+  interfaces:
+  - name: bond0
+    type: bond
+    state: up
+    link-aggregation:
+      mode: active-backup
+      port:
+      - "{{ capture.mlx.interfaces.0.name }}"
+      - "{{ capture.mlx.interfaces.1.name }}"
+"#,
+    )
+    .unwrap();
+
+    let iface = state
+        .interfaces
+        .get_iface("bond0", InterfaceType::Bond)
+        .unwrap();
+    assert_eq!(iface.ports(), Some(vec!["eth2", "eth3"]));
+}
+
+#[test]
+fn test_policy_template_in_string() {
+    let state = render_policy(
+        r#"---
+capture:
+  gw: routes.running.destination=="0.0.0.0/0"
+desired-state:
+  interfaces:
+  - name: "{{ capture.gw.routes.running.0.next-hop-interface }}.100"
+    type: vlan
+    state: up
+    vlan:
+      base-iface: "{{ capture.gw.routes.running.0.next-hop-interface }}"
+      id: 100
+"#,
+    )
+    .unwrap();
+
+    assert!(state
+        .interfaces
+        .get_iface("eth1.100", InterfaceType::Vlan)
+        .is_some());
+}
+
+#[test]
+fn test_policy_capture_not_found() {
+    let result = render_policy(
+        r#"---
+capture:
+  mlx: interfaces.driver=="ice"
+desired-state:
+  interfaces:
+  - name: "{{ capture.mlx.interfaces.0.name }}"
+    type: ethernet
+    state: up
+"#,
+    );
+
+    assert!(result.is_err());
+    if let Err(e) = result {
+        assert_eq!(e.kind(), ErrorKind::InvalidArgument);
+    }
+}
+
+#[test]
+fn test_policy_circular_capture() {
+    let result = render_policy(
+        r#"---
+capture:
+  a: interfaces.name==capture.b.interfaces.0.name
+  b: interfaces.name==capture.a.interfaces.0.name
+desired-state: {}
+"#,
+    );
+
+    assert!(result.is_err());
+    if let Err(e) = result {
+        assert_eq!(e.kind(), ErrorKind::InvalidArgument);
+        assert!(e.msg().contains("Circular reference"));
+    }
+}
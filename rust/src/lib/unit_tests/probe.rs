@@ -0,0 +1,82 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{NetworkProbe, NetworkState};
+
+#[test]
+fn test_probes_deserialize() {
+    let net_state: NetworkState = serde_yaml::from_str(
+        r#"---
+probes:
+- type: ping
+  address: 192.0.2.1
+- type: tcp
+  address: 2001:db8::1
+  port: 22
+  timeout: 3
+- type: dns
+  name: example.com
+"#,
+    )
+    .unwrap();
+    assert!(net_state.prop_list.contains(&"probes"));
+    assert_eq!(net_state.probes.len(), 3);
+    assert!(matches!(net_state.probes[0], NetworkProbe::Ping(_)));
+    assert_eq!(net_state.probes[0].timeout(), NetworkProbe::DEFAULT_TIMEOUT);
+    if let NetworkProbe::Tcp(probe) = &net_state.probes[1] {
+        assert_eq!(probe.port, 22);
+    } else {
+        panic!("Expecting TCP probe, got {:?}", net_state.probes[1]);
+    }
+    assert_eq!(net_state.probes[1].timeout(), 3);
+    if let NetworkProbe::Dns(probe) = &net_state.probes[2] {
+        assert_eq!(probe.name, "example.com");
+    } else {
+        panic!("Expecting DNS probe, got {:?}", net_state.probes[2]);
+    }
+}
+
+#[test]
+fn test_probes_invalid_type() {
+    let result = serde_yaml::from_str::<NetworkState>(
+        r#"---
+probes:
+- type: http
+  address: 192.0.2.1
+"#,
+    );
+    assert!(result.is_err());
+}
+
+#[cfg(feature = "query_apply")]
+#[test]
+fn test_kernel_checkpoint_timeout_extend_before_probes() {
+    use crate::nispor::nispor_checkpoint_timeout_extend;
+
+    let now = std::time::SystemTime::now()
+        .duration_since(std::time::SystemTime::UNIX_EPOCH)
+        .unwrap()
+        .as_secs();
+    let path = std::env::temp_dir()
+        .join(format!("nmstate-test-{}.json", uuid::Uuid::new_v4()));
+    let path = path.to_str().unwrap();
+    // Created 100 seconds ago with 60 seconds timeout, already expired
+    std::fs::write(
+        path,
+        serde_json::json!({
+            "created": now - 100,
+            "timeout": 60,
+            "state": {},
+        })
+        .to_string(),
+    )
+    .unwrap();
+
+    nispor_checkpoint_timeout_extend(path, 30).unwrap();
+    let content: serde_json::Value =
+        serde_json::from_str(&std::fs::read_to_string(path).unwrap()).unwrap();
+    std::fs::remove_file(path).unwrap();
+
+    let timeout = content["timeout"].as_u64().unwrap();
+    assert!((130..=131).contains(&timeout));
+    assert_eq!(content["created"].as_u64(), Some(now - 100));
+}
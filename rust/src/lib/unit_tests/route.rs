@@ -177,6 +177,20 @@ fn test_verify_desire_route_not_found() {
     assert_eq!(result.err().unwrap().kind(), ErrorKind::VerificationError);
 }
 
+#[test]
+fn test_verify_report_all_missing_routes() {
+    let des_routes = gen_test_routes_conf();
+
+    let result = des_routes.verify(&Routes::new(), &[]);
+    assert!(result.is_err());
+    if let Err(e) = result {
+        assert_eq!(e.kind(), ErrorKind::VerificationError);
+        assert_eq!(e.differences().len(), 2);
+        assert_eq!(e.differences()[0].path, "routes.config");
+        assert_eq!(e.differences()[0].current, serde_json::Value::Null);
+    }
+}
+
 #[test]
 fn test_verify_absent_route_still_found() {
     let cur_routes = gen_test_routes_conf();
@@ -210,6 +224,94 @@ fn test_verify_current_has_more_routes() {
     des_routes.verify(&cur_routes, &[]).unwrap();
 }
 
+#[test]
+fn test_gen_changed_ifaces_and_routes_wildcard_absent() {
+    let cur_routes = Routes {
+        running: None,
+        config: Some(gen_test_route_entries()),
+    };
+    let mut absent_route = RouteEntry::new();
+    absent_route.destination = Some(TEST_IPV4_NET1.to_string());
+    absent_route.state = Some(RouteState::Absent);
+    let new_route = gen_route_entry(TEST_IPV6_NET2, "eth2", TEST_IPV6_ADDR2);
+    let des_routes = Routes {
+        running: None,
+        config: Some(vec![absent_route, new_route.clone()]),
+    };
+
+    let changed_routes = des_routes
+        .gen_changed_ifaces_and_routes(&cur_routes)
+        .unwrap();
+
+    assert_eq!(changed_routes.len(), 2);
+    assert_eq!(
+        changed_routes[TEST_NIC],
+        vec![gen_route_entry(TEST_IPV6_NET1, TEST_NIC, TEST_IPV6_ADDR1)]
+    );
+    assert_eq!(changed_routes["eth2"], vec![new_route]);
+}
+
+#[test]
+fn test_gen_changed_ifaces_and_routes_keep_current() {
+    let cur_routes = Routes {
+        running: None,
+        config: Some(vec![gen_route_entry(
+            TEST_IPV6_NET1,
+            TEST_NIC,
+            TEST_IPV6_ADDR1,
+        )]),
+    };
+    let des_routes = Routes {
+        running: None,
+        config: Some(vec![gen_route_entry(
+            TEST_IPV4_NET1,
+            TEST_NIC,
+            TEST_IPV4_ADDR1,
+        )]),
+    };
+
+    let changed_routes = des_routes
+        .gen_changed_ifaces_and_routes(&cur_routes)
+        .unwrap();
+
+    assert_eq!(changed_routes.len(), 1);
+    let mut expected_routes = gen_test_route_entries();
+    expected_routes.sort_unstable();
+    assert_eq!(changed_routes[TEST_NIC], expected_routes);
+}
+
+#[cfg(feature = "query_apply")]
+#[test]
+fn test_is_route_applied_metric_and_table() {
+    use crate::nispor::is_route_applied;
+
+    let mut cur_route =
+        gen_route_entry(TEST_IPV4_NET1, TEST_NIC, TEST_IPV4_ADDR1);
+    cur_route.table_id = Some(254);
+
+    let mut des_route = cur_route.clone();
+    des_route.metric = None;
+    des_route.table_id = None;
+    assert!(is_route_applied(&des_route, &cur_route));
+
+    des_route.metric = Some(RouteEntry::USE_DEFAULT_METRIC);
+    assert!(is_route_applied(&des_route, &cur_route));
+
+    des_route.metric = Some(TEST_ROUTE_METRIC + 1);
+    assert!(!is_route_applied(&des_route, &cur_route));
+
+    des_route.metric = Some(TEST_ROUTE_METRIC);
+    des_route.table_id = Some(100);
+    assert!(!is_route_applied(&des_route, &cur_route));
+
+    cur_route.table_id = Some(100);
+    assert!(is_route_applied(&des_route, &cur_route));
+
+    // Undefined table ID means main route table
+    des_route.table_id = None;
+    assert!(!is_route_applied(&des_route, &cur_route));
+}
+
 fn gen_test_routes_conf() -> Routes {
     let mut ret = Routes::new();
     ret.running = Some(gen_test_route_entries());
@@ -0,0 +1,289 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{ErrorKind, InterfaceType, NetworkState};
+
+#[test]
+fn test_validate_offline_valid_state() {
+    let net_state = NetworkState::validate_offline(
+        r#"{
+            "interfaces": [
+                {
+                    "name": "eth1",
+                    "type": "ethernet",
+                    "state": "up",
+                    "mtu": 1500,
+                    "ipv4": {
+                        "enabled": true,
+                        "dhcp": true
+                    },
+                    "ovs-db": {
+                        "external_ids": {"foo": "abc"}
+                    }
+                },
+                {
+                    "name": "eth2",
+                    "state": "down"
+                }
+            ],
+            "routes": {
+                "config": [
+                    {
+                        "destination": "0.0.0.0/0",
+                        "next-hop-interface": "eth1",
+                        "next-hop-address": "192.0.2.1"
+                    }
+                ]
+            }
+        }"#,
+    )
+    .unwrap();
+
+    let ifaces = net_state.interfaces.to_vec();
+    assert_eq!(ifaces.len(), 2);
+    assert_eq!(ifaces[0].name(), "eth1");
+    assert_eq!(ifaces[0].iface_type(), InterfaceType::Ethernet);
+    assert_eq!(net_state.routes.config.as_ref().map(Vec::len), Some(1));
+}
+
+#[test]
+fn test_validate_offline_unknown_iface_property() {
+    let result = NetworkState::validate_offline(
+        r#"{
+            "interfaces": [
+                {
+                    "name": "eth1",
+                    "type": "ethernet",
+                    "state": "up"
+                },
+                {
+                    "name": "eth2",
+                    "type": "ethernet",
+                    "state": "up",
+                    "mtuu": 9000
+                }
+            ]
+        }"#,
+    );
+
+    assert!(result.is_err());
+    if let Err(e) = result {
+        assert_eq!(e.kind(), ErrorKind::InvalidArgument);
+        assert!(e.msg().contains("interfaces[1].mtuu"));
+    }
+}
+
+#[test]
+fn test_validate_offline_nested_unknown_property() {
+    let result = NetworkState::validate_offline(
+        r#"{
+            "interfaces": [
+                {
+                    "name": "eth1",
+                    "type": "ethernet",
+                    "state": "up",
+                    "ipv4": {
+                        "enabled": true,
+                        "dhcpp": true
+                    }
+                }
+            ]
+        }"#,
+    );
+
+    assert!(result.is_err());
+    if let Err(e) = result {
+        assert_eq!(e.kind(), ErrorKind::InvalidArgument);
+        assert!(e
+            .msg()
+            .contains("Unknown property interfaces[0].ipv4.dhcpp"));
+    }
+}
+
+#[test]
+fn test_validate_offline_collect_all_unknown_properties() {
+    let result = NetworkState::validate_offline(
+        r#"{
+            "routes": {
+                "config": [
+                    {
+                        "destination": "198.51.100.0/24",
+                        "next-hop-interface": "eth1",
+                        "metricc": 100
+                    }
+                ]
+            },
+            "interfaces": [
+                {
+                    "name": "eth1",
+                    "type": "ethernet",
+                    "state": "up",
+                    "ipv4": {
+                        "enabled": true,
+                        "dhcpp": true
+                    },
+                    "ipv6": {
+                        "enabled": true,
+                        "autoconff": true
+                    }
+                }
+            ]
+        }"#,
+    );
+
+    assert!(result.is_err());
+    if let Err(e) = result {
+        assert_eq!(e.kind(), ErrorKind::InvalidArgument);
+        assert!(e
+            .msg()
+            .contains("Unknown property routes.config[0].metricc"));
+        assert!(e
+            .msg()
+            .contains("Unknown property interfaces[0].ipv4.dhcpp"));
+        assert!(e
+            .msg()
+            .contains("Unknown property interfaces[0].ipv6.autoconff"));
+    }
+}
+
+#[test]
+fn test_validate_offline_unknown_property_valid_elsewhere() {
+    let result = NetworkState::validate_offline(
+        r#"{
+            "interfaces": [
+                {
+                    "name": "eth1",
+                    "type": "ethernet",
+                    "ipv4": {
+                        "enabled": true,
+                        "dhcp": true
+                    },
+                    "lldp": {
+                        "enabled": false,
+                        "dhcp": true
+                    }
+                }
+            ]
+        }"#,
+    );
+
+    assert!(result.is_err());
+    if let Err(e) = result {
+        assert_eq!(e.kind(), ErrorKind::InvalidArgument);
+        assert_eq!(e.msg(), "Unknown property interfaces[0].lldp.dhcp");
+    }
+}
+
+#[test]
+fn test_validate_offline_unknown_top_level_property() {
+    let result = NetworkState::validate_offline(
+        r#"{
+            "interface": [
+                {
+                    "name": "eth1",
+                    "type": "ethernet",
+                    "state": "up"
+                }
+            ]
+        }"#,
+    );
+
+    assert!(result.is_err());
+    if let Err(e) = result {
+        assert_eq!(e.kind(), ErrorKind::InvalidArgument);
+        assert!(e.msg().contains("Unknown property interface"));
+    }
+}
+
+#[test]
+fn test_validate_offline_unknown_ovsdb_property() {
+    let result = NetworkState::validate_offline(
+        r#"{
+            "ovs-db": {
+                "external_id": {"foo": "abc"}
+            }
+        }"#,
+    );
+
+    assert!(result.is_err());
+    if let Err(e) = result {
+        assert_eq!(e.kind(), ErrorKind::InvalidArgument);
+        assert!(e.msg().contains("ovs-db.external_id"));
+    }
+}
+
+#[test]
+fn test_validate_offline_unsupported_iface_type() {
+    let result = NetworkState::validate_offline(
+        r#"{
+            "interfaces": [
+                {
+                    "name": "team0",
+                    "type": "team",
+                    "state": "up"
+                }
+            ]
+        }"#,
+    );
+
+    assert!(result.is_err());
+    if let Err(e) = result {
+        assert_eq!(e.kind(), ErrorKind::InvalidArgument);
+        assert!(e.msg().contains("interfaces[0].type"));
+    }
+}
+
+#[test]
+fn test_validate_offline_iface_without_type() {
+    let result = NetworkState::validate_offline(
+        r#"{
+            "interfaces": [
+                {
+                    "name": "eth1",
+                    "state": "up",
+                    "mtu": 1500
+                }
+            ]
+        }"#,
+    );
+
+    assert!(result.is_err());
+    if let Err(e) = result {
+        assert_eq!(e.kind(), ErrorKind::InvalidArgument);
+        assert!(e.msg().contains("interfaces[0].mtu"));
+    }
+}
+
+#[test]
+fn test_validate_offline_ignore_absent_iface() {
+    let net_state = NetworkState::validate_offline(
+        r#"{
+            "interfaces": [
+                {
+                    "name": "eth1",
+                    "type": "ethernet",
+                    "state": "absent",
+                    "foo": "bar"
+                }
+            ]
+        }"#,
+    )
+    .unwrap();
+
+    assert_eq!(net_state.interfaces.to_vec().len(), 1);
+}
+
+#[cfg(feature = "schema")]
+#[test]
+fn test_json_schema() {
+    let schema: serde_json::Value =
+        serde_json::from_str(&NetworkState::json_schema().unwrap()).unwrap();
+
+    assert_eq!(schema["additionalProperties"], serde_json::json!(false));
+    assert!(schema["properties"]["interfaces"].is_object());
+    assert!(schema["properties"]["dns-resolver"].is_object());
+    assert!(schema["definitions"]["EthernetInterface"].is_object());
+    assert_eq!(
+        schema["definitions"]["EthernetInterface"]["additionalProperties"],
+        serde_json::json!(false)
+    );
+}
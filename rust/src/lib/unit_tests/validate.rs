@@ -0,0 +1,165 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{ErrorKind, NetworkState};
+
+#[test]
+fn test_validate_without_current() {
+    let desired: NetworkState = serde_yaml::from_str(
+        r#"---
+interfaces:
+- name: bond0
+  type: bond
+  state: up
+  link-aggregation:
+    mode: active-backup
+    options:
+      miimon: 100
+    port:
+    - eth1
+    - eth2
+routes:
+  config:
+  - destination: 198.51.100.0/24
+    next-hop-address: 192.0.2.254
+    next-hop-interface: bond0
+"#,
+    )
+    .unwrap();
+
+    desired.validate(None).unwrap();
+}
+
+#[test]
+fn test_validate_overbook_ports() {
+    let desired: NetworkState = serde_yaml::from_str(
+        r#"---
+interfaces:
+- name: bond0
+  type: bond
+  state: up
+  link-aggregation:
+    mode: active-backup
+    port:
+    - eth1
+- name: br0
+  type: linux-bridge
+  state: up
+  bridge:
+    port:
+    - name: eth1
+"#,
+    )
+    .unwrap();
+
+    let result = desired.validate(None);
+    assert!(result.is_err());
+    if let Err(e) = result {
+        assert_eq!(e.kind(), ErrorKind::InvalidArgument);
+        assert!(e.msg().contains("eth1"));
+    }
+}
+
+#[test]
+fn test_validate_bond_incompatible_options() {
+    let desired: NetworkState = serde_yaml::from_str(
+        r#"---
+interfaces:
+- name: bond0
+  type: bond
+  state: up
+  link-aggregation:
+    mode: active-backup
+    options:
+      miimon: 100
+      arp_interval: 100
+"#,
+    )
+    .unwrap();
+
+    let result = desired.validate(None);
+    assert!(result.is_err());
+    if let Err(e) = result {
+        assert_eq!(e.kind(), ErrorKind::InvalidArgument);
+    }
+}
+
+#[test]
+fn test_validate_route_without_next_hop_iface() {
+    let desired: NetworkState = serde_yaml::from_str(
+        r#"---
+routes:
+  config:
+  - destination: 198.51.100.0/24
+    next-hop-address: 192.0.2.254
+"#,
+    )
+    .unwrap();
+
+    let result = desired.validate(None);
+    assert!(result.is_err());
+    if let Err(e) = result {
+        assert_eq!(e.kind(), ErrorKind::NotImplementedError);
+    }
+}
+
+#[test]
+fn test_validate_mtu_against_current() {
+    let current: NetworkState = serde_yaml::from_str(
+        r#"---
+interfaces:
+- name: eth1
+  type: ethernet
+  state: up
+  mtu: 1500
+  min-mtu: 68
+  max-mtu: 9000
+"#,
+    )
+    .unwrap();
+    let desired: NetworkState = serde_yaml::from_str(
+        r#"---
+interfaces:
+- name: eth1
+  state: up
+  mtu: 9001
+"#,
+    )
+    .unwrap();
+
+    let result = desired.validate(Some(&current));
+    assert!(result.is_err());
+    if let Err(e) = result {
+        assert_eq!(e.kind(), ErrorKind::InvalidArgument);
+        assert!(e.msg().contains("maximum allowed MTU 9000"));
+    }
+}
+
+#[test]
+fn test_validate_port_found_in_current() {
+    let current: NetworkState = serde_yaml::from_str(
+        r#"---
+interfaces:
+- name: eth1
+  type: ethernet
+  state: up
+"#,
+    )
+    .unwrap();
+    let desired: NetworkState = serde_yaml::from_str(
+        r#"---
+interfaces:
+- name: eth1
+  state: up
+  mtu: 9000
+- name: br0
+  type: linux-bridge
+  state: up
+  bridge:
+    port:
+    - name: eth1
+"#,
+    )
+    .unwrap();
+
+    desired.validate(Some(&current)).unwrap();
+}
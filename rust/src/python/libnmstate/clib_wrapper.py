@@ -50,6 +50,7 @@ NMSTATE_FLAG_INCLUDE_SECRETS = 1 << 4
 NMSTATE_FLAG_NO_COMMIT = 1 << 5
 NMSTATE_FLAG_MEMORY_ONLY = 1 << 6
 NMSTATE_FLAG_RUNNING_CONFIG_ONLY = 1 << 7
+NMSTATE_FLAG_ALLOW_MGMT_DISRUPTION = 1 << 8
 NMSTATE_PASS = 0
 
 
@@ -102,6 +103,7 @@ def apply_net_state(
     save_to_disk=True,
     commit=True,
     rollback_timeout=60,
+    allow_mgmt_disruption=False,
 ):
     c_err_msg = c_char_p()
     c_err_kind = c_char_p()
@@ -120,6 +122,9 @@ def apply_net_state(
     if not save_to_disk:
         flags |= NMSTATE_FLAG_MEMORY_ONLY
 
+    if allow_mgmt_disruption:
+        flags |= NMSTATE_FLAG_ALLOW_MGMT_DISRUPTION
+
     rc = lib.nmstate_net_state_apply(
         flags,
         c_state,